@@ -0,0 +1,79 @@
+//! Offline reader of binary journals written by `KJournalListenerBinary`.
+//!
+//! Usage : `olympus_journal [--json] [--filter <directives>] <journal>...`
+//!
+//! Records of all journals are merged by date and time, filtered with `KJournalFilter` directives
+//! and printed as text (default) or JSON lines. Collapsed duplicates are printed once with their total repeat count.
+
+use std::{fs::File, io::{BufReader, Write}, process::exit};
+
+use olympus::kleio::journal::{KJournalFilter, KJournalBinaryReader, KJournalRecord, binary::merge_records};
+
+/// Usage printed on argument error.
+const USAGE : &str = "Usage : olympus_journal [--json] [--filter <directives>] <journal>...";
+
+fn main() {
+    let mut json = false;
+    let mut filter = KJournalFilter::parse("").unwrap();
+    let mut paths : Vec<String> = Vec::new();
+
+    // Parse arguments
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--text" => json = false,
+            "--filter" => match args.next().map(|d| KJournalFilter::parse(&d)) {
+                Some(Ok(f)) => filter = f,
+                Some(Err(err)) => fail(&format!("Invalid filter : {:?}", err)),
+                None => fail(USAGE),
+            },
+            "--help" | "-h" => { println!("{}", USAGE); return },
+            _ => paths.push(arg),
+        }
+    }
+
+    if paths.is_empty() {
+        fail(USAGE);
+    }
+
+    // Read all journals
+    let mut journals : Vec<Vec<KJournalRecord>> = Vec::new();
+    for path in &paths {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) => fail(&format!("{} : {}", path, err)),
+        };
+
+        let reader = match KJournalBinaryReader::new(BufReader::new(file)) {
+            Ok(reader) => reader,
+            Err(err) => fail(&format!("{} : {:?}", path, err)),
+        };
+
+        let mut records : Vec<KJournalRecord> = Vec::new();
+        for record in reader {
+            match record {
+                Ok(record) => if record.is_enabled(&filter) { records.push(record) },
+                // Keep records read before a truncated end, which happens if the writer crashed.
+                Err(err) => { eprintln!("{} : {:?}, remaining records ignored.", path, err); break },
+            }
+        }
+        journals.push(records);
+    }
+
+    // Print merged records
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    for record in merge_records(journals) {
+        let line = if json { record.to_json() } else { record.to_text() };
+        if writeln!(out, "{}", line).is_err() {
+            exit(1);
+        }
+    }
+}
+
+/// Print message to stderr and exit with error code.
+fn fail(message : &str) -> ! {
+    eprintln!("{}", message);
+    exit(1);
+}
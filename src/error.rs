@@ -11,6 +11,18 @@ pub enum OlympusError {
     
     /// Error that happens within [KAssetBroker].
     KAssetBroker(KAssetBrokerError),
+
+    /// Error that happens within [KInputActionMap].
+    KInputActionMap(KInputActionMapError),
+
+    /// Error that happens within [KController].
+    KController(KControllerError),
+
+    /// Error that happens within [KControllerMappingDatabase].
+    KControllerMapping(KControllerMappingError),
+
+    /// Error that happens within [KEventRecorder] and [KEventReplay].
+    KEventRecord(KEventRecordError),
 }
 
 /// Enumeration of possible [KWindow] errors.
@@ -37,6 +49,12 @@ pub enum KEventDispatcherError {
 
     /// Happens when trying to remove a [KEventReceiver] not added to a [KWindow].
     ReceiverNotFound,
+
+    /// Happens when trying to add a layer with the name of an existing layer.
+    LayerAlreadyExists,
+
+    /// Happens when a layer is not found within the [KEventDispatcher].
+    LayerNotFound,
 }
 
 /// Enumeration of possible errors that can happens within [KAssetBroker].
@@ -50,4 +68,55 @@ pub enum KAssetBrokerError {
 
     /// Happens when new priority set for [KAssetSource] is higher then the length of sources.
     PriorityOutOfBound,
-}
\ No newline at end of file
+}
+
+/// Enumeration of possible [KInputActionMap] errors.
+#[derive(Debug, Clone, Copy)]
+pub enum KInputActionMapError {
+    /// Happens when an action isn't in the [KInputActionMap].
+    ActionNotFound,
+
+    /// Happens when a bindings configuration line can't be parsed. Provides the line number, starting at 1.
+    InvalidBinding(usize),
+
+    /// Happens when a bindings configuration file can't be read or written.
+    FileError,
+}
+/// Enumeration of possible [KController] errors.
+#[derive(Debug, Clone, Copy)]
+pub enum KControllerError {
+    /// Happens when no controller is connected with the id given.
+    NotConnected,
+
+    /// Happens when the controller or its driver doesn't support the operation (ie. rumble, LED or battery).
+    NotSupported,
+
+    /// Happens when the controller device refused the operation (ie. missing permissions).
+    DeviceError,
+}
+
+/// Enumeration of possible [KControllerMappingDatabase] errors.
+#[derive(Debug, Clone, Copy)]
+pub enum KControllerMappingError {
+    /// Happens when a SDL mapping line can't be parsed. Provides the line number, starting at 1.
+    InvalidMapping(usize),
+
+    /// Happens when a mappings file can't be read.
+    FileError,
+}
+
+/// Enumeration of possible [KEventRecorder] and [KEventReplay] errors.
+#[derive(Debug, Clone, Copy)]
+pub enum KEventRecordError {
+    /// Happens when a record file can't be read or written.
+    FileError,
+
+    /// Happens when the record doesn't start with [KEVENT_RECORD_MAGIC].
+    InvalidHeader,
+
+    /// Happens when the record version is newer than [KEVENT_RECORD_VERSION].
+    UnsupportedVersion,
+
+    /// Happens when a recorded event is truncated or malformed. Provides the index of the event, starting at 0.
+    CorruptedEvent(usize),
+}
@@ -27,6 +27,42 @@ impl KWindow {
         todo!()
     }
 
+    /// Detect controllers connected or disconnected and read their input.
+    #[inline(always)]
+    pub(super) fn __update_controllers(&mut self) {
+        todo!()
+    }
+
+    /// Get a handle of a connected controller.
+    #[inline(always)]
+    pub(super) fn __get_controller(&mut self, id : u8) -> Result<super::KController<'_>, OlympusError> {
+        todo!()
+    }
+
+    /// Get the ids of connected controllers.
+    #[inline(always)]
+    pub(super) fn __get_controllers(&self) -> Vec<u8> {
+        todo!()
+    }
+
+    /// Add controller mappings in SDL format.
+    #[inline(always)]
+    pub(super) fn __add_controller_mappings(&mut self, mappings : &str) -> Result<usize, OlympusError> {
+        todo!()
+    }
+
+    /// Load controller mappings from a file in SDL format.
+    #[inline(always)]
+    pub(super) fn __load_controller_mappings(&mut self, path : &std::path::Path) -> Result<usize, OlympusError> {
+        todo!()
+    }
+
+    /// Queue an event as if sent by display server.
+    #[inline(always)]
+    pub(super) fn __inject_event(&mut self, event : KEvent) -> Result<(), OlympusError> {
+        todo!()
+    }
+
     /// Get the count of events that need handling.
     #[inline(always)]
     pub(super) fn __get_event_count(&self) -> usize {
@@ -64,6 +100,12 @@ impl KWindow {
         todo!()
     }
 
+    /// Apply cursor mode change to display server.
+    #[inline(always)]
+    pub(super) fn __set_cursor_mode(&mut self) {
+        todo!()
+    }
+
 
     /// Restore the [KWindow], undoing any minimized, maximized and/or fullscreen status.
     #[inline(always)]
@@ -0,0 +1,59 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::error::OlympusError;
+
+#[allow(unused_imports)]
+use super::{KWindow, event::{KEventEntry, KEventWindow}};
+
+/// ##### Display server connection shared by several [KWindow].
+///
+/// Windows created with [KWindow::from_context()] share one display server connection. Events read from the connection
+/// are routed to the window they belong to and dispatched by that window. Each [KEventEntry] carries the id of the window
+/// that dispatched it ([KEventEntry::get_window_id()]), which is also the id given by [KWindow::get_id()].
+///
+/// Controllers are part of the context. Their events are dispatched once, by the window that reads them first.
+///
+/// Context is cloned cheaply and connection is closed when the last clone and the last window created from it are dropped.
+///
+/// # Example(s)
+/// ```no_run
+/// use olympus::kleio::display::{KWindow, KDisplayContext, linux::server::KLinuxDisplayServerProvider};
+///
+/// let context = KDisplayContext::new(KLinuxDisplayServerProvider::Default).unwrap();
+///
+/// // Main viewport and a tool palette sharing one connection.
+/// let viewport = KWindow::from_context(&context, 1280, 720).unwrap();
+/// let palette = KWindow::from_context(&context, 240, 480).unwrap();
+/// assert_ne!(viewport.get_id(), palette.get_id());
+/// ```
+#[derive(Clone)]
+pub struct KDisplayContext {
+    /// Linux display server connection.
+    pub(super) connection : Rc<RefCell<super::linux::server::KLinuxDisplayConnection>>,
+}
+
+impl KDisplayContext {
+    /// Create a new display context connected to the display server provider.
+    ///
+    /// Default provider tries [Wayland](https://en.wikipedia.org/wiki/Wayland_(protocol)) first then
+    /// [x11](https://en.wikipedia.org/wiki/X_Window_System).
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KWindow(KWindowError::NotSupported)](crate::error::KWindowError::NotSupported) if display server isn't supported.
+    ///
+    /// Returns [OlympusError::KWindow(KWindowError::NoDisplayServer)](crate::error::KWindowError::NoDisplayServer) if connection failed.
+    pub fn new(provider : super::linux::server::KLinuxDisplayServerProvider) -> Result<KDisplayContext, OlympusError> {
+        let connection = super::linux::server::KLinuxDisplayConnection::new(provider)?;
+        Ok(KDisplayContext { connection : Rc::new(RefCell::new(connection)) })
+    }
+
+    /// Get the display server provider of the connection. Never [KLinuxDisplayServerProvider::Default](super::linux::server::KLinuxDisplayServerProvider::Default).
+    pub fn get_display_server_provider(&self) -> super::linux::server::KLinuxDisplayServerProvider {
+        self.connection.borrow().provider
+    }
+
+    /// Get the display server connection.
+    pub fn get_display_server_connection(&self) -> *const super::linux::server::Display {
+        self.connection.borrow().display
+    }
+}
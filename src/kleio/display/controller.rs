@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use crate::error::OlympusError;
+
+use super::event::KControllerGuid;
+
+#[allow(unused_imports)]
+use crate::error::KControllerError;
+
+#[allow(unused_imports)]
+use super::{KWindow, event::KEventController};
+
+/// ##### Handle of a connected controller.
+///
+/// Obtained with [KWindow::get_controller()] using the id given by [KEventController::Connected]. Gives access to
+/// controller outputs like rumble and player LED, and to battery state.
+///
+/// Operations the controller or its driver doesn't support return [OlympusError::KController(KControllerError::NotSupported)].
+///
+/// # Example(s)
+/// ```no_run
+/// use std::time::Duration;
+/// use olympus::kleio::display::{KWindow, linux::server::KLinuxDisplayServerProvider};
+///
+/// let mut window = KWindow::new(640, 480, KLinuxDisplayServerProvider::Default).unwrap();
+///
+/// if let Ok(mut controller) = window.get_controller(0) {
+///     // Half strength rumble for 200 ms. Errors are ignored if controller can't rumble.
+///     let _ = controller.rumble(0.5, 0.5, Duration::from_millis(200));
+/// };
+/// ```
+pub struct KController<'a> {
+    /// Controller id.
+    pub(super) id : u8,
+
+    /// Linux evdev controller (Linux only).
+    #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+    pub(super) controller : std::cell::RefMut<'a, super::linux::controller::KLinuxController>,
+
+    /// Lifetime of the controller borrow on platforms without controllers.
+    #[cfg(not(any(doc, all(not(target_family = "wasm"), target_os = "linux"))))]
+    pub(super) controller : std::marker::PhantomData<&'a mut ()>,
+}
+
+impl KController<'_> {
+    /// Returns the controller id given by [KEventController::Connected].
+    pub fn get_id(&self) -> u8 {
+        self.id
+    }
+
+    /// Returns the controller GUID used to find its mapping.
+    pub fn get_guid(&self) -> KControllerGuid {
+        #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+        { self.controller.get_guid() }
+
+        #[cfg(not(any(doc, all(not(target_family = "wasm"), target_os = "linux"))))]
+        { KControllerGuid::default() }
+    }
+
+    /// Returns the controller name given by its driver.
+    pub fn get_name(&self) -> &str {
+        #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+        { self.controller.get_name() }
+
+        #[cfg(not(any(doc, all(not(target_family = "wasm"), target_os = "linux"))))]
+        { "" }
+    }
+
+    /// Start rumble motors for a duration, replacing the rumble already playing.
+    ///
+    /// Strong is the low frequency (left) motor and weak the high frequency (right) motor, both from 0.0 to 1.0.
+    /// Duration is limited to 65535 milliseconds.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotSupported)] if controller can't rumble.
+    ///
+    /// Returns [OlympusError::KController(KControllerError::DeviceError)] if controller refused the effect.
+    pub fn rumble(&mut self, strong : f32, weak : f32, duration : Duration) -> Result<(), OlympusError> {
+        #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+        { self.controller.rumble(strong, weak, duration) }
+
+        #[cfg(not(any(doc, all(not(target_family = "wasm"), target_os = "linux"))))]
+        { let _ = (strong, weak, duration); Err(OlympusError::KController(KControllerError::NotSupported)) }
+    }
+
+    /// Stop rumble motors.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotSupported)] if controller can't rumble.
+    ///
+    /// Returns [OlympusError::KController(KControllerError::DeviceError)] if controller refused to stop.
+    pub fn stop_rumble(&mut self) -> Result<(), OlympusError> {
+        #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+        { self.controller.stop_rumble() }
+
+        #[cfg(not(any(doc, all(not(target_family = "wasm"), target_os = "linux"))))]
+        { Err(OlympusError::KController(KControllerError::NotSupported)) }
+    }
+
+    /// Light the LED of a player index, starting at 1. Player 0 turns player LEDs off.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotSupported)] if controller has no player LED.
+    ///
+    /// Returns [OlympusError::KController(KControllerError::DeviceError)] if LED can't be written (ie. missing permissions).
+    pub fn set_player_led(&mut self, player : u8) -> Result<(), OlympusError> {
+        #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+        { self.controller.set_player_led(player) }
+
+        #[cfg(not(any(doc, all(not(target_family = "wasm"), target_os = "linux"))))]
+        { let _ = player; Err(OlympusError::KController(KControllerError::NotSupported)) }
+    }
+
+    /// Returns the battery level and charging state.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotSupported)] if controller doesn't report a battery (ie. wired only).
+    pub fn get_battery(&self) -> Result<KControllerBattery, OlympusError> {
+        #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+        { self.controller.get_battery() }
+
+        #[cfg(not(any(doc, all(not(target_family = "wasm"), target_os = "linux"))))]
+        { Err(OlympusError::KController(KControllerError::NotSupported)) }
+    }
+}
+
+/// Battery level and charging state of a controller.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct KControllerBattery {
+    /// Battery level in percent. None if controller only reports the charging state.
+    pub level : Option<u8>,
+
+    /// Charging state.
+    pub state : KControllerBatteryState,
+}
+
+/// Enumeration of controller battery charging states.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KControllerBatteryState {
+    /// Battery is used.
+    Discharging,
+
+    /// Battery is charging.
+    Charging,
+
+    /// Battery is fully charged.
+    Full,
+
+    /// Controller is plugged but battery isn't charging.
+    NotCharging,
+
+    /// Controller doesn't report the state.
+    Unknown,
+}
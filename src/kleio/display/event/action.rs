@@ -0,0 +1,638 @@
+use std::{fmt::Display, path::Path};
+
+use crate::error::{OlympusError, KInputActionMapError};
+
+use super::{KEvent, KEventEntry, KEventReceiver, KEventKeyboard, KEventMouse, KEventController, KInputState, KKey, KScancode, KMouseButton};
+
+#[allow(unused_imports)]
+use super::{KEventDispatcher, super::KWindow};
+
+/// Controller axis value needed to capture the axis with [KInputActionMap::capture_next_input()].
+pub const KINPUT_CAPTURE_AXIS_THRESHOLD : i16 = 16384;
+
+/// ##### Enumeration of inputs that can be bound to an action.
+///
+/// The text form of an input, used in bindings configuration, is the same as its [Debug] form (ie. `Scancode(W)`,
+/// `Key(Character('z'))`, `MouseButton(Left)`, `ControllerAxis(0)`).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum KInput {
+    /// Logical key, according to keyboard layout.
+    Key(KKey),
+
+    /// Physical key, independent of keyboard layout.
+    Scancode(KScancode),
+
+    /// Mouse button.
+    MouseButton(KMouseButton),
+
+    /// Controller button id.
+    ControllerButton(u8),
+
+    /// Controller axis id. Value is normalized between -1.0 and 1.0.
+    ControllerAxis(u8),
+}
+
+/// ##### Binding of inputs to an action.
+///
+/// A binding with more than 1 input is a chord : all inputs but the last must be held and the value of the binding
+/// is the value of the last input (ie. `Key(LeftControl)+Key(Character('z'))`).
+///
+/// Axis values within the dead zone give 0.0 and values outside are rescaled from 0.0 to 1.0. The value is then multiplied by
+/// the scale, which gives the sensitivity and direction of the binding (ie. scale -1.0 for the left key of a horizontal axis).
+#[derive(Debug, Clone, PartialEq)]
+pub struct KInputBinding {
+    /// Inputs of the binding. Last input gives the value.
+    inputs : Vec<KInput>,
+
+    /// Dead zone of axis inputs, from 0.0 to 1.0.
+    dead_zone : f32,
+
+    /// Multiplier of the binding value.
+    scale : f32,
+}
+
+impl KInputBinding {
+    /// Create a new [KInputBinding] from inputs, dead zone and scale.
+    pub fn new(inputs : &[KInput], dead_zone : f32, scale : f32) -> KInputBinding {
+        KInputBinding { inputs: inputs.to_vec(), dead_zone, scale }
+    }
+
+    /// Returns the inputs of the binding.
+    pub fn get_inputs(&self) -> &[KInput] {
+        &self.inputs
+    }
+
+    /// Returns the dead zone of axis inputs.
+    pub fn get_dead_zone(&self) -> f32 {
+        self.dead_zone
+    }
+
+    /// Returns the multiplier of the binding value.
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+}
+
+impl From<KInput> for KInputBinding {
+    /// Binding of a single input without dead zone and with a scale of 1.0.
+    fn from(input: KInput) -> Self {
+        KInputBinding::new(&[input], 0.0, 1.0)
+    }
+}
+
+impl Display for KInputBinding {
+    /// Inputs separated by `+`, followed by `dead_zone=` and `scale=` if not default.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inputs : Vec<String> = self.inputs.iter().map(|i| format!("{:?}", i)).collect();
+        write!(f, "{}", inputs.join("+"))?;
+
+        if self.dead_zone != 0.0 {
+            write!(f, " dead_zone={}", self.dead_zone)?;
+        }
+        if self.scale != 1.0 {
+            write!(f, " scale={}", self.scale)?;
+        }
+        Ok(())
+    }
+}
+
+/// Action of a [KInputActionMap] with its bindings and states.
+struct KInputAction {
+    /// Name of the action.
+    name : String,
+
+    /// Bindings of the action.
+    bindings : Vec<KInputBinding>,
+
+    /// Current value of the action.
+    value : f32,
+
+    /// Action pressed since last frame reset.
+    pressed : bool,
+
+    /// Action released since last frame reset.
+    released : bool,
+
+    /// Action pressed edge was set and its released edge is pending. Input completing a capture never sets it.
+    held : bool,
+}
+
+/// Capture of the next input requested with [KInputActionMap::capture_next_input()].
+struct KInputCapture {
+    /// Index of action to bind.
+    action : usize,
+
+    /// If True, captured input replaces action bindings.
+    replace : bool,
+}
+
+/// ##### Map of game actions bound to keyboard, mouse and controller inputs.
+///
+/// [KInputActionMap] is a [KEventReceiver] that gives the value of actions (ie. "Jump", "MoveX") according to their
+/// [KInputBinding]. Each action can have multiple bindings and its value is the one of the binding with the highest absolute value.
+/// An action is down if its value isn't 0.0.
+///
+/// Pressed and released edges are kept until [KInputActionMap::reset_frame()] is called, usually once per frame before [KWindow::dispatch_events()].
+///
+/// # Bindings configuration
+/// Bindings are serialized with 1 action per line, `#` starting a comment :
+/// ```text
+/// # Action = Binding | Binding
+/// Jump = Scancode(Space) | ControllerButton(0)
+/// MoveX = Scancode(D) | Scancode(A) scale=-1 | ControllerAxis(0) dead_zone=0.2 scale=1.5
+/// Undo = Key(LeftControl)+Key(Character('z'))
+/// ```
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::display::event::{KEvent, KEventKeyboard, KEventReceiver, KInputActionMap, KKey, KKeyInput, KKeyModifiers, KScancode};
+///
+/// let mut map = KInputActionMap::parse("MoveX = Scancode(D) | Scancode(A) scale=-1").unwrap();
+///
+/// map.handle_event(&KEvent::Keyboard(KEventKeyboard::KeyDown(KKeyInput::new(KScancode::A, KKey::Character('q'), KKeyModifiers::NONE, false))).into());
+///
+/// assert_eq!(map.get_action_value("MoveX"), -1.0);
+/// assert!(map.is_action_pressed("MoveX"));
+/// ```
+pub struct KInputActionMap {
+    /// If False, no [KEvent] is received.
+    enabled : bool,
+
+    /// Actions in order of addition.
+    actions : Vec<KInputAction>,
+
+    /// Inputs states used to compute action values.
+    state : KInputState,
+
+    /// Controller id used by controller inputs. Any controller if None.
+    controller : Option<u8>,
+
+    /// Pending capture of next input.
+    capture : Option<KInputCapture>,
+}
+
+impl KInputActionMap {
+    /// Create a new empty [KInputActionMap] listening to any controller.
+    pub fn new() -> KInputActionMap {
+        KInputActionMap { enabled: true, actions: Vec::new(), state: KInputState::new(), controller: None, capture: None }
+    }
+
+    /// Create a new [KInputActionMap] from a bindings configuration. See [KInputActionMap] for format.
+    ///
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(line))]`)` if a line can't be parsed.
+    pub fn parse(config : &str) -> Result<KInputActionMap, OlympusError> {
+        let mut map = KInputActionMap::new();
+
+        for (index, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(index + 1));
+            let (name, bindings) = line.split_once('=').ok_or(error)?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(error);
+            }
+
+            map.add_action(name);
+            for binding in split_unquoted(bindings, |c| c == '|').iter().filter(|b| !b.trim().is_empty()) {
+                map.add_binding(name, parse_binding(binding).ok_or(error)?);
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Create a new [KInputActionMap] from a bindings configuration file.
+    ///
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KInputActionMap(KInputActionMapError::FileError)]`)` if file can't be read.
+    ///
+    /// Returns `Err(`[OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(line))]`)` if a line can't be parsed.
+    pub fn load<P: AsRef<Path>>(path : P) -> Result<KInputActionMap, OlympusError> {
+        match std::fs::read_to_string(path) {
+            Ok(config) => KInputActionMap::parse(&config),
+            Err(_) => Err(OlympusError::KInputActionMap(KInputActionMapError::FileError)),
+        }
+    }
+
+    /// Write the bindings configuration into a file.
+    ///
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KInputActionMap(KInputActionMapError::FileError)]`)` if file can't be written.
+    pub fn save<P: AsRef<Path>>(&self, path : P) -> Result<(), OlympusError> {
+        match std::fs::write(path, self.to_string()) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(OlympusError::KInputActionMap(KInputActionMapError::FileError)),
+        }
+    }
+
+    /// Add an action without binding. Nothing happens if action already exists.
+    pub fn add_action(&mut self, action : &str) {
+        if self.get_action_index(action).is_err() {
+            self.actions.push(KInputAction { name: action.to_string(), bindings: Vec::new(), value: 0.0, pressed: false, released: false, held: false });
+        }
+    }
+
+    /// Add a binding to an action. Action is added if it doesn't exist.
+    pub fn add_binding(&mut self, action : &str, binding : impl Into<KInputBinding>) {
+        self.add_action(action);
+        if let Ok(index) = self.get_action_index(action) {
+            self.actions[index].bindings.push(binding.into());
+        }
+    }
+
+    /// Remove all bindings of an action.
+    ///
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KInputActionMap(KInputActionMapError::ActionNotFound)]`)` if action doesn't exist.
+    pub fn clear_bindings(&mut self, action : &str) -> Result<(), OlympusError> {
+        let index = self.get_action_index(action)?;
+        self.actions[index].bindings.clear();
+        Ok(())
+    }
+
+    /// Remove an action and its bindings.
+    ///
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KInputActionMap(KInputActionMapError::ActionNotFound)]`)` if action doesn't exist.
+    pub fn remove_action(&mut self, action : &str) -> Result<(), OlympusError> {
+        let index = self.get_action_index(action)?;
+        self.actions.remove(index);
+        self.capture = None;
+        Ok(())
+    }
+
+    /// Returns the names of actions in order of addition.
+    pub fn get_actions(&self) -> Vec<&str> {
+        self.actions.iter().map(|a| a.name.as_str()).collect()
+    }
+
+    /// Returns the bindings of an action or [None] if action doesn't exist.
+    pub fn get_bindings(&self, action : &str) -> Option<&[KInputBinding]> {
+        self.get_action_index(action).ok().map(|index| self.actions[index].bindings.as_slice())
+    }
+
+    /// Set the controller id used by controller inputs. Any connected controller is used if None.
+    pub fn set_controller(&mut self, controller : Option<u8>) {
+        self.controller = controller;
+    }
+
+    /// Returns the controller id used by controller inputs or None if any controller is used.
+    pub fn get_controller(&self) -> Option<u8> {
+        self.controller
+    }
+
+    /// Enable or disable the [KInputActionMap]. A disabled [KInputActionMap] doesn't receive [KEvent].
+    pub fn set_enabled(&mut self, enabled : bool) {
+        self.enabled = enabled;
+    }
+
+    /// Bind the next key, mouse button, controller button or controller axis moved over [KINPUT_CAPTURE_AXIS_THRESHOLD] to an action.
+    ///
+    /// Keys are captured as [KInput::Scancode] and axes with the scale sign of the direction moved. The [KEvent] captured is
+    /// handled and not given to other receivers. If replace is True, captured input replaces all bindings of the action.
+    ///
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KInputActionMap(KInputActionMapError::ActionNotFound)]`)` if action doesn't exist.
+    pub fn capture_next_input(&mut self, action : &str, replace : bool) -> Result<(), OlympusError> {
+        let action = self.get_action_index(action)?;
+        self.capture = Some(KInputCapture { action, replace });
+        Ok(())
+    }
+
+    /// Cancel the capture of next input.
+    pub fn cancel_capture(&mut self) {
+        self.capture = None;
+    }
+
+    /// Returns True if next input will be captured.
+    pub fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    /// Clear actions pressed and released edges.
+    ///
+    /// Should be called once per frame before [KWindow::dispatch_events()].
+    pub fn reset_frame(&mut self) {
+        self.state.reset_frame();
+        for action in self.actions.iter_mut() {
+            action.pressed = false;
+            action.released = false;
+        }
+    }
+
+    /// Returns the value of an action. Returns 0.0 if action doesn't exist.
+    pub fn get_action_value(&self, action : &str) -> f32 {
+        self.get_action_index(action).map(|index| self.actions[index].value).unwrap_or(0.0)
+    }
+
+    /// Returns True if action value isn't 0.0.
+    pub fn is_action_down(&self, action : &str) -> bool {
+        self.get_action_value(action) != 0.0
+    }
+
+    /// Returns True if action was pressed since last frame reset.
+    pub fn is_action_pressed(&self, action : &str) -> bool {
+        self.get_action_index(action).is_ok_and(|index| self.actions[index].pressed)
+    }
+
+    /// Returns True if action was released since last frame reset.
+    pub fn is_action_released(&self, action : &str) -> bool {
+        self.get_action_index(action).is_ok_and(|index| self.actions[index].released)
+    }
+
+    /// Returns the index of an action.
+    ///
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KInputActionMap(KInputActionMapError::ActionNotFound)]`)` if action doesn't exist.
+    fn get_action_index(&self, action : &str) -> Result<usize, OlympusError> {
+        match self.actions.iter().position(|a| a.name == action) {
+            Some(index) => Ok(index),
+            None => Err(OlympusError::KInputActionMap(KInputActionMapError::ActionNotFound)),
+        }
+    }
+
+    /// Returns the input and scale captured from an event, if any.
+    fn get_captured_input(&self, event : &KEvent) -> Option<(KInput, f32)> {
+        match event {
+            KEvent::Keyboard(KEventKeyboard::KeyDown(input)) if !input.repeat => Some((KInput::Scancode(input.scancode), 1.0)),
+            KEvent::Mouse(KEventMouse::ButtonDown(button, _)) => Some((KInput::MouseButton(*button), 1.0)),
+            KEvent::Controller(KEventController::ButtonDown(controller, button)) if self.is_controller_used(*controller) =>
+                Some((KInput::ControllerButton(*button), 1.0)),
+            KEvent::Controller(KEventController::Axis(controller, axis, value)) if self.is_controller_used(*controller)
+                && value.unsigned_abs() >= KINPUT_CAPTURE_AXIS_THRESHOLD as u16 =>
+                Some((KInput::ControllerAxis(*axis), if *value < 0 { -1.0 } else { 1.0 })),
+            _ => None,
+        }
+    }
+
+    /// Returns True if controller inputs of controller id are used.
+    fn is_controller_used(&self, controller : u8) -> bool {
+        self.controller.is_none_or(|c| c == controller)
+    }
+
+    /// Returns the value of an input, from -1.0 to 1.0. Buttons give 1.0 when held.
+    fn get_input_value(&self, input : &KInput) -> f32 {
+        let controllers = match self.controller {
+            Some(controller) => vec![controller],
+            None => self.state.get_controllers(),
+        };
+
+        match input {
+            KInput::Key(key) => self.state.is_key_down(*key) as u8 as f32,
+            KInput::Scancode(scancode) => self.state.is_scancode_down(*scancode) as u8 as f32,
+            KInput::MouseButton(button) => self.state.is_mouse_button_down(*button) as u8 as f32,
+            KInput::ControllerButton(button) => controllers.iter().any(|c| self.state.is_controller_button_down(*c, *button)) as u8 as f32,
+            KInput::ControllerAxis(axis) => controllers.iter()
+                .map(|c| (self.state.get_controller_axis(*c, *axis) as f32 / i16::MAX as f32).clamp(-1.0, 1.0))
+                .fold(0.0, |max, value| if value.abs() > f32::abs(max) { value } else { max }),
+        }
+    }
+
+    /// Returns the value of a binding. Returns 0.0 if an input of the chord isn't held.
+    fn get_binding_value(&self, binding : &KInputBinding) -> f32 {
+        match binding.inputs.split_last() {
+            Some((last, chord)) => {
+                if chord.iter().any(|input| get_dead_zone_value(self.get_input_value(input), binding.dead_zone) == 0.0) {
+                    0.0
+                } else {
+                    get_dead_zone_value(self.get_input_value(last), binding.dead_zone) * binding.scale
+                }
+            },
+            None => 0.0,
+        }
+    }
+
+    /// Returns the value of an action according to its bindings.
+    fn get_bindings_value(&self, bindings : &[KInputBinding]) -> f32 {
+        bindings.iter().map(|b| self.get_binding_value(b)).fold(0.0, |max, value| if value.abs() > f32::abs(max) { value } else { max })
+    }
+
+    /// Update actions values with current input states. Pressed and released edges are set if edges is True.
+    ///
+    /// Released edge is only set for actions which had their pressed edge set, so releasing a captured input is silent.
+    fn update_actions(&mut self, edges : bool) {
+        for index in 0..self.actions.len() {
+            let value = self.get_bindings_value(&self.actions[index].bindings);
+            let action = &mut self.actions[index];
+
+            if edges && value != 0.0 && action.value == 0.0 {
+                action.pressed = true;
+                action.held = true;
+            } else if edges && value == 0.0 && action.held {
+                action.released = true;
+            }
+            action.held &= value != 0.0;
+            action.value = value;
+        }
+    }
+}
+
+impl Default for KInputActionMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for KInputActionMap {
+    /// Bindings configuration with 1 action per line. See [KInputActionMap] for format.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for action in self.actions.iter() {
+            let bindings : Vec<String> = action.bindings.iter().map(|b| b.to_string()).collect();
+            writeln!(f, "{} = {}", action.name, bindings.join(" | "))?;
+        }
+        Ok(())
+    }
+}
+
+impl KEventReceiver for KInputActionMap {
+    /// Update actions with [KEvent]. Returns True only if [KEvent] was captured by [KInputActionMap::capture_next_input()].
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        self.state.handle_event(entry);
+
+        if let Some(capture) = &self.capture {
+            if let Some((input, scale)) = self.get_captured_input(entry.get_event()) {
+                let action = &mut self.actions[capture.action];
+                if capture.replace {
+                    action.bindings.clear();
+                }
+                action.bindings.push(KInputBinding::new(&[input], 0.0, scale));
+                self.capture = None;
+
+                // Captured input doesn't press the action.
+                self.update_actions(false);
+                return true;
+            }
+        }
+
+        self.update_actions(true);
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Returns an axis value with dead zone applied. Values outside dead zone are rescaled from 0.0 to 1.0.
+fn get_dead_zone_value(value : f32, dead_zone : f32) -> f32 {
+    if value.abs() <= dead_zone || dead_zone >= 1.0 {
+        0.0
+    } else {
+        value.signum() * (value.abs() - dead_zone.max(0.0)) / (1.0 - dead_zone.max(0.0))
+    }
+}
+
+/// Split text on separator characters outside of quoted characters (ie. `Character('|')`).
+fn split_unquoted(text : &str, separator : impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (index, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if quoted && c == '\\' {
+            escaped = true;
+        } else if c == '\'' {
+            quoted = !quoted;
+        } else if !quoted && separator(c) {
+            parts.push(&text[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Parse a binding from inputs separated by `+` followed by optional `dead_zone=` and `scale=`.
+fn parse_binding(text : &str) -> Option<KInputBinding> {
+    let mut tokens = split_unquoted(text.trim(), char::is_whitespace).into_iter().filter(|t| !t.is_empty());
+
+    let inputs = split_unquoted(tokens.next()?, |c| c == '+').into_iter().map(parse_input).collect::<Option<Vec<KInput>>>()?;
+    let mut binding = KInputBinding::new(&inputs, 0.0, 1.0);
+
+    for token in tokens {
+        match token.split_once('=')? {
+            ("dead_zone", value) => binding.dead_zone = value.parse().ok()?,
+            ("scale", value) => binding.scale = value.parse().ok()?,
+            _ => return None,
+        }
+    }
+    Some(binding)
+}
+
+/// Parse an input from its [Debug] form.
+fn parse_input(text : &str) -> Option<KInput> {
+    let (name, value) = split_variant(text)?;
+
+    match name {
+        "Key" => parse_key(value?).map(KInput::Key),
+        "Scancode" => parse_scancode(value?).map(KInput::Scancode),
+        "MouseButton" => parse_mouse_button(value?).map(KInput::MouseButton),
+        "ControllerButton" => value?.parse().ok().map(KInput::ControllerButton),
+        "ControllerAxis" => value?.parse().ok().map(KInput::ControllerAxis),
+        _ => None,
+    }
+}
+
+/// Split an enumeration variant [Debug] form into name and optional value between parentheses.
+fn split_variant(text : &str) -> Option<(&str, Option<&str>)> {
+    let text = text.trim();
+    match text.split_once('(') {
+        Some((name, value)) => Some((name, Some(value.strip_suffix(')')?))),
+        None => Some((text, None)),
+    }
+}
+
+/// Parse a [KScancode] from its [Debug] form.
+pub(super) fn parse_scancode(text : &str) -> Option<KScancode> {
+    match split_variant(text)? {
+        ("Function", Some(value)) => value.parse().ok().map(KScancode::Function),
+        ("Unknown", Some(value)) => value.parse().ok().map(KScancode::Unknown),
+        (name, None) => SCANCODES.iter().find(|s| format!("{:?}", s) == name).copied(),
+        _ => None,
+    }
+}
+
+/// Parse a [KKey] from its [Debug] form.
+pub(super) fn parse_key(text : &str) -> Option<KKey> {
+    match split_variant(text)? {
+        ("Character", Some(value)) => parse_char(value).map(KKey::Character),
+        ("Function", Some(value)) => value.parse().ok().map(KKey::Function),
+        ("Unknown", Some(value)) => value.parse().ok().map(KKey::Unknown),
+        (name, None) => KEYS.iter().find(|k| format!("{:?}", k) == name).copied(),
+        _ => None,
+    }
+}
+
+/// Parse a [KMouseButton] from its [Debug] form.
+pub(super) fn parse_mouse_button(text : &str) -> Option<KMouseButton> {
+    match split_variant(text)? {
+        ("Left", None) => Some(KMouseButton::Left),
+        ("Middle", None) => Some(KMouseButton::Middle),
+        ("Right", None) => Some(KMouseButton::Right),
+        ("Back", None) => Some(KMouseButton::Back),
+        ("Forward", None) => Some(KMouseButton::Forward),
+        ("Other", Some(value)) => value.parse().ok().map(KMouseButton::Other),
+        _ => None,
+    }
+}
+
+/// Parse a quoted character from its [Debug] form (ie. `'a'`, `'\''`, `'\u{301}'`).
+fn parse_char(text : &str) -> Option<char> {
+    let text = text.strip_prefix('\'')?.strip_suffix('\'')?;
+
+    match text.strip_prefix('\\') {
+        Some(escape) => match escape {
+            "'" => Some('\''),
+            "\"" => Some('"'),
+            "\\" => Some('\\'),
+            "n" => Some('\n'),
+            "r" => Some('\r'),
+            "t" => Some('\t'),
+            "0" => Some('\0'),
+            _ => char::from_u32(u32::from_str_radix(escape.strip_prefix("u{")?.strip_suffix('}')?, 16).ok()?),
+        },
+        None => {
+            let mut chars = text.chars();
+            let c = chars.next()?;
+            chars.next().is_none().then_some(c)
+        },
+    }
+}
+
+/// [KScancode] without value, used to parse names.
+const SCANCODES : [KScancode; 94] = [
+    KScancode::A, KScancode::B, KScancode::C, KScancode::D, KScancode::E, KScancode::F, KScancode::G, KScancode::H, KScancode::I,
+    KScancode::J, KScancode::K, KScancode::L, KScancode::M, KScancode::N, KScancode::O, KScancode::P, KScancode::Q, KScancode::R,
+    KScancode::S, KScancode::T, KScancode::U, KScancode::V, KScancode::W, KScancode::X, KScancode::Y, KScancode::Z,
+    KScancode::Digit0, KScancode::Digit1, KScancode::Digit2, KScancode::Digit3, KScancode::Digit4, KScancode::Digit5, KScancode::Digit6,
+    KScancode::Digit7, KScancode::Digit8, KScancode::Digit9,
+    KScancode::Escape, KScancode::Enter, KScancode::Tab, KScancode::Backspace, KScancode::Space,
+    KScancode::Minus, KScancode::Equal, KScancode::LeftBracket, KScancode::RightBracket, KScancode::Backslash, KScancode::Semicolon,
+    KScancode::Apostrophe, KScancode::Grave, KScancode::Comma, KScancode::Period, KScancode::Slash, KScancode::NonUsBackslash,
+    KScancode::Insert, KScancode::Delete, KScancode::Home, KScancode::End, KScancode::PageUp, KScancode::PageDown,
+    KScancode::Left, KScancode::Right, KScancode::Up, KScancode::Down,
+    KScancode::CapsLock, KScancode::NumLock, KScancode::ScrollLock, KScancode::PrintScreen, KScancode::Pause, KScancode::Menu,
+    KScancode::LeftShift, KScancode::RightShift, KScancode::LeftControl, KScancode::RightControl, KScancode::LeftAlt, KScancode::RightAlt,
+    KScancode::LeftSuper, KScancode::RightSuper,
+    KScancode::Keypad0, KScancode::Keypad1, KScancode::Keypad2, KScancode::Keypad3, KScancode::Keypad4, KScancode::Keypad5,
+    KScancode::Keypad6, KScancode::Keypad7, KScancode::Keypad8, KScancode::Keypad9,
+    KScancode::KeypadPeriod, KScancode::KeypadDivide, KScancode::KeypadMultiply, KScancode::KeypadMinus, KScancode::KeypadPlus,
+    KScancode::KeypadEnter, KScancode::KeypadEqual,
+];
+
+/// [KKey] without value, used to parse names.
+const KEYS : [KKey; 28] = [
+    KKey::Escape, KKey::Enter, KKey::Tab, KKey::Backspace,
+    KKey::Insert, KKey::Delete, KKey::Home, KKey::End, KKey::PageUp, KKey::PageDown,
+    KKey::Left, KKey::Right, KKey::Up, KKey::Down,
+    KKey::CapsLock, KKey::NumLock, KKey::ScrollLock, KKey::PrintScreen, KKey::Pause, KKey::Menu,
+    KKey::LeftShift, KKey::RightShift, KKey::LeftControl, KKey::RightControl, KKey::LeftAlt, KKey::RightAlt, KKey::LeftSuper, KKey::RightSuper,
+];
@@ -0,0 +1,121 @@
+use std::sync::mpsc::{self, Sender, Receiver};
+
+use super::{KEventDispatcher, KEventEntry, KEventReceiver};
+
+#[allow(unused_imports)]
+use super::{KEvent, KEventReceiverMode};
+
+#[allow(unused_imports)]
+use super::super::KWindow;
+
+/// ##### Receiving end of a bridge that forwards [KEvent] from the [KWindow] thread to another thread.
+///
+/// [KEventDispatcher] holds [KEventReceiver] in [Rc](std::rc::Rc) [RefCell](std::cell::RefCell) and can't be sent to another thread.
+/// [KEventBridgeSender] is added to the [KEventDispatcher] of the [KWindow] thread and pushes each [KEventEntry] into a lock-free queue.
+/// [KEventBridgeReceiver] is sent to the other thread and dispatches queued [KEventEntry] to a [KEventDispatcher] of that thread, keeping
+/// the stack ordered consumption of [KEventReceiver].
+///
+/// Timestamp and window id of [KEventEntry] are kept. Sequence numbers are given by the [KEventDispatcher] of the other thread.
+///
+/// # Example(s)
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use olympus::kleio::display::event::{KEvent, KEventDispatcher, KEventBridgeReceiver, KEventMouse};
+///
+/// let mut bridge = KEventBridgeReceiver::new();
+///
+/// // Window thread dispatcher forwards events to bridge.
+/// let mut window_dispatcher = KEventDispatcher::new(false);
+/// let _handle = window_dispatcher.add_event_receiver(Rc::new(RefCell::new(bridge.create_sender()))).unwrap();
+/// window_dispatcher.dispatch(&KEvent::Mouse(KEventMouse::Moved((10, 10))));
+///
+/// // Simulation thread dispatches forwarded events to its receivers.
+/// let simulation = std::thread::spawn(move || {
+///     let mut dispatcher = KEventDispatcher::new(true);
+///     bridge.dispatch_events(&mut dispatcher)
+/// });
+/// assert_eq!(simulation.join().unwrap(), 1);
+/// ```
+pub struct KEventBridgeReceiver {
+    /// Sender cloned into each [KEventBridgeSender].
+    sender : Sender<KEventEntry>,
+
+    /// Queue of forwarded [KEventEntry].
+    receiver : Receiver<KEventEntry>,
+}
+
+impl KEventBridgeReceiver {
+    /// Create a new [KEventBridgeReceiver] with an empty queue.
+    pub fn new() -> KEventBridgeReceiver {
+        let (sender, receiver) = mpsc::channel();
+        KEventBridgeReceiver { sender, receiver }
+    }
+
+    /// Create a [KEventBridgeSender] that forwards [KEventEntry] to this queue. Multiple senders can forward to the same queue.
+    pub fn create_sender(&self) -> KEventBridgeSender {
+        KEventBridgeSender { sender: self.sender.clone(), handle: false }
+    }
+
+    /// Pop the oldest forwarded [KEventEntry] or None if queue is empty.
+    pub fn poll_event(&mut self) -> Option<KEventEntry> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Dispatch all forwarded [KEventEntry] to a [KEventDispatcher] in the order they were received.
+    ///
+    /// Returns the count of [KEvent] dispatched.
+    pub fn dispatch_events(&mut self, dispatcher : &mut KEventDispatcher) -> usize {
+        let mut count : usize = 0;
+
+        while let Ok(entry) = self.receiver.try_recv() {
+            dispatcher.dispatch_from(entry.get_event(), entry.get_timestamp(), entry.get_window_id());
+            count += 1;
+        }
+        count
+    }
+}
+
+impl Default for KEventBridgeReceiver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ##### [KEventReceiver] that forwards [KEventEntry] to a [KEventBridgeReceiver].
+///
+/// By default, [KEventBridgeSender] doesn't handle [KEvent] so older [KEventReceiver] of the [KWindow] thread still receive them.
+/// It can also be added as a [KEventReceiverMode::Observer].
+#[derive(Clone)]
+pub struct KEventBridgeSender {
+    /// Sending end of the queue.
+    sender : Sender<KEventEntry>,
+
+    /// Value returned by [KEventReceiver::handle_event()].
+    handle : bool,
+}
+
+impl KEventBridgeSender {
+    /// Set if forwarded [KEvent] are handled, preventing older [KEventReceiver] of the [KWindow] thread from receiving them.
+    pub fn set_handle_events(&mut self, handle : bool) {
+        self.handle = handle;
+    }
+
+    /// Returns True if forwarded [KEvent] are handled.
+    pub fn is_handling_events(&self) -> bool {
+        self.handle
+    }
+}
+
+impl KEventReceiver for KEventBridgeSender {
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        // Receiving end dropped, nothing to forward to.
+        if self.sender.send(*entry).is_err() {
+            return false;
+        }
+        self.handle
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,119 @@
+use std::ops::{BitOr, BitOrAssign};
+
+use super::KEvent;
+
+#[allow(unused_imports)]
+use super::{KEventDispatcher, KEventReceiver};
+
+/// Count of [KEvent] categories.
+pub const KEVENT_CATEGORY_COUNT : usize = 7;
+
+/// ##### Set of [KEvent] categories a [KEventReceiver] subscribes to.
+///
+/// Categories are combined with `|` like flags.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::display::event::{KEvent, KEventCategories, KEventMouse};
+///
+/// let categories = KEventCategories::KEYBOARD | KEventCategories::MOUSE;
+///
+/// assert!(categories.contains_event(&KEvent::Mouse(KEventMouse::Wheel(0, 1))));
+/// assert!(!categories.contains(KEventCategories::WINDOW));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct KEventCategories {
+    /// Categories flags.
+    flags : u8,
+}
+
+impl KEventCategories {
+    /// No category.
+    pub const NONE : KEventCategories = KEventCategories { flags: 0 };
+
+    /// [KEvent::Window] events.
+    pub const WINDOW : KEventCategories = KEventCategories { flags: 1 };
+
+    /// [KEvent::Keyboard] events.
+    pub const KEYBOARD : KEventCategories = KEventCategories { flags: 2 };
+
+    /// [KEvent::Mouse] events.
+    pub const MOUSE : KEventCategories = KEventCategories { flags: 4 };
+
+    /// [KEvent::Controller] events.
+    pub const CONTROLLER : KEventCategories = KEventCategories { flags: 8 };
+
+    /// [KEvent::Touch] events.
+    pub const TOUCH : KEventCategories = KEventCategories { flags: 16 };
+
+    /// [KEvent::None] and [KEvent::Unknown] events.
+    pub const OTHER : KEventCategories = KEventCategories { flags: 32 };
+
+    /// [KEvent::User] events.
+    pub const USER : KEventCategories = KEventCategories { flags: 64 };
+
+    /// All categories.
+    pub const ALL : KEventCategories = KEventCategories { flags: 127 };
+
+    /// Returns the category of a [KEvent].
+    pub fn from_event(event : &KEvent) -> KEventCategories {
+        match event {
+            KEvent::Window(_) => KEventCategories::WINDOW,
+            KEvent::Keyboard(_) => KEventCategories::KEYBOARD,
+            KEvent::Mouse(_) => KEventCategories::MOUSE,
+            KEvent::Controller(_) => KEventCategories::CONTROLLER,
+            KEvent::Touch(_) => KEventCategories::TOUCH,
+            KEvent::User(_) => KEventCategories::USER,
+            KEvent::None | KEvent::Unknown => KEventCategories::OTHER,
+        }
+    }
+
+    /// Returns the index of the category of a [KEvent], from 0 to [KEVENT_CATEGORY_COUNT] excluded.
+    pub(crate) fn get_event_index(event : &KEvent) -> usize {
+        KEventCategories::from_event(event).flags.trailing_zeros() as usize
+    }
+
+    /// Returns the categories flags.
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Returns True if all categories given are in the set.
+    pub fn contains(&self, categories : KEventCategories) -> bool {
+        self.flags & categories.flags == categories.flags
+    }
+
+    /// Returns True if the category of the [KEvent] is in the set.
+    pub fn contains_event(&self, event : &KEvent) -> bool {
+        self.contains(KEventCategories::from_event(event))
+    }
+
+    /// Returns True if no category is in the set.
+    pub fn is_empty(&self) -> bool {
+        self.flags == 0
+    }
+
+    /// Add categories to the set.
+    pub fn insert(&mut self, categories : KEventCategories) {
+        self.flags |= categories.flags;
+    }
+
+    /// Remove categories from the set.
+    pub fn remove(&mut self, categories : KEventCategories) {
+        self.flags &= !categories.flags;
+    }
+}
+
+impl BitOr for KEventCategories {
+    type Output = KEventCategories;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        KEventCategories { flags: self.flags | rhs.flags }
+    }
+}
+
+impl BitOrAssign for KEventCategories {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.flags |= rhs.flags;
+    }
+}
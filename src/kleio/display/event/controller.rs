@@ -9,13 +9,20 @@ pub enum KEventController {
     Disconnected(u8),
 
     /// Happens when a controller button is pressed. Provides controller id and button id.
+    /// 
+    /// Button id is a [KControllerButton] or a raw button index + [KCONTROLLER_RAW_BUTTON_FIRST] if the button isn't part of the standard layout.
     ButtonDown(u8, u8),
 
     /// Happens when a controller button is released. Provides controller id and button id.
+    /// 
+    /// Button id is a [KControllerButton] or a raw button index + [KCONTROLLER_RAW_BUTTON_FIRST] if the button isn't part of the standard layout.
     ButtonUp(u8, u8),
 
     /// Happens when a controller axis is used. Provides controller id, axis id and axis value range from (range: -32768 to 32767).
     /// 
+    /// Axis id is a [KControllerAxis] or a raw axis index + [KCONTROLLER_RAW_AXIS_FIRST] if the axis isn't part of the standard layout.
+    /// Triggers range from 0 (released) to 32767 (fully pressed).
+    /// 
     /// # Reference(s)
     /// Based on SDL_ControllerAxisEvent : <https://wiki.libsdl.org/SDL2/SDL_ControllerAxisEvent>
     Axis(u8, u8, i16)
@@ -33,3 +40,68 @@ impl std::fmt::Debug for KEventController {
         }
     }
 }
+
+
+/// First button id given to buttons that aren't part of the [KControllerButton] standard layout.
+pub const KCONTROLLER_RAW_BUTTON_FIRST : u8 = 32;
+
+/// First axis id given to axes that aren't part of the [KControllerAxis] standard layout.
+pub const KCONTROLLER_RAW_AXIS_FIRST : u8 = 16;
+
+/// ##### Standard controller buttons layout.
+/// 
+/// Buttons are named after their position on a Xbox controller. [KControllerButton::A] is the bottom face button
+/// (cross on a PlayStation controller).
+/// 
+/// # Reference(s)
+/// Based on SDL_GameControllerButton : <https://wiki.libsdl.org/SDL2/SDL_GameControllerButton>
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KControllerButton {
+    A, B, X, Y, Back, Guide, Start, LeftStick, RightStick, LeftShoulder, RightShoulder,
+
+    DPadUp, DPadDown, DPadLeft, DPadRight,
+
+    /// Extra button like share, microphone or capture.
+    Misc1,
+
+    Paddle1, Paddle2, Paddle3, Paddle4,
+
+    /// Touchpad click.
+    Touchpad,
+}
+
+impl KControllerButton {
+    /// Returns the [KControllerButton] of a button id or [None] if id isn't part of the standard layout.
+    pub fn from_id(id : u8) -> Option<KControllerButton> {
+        KCONTROLLER_BUTTONS.get(id as usize).copied()
+    }
+}
+
+/// ##### Standard controller axes layout.
+/// 
+/// # Reference(s)
+/// Based on SDL_GameControllerAxis : <https://wiki.libsdl.org/SDL2/SDL_GameControllerAxis>
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KControllerAxis {
+    LeftX, LeftY, RightX, RightY, TriggerLeft, TriggerRight,
+}
+
+impl KControllerAxis {
+    /// Returns the [KControllerAxis] of an axis id or [None] if id isn't part of the standard layout.
+    pub fn from_id(id : u8) -> Option<KControllerAxis> {
+        KCONTROLLER_AXES.get(id as usize).copied()
+    }
+}
+
+/// Standard buttons ordered by id.
+const KCONTROLLER_BUTTONS : [KControllerButton; 21] = [KControllerButton::A, KControllerButton::B, KControllerButton::X, KControllerButton::Y,
+    KControllerButton::Back, KControllerButton::Guide, KControllerButton::Start, KControllerButton::LeftStick, KControllerButton::RightStick,
+    KControllerButton::LeftShoulder, KControllerButton::RightShoulder, KControllerButton::DPadUp, KControllerButton::DPadDown,
+    KControllerButton::DPadLeft, KControllerButton::DPadRight, KControllerButton::Misc1, KControllerButton::Paddle1, KControllerButton::Paddle2,
+    KControllerButton::Paddle3, KControllerButton::Paddle4, KControllerButton::Touchpad];
+
+/// Standard axes ordered by id.
+const KCONTROLLER_AXES : [KControllerAxis; 6] = [KControllerAxis::LeftX, KControllerAxis::LeftY, KControllerAxis::RightX, KControllerAxis::RightY,
+    KControllerAxis::TriggerLeft, KControllerAxis::TriggerRight];
@@ -1,32 +1,99 @@
-use std::{rc::Rc, cell::RefCell};
-use crate::error::{OlympusError, KEventDispatcherError};
+use std::{rc::Rc, cell::RefCell, collections::VecDeque, time::Instant, sync::mpsc::{self, Receiver}};
+use crate::{error::{OlympusError, KEventDispatcherError}, kleio::journal::{KJournal, KJournalEntrySeverity}};
 
-use super::KEvent;
+use super::{KEvent, KEventEntry, KEventRecorder, KEventCategories, KEventReceiverHandle, KEVENT_NO_WINDOW, KEVENT_CATEGORY_COUNT};
+use super::receiver::{KEventReceiverClosure, KEventReceiverChannel};
 
 #[allow(unused_imports)]
 use super::super::KWindow;
 
+/// Name of the layer used by [KEventDispatcher::add_event_receiver()]. Its priority is 0.
+pub const KEVENT_DEFAULT_LAYER : &str = "default";
+
+/// Default count of unhandled [KEvent] kept by [KEventDispatcher] before the oldest are dropped.
+pub const KEVENT_UNHANDLED_CAPACITY : usize = 256;
+
+/// Target of [KJournal] entries written by [KEventDispatcher::forward_unhandled_events()].
+pub const KEVENT_UNHANDLED_JOURNAL_TARGET : &str = "kleio::display::event";
+
 /// [KEventDispatcher] dispatch [KWindow] [KEvent] to [KEventReceiver].
 /// 
 /// [KEventDispatcher::dispatch()]  from the most recent added [KEventReceiver] to the last, like a [Stack](https://en.wikipedia.org/wiki/Stack_(abstract_data_type)).
 /// That means that most recent [KEventReceiver] can mask events for older [KEventReceiver] if [KEventReceiver::handle_event()] returns True. 
+/// 
+/// Each [KEvent] is given to [KEventReceiver] as a [KEventEntry] with a timestamp, the id of the window that sent it and
+/// a sequence number incremented at each dispatch.
+/// 
+/// # Layer(s)
+/// [KEventReceiver] are grouped in named layers (ie. "debug console", "UI" and "game") added with [KEventDispatcher::add_layer()].
+/// Layers receive [KEvent] from the highest priority to the lowest and each layer is a stack of [KEventReceiver]. 
+/// A [KEventReceiver] only receives [KEvent] of the [KEventCategories] it subscribed to. A [KEventReceiverMode::Observer]
+/// receives [KEvent] even if already handled and can't handle them.
+/// 
+/// Every add method returns a [KEventReceiverHandle] identifying the [KEventReceiver]. It is removed when the handle is dropped
+/// or given to [KEventDispatcher::remove_event_receiver()].
+/// 
+/// # Example(s)
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use olympus::kleio::display::event::{KEventDispatcher, KEventCategories, KEventReceiverMode, KInputState, KTouchGestureRecognizer};
+/// 
+/// let mut dispatcher = KEventDispatcher::new(false);
+/// dispatcher.add_layer("UI", 10).unwrap();
+/// dispatcher.add_layer("game", 0).unwrap();
+/// 
+/// // Input state sees keyboard and mouse events, even those handled by UI.
+/// let state = Rc::new(RefCell::new(KInputState::new()));
+/// let _state_handle = dispatcher.add_event_receiver_to(state.clone(), "game", KEventCategories::KEYBOARD | KEventCategories::MOUSE, KEventReceiverMode::Observer).unwrap();
+/// 
+/// // Gestures are only recognized when UI doesn't handle touch events.
+/// let gestures = Rc::new(RefCell::new(KTouchGestureRecognizer::new()));
+/// let _gestures_handle = dispatcher.add_event_receiver_to(gestures.clone(), "game", KEventCategories::TOUCH, KEventReceiverMode::Consumer).unwrap();
+/// ```
 pub struct KEventDispatcher {
-    /// List of [KEventReceiver].
-    receivers : Vec<Rc<RefCell<dyn KEventReceiver>>>,
+    /// List of [KEventReceiver] with their subscription.
+    receivers : Vec<KEventSubscription>,
 
-    /// If true, [KEventDispatcher] will log unhandled events. Make sure to clear_unhandled_events() once in a while to prevent memory overload.
+    /// Layers sorted from highest to lowest priority.
+    layers : Vec<KEventLayer>,
+
+    /// If true, [KEventDispatcher] will log unhandled events in a ring buffer.
     log_unhandled : bool,
 
-    /// List of unhandled [KEvent]
-    unhandled : Vec<KEvent>,
+    /// Ring buffer of latest unhandled [KEvent].
+    unhandled : VecDeque<KEvent>,
+
+    /// Maximum count of unhandled [KEvent] kept.
+    unhandled_capacity : usize,
+
+    /// Count of unhandled [KEvent] per category.
+    unhandled_count : [u64; KEVENT_CATEGORY_COUNT],
+
+    /// Count of latest unhandled [KEvent] not forwarded to a [KJournal] yet.
+    unforwarded : usize,
+
+    /// Sequence number of the next dispatched [KEvent].
+    sequence : u64,
+
+    /// Recorder of dispatched [KEvent], if recording.
+    recorder : Option<KEventRecorder>,
+
+    /// Id given to the next [KEventReceiver] added.
+    next_id : u64,
+
+    /// Ids of [KEventReceiver] whose [KEventReceiverHandle] was dropped.
+    removed : Rc<RefCell<Vec<u64>>>,
 }
 
 impl KEventDispatcher {
     /// Create a new [KEventDispatcher] used to dispatch [KEvent] to [KEventReceiver] with possibility to log unhandled event.
     /// 
-    /// If log_unhandled_event is True, make sure to clear_unhandled_events() once in a while to prevent memory overload.
+    /// If log_unhandled_event is True, the latest [KEVENT_UNHANDLED_CAPACITY] unhandled [KEvent] are kept. Unhandled [KEvent] 
+    /// are counted per category either way.
     pub fn new(log_unhandled_event : bool) -> KEventDispatcher {
-        KEventDispatcher { receivers: Vec::new(), log_unhandled: log_unhandled_event, unhandled: Vec::new() }
+        KEventDispatcher { receivers: Vec::new(), layers: vec![KEventLayer { name: String::from(KEVENT_DEFAULT_LAYER), priority: 0, enabled: true }],
+            log_unhandled: log_unhandled_event, unhandled: VecDeque::new(), unhandled_capacity: KEVENT_UNHANDLED_CAPACITY, 
+            unhandled_count: [0; KEVENT_CATEGORY_COUNT], unforwarded: 0, sequence: 0, recorder: None, next_id: 0, removed: Rc::new(RefCell::new(Vec::new())) }
     }
 
     /// Dispatch a [KEvent] to the [KEventReceiver] list.
@@ -34,39 +101,191 @@ impl KEventDispatcher {
     /// [KEvent] dispatch from the most recent added [KEventReceiver] to the last, like a [Stack](https://en.wikipedia.org/wiki/Stack_(abstract_data_type)).
     /// That means that most recent [KEventReceiver] can mask events for older [KEventReceiver] if [KEventReceiver::handle_event()] returns True.
     ///
+    /// [KEvent] is timestamped now and isn't linked to a window. Use [KEventDispatcher::dispatch_from()] to give those.
+    ///
     /// Returns True if the [KEvent] was handled by a [KEventReceiver], false otherwise.
     pub fn dispatch(&mut self, event : &KEvent) -> bool {
-        // Iterate enabled receivers from newest to oldest
-        for receiver in self.receivers.iter().rev().filter(|x| x.borrow().is_enabled() ) {          
-            let mut receiver = receiver.borrow_mut();
-            if receiver.handle_event(&event) {
-                // Event has been handled, 
-                return true
+        self.dispatch_from(event, Instant::now(), KEVENT_NO_WINDOW)
+    }
+
+    /// Dispatch a [KEvent] polled at timestamp from a window id to the [KEventReceiver] list.
+    /// 
+    /// [KEvent] dispatch from the highest priority layer to the lowest, and in each layer from the most recent added [KEventReceiver]
+    /// to the last, like a [Stack](https://en.wikipedia.org/wiki/Stack_(abstract_data_type)). Once handled, only [KEventReceiverMode::Observer]
+    /// still receive the [KEvent].
+    ///
+    /// Returns True if the [KEvent] was handled by a [KEventReceiver], false otherwise.
+    pub fn dispatch_from(&mut self, event : &KEvent, timestamp : Instant, window_id : u64) -> bool {
+        self.remove_dropped_receivers();
+
+        let entry = KEventEntry::new(*event, timestamp, self.sequence, window_id);
+        self.sequence = self.sequence.wrapping_add(1);
+
+        // Record event before receivers so handled events are recorded too.
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(&entry);
+        }
+
+        let category = KEventCategories::from_event(event);
+        let mut handled = false;
+
+        // Iterate enabled layers by priority, then subscribed receivers from newest to oldest
+        for layer in self.layers.iter().filter(|l| l.enabled) {
+            for subscription in self.receivers.iter().rev().filter(|s| s.layer == layer.name && s.categories.contains(category)) {
+                // Event has been handled, only observers still receive it.
+                if handled && subscription.mode == KEventReceiverMode::Consumer {
+                    continue;
+                }
+
+                let mut receiver = subscription.receiver.borrow_mut();
+                if receiver.is_enabled() && receiver.handle_event(&entry) && subscription.mode == KEventReceiverMode::Consumer {
+                    handled = true;
+                }
             }
         }
-        if self.log_unhandled {
-            // Copy event in unhandled.
-            self.unhandled.push(*event);
+
+        if handled {
+            return true;
+        }
+        self.unhandled_count[KEventCategories::get_event_index(event)] += 1;
+        if self.log_unhandled && self.unhandled_capacity > 0 {
+            // Drop oldest event when buffer is full.
+            if self.unhandled.len() == self.unhandled_capacity {
+                self.unhandled.pop_front();
+            }
+            self.unhandled.push_back(*event);
+            self.unforwarded = (self.unforwarded + 1).min(self.unhandled.len());
         }
         false
     }
 
-    /// Clear all logged unhandled [KEvent].
+    /// Clear all logged unhandled [KEvent]. Counts of unhandled [KEvent] are kept.
     pub fn clear_unhandled_events(&mut self){
         self.unhandled.clear();
+        self.unforwarded = 0;
+    }
+
+    /// Set the maximum count of unhandled [KEvent] kept. Oldest unhandled [KEvent] are dropped if over capacity.
+    pub fn set_unhandled_capacity(&mut self, capacity : usize) {
+        self.unhandled_capacity = capacity;
+        while self.unhandled.len() > capacity {
+            self.unhandled.pop_front();
+        }
+        self.unforwarded = self.unforwarded.min(self.unhandled.len());
     }
 
+    /// Returns the maximum count of unhandled [KEvent] kept.
+    pub fn get_unhandled_capacity(&self) -> usize {
+        self.unhandled_capacity
+    }
 
-    /// Get a immutable reference to the list of unhandled [KEvent].
-    pub fn get_unhandled_events(&self) -> &Vec<KEvent>{
+    /// Returns the count of unhandled [KEvent] of categories given since creation or last [KEventDispatcher::reset_unhandled_count()].
+    /// 
+    /// Unhandled [KEvent] are counted even if not logged.
+    pub fn get_unhandled_count(&self, categories : KEventCategories) -> u64 {
+        self.unhandled_count.iter().enumerate().filter(|(index, _)| categories.get_flags() & (1 << index) != 0).map(|(_, count)| count).sum()
+    }
+
+    /// Reset counts of unhandled [KEvent] to 0.
+    pub fn reset_unhandled_count(&mut self) {
+        self.unhandled_count = [0; KEVENT_CATEGORY_COUNT];
+    }
+
+    /// Write unhandled [KEvent] logged since last call to a [KJournal] with [KJournalEntrySeverity::Debug] severity and 
+    /// [KEVENT_UNHANDLED_JOURNAL_TARGET] target.
+    /// 
+    /// Unhandled [KEvent] dropped from the ring buffer before being forwarded are lost.
+    /// 
+    /// Returns the count of [KEvent] forwarded.
+    pub fn forward_unhandled_events(&mut self, journal : &mut KJournal) -> usize {
+        let forwarded = self.unforwarded;
+        for event in self.unhandled.iter().skip(self.unhandled.len() - forwarded) {
+            journal.write_target(KEVENT_UNHANDLED_JOURNAL_TARGET, KJournalEntrySeverity::Debug, &format!("Unhandled event {:?}", event));
+        }
+        self.unforwarded = 0;
+        forwarded
+    }
+
+
+    /// Set the [KEventRecorder] that records every dispatched [KEvent], handled or not. None stops recording.
+    ///
+    /// Returns the previous [KEventRecorder] so it can be finished with [KEventRecorder::finish()].
+    pub fn set_recorder(&mut self, recorder : Option<KEventRecorder>) -> Option<KEventRecorder> {
+        std::mem::replace(&mut self.recorder, recorder)
+    }
+
+    /// Returns True if dispatched [KEvent] are recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Add a named layer of [KEventReceiver]. Layers with higher priority receive [KEvent] first. Layers with the same
+    /// priority receive [KEvent] in order of addition.
+    /// 
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::LayerAlreadyExists)]`)` if a layer has the same name.
+    pub fn add_layer(&mut self, name : &str, priority : i32) -> Result<(), OlympusError> {
+        if self.get_layer_index(name).is_some() {
+            return Err(OlympusError::KEventDispatcher(KEventDispatcherError::LayerAlreadyExists));
+        }
+        self.layers.push(KEventLayer { name: String::from(name), priority, enabled: true });
+        self.sort_layers();
+        Ok(())
+    }
+
+    /// Set the priority of a layer.
+    /// 
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound)]`)` if layer doesn't exist.
+    pub fn set_layer_priority(&mut self, name : &str, priority : i32) -> Result<(), OlympusError> {
+        let index = self.get_layer_index(name).ok_or(OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound))?;
+        self.layers[index].priority = priority;
+        self.sort_layers();
+        Ok(())
+    }
+
+    /// Enable or disable a layer. [KEventReceiver] of a disabled layer don't receive [KEvent].
+    /// 
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound)]`)` if layer doesn't exist.
+    pub fn set_layer_enabled(&mut self, name : &str, enabled : bool) -> Result<(), OlympusError> {
+        let index = self.get_layer_index(name).ok_or(OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound))?;
+        self.layers[index].enabled = enabled;
+        Ok(())
+    }
+
+    /// Returns the names of layers from the highest priority to the lowest.
+    pub fn get_layers(&self) -> Vec<&str> {
+        self.layers.iter().map(|l| l.name.as_str()).collect()
+    }
+
+    /// Returns the priority of a layer or None if layer doesn't exist.
+    pub fn get_layer_priority(&self, name : &str) -> Option<i32> {
+        self.get_layer_index(name).map(|index| self.layers[index].priority)
+    }
+
+    /// Returns True if layer exists and is enabled.
+    pub fn is_layer_enabled(&self, name : &str) -> bool {
+        self.get_layer_index(name).is_some_and(|index| self.layers[index].enabled)
+    }
+
+    /// Returns the sequence number that will be given to the next dispatched [KEvent].
+    pub fn get_sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Get a immutable reference to the ring buffer of latest unhandled [KEvent], from oldest to newest.
+    pub fn get_unhandled_events(&self) -> &VecDeque<KEvent>{
         &self.unhandled
     }
 
     /// Add a [KEventReceiver] to the [KEventDispatcher] that will receive [KEvent] dispatched.
     /// 
+    /// [KEventReceiver] is added to the [KEVENT_DEFAULT_LAYER] layer as a [KEventReceiverMode::Consumer] of all [KEventCategories].
+    /// 
     /// [KEventReceiver] must be wrapped in [Rc] [RefCell] since [KWindow::dispatch_events()] is MUTABLE.
     /// 
-    /// Returns [OK(usize)][Ok] with index of new receiver added.
+    /// Returns [OK(KEventReceiverHandle)][Ok] that removes the receiver when dropped.
     /// 
     /// # Example(s)
     /// ```no_run
@@ -78,8 +297,8 @@ impl KEventDispatcher {
     /// // Create variable for MyReceiver 
     /// let myr = Rc::new(RefCell::new(MyReceiver::new()));
     /// 
-    /// // Clone MyReceiver variable when adding to KWindow
-    /// my_kwindow.add_event_receiver(myr.clone());
+    /// // Clone MyReceiver variable when adding to dispatcher. Receiver is removed when handle is dropped.
+    /// let handle = my_dispatcher.add_event_receiver(myr.clone()).unwrap();
     /// 
     /// ```
     /// 
@@ -89,55 +308,156 @@ impl KEventDispatcher {
     /// # Note(s)
     /// [KEvent] dispatch from the most recent added [KEventReceiver] to the older, like a [Stack](https://en.wikipedia.org/wiki/Stack_(abstract_data_type)).
     /// That means that most recent [KEventReceiver] can mask events for older [KEventReceiver].
-    pub fn add_event_receiver(&mut self, receiver : Rc<RefCell<dyn KEventReceiver>>) -> Result<usize, OlympusError> {
+    pub fn add_event_receiver(&mut self, receiver : Rc<RefCell<dyn KEventReceiver>>) -> Result<KEventReceiverHandle, OlympusError> {
+        self.add_event_receiver_to(receiver, KEVENT_DEFAULT_LAYER, KEventCategories::ALL, KEventReceiverMode::Consumer)
+    }
 
-        match self.get_receiver_index(receiver.clone()) {
-            Ok(_) => Err(OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverAlreadyExists)),
-            Err(_) => { self.receivers.push(receiver.clone()); Ok(self.receivers.len() - 1) },
-        }
+    /// Add a [KEventReceiver] to a layer of the [KEventDispatcher]. It will only receive [KEvent] of categories given.
+    /// 
+    /// Returns [OK(KEventReceiverHandle)][Ok] that removes the receiver when dropped.
+    /// 
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound)]`)` if layer doesn't exist.
+    /// 
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverAlreadyExists)]`)` if receiver is already in list.
+    pub fn add_event_receiver_to(&mut self, receiver : Rc<RefCell<dyn KEventReceiver>>, layer : &str, categories : KEventCategories, 
+        mode : KEventReceiverMode) -> Result<KEventReceiverHandle, OlympusError> {
+        let id = self.subscribe(receiver, layer, categories, mode)?;
+        Ok(KEventReceiverHandle::new(id, Rc::downgrade(&self.removed)))
+    }
+
+    /// Add a closure to a layer of the [KEventDispatcher] that receives [KEvent] of categories given. The closure returns True
+    /// if the [KEvent] was handled, like [KEventReceiver::handle_event()].
+    /// 
+    /// Returns a [KEventReceiverHandle] that removes the closure when dropped.
+    /// 
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound)]`)` if layer doesn't exist.
+    pub fn add_closure_receiver<F : FnMut(&KEventEntry) -> bool + 'static>(&mut self, layer : &str, categories : KEventCategories, 
+        closure : F) -> Result<KEventReceiverHandle, OlympusError> {
+        self.add_event_receiver_to(Rc::new(RefCell::new(KEventReceiverClosure::new(closure))), layer, categories, KEventReceiverMode::Consumer)
+    }
 
+    /// Add a channel to a layer of the [KEventDispatcher] that receives a copy of [KEvent] of categories given. The channel
+    /// is a [KEventReceiverMode::Observer] and never handles [KEvent].
+    /// 
+    /// Returns a [KEventReceiverHandle] that removes the channel when dropped with the receiving end of the channel.
+    /// 
+    /// # Error(s)
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound)]`)` if layer doesn't exist.
+    pub fn add_channel_receiver(&mut self, layer : &str, categories : KEventCategories) -> Result<(KEventReceiverHandle, Receiver<KEventEntry>), OlympusError> {
+        let (sender, receiver) = mpsc::channel();
+        let handle = self.add_event_receiver_to(Rc::new(RefCell::new(KEventReceiverChannel::new(sender))), layer, categories, 
+            KEventReceiverMode::Observer)?;
+        Ok((handle, receiver))
+    }
+
+    /// Returns the count of [KEventReceiver] in the [KEventDispatcher], excluding those whose [KEventReceiverHandle] was dropped.
+    pub fn get_receiver_count(&self) -> usize {
+        let removed = self.removed.borrow();
+        self.receivers.iter().filter(|s| !removed.contains(&s.id)).count()
     }
 
-    /// Remove a [KEventReceiver] from the [KWindow] list.
+    /// Remove the [KEventReceiver] of a [KEventReceiverHandle] from the [KEventDispatcher] immediately.
     /// 
-    /// Returns [OK(usize)][Ok] with index of receiver removed.
+    /// Dropping the handle also removes the receiver, before the next dispatch.
     /// 
     /// # Error(s)
-    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverNotFound)]`)` if receiver not found.
-    pub fn remove_event_receiver(&mut self, receiver : Rc<RefCell<dyn KEventReceiver>>) -> Result<usize, OlympusError> {
-        
-        match self.get_receiver_index(receiver.clone()) {
-            Ok(index) => { self.receivers.remove(index); Ok(index) },
-            Err(_) => Err(OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverNotFound)),
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverNotFound)]`)` if handle was given by another dispatcher.
+    pub fn remove_event_receiver(&mut self, handle : KEventReceiverHandle) -> Result<(), OlympusError> {
+        if !handle.is_from(&self.removed) {
+            return Err(OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverNotFound));
         }
 
+        // Dropped handle queues its id, removing the receiver like any dropped handle.
+        drop(handle);
+        self.remove_dropped_receivers();
+        Ok(())
     }
 
-    
-    /// Returns the index of a receiver from the list.
+    /// Add a [KEventReceiver] to a layer and returns its id.
     /// 
     /// # Error(s)
-    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverNotFound)]`)` if receiver not found.
-    fn get_receiver_index(&self, receiver : Rc<RefCell<dyn KEventReceiver>>)-> Result<usize, OlympusError> {
-        let mut found = false;
-        let mut index: usize = 0;
-
-        for i in 0..self.receivers.len() {
-            if std::ptr::eq(receiver.as_ptr(), self.receivers[i].as_ptr()) {
-                found = true;
-                index = i;
-                break;
-            }
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound)]`)` if layer doesn't exist.
+    /// 
+    /// Returns `Err(`[OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverAlreadyExists)]`)` if receiver is already in list.
+    fn subscribe(&mut self, receiver : Rc<RefCell<dyn KEventReceiver>>, layer : &str, categories : KEventCategories, 
+        mode : KEventReceiverMode) -> Result<u64, OlympusError> {
+        self.remove_dropped_receivers();
+
+        if self.get_layer_index(layer).is_none() {
+            return Err(OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound));
         }
-        
-        if found {
-            Ok(index)
+        // Rc::ptr_eq ignores vtable, which may differ for the same receiver.
+        if self.receivers.iter().any(|s| Rc::ptr_eq(&s.receiver, &receiver)) {
+            return Err(OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverAlreadyExists));
         }
-        else {
-            Err(OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverNotFound))
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.receivers.push(KEventSubscription { id, receiver, layer: String::from(layer), categories, mode });
+        Ok(id)
+    }
+
+    /// Remove [KEventReceiver] whose [KEventReceiverHandle] was dropped.
+    fn remove_dropped_receivers(&mut self) {
+        let mut removed = self.removed.borrow_mut();
+        if !removed.is_empty() {
+            self.receivers.retain(|s| !removed.contains(&s.id));
+            removed.clear();
         }
     }
 
+    /// Returns the index of a layer from the list or None if not found.
+    fn get_layer_index(&self, name : &str) -> Option<usize> {
+        self.layers.iter().position(|l| l.name == name)
+    }
+
+    /// Sort layers from highest to lowest priority, keeping order of addition for same priority.
+    fn sort_layers(&mut self) {
+        self.layers.sort_by_key(|l| std::cmp::Reverse(l.priority));
+    }
+
+}
+
+/// Mode of a [KEventReceiver] within a [KEventDispatcher] layer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KEventReceiverMode {
+    /// [KEventReceiver] can handle [KEvent], preventing next consumers from receiving it.
+    Consumer,
+
+    /// [KEventReceiver] receives [KEvent] even if handled. Value returned by [KEventReceiver::handle_event()] is ignored.
+    Observer,
+}
+
+/// [KEventReceiver] registered in a [KEventDispatcher] layer.
+struct KEventSubscription {
+    /// Id of the receiver, given to its [KEventReceiverHandle].
+    id : u64,
+
+    /// Receiver of [KEvent].
+    receiver : Rc<RefCell<dyn KEventReceiver>>,
+
+    /// Name of the layer of the receiver.
+    layer : String,
+
+    /// Categories of [KEvent] received.
+    categories : KEventCategories,
+
+    /// Consumer or observer.
+    mode : KEventReceiverMode,
+}
+
+/// Named layer of [KEventReceiver].
+struct KEventLayer {
+    /// Name of the layer.
+    name : String,
+
+    /// Priority of the layer. Higher priority receive [KEvent] first.
+    priority : i32,
+
+    /// Disabled layers don't receive [KEvent].
+    enabled : bool,
 }
 
 
@@ -147,11 +467,11 @@ impl KEventDispatcher {
 /// modification of object that implement [KEventReceiver].
 pub trait KEventReceiver {
 
-    /// Handle a [KEvent] received from the dispatcher.
+    /// Handle a [KEvent] received from the dispatcher. The [KEventEntry] gives the [KEvent] with its timestamp, sequence number and window id.
     /// 
     /// Return True if the [KEvent] has been handled, which will prevent other receiver from handling it.
     /// Return False if the [KEvent] wasn't handled, giving it to the next receiver.
-    fn handle_event(&mut self, event : &KEvent) -> bool;
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool;
 
     /// Returns if [KEventReceiver] is enabled and ready to receive [KEvent].
     /// 
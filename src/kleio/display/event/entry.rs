@@ -0,0 +1,84 @@
+use std::time::Instant;
+
+use super::KEvent;
+
+#[allow(unused_imports)]
+use super::{KEventDispatcher, KEventReceiver};
+
+#[allow(unused_imports)]
+use super::super::KWindow;
+
+/// Window id of [KEventEntry] not sent by a [KWindow].
+pub const KEVENT_NO_WINDOW : u64 = 0;
+
+/// ##### [KEvent] with its dispatch metadata.
+///
+/// [KEventEntry] is given to [KEventReceiver::handle_event()] by [KEventDispatcher]. It adds to the [KEvent] :
+/// * A monotonic timestamp, taken when the [KEvent] was polled from the [KWindow].
+/// * A sequence number, incremented by the [KEventDispatcher] at each dispatch. It orders events with the same timestamp.
+/// * The id of the [KWindow] that sent the [KEvent] or [KEVENT_NO_WINDOW].
+///
+/// # Example(s)
+/// ```
+/// use std::time::Duration;
+/// use olympus::kleio::display::event::{KEvent, KEventEntry, KEventMouse, KMouseButton};
+///
+/// let first = KEventEntry::from(KEvent::Mouse(KEventMouse::ButtonDown(KMouseButton::Left, (10, 10))));
+/// let second = KEventEntry::new(*first.get_event(), first.get_timestamp() + Duration::from_millis(200), 1, 0);
+///
+/// // Double click if both clicks are within 250 ms.
+/// assert!(second.get_elapsed(&first) < Duration::from_millis(250));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct KEventEntry {
+    /// Event dispatched.
+    event : KEvent,
+
+    /// Monotonic time of the event.
+    timestamp : Instant,
+
+    /// Dispatch sequence number.
+    sequence : u64,
+
+    /// Id of the window that sent the event.
+    window_id : u64,
+}
+
+impl KEventEntry {
+    /// Create a new [KEventEntry] from a [KEvent] and its metadata.
+    pub fn new(event : KEvent, timestamp : Instant, sequence : u64, window_id : u64) -> KEventEntry {
+        KEventEntry { event, timestamp, sequence, window_id }
+    }
+
+    /// Returns a reference to the [KEvent].
+    pub fn get_event(&self) -> &KEvent {
+        &self.event
+    }
+
+    /// Returns the monotonic time of the [KEvent].
+    pub fn get_timestamp(&self) -> Instant {
+        self.timestamp
+    }
+
+    /// Returns the dispatch sequence number of the [KEvent].
+    pub fn get_sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the id of the [KWindow] that sent the [KEvent] or [KEVENT_NO_WINDOW].
+    pub fn get_window_id(&self) -> u64 {
+        self.window_id
+    }
+
+    /// Returns the time elapsed since an older [KEventEntry]. Returns zero if other is more recent.
+    pub fn get_elapsed(&self, other : &KEventEntry) -> std::time::Duration {
+        self.timestamp.saturating_duration_since(other.timestamp)
+    }
+}
+
+impl From<KEvent> for KEventEntry {
+    /// Create a [KEventEntry] timestamped now with sequence 0 and [KEVENT_NO_WINDOW].
+    fn from(event : KEvent) -> Self {
+        KEventEntry::new(event, Instant::now(), 0, KEVENT_NO_WINDOW)
+    }
+}
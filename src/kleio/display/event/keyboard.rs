@@ -1,12 +1,20 @@
+use std::ops::{BitOr, BitOrAssign};
+
+/// Maximum size in bytes of the UTF-8 text carried by a [KTextInput].
+pub const KTEXT_INPUT_MAX : usize = 32;
+
 /// Enumeration of possible Keyboard events
 #[derive(Copy, Clone)]
 pub enum KEventKeyboard {
 
-    // Keyboard key down event. Provides unicode of key pressed.
-    KeyDown(u32),
+    // Keyboard key down event. Provides physical and logical key pressed with modifiers and repeat flag.
+    KeyDown(KKeyInput),
+
+    // Keyboard key up event. Provides physical and logical key released with modifiers.
+    KeyUp(KKeyInput),
 
-    // Keyboard key up event. Provides unicode of key released.
-    KeyUp(u32),
+    // Text input event. Provides the UTF-8 text composed by the keyboard layout and input method.
+    TextInput(KTextInput),
 }
 
 impl std::fmt::Debug for KEventKeyboard {
@@ -14,6 +22,235 @@ impl std::fmt::Debug for KEventKeyboard {
         match self {
             Self::KeyDown(arg0) => f.debug_tuple("KeyDown").field(arg0).finish(),
             Self::KeyUp(arg0) => f.debug_tuple("KeyUp").field(arg0).finish(),
+            Self::TextInput(arg0) => f.debug_tuple("TextInput").field(arg0).finish(),
+        }
+    }
+}
+
+/// ##### Key pressed or released with modifiers.
+///
+/// [KKeyInput::scancode] is the physical position of the key and doesn't change with keyboard layout. Use it for
+/// game controls like WASD. [KKeyInput::key] is the key according to keyboard layout. Use it for shortcuts like Ctrl+Z.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KKeyInput {
+    /// Physical key, independent of keyboard layout.
+    pub scancode : KScancode,
+
+    /// Logical key according to keyboard layout, without shift applied.
+    pub key : KKey,
+
+    /// Modifiers active when the key was pressed or released.
+    pub modifiers : KKeyModifiers,
+
+    /// True if key down was generated by the key being held (auto-repeat). Always false for key up.
+    pub repeat : bool,
+}
+
+impl KKeyInput {
+    /// Create a new [KKeyInput] from physical key, logical key, modifiers and repeat flag.
+    pub fn new(scancode : KScancode, key : KKey, modifiers : KKeyModifiers, repeat : bool) -> KKeyInput {
+        KKeyInput { scancode, key, modifiers, repeat }
+    }
+}
+
+/// ##### Enumeration of physical keyboard keys.
+///
+/// Keys are named after their position on a US QWERTY keyboard. The [KScancode::A] key is the one at the
+/// left of the home row, even if it writes a Q on an AZERTY keyboard.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KScancode {
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+
+    Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+
+    /// Function key F1 to F24.
+    Function(u8),
+
+    Escape, Enter, Tab, Backspace, Space,
+
+    Minus, Equal, LeftBracket, RightBracket, Backslash, Semicolon, Apostrophe, Grave, Comma, Period, Slash,
+
+    /// Extra key between left shift and Z on ISO keyboards.
+    NonUsBackslash,
+
+    Insert, Delete, Home, End, PageUp, PageDown,
+
+    Left, Right, Up, Down,
+
+    CapsLock, NumLock, ScrollLock, PrintScreen, Pause, Menu,
+
+    LeftShift, RightShift, LeftControl, RightControl, LeftAlt, RightAlt, LeftSuper, RightSuper,
+
+    Keypad0, Keypad1, Keypad2, Keypad3, Keypad4, Keypad5, Keypad6, Keypad7, Keypad8, Keypad9,
+
+    KeypadPeriod, KeypadDivide, KeypadMultiply, KeypadMinus, KeypadPlus, KeypadEnter, KeypadEqual,
+
+    /// Any other key. Provides the native key code (evdev key code on Linux).
+    Unknown(u32),
+}
+
+/// ##### Enumeration of logical keyboard keys.
+///
+/// Keys that write a character are given as [KKey::Character] in lower case (without shift applied). The other keys are named.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KKey {
+    /// Key writing a character according to keyboard layout.
+    Character(char),
+
+    /// Function key F1 to F24.
+    Function(u8),
+
+    Escape, Enter, Tab, Backspace,
+
+    Insert, Delete, Home, End, PageUp, PageDown,
+
+    Left, Right, Up, Down,
+
+    CapsLock, NumLock, ScrollLock, PrintScreen, Pause, Menu,
+
+    LeftShift, RightShift, LeftControl, RightControl, LeftAlt, RightAlt, LeftSuper, RightSuper,
+
+    /// Any other key. Provides the keysym of the display server.
+    Unknown(u32),
+}
+
+/// ##### Set of keyboard modifiers active during a key event.
+///
+/// Modifiers are combined with `|` like flags.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::display::event::KKeyModifiers;
+///
+/// let modifiers = KKeyModifiers::CONTROL | KKeyModifiers::SHIFT;
+///
+/// assert!(modifiers.contains(KKeyModifiers::CONTROL));
+/// assert!(!modifiers.contains(KKeyModifiers::ALT));
+/// assert!(modifiers.contains(KKeyModifiers::CONTROL | KKeyModifiers::SHIFT));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct KKeyModifiers {
+    /// Modifiers flags.
+    flags : u8,
+}
+
+impl KKeyModifiers {
+    /// No modifier.
+    pub const NONE : KKeyModifiers = KKeyModifiers { flags: 0 };
+
+    /// Any shift key.
+    pub const SHIFT : KKeyModifiers = KKeyModifiers { flags: 1 };
+
+    /// Any control key.
+    pub const CONTROL : KKeyModifiers = KKeyModifiers { flags: 2 };
+
+    /// Any alt key.
+    pub const ALT : KKeyModifiers = KKeyModifiers { flags: 4 };
+
+    /// Any super key (Windows or Command key).
+    pub const SUPER : KKeyModifiers = KKeyModifiers { flags: 8 };
+
+    /// Caps lock is on.
+    pub const CAPS_LOCK : KKeyModifiers = KKeyModifiers { flags: 16 };
+
+    /// Num lock is on.
+    pub const NUM_LOCK : KKeyModifiers = KKeyModifiers { flags: 32 };
+
+    /// Returns the modifiers flags.
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Returns True if all modifiers given are active.
+    pub fn contains(&self, modifiers : KKeyModifiers) -> bool {
+        self.flags & modifiers.flags == modifiers.flags
+    }
+
+    /// Returns True if no modifier is active.
+    pub fn is_empty(&self) -> bool {
+        self.flags == 0
+    }
+
+    /// Add modifiers to the set.
+    pub fn insert(&mut self, modifiers : KKeyModifiers) {
+        self.flags |= modifiers.flags;
+    }
+
+    /// Remove modifiers from the set.
+    pub fn remove(&mut self, modifiers : KKeyModifiers) {
+        self.flags &= !modifiers.flags;
+    }
+}
+
+impl BitOr for KKeyModifiers {
+    type Output = KKeyModifiers;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        KKeyModifiers { flags: self.flags | rhs.flags }
+    }
+}
+
+impl BitOrAssign for KKeyModifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.flags |= rhs.flags;
+    }
+}
+
+/// ##### UTF-8 text composed by the keyboard layout and input method.
+///
+/// Text is stored inline so [KEventKeyboard] stays [Copy]. Text longer than [KTEXT_INPUT_MAX] bytes is truncated
+/// on a character boundary.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::display::event::KTextInput;
+///
+/// let text = KTextInput::new("é");
+///
+/// assert_eq!(text.as_str(), "é");
+/// assert_eq!(text.len(), 2);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KTextInput {
+    /// UTF-8 bytes of text.
+    bytes : [u8; KTEXT_INPUT_MAX],
+
+    /// Count of bytes used.
+    length : u8,
+}
+
+impl KTextInput {
+    /// Create a new [KTextInput] from text, truncated to [KTEXT_INPUT_MAX] bytes on a character boundary.
+    pub fn new(text : &str) -> KTextInput {
+        let mut length = text.len().min(KTEXT_INPUT_MAX);
+        while !text.is_char_boundary(length) {
+            length -= 1;
         }
+
+        let mut bytes = [0; KTEXT_INPUT_MAX];
+        bytes[0..length].copy_from_slice(&text.as_bytes()[0..length]);
+        KTextInput { bytes, length : length as u8 }
+    }
+
+    /// Returns the text as a string slice.
+    pub fn as_str(&self) -> &str {
+        // Bytes always come from a str truncated on a character boundary.
+        std::str::from_utf8(&self.bytes[0..self.length as usize]).unwrap_or_default()
+    }
+
+    /// Returns the length of the text in bytes.
+    pub fn len(&self) -> usize {
+        self.length as usize
+    }
+
+    /// Returns True if text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
     }
-}
\ No newline at end of file
+}
+
+impl std::fmt::Debug for KTextInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("KTextInput").field(&self.as_str()).finish()
+    }
+}
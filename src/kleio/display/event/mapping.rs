@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::{OlympusError, KControllerMappingError};
+
+use super::{KControllerButton, KControllerAxis};
+
+#[allow(unused_imports)]
+use super::KEventController;
+
+/// Built-in mappings of common controllers on Linux. Versions are 0 so any firmware revision matches.
+const KCONTROLLER_MAPPINGS_BUILTIN : &str = "\
+030000005e0400008e02000000000000,Xbox 360 Controller,a:b0,b:b1,back:b6,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b8,leftshoulder:b4,leftstick:b9,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b10,righttrigger:a5,rightx:a3,righty:a4,start:b7,x:b2,y:b3,platform:Linux,
+030000005e040000ea02000000000000,Xbox One Controller,a:b0,b:b1,back:b6,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b8,leftshoulder:b4,leftstick:b9,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b10,righttrigger:a5,rightx:a3,righty:a4,start:b7,x:b2,y:b3,platform:Linux,
+030000004c050000c405000000000000,PS4 Controller,a:b0,b:b1,back:b8,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b10,leftshoulder:b4,leftstick:b11,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b12,righttrigger:a5,rightx:a3,righty:a4,start:b9,x:b3,y:b2,platform:Linux,
+030000004c050000cc09000000000000,PS4 Controller,a:b0,b:b1,back:b8,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b10,leftshoulder:b4,leftstick:b11,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b12,righttrigger:a5,rightx:a3,righty:a4,start:b9,x:b3,y:b2,platform:Linux,
+030000004c050000e60c000000000000,PS5 Controller,a:b0,b:b1,back:b8,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b10,leftshoulder:b4,leftstick:b11,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b12,righttrigger:a5,rightx:a3,righty:a4,start:b9,x:b3,y:b2,platform:Linux,
+";
+
+/// SDL names of [KControllerButton] ordered by id.
+const BUTTON_NAMES : [&str; 21] = ["a", "b", "x", "y", "back", "guide", "start", "leftstick", "rightstick", "leftshoulder", "rightshoulder",
+    "dpup", "dpdown", "dpleft", "dpright", "misc1", "paddle1", "paddle2", "paddle3", "paddle4", "touchpad"];
+
+/// SDL names of [KControllerAxis] ordered by id.
+const AXIS_NAMES : [&str; 6] = ["leftx", "lefty", "rightx", "righty", "lefttrigger", "righttrigger"];
+
+/// ##### Controller device identifier used to find its mapping.
+///
+/// Same 16 bytes layout as SDL joystick GUID. Written as 32 hexadecimal characters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct KControllerGuid {
+    /// GUID bytes.
+    bytes : [u8; 16],
+}
+
+impl KControllerGuid {
+    /// Create a new [KControllerGuid] from bytes.
+    pub fn new(bytes : [u8; 16]) -> KControllerGuid {
+        KControllerGuid { bytes }
+    }
+
+    /// Create a new [KControllerGuid] from device bus, vendor, product and version like SDL does.
+    ///
+    /// Devices without vendor and product are identified by their name instead.
+    pub fn from_device(bus : u16, vendor : u16, product : u16, version : u16, name : &str) -> KControllerGuid {
+        let mut bytes = [0u8; 16];
+        bytes[0..2].copy_from_slice(&bus.to_le_bytes());
+
+        if vendor != 0 && product != 0 {
+            bytes[4..6].copy_from_slice(&vendor.to_le_bytes());
+            bytes[8..10].copy_from_slice(&product.to_le_bytes());
+            bytes[12..14].copy_from_slice(&version.to_le_bytes());
+        } else {
+            let name = name.as_bytes();
+            let length = name.len().min(12);
+            bytes[4..4 + length].copy_from_slice(&name[..length]);
+        }
+
+        KControllerGuid { bytes }
+    }
+
+    /// Parse a [KControllerGuid] from 32 hexadecimal characters.
+    ///
+    /// Returns [None] if text isn't a valid GUID.
+    pub fn parse(text : &str) -> Option<KControllerGuid> {
+        if text.len() != 32 || !text.is_ascii() {
+            return None;
+        }
+
+        let mut bytes = [0u8; 16];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&text[index * 2..index * 2 + 2], 16).ok()?;
+        }
+        Some(KControllerGuid { bytes })
+    }
+
+    /// Returns the GUID bytes.
+    pub fn get_bytes(&self) -> [u8; 16] {
+        self.bytes
+    }
+
+    /// Returns the GUID without the name checksum some SDL versions write in bytes 2 and 3.
+    fn without_checksum(&self) -> KControllerGuid {
+        let mut bytes = self.bytes;
+        bytes[2..4].fill(0);
+        KControllerGuid { bytes }
+    }
+
+    /// Returns the GUID without the device version.
+    fn without_version(&self) -> KControllerGuid {
+        let mut bytes = self.bytes;
+        bytes[12..14].fill(0);
+        KControllerGuid { bytes }
+    }
+}
+
+impl std::fmt::Display for KControllerGuid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.bytes {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Part of an axis range used by a mapping.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KControllerAxisRange {
+    /// Whole axis range.
+    Full,
+
+    /// Positive half of the axis (`+` prefix).
+    Positive,
+
+    /// Negative half of the axis (`-` prefix).
+    Negative,
+}
+
+/// Raw controller input of a mapping, indexed like SDL.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KControllerMappingSource {
+    /// Raw button index (`bN`).
+    Button(u8),
+
+    /// Raw axis index, range used and inverted flag (`aN`, `+aN`, `-aN`, `aN~`).
+    Axis(u8, KControllerAxisRange, bool),
+
+    /// Raw hat index and direction mask, 1 up, 2 right, 4 down and 8 left (`hN.M`).
+    Hat(u8, u8),
+}
+
+/// Standard layout input of a mapping.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KControllerMappingTarget {
+    /// Standard button.
+    Button(KControllerButton),
+
+    /// Standard axis and range written.
+    Axis(KControllerAxis, KControllerAxisRange),
+}
+
+/// ##### Translation of a controller raw inputs to the standard layout.
+///
+/// Written in SDL `gamecontrollerdb.txt` format : `GUID,Name,target:source,...` where targets are SDL names (`a`, `leftx`,
+/// `-lefty`, ...) and sources are raw buttons `bN`, axes `aN` (optional `+`/`-` half and `~` inversion) or hats `hN.M`.
+///
+/// Unknown fields like `platform` are ignored.
+///
+/// # Reference(s)
+/// <https://github.com/mdqinc/SDL_GameControllerDB>
+#[derive(Debug, Clone, PartialEq)]
+pub struct KControllerMapping {
+    /// Controller GUID.
+    guid : KControllerGuid,
+
+    /// Controller name.
+    name : String,
+
+    /// Sources translated to targets.
+    entries : Vec<(KControllerMappingSource, KControllerMappingTarget)>,
+}
+
+impl KControllerMapping {
+    /// Create a new [KControllerMapping] from entries.
+    pub fn new(guid : KControllerGuid, name : &str, entries : &[(KControllerMappingSource, KControllerMappingTarget)]) -> KControllerMapping {
+        KControllerMapping { guid, name: String::from(name), entries: entries.to_vec() }
+    }
+
+    /// Parse a [KControllerMapping] from a SDL mapping line.
+    ///
+    /// Returns [None] if GUID, name or an entry of a known target is invalid.
+    pub fn parse(line : &str) -> Option<KControllerMapping> {
+        let mut fields = line.trim().split(',');
+        let guid = KControllerGuid::parse(fields.next()?.trim())?;
+        let name = fields.next()?.trim();
+
+        let mut entries = Vec::new();
+        for field in fields.map(|field| field.trim()).filter(|field| !field.is_empty()) {
+            let (target, source) = field.split_once(':')?;
+            if let Some(target) = parse_target(target) {
+                entries.push((parse_source(source)?, target));
+            }
+        }
+
+        Some(KControllerMapping { guid, name: String::from(name), entries })
+    }
+
+    /// Returns the controller GUID.
+    pub fn get_guid(&self) -> KControllerGuid {
+        self.guid
+    }
+
+    /// Returns the controller name.
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Returns the sources translated to targets.
+    pub fn get_entries(&self) -> &[(KControllerMappingSource, KControllerMappingTarget)] {
+        self.entries.as_slice()
+    }
+}
+
+impl std::fmt::Display for KControllerMapping {
+    /// Write the mapping as a SDL mapping line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},", self.guid, self.name)?;
+        for (source, target) in self.entries.iter() {
+            match target {
+                KControllerMappingTarget::Button(button) => write!(f, "{}:", BUTTON_NAMES[*button as usize])?,
+                KControllerMappingTarget::Axis(axis, range) => write!(f, "{}{}:", get_range_prefix(*range), AXIS_NAMES[*axis as usize])?,
+            }
+            match source {
+                KControllerMappingSource::Button(index) => write!(f, "b{},", index)?,
+                KControllerMappingSource::Axis(index, range, inverted) => write!(f, "{}a{}{},", get_range_prefix(*range), index, if *inverted { "~" } else { "" })?,
+                KControllerMappingSource::Hat(index, mask) => write!(f, "h{}.{},", index, mask)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// ##### Database of [KControllerMapping] keyed by [KControllerGuid].
+///
+/// The database starts with built-in mappings of common controllers. Mappings added by the user override built-in
+/// mappings of the same GUID.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::display::event::{KControllerMappingDatabase, KControllerGuid};
+///
+/// let mut database = KControllerMappingDatabase::new();
+/// let count = database.parse("# Custom pad\n\
+///     03000000aa550000bb66000000000000,Custom Pad,a:b1,b:b0,leftx:a0,lefty:a1,platform:Linux,\n\
+///     03000000aa550000bb66000000000000,Custom Pad,a:b0,platform:Windows,").unwrap();
+///
+/// assert_eq!(count, 1);
+/// let guid = KControllerGuid::parse("03000000aa550000bb66000000000000").unwrap();
+/// assert_eq!(database.get_mapping(guid).unwrap().get_name(), "Custom Pad");
+/// ```
+#[derive(Debug, Clone)]
+pub struct KControllerMappingDatabase {
+    /// Built-in mappings.
+    builtin : HashMap<KControllerGuid, KControllerMapping>,
+
+    /// Mappings added by the user.
+    user : HashMap<KControllerGuid, KControllerMapping>,
+}
+
+impl KControllerMappingDatabase {
+    /// Create a new [KControllerMappingDatabase] with built-in mappings.
+    pub fn new() -> KControllerMappingDatabase {
+        let builtin = KCONTROLLER_MAPPINGS_BUILTIN.lines().filter_map(KControllerMapping::parse)
+            .map(|mapping| (mapping.guid.without_checksum(), mapping)).collect();
+        KControllerMappingDatabase { builtin, user: HashMap::new() }
+    }
+
+    /// Add a user mapping, overriding any mapping with the same GUID.
+    pub fn add_mapping(&mut self, mapping : KControllerMapping) {
+        self.user.insert(mapping.guid.without_checksum(), mapping);
+    }
+
+    /// Parse SDL mapping lines and add them as user mappings. Empty lines, `#` comments and lines of other platforms are skipped.
+    ///
+    /// Returns the count of mappings added.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::InvalidMapping(line))] if a line can't be parsed. No mapping is added.
+    pub fn parse(&mut self, text : &str) -> Result<usize, OlympusError> {
+        let mut mappings = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // Skip mappings of other platforms.
+            let platform = line.split(',').find_map(|field| field.trim().strip_prefix("platform:"));
+            if platform.is_some_and(|platform| platform != "Linux") {
+                continue;
+            }
+
+            match KControllerMapping::parse(line) {
+                Some(mapping) => mappings.push(mapping),
+                None => return Err(OlympusError::KControllerMapping(KControllerMappingError::InvalidMapping(index + 1))),
+            }
+        }
+
+        let count = mappings.len();
+        for mapping in mappings {
+            self.add_mapping(mapping);
+        }
+        Ok(count)
+    }
+
+    /// Load a SDL `gamecontrollerdb.txt` file and add its mappings as user mappings.
+    ///
+    /// Returns the count of mappings added.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::FileError)] if file can't be read.
+    ///
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::InvalidMapping(line))] if a line can't be parsed.
+    pub fn load(&mut self, path : impl AsRef<Path>) -> Result<usize, OlympusError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => self.parse(&text),
+            Err(_) => Err(OlympusError::KControllerMapping(KControllerMappingError::FileError)),
+        }
+    }
+
+    /// Returns the mapping of a GUID or [None] if controller isn't in database.
+    ///
+    /// User mappings are searched before built-in mappings. A mapping without device version is used if no mapping matches the version.
+    pub fn get_mapping(&self, guid : KControllerGuid) -> Option<&KControllerMapping> {
+        let guid = guid.without_checksum();
+        let find = |guid : &KControllerGuid| self.user.get(guid).or_else(|| self.builtin.get(guid));
+
+        find(&guid).or_else(|| find(&guid.without_version()))
+    }
+}
+
+impl Default for KControllerMappingDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a mapping target. Returns [None] if target isn't a standard button or axis.
+fn parse_target(text : &str) -> Option<KControllerMappingTarget> {
+    let (range, name) = split_range_prefix(text.trim());
+
+    if let Some(index) = AXIS_NAMES.iter().position(|axis| *axis == name) {
+        Some(KControllerMappingTarget::Axis(KControllerAxis::from_id(index as u8)?, range))
+    } else if range == KControllerAxisRange::Full {
+        let index = BUTTON_NAMES.iter().position(|button| *button == name)?;
+        Some(KControllerMappingTarget::Button(KControllerButton::from_id(index as u8)?))
+    } else {
+        None
+    }
+}
+
+/// Parse a mapping source. Returns [None] if source is invalid.
+fn parse_source(text : &str) -> Option<KControllerMappingSource> {
+    let (range, text) = split_range_prefix(text.trim());
+    let (text, inverted) = match text.strip_suffix('~') {
+        Some(text) => (text, true),
+        None => (text, false),
+    };
+
+    if let Some(index) = text.strip_prefix('a') {
+        Some(KControllerMappingSource::Axis(index.parse().ok()?, range, inverted))
+    } else if range != KControllerAxisRange::Full || inverted {
+        None    // Only axes have range and inversion.
+    } else if let Some(index) = text.strip_prefix('b') {
+        Some(KControllerMappingSource::Button(index.parse().ok()?))
+    } else {
+        let (index, mask) = text.strip_prefix('h')?.split_once('.')?;
+        Some(KControllerMappingSource::Hat(index.parse().ok()?, mask.parse().ok()?))
+    }
+}
+
+/// Split the `+` or `-` range prefix of a mapping field.
+fn split_range_prefix(text : &str) -> (KControllerAxisRange, &str) {
+    if let Some(text) = text.strip_prefix('+') {
+        (KControllerAxisRange::Positive, text)
+    } else if let Some(text) = text.strip_prefix('-') {
+        (KControllerAxisRange::Negative, text)
+    } else {
+        (KControllerAxisRange::Full, text)
+    }
+}
+
+/// Returns the mapping prefix of a range.
+fn get_range_prefix(range : KControllerAxisRange) -> &'static str {
+    match range {
+        KControllerAxisRange::Full => "",
+        KControllerAxisRange::Positive => "+",
+        KControllerAxisRange::Negative => "-",
+    }
+}
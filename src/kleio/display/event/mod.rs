@@ -1,11 +1,59 @@
 /// # Re-export for Public API
 #[doc(inline)]
 pub use mouse::KEventMouse as KEventMouse;
+pub use mouse::KMouseButton as KMouseButton;
 pub use window::KEventWindow as KEventWindow;
 pub use controller::KEventController as KEventController;
+pub use controller::KControllerButton as KControllerButton;
+pub use controller::KControllerAxis as KControllerAxis;
+pub use controller::KCONTROLLER_RAW_BUTTON_FIRST as KCONTROLLER_RAW_BUTTON_FIRST;
+pub use controller::KCONTROLLER_RAW_AXIS_FIRST as KCONTROLLER_RAW_AXIS_FIRST;
+pub use mapping::KControllerGuid as KControllerGuid;
+pub use mapping::KControllerMapping as KControllerMapping;
+pub use mapping::KControllerMappingDatabase as KControllerMappingDatabase;
+pub use mapping::KControllerMappingSource as KControllerMappingSource;
+pub use mapping::KControllerMappingTarget as KControllerMappingTarget;
+pub use mapping::KControllerAxisRange as KControllerAxisRange;
+pub use touch::KEventTouch as KEventTouch;
+pub use user::KEventUser as KEventUser;
+pub use user::KEventUserPoster as KEventUserPoster;
+pub use user::KEVENT_USER_PAYLOAD_SIZE as KEVENT_USER_PAYLOAD_SIZE;
+pub use touch::KTouch as KTouch;
+pub use touch::KPen as KPen;
+pub use touch::KTouchGesture as KTouchGesture;
+pub use touch::KTouchSwipeDirection as KTouchSwipeDirection;
+pub use touch::KTouchGestureRecognizer as KTouchGestureRecognizer;
+pub use touch::KTOUCH_SWIPE_DISTANCE as KTOUCH_SWIPE_DISTANCE;
+pub use touch::KTOUCH_PINCH_THRESHOLD as KTOUCH_PINCH_THRESHOLD;
 pub use keyboard::KEventKeyboard as KEventKeyboard;
+pub use keyboard::KKeyInput as KKeyInput;
+pub use keyboard::KScancode as KScancode;
+pub use keyboard::KKey as KKey;
+pub use keyboard::KKeyModifiers as KKeyModifiers;
+pub use keyboard::KTextInput as KTextInput;
+pub use keyboard::KTEXT_INPUT_MAX as KTEXT_INPUT_MAX;
 pub use dispatcher::KEventDispatcher as KEventDispatcher;
 pub use dispatcher::KEventReceiver as KEventReceiver;
+pub use dispatcher::KEventReceiverMode as KEventReceiverMode;
+pub use dispatcher::KEVENT_DEFAULT_LAYER as KEVENT_DEFAULT_LAYER;
+pub use category::KEventCategories as KEventCategories;
+pub use category::KEVENT_CATEGORY_COUNT as KEVENT_CATEGORY_COUNT;
+pub use dispatcher::KEVENT_UNHANDLED_CAPACITY as KEVENT_UNHANDLED_CAPACITY;
+pub use dispatcher::KEVENT_UNHANDLED_JOURNAL_TARGET as KEVENT_UNHANDLED_JOURNAL_TARGET;
+pub use receiver::KEventReceiverHandle as KEventReceiverHandle;
+pub use bridge::KEventBridgeReceiver as KEventBridgeReceiver;
+pub use bridge::KEventBridgeSender as KEventBridgeSender;
+pub use entry::KEventEntry as KEventEntry;
+pub use entry::KEVENT_NO_WINDOW as KEVENT_NO_WINDOW;
+pub use record::KEventRecorder as KEventRecorder;
+pub use record::KEventReplay as KEventReplay;
+pub use record::KEVENT_RECORD_MAGIC as KEVENT_RECORD_MAGIC;
+pub use record::KEVENT_RECORD_VERSION as KEVENT_RECORD_VERSION;
+pub use state::KInputState as KInputState;
+pub use action::KInputActionMap as KInputActionMap;
+pub use action::KInputBinding as KInputBinding;
+pub use action::KInput as KInput;
+pub use action::KINPUT_CAPTURE_AXIS_THRESHOLD as KINPUT_CAPTURE_AXIS_THRESHOLD;
 
 // Kleio window events
 #[doc(hidden)]
@@ -19,14 +67,54 @@ pub mod keyboard;
 #[doc(hidden)]
 pub mod mouse;
 
+// Kleio touch and pen events
+#[doc(hidden)]
+pub mod touch;
+
 // Kleio controller events
 #[doc(hidden)]
 pub mod controller;
 
+// Kleio controller mappings database
+#[doc(hidden)]
+pub mod mapping;
+
+// Kleio user events
+#[doc(hidden)]
+pub mod user;
+
 // Kleio events dispatcher and receiver
 #[doc(hidden)]
 pub mod dispatcher;
 
+// Kleio event categories
+#[doc(hidden)]
+pub mod category;
+
+// Kleio closure and channel receivers
+#[doc(hidden)]
+pub mod receiver;
+
+// Kleio events bridge between threads
+#[doc(hidden)]
+pub mod bridge;
+
+// Kleio event with dispatch metadata
+#[doc(hidden)]
+pub mod entry;
+
+// Kleio events recording and replay
+#[doc(hidden)]
+pub mod record;
+
+// Kleio input state tracker
+#[doc(hidden)]
+pub mod state;
+
+// Kleio input actions mapping
+#[doc(hidden)]
+pub mod action;
+
 /// Union of possible events into an enumeration.
 #[derive(Copy, Clone)]
 pub enum KEvent {
@@ -46,6 +134,12 @@ pub enum KEvent {
     /// Controller events
     Controller(KEventController),
 
+    /// Touch and pen events
+    Touch(KEventTouch),
+
+    /// User events posted by the application
+    User(KEventUser),
+
     /// Unknown/Unhandled by Kleio event
     Unknown,
 }
@@ -58,6 +152,8 @@ impl std::fmt::Debug for KEvent {
             Self::Keyboard(arg0) => f.debug_tuple("Keyboard").field(arg0).finish(),
             Self::Mouse(arg0) => f.debug_tuple("Mouse").field(arg0).finish(),
             Self::Controller(arg0) => f.debug_tuple("Controller").field(arg0).finish(),
+            Self::Touch(arg0) => f.debug_tuple("Touch").field(arg0).finish(),
+            Self::User(arg0) => f.debug_tuple("User").field(arg0).finish(),
             Self::Unknown => write!(f, "Unknown"),
         }
     }
@@ -5,15 +5,18 @@ pub enum KEventMouse {
     // Mouse move event. Provides new (x, y) position or acceleration according to [KWindowMotionMode].
     Moved((i32, i32)),
 
-    // Mouse button down event. Provides button number (up to 255) and cursor position (x,y).
-    ButtonDown(u8, (i32, i32)),
+    // Mouse button down event. Provides [KMouseButton] pressed and cursor position (x,y).
+    ButtonDown(KMouseButton, (i32, i32)),
 
-    // Mouse button up event. Provides button number (up to 255) and cursor position (x,y).
-    ButtonUp(u8, (i32, i32)),
+    // Mouse button up event. Provides [KMouseButton] released and cursor position (x,y).
+    ButtonUp(KMouseButton, (i32, i32)),
 
-    // Mouse wheel event. Provide amount scrolled horizontally and vertically.
+    // Mouse wheel event. Provide amount scrolled horizontally (positive to the right) and vertically (positive away from user).
     Wheel(i32, i32),
 
+    // Raw mouse motion event in [KCursorMode::Acceleration]. Provides unaccelerated (x, y) motion of the device, not limited by screen edges.
+    RawMotion(f64, f64),
+
 }
 
 impl std::fmt::Debug for KEventMouse {
@@ -23,7 +26,29 @@ impl std::fmt::Debug for KEventMouse {
             Self::ButtonDown(arg0, arg1) => f.debug_tuple("ButtonDown").field(arg0).field(arg1).finish(),
             Self::ButtonUp(arg0, arg1) => f.debug_tuple("ButtonUp").field(arg0).field(arg1).finish(),
             Self::Wheel(arg0, arg1) => f.debug_tuple("Wheel").field(arg0).field(arg1).finish(),
+            Self::RawMotion(arg0, arg1) => f.debug_tuple("RawMotion").field(arg0).field(arg1).finish(),
         }
     }
 }
 
+/// Enumeration of mouse buttons.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KMouseButton {
+    /// Primary button, usually left.
+    Left,
+
+    /// Middle button, usually the wheel click.
+    Middle,
+
+    /// Secondary button, usually right.
+    Right,
+
+    /// Extra side button used to navigate back.
+    Back,
+
+    /// Extra side button used to navigate forward.
+    Forward,
+
+    /// Any other button. Provides the button number of the display server.
+    Other(u8),
+}
@@ -0,0 +1,108 @@
+use std::{rc::{Rc, Weak}, cell::RefCell, sync::mpsc::Sender};
+
+use super::{KEventEntry, KEventReceiver};
+
+#[allow(unused_imports)]
+use super::{KEvent, KEventDispatcher};
+
+/// ##### Handle of a [KEventReceiver] added to a [KEventDispatcher].
+///
+/// The [KEventReceiver] is removed from the [KEventDispatcher] when the handle is dropped. Removal happens before the next dispatch.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::display::event::{KEvent, KEventDispatcher, KEventCategories, KEventMouse, KEVENT_DEFAULT_LAYER};
+///
+/// let mut dispatcher = KEventDispatcher::new(false);
+/// let handle = dispatcher.add_closure_receiver(KEVENT_DEFAULT_LAYER, KEventCategories::MOUSE, |_| true).unwrap();
+/// assert!(dispatcher.dispatch(&KEvent::Mouse(KEventMouse::Wheel(0, 1))));
+///
+/// // Closure is removed with its handle.
+/// drop(handle);
+/// assert!(!dispatcher.dispatch(&KEvent::Mouse(KEventMouse::Wheel(0, 1))));
+/// ```
+#[must_use = "receiver is removed when handle is dropped"]
+pub struct KEventReceiverHandle {
+    /// Id of the receiver within the dispatcher.
+    id : u64,
+
+    /// Ids of receivers to remove, shared with the dispatcher.
+    removed : Weak<RefCell<Vec<u64>>>,
+}
+
+impl KEventReceiverHandle {
+    /// Create a new handle for receiver id. Used by [KEventDispatcher].
+    pub(super) fn new(id : u64, removed : Weak<RefCell<Vec<u64>>>) -> KEventReceiverHandle {
+        KEventReceiverHandle { id, removed }
+    }
+
+    /// Returns the id of the [KEventReceiver] within the [KEventDispatcher].
+    pub fn get_id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns True if handle was given by the dispatcher owning removed.
+    pub(super) fn is_from(&self, removed : &Rc<RefCell<Vec<u64>>>) -> bool {
+        Weak::ptr_eq(&self.removed, &Rc::downgrade(removed))
+    }
+}
+
+impl Drop for KEventReceiverHandle {
+    fn drop(&mut self) {
+        // Dispatcher may have been dropped before handle.
+        if let Some(removed) = self.removed.upgrade() {
+            removed.borrow_mut().push(self.id);
+        }
+    }
+}
+
+/// [KEventReceiver] that calls a closure for each [KEventEntry] received.
+pub(super) struct KEventReceiverClosure<F : FnMut(&KEventEntry) -> bool> {
+    /// Closure called. Returns True if [KEvent] was handled.
+    closure : F,
+}
+
+impl<F : FnMut(&KEventEntry) -> bool> KEventReceiverClosure<F> {
+    /// Create a new receiver that calls closure.
+    pub(super) fn new(closure : F) -> KEventReceiverClosure<F> {
+        KEventReceiverClosure { closure }
+    }
+}
+
+impl<F : FnMut(&KEventEntry) -> bool> KEventReceiver for KEventReceiverClosure<F> {
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        (self.closure)(entry)
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+/// [KEventReceiver] that sends a copy of each [KEventEntry] received into a channel.
+pub(super) struct KEventReceiverChannel {
+    /// Sending end of the channel.
+    sender : Sender<KEventEntry>,
+
+    /// False once the receiving end of the channel is dropped.
+    connected : bool,
+}
+
+impl KEventReceiverChannel {
+    /// Create a new receiver that sends entries to sender.
+    pub(super) fn new(sender : Sender<KEventEntry>) -> KEventReceiverChannel {
+        KEventReceiverChannel { sender, connected: true }
+    }
+}
+
+impl KEventReceiver for KEventReceiverChannel {
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        // Stop sending once the receiving end is gone.
+        self.connected = self.sender.send(*entry).is_ok();
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.connected
+    }
+}
@@ -0,0 +1,578 @@
+use std::{fs::File, io::{BufReader, BufWriter, Read, Write}, path::Path, time::{Duration, Instant}};
+
+use crate::error::{OlympusError, KEventRecordError};
+
+use super::{KEvent, KEventEntry, KEventDispatcher, KEventWindow, KEventKeyboard, KEventMouse, KEventController, KEventTouch,
+    KEventUser, KEVENT_USER_PAYLOAD_SIZE, KKeyInput, KKeyModifiers, KTextInput, KTouch, KPen};
+use super::action::{parse_scancode, parse_key, parse_mouse_button};
+
+#[allow(unused_imports)]
+use super::super::KWindow;
+
+/// Magic bytes at the start of every event record.
+pub const KEVENT_RECORD_MAGIC : [u8; 4] = *b"KEVT";
+
+/// Current version of the event record format.
+pub const KEVENT_RECORD_VERSION : u16 = 1;
+
+/// ##### Record [KEvent] dispatched by a [KEventDispatcher] into a file.
+///
+/// A [KEventRecorder] is given to [KEventDispatcher::set_recorder()]. Every [KEvent] passing through [KEventDispatcher::dispatch()],
+/// handled or not, is then written with its time since the first recorded [KEvent] and its window id. Records are read back by [KEventReplay].
+///
+/// Write errors don't interrupt dispatch. They are returned by [KEventRecorder::finish()].
+///
+/// # Format
+/// A record starts with [KEVENT_RECORD_MAGIC] followed by the format version as little-endian u16.
+/// Each event is a little-endian u16 payload length followed by the payload :
+/// * `u64` nanoseconds since the first recorded event.
+/// * `u64` window id.
+/// * `u8` category and `u8` variant of the [KEvent] followed by its values as little-endian numbers.
+///
+/// Scancodes, keys and mouse buttons are written as their [Debug] form, texts as a u8 length followed by UTF-8 bytes.
+/// Readers ignore payload bytes they don't know so that future versions can append data to events.
+///
+/// # Example(s)
+/// ```no_run
+/// use olympus::kleio::display::event::{KEventDispatcher, KEventRecorder};
+///
+/// let mut dispatcher = KEventDispatcher::new(false);
+/// dispatcher.set_recorder(Some(KEventRecorder::new("session.kevt").unwrap()));
+///
+/// // ... window.dispatch_events(&mut dispatcher, true) in main loop ...
+///
+/// if let Some(recorder) = dispatcher.set_recorder(None) {
+///     println!("{} events recorded.", recorder.finish().unwrap());
+/// }
+/// ```
+pub struct KEventRecorder {
+    /// Destination of the record.
+    writer : Box<dyn Write>,
+
+    /// Timestamp of the first recorded event.
+    start : Option<Instant>,
+
+    /// Count of events recorded.
+    count : usize,
+
+    /// True if a write failed.
+    failed : bool,
+}
+
+impl KEventRecorder {
+    /// Create a new [KEventRecorder] writing into a new file at path.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KEventRecord(KEventRecordError::FileError)] if file can't be created.
+    pub fn new(path : impl AsRef<Path>) -> Result<KEventRecorder, OlympusError> {
+        match File::create(path) {
+            Ok(file) => KEventRecorder::from_writer(Box::new(BufWriter::new(file))),
+            Err(_) => Err(OlympusError::KEventRecord(KEventRecordError::FileError)),
+        }
+    }
+
+    /// Create a new [KEventRecorder] writing into a writer.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KEventRecord(KEventRecordError::FileError)] if header can't be written.
+    pub fn from_writer(mut writer : Box<dyn Write>) -> Result<KEventRecorder, OlympusError> {
+        let mut header = KEVENT_RECORD_MAGIC.to_vec();
+        header.extend_from_slice(&KEVENT_RECORD_VERSION.to_le_bytes());
+
+        match writer.write_all(&header) {
+            Ok(_) => Ok(KEventRecorder { writer, start: None, count: 0, failed: false }),
+            Err(_) => Err(OlympusError::KEventRecord(KEventRecordError::FileError)),
+        }
+    }
+
+    /// Write a [KEventEntry] into the record.
+    pub fn record(&mut self, entry : &KEventEntry) {
+        let start = *self.start.get_or_insert(entry.get_timestamp());
+
+        let mut payload : Vec<u8> = Vec::new();
+        payload.extend_from_slice(&(entry.get_timestamp().saturating_duration_since(start).as_nanos().min(u64::MAX as u128) as u64).to_le_bytes());
+        payload.extend_from_slice(&entry.get_window_id().to_le_bytes());
+        encode_event(&mut payload, entry.get_event());
+
+        let mut record = (payload.len() as u16).to_le_bytes().to_vec();
+        record.extend(payload);
+
+        if self.writer.write_all(&record).is_ok() {
+            self.count += 1;
+        } else {
+            self.failed = true;
+        }
+    }
+
+    /// Returns the count of events recorded.
+    pub fn get_event_count(&self) -> usize {
+        self.count
+    }
+
+    /// Flush the record and close it.
+    ///
+    /// Returns the count of events recorded.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KEventRecord(KEventRecordError::FileError)] if an event couldn't be written.
+    pub fn finish(mut self) -> Result<usize, OlympusError> {
+        if self.writer.flush().is_err() || self.failed {
+            Err(OlympusError::KEventRecord(KEventRecordError::FileError))
+        } else {
+            Ok(self.count)
+        }
+    }
+}
+
+/// ##### Replay [KEvent] recorded by a [KEventRecorder] through a [KEventDispatcher].
+///
+/// Events are dispatched with [KEventDispatcher::dispatch_from()] using their recorded window id and a timestamp
+/// relative to the start of the replay, so no [KWindow] is needed. Replay speed can be accelerated with [KEventReplay::set_speed()].
+///
+/// [KEventReplay::dispatch_events()] follows the clock and is called once per frame. [KEventReplay::dispatch_until()] follows
+/// a given elapsed time, which makes replays deterministic for tests and fixed time step simulations.
+///
+/// # Example(s)
+/// ```no_run
+/// use std::time::Duration;
+/// use olympus::kleio::display::event::{KEventDispatcher, KEventReplay};
+///
+/// let mut dispatcher = KEventDispatcher::new(false);
+/// let mut replay = KEventReplay::new("session.kevt").unwrap();
+///
+/// // ... add receivers via dispatcher.add_event_receiver() ...
+///
+/// // Replay 60 frames per second whatever the time needed by each frame.
+/// let mut elapsed = Duration::ZERO;
+/// while !replay.is_finished() {
+///     elapsed += Duration::from_micros(16_667);
+///     replay.dispatch_until(&mut dispatcher, elapsed);
+/// }
+/// ```
+pub struct KEventReplay {
+    /// Recorded events with their time since the first event and their window id.
+    events : Vec<(Duration, u64, KEvent)>,
+
+    /// Index of the next event to dispatch.
+    position : usize,
+
+    /// Replay speed multiplier.
+    speed : f64,
+
+    /// Timestamp of the start of the replay. None until first dispatch.
+    start : Option<Instant>,
+}
+
+impl KEventReplay {
+    /// Create a new [KEventReplay] reading a record file at path.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KEventRecord(KEventRecordError::FileError)] if file can't be read.
+    ///
+    /// Returns [OlympusError::KEventRecord(KEventRecordError::InvalidHeader)] if file isn't an event record.
+    ///
+    /// Returns [OlympusError::KEventRecord(KEventRecordError::UnsupportedVersion)] if record version is newer than [KEVENT_RECORD_VERSION].
+    ///
+    /// Returns [OlympusError::KEventRecord(KEventRecordError::CorruptedEvent(index))] if an event is truncated or malformed.
+    pub fn new(path : impl AsRef<Path>) -> Result<KEventReplay, OlympusError> {
+        match File::open(path) {
+            Ok(file) => KEventReplay::from_reader(BufReader::new(file)),
+            Err(_) => Err(OlympusError::KEventRecord(KEventRecordError::FileError)),
+        }
+    }
+
+    /// Create a new [KEventReplay] reading a record from a reader.
+    ///
+    /// # Error(s)
+    /// See [KEventReplay::new()].
+    pub fn from_reader(mut reader : impl Read) -> Result<KEventReplay, OlympusError> {
+        let mut data : Vec<u8> = Vec::new();
+        reader.read_to_end(&mut data).map_err(|_| OlympusError::KEventRecord(KEventRecordError::FileError))?;
+
+        if data.len() < 6 || data[0..4] != KEVENT_RECORD_MAGIC {
+            return Err(OlympusError::KEventRecord(KEventRecordError::InvalidHeader));
+        }
+        if u16::from_le_bytes([data[4], data[5]]) > KEVENT_RECORD_VERSION {
+            return Err(OlympusError::KEventRecord(KEventRecordError::UnsupportedVersion));
+        }
+
+        let mut cursor = KEventRecordCursor { data: &data, position: 6 };
+        let mut events : Vec<(Duration, u64, KEvent)> = Vec::new();
+
+        while !cursor.is_end() {
+            let error = OlympusError::KEventRecord(KEventRecordError::CorruptedEvent(events.len()));
+            let length = cursor.u16().ok_or(error)?;
+            let mut payload = KEventRecordCursor { data: cursor.take(length as usize).ok_or(error)?, position: 0 };
+
+            let time = Duration::from_nanos(payload.u64().ok_or(error)?);
+            let window_id = payload.u64().ok_or(error)?;
+            let event = decode_event(&mut payload).ok_or(error)?;
+
+            // Remaining bytes are ignored for forward compatibility.
+            events.push((time, window_id, event));
+        }
+
+        Ok(KEventReplay { events, position: 0, speed: 1.0, start: None })
+    }
+
+    /// Set the replay speed multiplier. 1.0 replays at recorded speed, 2.0 twice faster. Speeds of 0.0 or less are ignored.
+    pub fn set_speed(&mut self, speed : f64) {
+        if speed > 0.0 {
+            self.speed = speed;
+        }
+    }
+
+    /// Returns the replay speed multiplier.
+    pub fn get_speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Returns the count of recorded events.
+    pub fn get_event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns the recorded events with their time since the first event and their window id.
+    pub fn get_events(&self) -> &Vec<(Duration, u64, KEvent)> {
+        &self.events
+    }
+
+    /// Returns the duration of the replay at current speed.
+    pub fn get_duration(&self) -> Duration {
+        self.events.last().map_or(Duration::ZERO, |(time, _, _)| self.get_replay_time(*time))
+    }
+
+    /// Returns True if all events were dispatched.
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.events.len()
+    }
+
+    /// Restart the replay from the first event.
+    pub fn rewind(&mut self) {
+        self.position = 0;
+        self.start = None;
+    }
+
+    /// Dispatch events due since the first call according to the clock.
+    ///
+    /// Returns the count of events dispatched.
+    pub fn dispatch_events(&mut self, dispatcher : &mut KEventDispatcher) -> usize {
+        let elapsed = self.start.get_or_insert_with(Instant::now).elapsed();
+        self.dispatch_until(dispatcher, elapsed)
+    }
+
+    /// Dispatch events due when elapsed time passed since the start of the replay. Use [Duration::MAX] to dispatch all events.
+    ///
+    /// Returns the count of events dispatched.
+    pub fn dispatch_until(&mut self, dispatcher : &mut KEventDispatcher, elapsed : Duration) -> usize {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let mut count : usize = 0;
+
+        while let Some((time, window_id, event)) = self.events.get(self.position) {
+            let time = self.get_replay_time(*time);
+            if time > elapsed {
+                break;
+            }
+            dispatcher.dispatch_from(event, start + time, *window_id);
+            self.position += 1;
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns recorded time scaled by replay speed.
+    fn get_replay_time(&self, time : Duration) -> Duration {
+        Duration::from_secs_f64(time.as_secs_f64() / self.speed)
+    }
+}
+
+/// Cursor used to decode a record.
+struct KEventRecordCursor<'a> {
+    /// Data decoded.
+    data : &'a [u8],
+
+    /// Current position in data.
+    position : usize,
+}
+
+impl<'a> KEventRecordCursor<'a> {
+    /// Take the next count bytes.
+    fn take(&mut self, count : usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.position..self.position + count)?;
+        self.position += count;
+        Some(bytes)
+    }
+
+    /// Returns True if all bytes were taken.
+    fn is_end(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    /// Take a u8.
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    /// Take a little-endian u16.
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes(self.take(2)?.try_into().ok()?))
+    }
+
+    /// Take a little-endian i16.
+    fn i16(&mut self) -> Option<i16> {
+        Some(i16::from_le_bytes(self.take(2)?.try_into().ok()?))
+    }
+
+    /// Take a little-endian i32.
+    fn i32(&mut self) -> Option<i32> {
+        Some(i32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    /// Take a little-endian u32.
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    /// Take a little-endian u64.
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    /// Take a little-endian f32.
+    fn f32(&mut self) -> Option<f32> {
+        Some(f32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    /// Take a little-endian f64.
+    fn f64(&mut self) -> Option<f64> {
+        Some(f64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    /// Take a pair of i32.
+    fn position(&mut self) -> Option<(i32, i32)> {
+        Some((self.i32()?, self.i32()?))
+    }
+
+    /// Take a pair of u32.
+    fn size(&mut self) -> Option<(u32, u32)> {
+        Some((self.u32()?, self.u32()?))
+    }
+
+    /// Take a pair of f32.
+    fn point(&mut self) -> Option<(f32, f32)> {
+        Some((self.f32()?, self.f32()?))
+    }
+
+    /// Take a u8 length-prefixed UTF-8 string.
+    fn string(&mut self) -> Option<&'a str> {
+        let length = self.u8()? as usize;
+        std::str::from_utf8(self.take(length)?).ok()
+    }
+}
+
+/// Encode a [KEvent] as category, variant and values.
+fn encode_event(payload : &mut Vec<u8>, event : &KEvent) {
+    match event {
+        KEvent::None => payload.extend_from_slice(&[0, 0]),
+        KEvent::Window(event) => {
+            payload.push(1);
+            match event {
+                KEventWindow::Shown() => payload.push(0),
+                KEventWindow::Hidden() => payload.push(1),
+                KEventWindow::Exposed(position, size) => { payload.push(2); encode_position(payload, *position); encode_size(payload, *size); },
+                KEventWindow::Moved(position) => { payload.push(3); encode_position(payload, *position); },
+                KEventWindow::MovedResized(position, size) => { payload.push(4); encode_position(payload, *position); encode_size(payload, *size); },
+                KEventWindow::Resized(size) => { payload.push(5); encode_size(payload, *size); },
+                KEventWindow::Minimized() => payload.push(6),
+                KEventWindow::Maximized() => payload.push(7),
+                KEventWindow::Fullscreen() => payload.push(8),
+                KEventWindow::Restored() => payload.push(9),
+                KEventWindow::CursorEnter() => payload.push(10),
+                KEventWindow::CursorLeave() => payload.push(11),
+                KEventWindow::Focus() => payload.push(12),
+                KEventWindow::Blur() => payload.push(13),
+                KEventWindow::Close() => payload.push(14),
+            }
+        },
+        KEvent::Keyboard(event) => {
+            payload.push(2);
+            match event {
+                KEventKeyboard::KeyDown(input) => { payload.push(0); encode_key_input(payload, input); },
+                KEventKeyboard::KeyUp(input) => { payload.push(1); encode_key_input(payload, input); },
+                KEventKeyboard::TextInput(text) => { payload.push(2); encode_string(payload, text.as_str()); },
+            }
+        },
+        KEvent::Mouse(event) => {
+            payload.push(3);
+            match event {
+                KEventMouse::Moved(position) => { payload.push(0); encode_position(payload, *position); },
+                KEventMouse::ButtonDown(button, position) => { payload.push(1); encode_string(payload, &format!("{:?}", button)); encode_position(payload, *position); },
+                KEventMouse::ButtonUp(button, position) => { payload.push(2); encode_string(payload, &format!("{:?}", button)); encode_position(payload, *position); },
+                KEventMouse::Wheel(horizontal, vertical) => { payload.push(3); encode_position(payload, (*horizontal, *vertical)); },
+                KEventMouse::RawMotion(x, y) => {
+                    payload.push(4);
+                    payload.extend_from_slice(&x.to_le_bytes());
+                    payload.extend_from_slice(&y.to_le_bytes());
+                },
+            }
+        },
+        KEvent::Controller(event) => {
+            payload.push(4);
+            match event {
+                KEventController::Connected(controller) => payload.extend_from_slice(&[0, *controller]),
+                KEventController::Disconnected(controller) => payload.extend_from_slice(&[1, *controller]),
+                KEventController::ButtonDown(controller, button) => payload.extend_from_slice(&[2, *controller, *button]),
+                KEventController::ButtonUp(controller, button) => payload.extend_from_slice(&[3, *controller, *button]),
+                KEventController::Axis(controller, axis, value) => {
+                    payload.extend_from_slice(&[4, *controller, *axis]);
+                    payload.extend_from_slice(&value.to_le_bytes());
+                },
+            }
+        },
+        KEvent::Touch(event) => {
+            payload.push(5);
+            match event {
+                KEventTouch::Begin(touch) => { payload.push(0); encode_touch(payload, touch); },
+                KEventTouch::Move(touch) => { payload.push(1); encode_touch(payload, touch); },
+                KEventTouch::End(touch) => { payload.push(2); encode_touch(payload, touch); },
+                KEventTouch::Cancel(touch) => { payload.push(3); encode_touch(payload, touch); },
+                KEventTouch::PenDown(pen) => { payload.push(4); encode_pen(payload, pen); },
+                KEventTouch::PenMove(pen) => { payload.push(5); encode_pen(payload, pen); },
+                KEventTouch::PenUp(pen) => { payload.push(6); encode_pen(payload, pen); },
+            }
+        },
+        KEvent::User(event) => {
+            payload.extend_from_slice(&[6, 0]);
+            payload.extend_from_slice(&event.get_id().to_le_bytes());
+            payload.extend_from_slice(event.get_payload());
+        },
+        KEvent::Unknown => payload.extend_from_slice(&[255, 0]),
+    }
+}
+
+/// Decode a [KEvent] written by encode_event().
+fn decode_event(cursor : &mut KEventRecordCursor) -> Option<KEvent> {
+    match (cursor.u8()?, cursor.u8()?) {
+        (0, 0) => Some(KEvent::None),
+        (1, variant) => Some(KEvent::Window(match variant {
+            0 => KEventWindow::Shown(),
+            1 => KEventWindow::Hidden(),
+            2 => KEventWindow::Exposed(cursor.position()?, cursor.size()?),
+            3 => KEventWindow::Moved(cursor.position()?),
+            4 => KEventWindow::MovedResized(cursor.position()?, cursor.size()?),
+            5 => KEventWindow::Resized(cursor.size()?),
+            6 => KEventWindow::Minimized(),
+            7 => KEventWindow::Maximized(),
+            8 => KEventWindow::Fullscreen(),
+            9 => KEventWindow::Restored(),
+            10 => KEventWindow::CursorEnter(),
+            11 => KEventWindow::CursorLeave(),
+            12 => KEventWindow::Focus(),
+            13 => KEventWindow::Blur(),
+            14 => KEventWindow::Close(),
+            _ => return None,
+        })),
+        (2, variant) => Some(KEvent::Keyboard(match variant {
+            0 => KEventKeyboard::KeyDown(decode_key_input(cursor)?),
+            1 => KEventKeyboard::KeyUp(decode_key_input(cursor)?),
+            2 => KEventKeyboard::TextInput(KTextInput::new(cursor.string()?)),
+            _ => return None,
+        })),
+        (3, variant) => Some(KEvent::Mouse(match variant {
+            0 => KEventMouse::Moved(cursor.position()?),
+            1 => KEventMouse::ButtonDown(parse_mouse_button(cursor.string()?)?, cursor.position()?),
+            2 => KEventMouse::ButtonUp(parse_mouse_button(cursor.string()?)?, cursor.position()?),
+            3 => KEventMouse::Wheel(cursor.i32()?, cursor.i32()?),
+            4 => KEventMouse::RawMotion(cursor.f64()?, cursor.f64()?),
+            _ => return None,
+        })),
+        (4, variant) => Some(KEvent::Controller(match variant {
+            0 => KEventController::Connected(cursor.u8()?),
+            1 => KEventController::Disconnected(cursor.u8()?),
+            2 => KEventController::ButtonDown(cursor.u8()?, cursor.u8()?),
+            3 => KEventController::ButtonUp(cursor.u8()?, cursor.u8()?),
+            4 => KEventController::Axis(cursor.u8()?, cursor.u8()?, cursor.i16()?),
+            _ => return None,
+        })),
+        (5, variant) => Some(KEvent::Touch(match variant {
+            0 => KEventTouch::Begin(decode_touch(cursor)?),
+            1 => KEventTouch::Move(decode_touch(cursor)?),
+            2 => KEventTouch::End(decode_touch(cursor)?),
+            3 => KEventTouch::Cancel(decode_touch(cursor)?),
+            4 => KEventTouch::PenDown(decode_pen(cursor)?),
+            5 => KEventTouch::PenMove(decode_pen(cursor)?),
+            6 => KEventTouch::PenUp(decode_pen(cursor)?),
+            _ => return None,
+        })),
+        (6, 0) => Some(KEvent::User(KEventUser::new(cursor.u32()?, cursor.take(KEVENT_USER_PAYLOAD_SIZE)?.try_into().ok()?))),
+        (255, 0) => Some(KEvent::Unknown),
+        _ => None,
+    }
+}
+
+/// Encode a pair of i32.
+fn encode_position(payload : &mut Vec<u8>, position : (i32, i32)) {
+    payload.extend_from_slice(&position.0.to_le_bytes());
+    payload.extend_from_slice(&position.1.to_le_bytes());
+}
+
+/// Encode a pair of u32.
+fn encode_size(payload : &mut Vec<u8>, size : (u32, u32)) {
+    payload.extend_from_slice(&size.0.to_le_bytes());
+    payload.extend_from_slice(&size.1.to_le_bytes());
+}
+
+/// Encode a pair of f32.
+fn encode_point(payload : &mut Vec<u8>, point : (f32, f32)) {
+    payload.extend_from_slice(&point.0.to_le_bytes());
+    payload.extend_from_slice(&point.1.to_le_bytes());
+}
+
+/// Encode a u8 length-prefixed UTF-8 string. Strings are always shorter than 256 bytes.
+fn encode_string(payload : &mut Vec<u8>, s : &str) {
+    payload.push(s.len().min(u8::MAX as usize) as u8);
+    payload.extend_from_slice(&s.as_bytes()[0..s.len().min(u8::MAX as usize)]);
+}
+
+/// Encode a [KKeyInput] as scancode, key, modifiers flags and repeat.
+fn encode_key_input(payload : &mut Vec<u8>, input : &KKeyInput) {
+    encode_string(payload, &format!("{:?}", input.scancode));
+    encode_string(payload, &format!("{:?}", input.key));
+    payload.push(input.modifiers.get_flags());
+    payload.push(input.repeat as u8);
+}
+
+/// Decode a [KKeyInput] written by encode_key_input().
+fn decode_key_input(cursor : &mut KEventRecordCursor) -> Option<KKeyInput> {
+    let scancode = parse_scancode(cursor.string()?)?;
+    let key = parse_key(cursor.string()?)?;
+    let flags = cursor.u8()?;
+    let modifiers = [KKeyModifiers::SHIFT, KKeyModifiers::CONTROL, KKeyModifiers::ALT, KKeyModifiers::SUPER, KKeyModifiers::CAPS_LOCK, KKeyModifiers::NUM_LOCK]
+        .into_iter().filter(|m| flags & m.get_flags() != 0).fold(KKeyModifiers::NONE, |all, m| all | m);
+
+    Some(KKeyInput::new(scancode, key, modifiers, cursor.u8()? != 0))
+}
+
+/// Encode a [KTouch] as id and position.
+fn encode_touch(payload : &mut Vec<u8>, touch : &KTouch) {
+    payload.extend_from_slice(&touch.id.to_le_bytes());
+    encode_point(payload, touch.position);
+}
+
+/// Decode a [KTouch] written by encode_touch().
+fn decode_touch(cursor : &mut KEventRecordCursor) -> Option<KTouch> {
+    Some(KTouch::new(cursor.u64()?, cursor.point()?))
+}
+
+/// Encode a [KPen] as position, pressure, tilt and eraser.
+fn encode_pen(payload : &mut Vec<u8>, pen : &KPen) {
+    encode_point(payload, pen.position);
+    payload.extend_from_slice(&pen.pressure.to_le_bytes());
+    encode_point(payload, pen.tilt);
+    payload.push(pen.eraser as u8);
+}
+
+/// Decode a [KPen] written by encode_pen().
+fn decode_pen(cursor : &mut KEventRecordCursor) -> Option<KPen> {
+    Some(KPen::new(cursor.point()?, cursor.f32()?, cursor.point()?, cursor.u8()? != 0))
+}
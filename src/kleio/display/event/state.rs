@@ -0,0 +1,314 @@
+use std::{collections::{HashMap, HashSet}, hash::Hash};
+
+use super::{KEvent, KEventEntry, KEventReceiver, KEventKeyboard, KEventMouse, KEventController, KEventWindow, KKey, KScancode, KKeyModifiers, KMouseButton};
+
+#[allow(unused_imports)]
+use super::{KEventDispatcher, super::KWindow};
+
+/// ##### Snapshot of keyboard, mouse and controller states fed by [KEvent].
+///
+/// [KInputState] is a [KEventReceiver] that never handles [KEvent], so other receivers still get them. Add it as the
+/// most recent receiver of the [KEventDispatcher] so no other receiver can mask events before it.
+///
+/// Pressed and released edges, wheel, raw mouse motion and text are kept until [KInputState::reset_frame()] is called, usually once per frame
+/// before [KWindow::dispatch_events()]. A key pressed and released within the same frame is both pressed and released, but not down.
+///
+/// Keys and buttons down are released when the window loses focus since their release won't be received.
+///
+/// # Example(s)
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use olympus::kleio::display::event::{KEvent, KEventDispatcher, KEventKeyboard, KInputState, KKey, KKeyInput, KKeyModifiers, KScancode};
+///
+/// let state = Rc::new(RefCell::new(KInputState::new()));
+/// let mut dispatcher = KEventDispatcher::new(false);
+/// let _handle = dispatcher.add_event_receiver(state.clone()).unwrap();
+///
+/// // Main loop
+/// state.borrow_mut().reset_frame();
+/// dispatcher.dispatch(&KEvent::Keyboard(KEventKeyboard::KeyDown(KKeyInput::new(KScancode::W, KKey::Character('z'), KKeyModifiers::NONE, false))));
+///
+/// // Game logic
+/// assert!(state.borrow().is_scancode_down(KScancode::W));
+/// assert!(state.borrow().is_key_pressed(KKey::Character('z')));
+/// ```
+pub struct KInputState {
+    /// If False, no [KEvent] is received.
+    enabled : bool,
+
+    /// Physical keys states.
+    scancodes : KInputButtonState<KScancode>,
+
+    /// Logical keys states.
+    keys : KInputButtonState<KKey>,
+
+    /// Modifiers of the last key event.
+    modifiers : KKeyModifiers,
+
+    /// Text input received since last frame reset.
+    text : String,
+
+    /// Mouse buttons states.
+    mouse_buttons : KInputButtonState<KMouseButton>,
+
+    /// Last cursor position (or acceleration according to cursor mode).
+    cursor_position : (i32, i32),
+
+    /// Wheel scrolled since last frame reset.
+    wheel : (i32, i32),
+
+    /// Raw mouse motion since last frame reset.
+    mouse_motion : (f64, f64),
+
+    /// Connected controllers states by controller id.
+    controllers : HashMap<u8, KInputControllerState>,
+}
+
+/// State of a connected controller.
+struct KInputControllerState {
+    /// Buttons states.
+    buttons : KInputButtonState<u8>,
+
+    /// Last value of each axis.
+    axes : HashMap<u8, i16>,
+}
+
+/// Down, pressed and released states of buttons.
+struct KInputButtonState<T : Eq + Hash + Copy> {
+    /// Buttons held down.
+    down : HashSet<T>,
+
+    /// Buttons pressed since last frame reset.
+    pressed : HashSet<T>,
+
+    /// Buttons released since last frame reset.
+    released : HashSet<T>,
+}
+
+impl<T : Eq + Hash + Copy> KInputButtonState<T> {
+    /// Create new empty button states.
+    fn new() -> KInputButtonState<T> {
+        KInputButtonState { down: HashSet::new(), pressed: HashSet::new(), released: HashSet::new() }
+    }
+
+    /// Register button pressed. Button already down (ie. auto-repeat) isn't pressed again.
+    fn press(&mut self, button : T) {
+        if self.down.insert(button) {
+            self.pressed.insert(button);
+        }
+    }
+
+    /// Register button released.
+    fn release(&mut self, button : T) {
+        if self.down.remove(&button) {
+            self.released.insert(button);
+        }
+    }
+
+    /// Release all buttons down.
+    fn release_all(&mut self) {
+        self.released.extend(self.down.drain());
+    }
+
+    /// Clear pressed and released edges.
+    fn reset_frame(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+}
+
+impl KInputState {
+    /// Create a new enabled [KInputState] with nothing pressed.
+    pub fn new() -> KInputState {
+        KInputState { enabled: true, scancodes: KInputButtonState::new(), keys: KInputButtonState::new(), modifiers: KKeyModifiers::NONE,
+            text: String::new(), mouse_buttons: KInputButtonState::new(), cursor_position: (0,0), wheel: (0,0), mouse_motion: (0.0, 0.0),
+            controllers: HashMap::new() }
+    }
+
+    /// Clear pressed and released edges, wheel, raw mouse motion and text. Keys and buttons down stay down.
+    ///
+    /// Should be called once per frame before [KWindow::dispatch_events()].
+    pub fn reset_frame(&mut self) {
+        self.scancodes.reset_frame();
+        self.keys.reset_frame();
+        self.text.clear();
+        self.mouse_buttons.reset_frame();
+        self.wheel = (0,0);
+        self.mouse_motion = (0.0, 0.0);
+        for controller in self.controllers.values_mut() {
+            controller.buttons.reset_frame();
+        }
+    }
+
+    /// Enable or disable the [KInputState]. A disabled [KInputState] doesn't receive [KEvent].
+    pub fn set_enabled(&mut self, enabled : bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns True if physical key is down.
+    pub fn is_scancode_down(&self, scancode : KScancode) -> bool {
+        self.scancodes.down.contains(&scancode)
+    }
+
+    /// Returns True if physical key was pressed since last frame reset.
+    pub fn is_scancode_pressed(&self, scancode : KScancode) -> bool {
+        self.scancodes.pressed.contains(&scancode)
+    }
+
+    /// Returns True if physical key was released since last frame reset.
+    pub fn is_scancode_released(&self, scancode : KScancode) -> bool {
+        self.scancodes.released.contains(&scancode)
+    }
+
+    /// Returns True if logical key is down.
+    pub fn is_key_down(&self, key : KKey) -> bool {
+        self.keys.down.contains(&key)
+    }
+
+    /// Returns True if logical key was pressed since last frame reset.
+    pub fn is_key_pressed(&self, key : KKey) -> bool {
+        self.keys.pressed.contains(&key)
+    }
+
+    /// Returns True if logical key was released since last frame reset.
+    pub fn is_key_released(&self, key : KKey) -> bool {
+        self.keys.released.contains(&key)
+    }
+
+    /// Returns the [KKeyModifiers] of the last key event.
+    pub fn get_modifiers(&self) -> KKeyModifiers {
+        self.modifiers
+    }
+
+    /// Returns the text input received since last frame reset.
+    pub fn get_text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// Returns True if mouse button is down.
+    pub fn is_mouse_button_down(&self, button : KMouseButton) -> bool {
+        self.mouse_buttons.down.contains(&button)
+    }
+
+    /// Returns True if mouse button was pressed since last frame reset.
+    pub fn is_mouse_button_pressed(&self, button : KMouseButton) -> bool {
+        self.mouse_buttons.pressed.contains(&button)
+    }
+
+    /// Returns True if mouse button was released since last frame reset.
+    pub fn is_mouse_button_released(&self, button : KMouseButton) -> bool {
+        self.mouse_buttons.released.contains(&button)
+    }
+
+    /// Returns the last cursor position (x,y), or acceleration when [KWindow] cursor mode is acceleration.
+    pub fn get_cursor_position(&self) -> (i32, i32) {
+        self.cursor_position
+    }
+
+    /// Returns the wheel amount scrolled (horizontal, vertical) since last frame reset.
+    pub fn get_wheel(&self) -> (i32, i32) {
+        self.wheel
+    }
+
+    /// Returns the raw mouse motion (x, y) received since last frame reset. Only sent when [KWindow] cursor mode is acceleration.
+    pub fn get_mouse_motion(&self) -> (f64, f64) {
+        self.mouse_motion
+    }
+
+    /// Returns the ids of connected controllers.
+    pub fn get_controllers(&self) -> Vec<u8> {
+        let mut ids : Vec<u8> = self.controllers.keys().copied().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Returns True if controller is connected.
+    pub fn is_controller_connected(&self, controller : u8) -> bool {
+        self.controllers.contains_key(&controller)
+    }
+
+    /// Returns True if controller button is down.
+    pub fn is_controller_button_down(&self, controller : u8, button : u8) -> bool {
+        self.controllers.get(&controller).is_some_and(|c| c.buttons.down.contains(&button))
+    }
+
+    /// Returns True if controller button was pressed since last frame reset.
+    pub fn is_controller_button_pressed(&self, controller : u8, button : u8) -> bool {
+        self.controllers.get(&controller).is_some_and(|c| c.buttons.pressed.contains(&button))
+    }
+
+    /// Returns True if controller button was released since last frame reset.
+    pub fn is_controller_button_released(&self, controller : u8, button : u8) -> bool {
+        self.controllers.get(&controller).is_some_and(|c| c.buttons.released.contains(&button))
+    }
+
+    /// Returns the last value of a controller axis. Returns 0 if controller isn't connected or axis wasn't used.
+    pub fn get_controller_axis(&self, controller : u8, axis : u8) -> i16 {
+        self.controllers.get(&controller).and_then(|c| c.axes.get(&axis).copied()).unwrap_or(0)
+    }
+
+    /// Returns the state of a controller, registering it if not connected yet.
+    fn get_controller_mut(&mut self, controller : u8) -> &mut KInputControllerState {
+        self.controllers.entry(controller).or_insert_with(|| KInputControllerState { buttons: KInputButtonState::new(), axes: HashMap::new() })
+    }
+}
+
+impl Default for KInputState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KEventReceiver for KInputState {
+    /// Update states with [KEvent]. Always returns False so other receivers get the [KEvent].
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        match entry.get_event() {
+            KEvent::Keyboard(event) => match event {
+                KEventKeyboard::KeyDown(input) => {
+                    self.scancodes.press(input.scancode);
+                    self.keys.press(input.key);
+                    self.modifiers = input.modifiers;
+                },
+                KEventKeyboard::KeyUp(input) => {
+                    self.scancodes.release(input.scancode);
+                    self.keys.release(input.key);
+                    self.modifiers = input.modifiers;
+                },
+                KEventKeyboard::TextInput(text) => self.text.push_str(text.as_str()),
+            },
+            KEvent::Mouse(event) => match event {
+                KEventMouse::Moved(position) => self.cursor_position = *position,
+                KEventMouse::ButtonDown(button, position) => {
+                    self.mouse_buttons.press(*button);
+                    self.cursor_position = *position;
+                },
+                KEventMouse::ButtonUp(button, position) => {
+                    self.mouse_buttons.release(*button);
+                    self.cursor_position = *position;
+                },
+                KEventMouse::Wheel(horizontal, vertical) => self.wheel = (self.wheel.0 + horizontal, self.wheel.1 + vertical),
+                KEventMouse::RawMotion(x, y) => self.mouse_motion = (self.mouse_motion.0 + x, self.mouse_motion.1 + y),
+            },
+            KEvent::Controller(event) => match event {
+                KEventController::Connected(controller) => { self.get_controller_mut(*controller); },
+                KEventController::Disconnected(controller) => { self.controllers.remove(controller); },
+                KEventController::ButtonDown(controller, button) => self.get_controller_mut(*controller).buttons.press(*button),
+                KEventController::ButtonUp(controller, button) => self.get_controller_mut(*controller).buttons.release(*button),
+                KEventController::Axis(controller, axis, value) => { self.get_controller_mut(*controller).axes.insert(*axis, *value); },
+            },
+            KEvent::Window(KEventWindow::Blur()) => {
+                // Releases won't be received while window isn't focused.
+                self.scancodes.release_all();
+                self.keys.release_all();
+                self.mouse_buttons.release_all();
+                self.modifiers = KKeyModifiers::NONE;
+            },
+            _ => {},
+        }
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
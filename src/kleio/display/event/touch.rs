@@ -0,0 +1,297 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::{KEvent, KEventEntry, KEventReceiver};
+
+/// Minimum distance travelled by fingers, in normalized window units, for a swipe to be recognized.
+pub const KTOUCH_SWIPE_DISTANCE : f32 = 0.1;
+
+/// Minimum change of distance between 2 fingers, in normalized window units, for a pinch to begin.
+pub const KTOUCH_PINCH_THRESHOLD : f32 = 0.02;
+
+/// Start and current (or end) positions of a finger.
+type KTouchPath = ((f32, f32), (f32, f32));
+
+/// Enumeration of possible touch and pen events.
+///
+/// Positions are normalized to the window, from (0.0, 0.0) at the top left corner to (1.0, 1.0) at the bottom right corner.
+/// Touches outside the window may go beyond that range.
+#[derive(Copy, Clone)]
+pub enum KEventTouch {
+
+    /// Happens when a finger touches the screen. Provides the [KTouch] with a new finger id.
+    Begin(KTouch),
+
+    /// Happens when a finger moves on the screen. Provides the [KTouch] with the finger id given by [KEventTouch::Begin].
+    Move(KTouch),
+
+    /// Happens when a finger is lifted from the screen. Provides the last [KTouch] of the finger.
+    End(KTouch),
+
+    /// Happens when a touch is taken by the system (ie. a system gesture). Provides the last [KTouch] of the finger.
+    ///
+    /// A cancelled touch must be undone since its [KEventTouch::End] will never come.
+    Cancel(KTouch),
+
+    /// Happens when a pen touches the screen. Provides the [KPen] state.
+    PenDown(KPen),
+
+    /// Happens when a pen moves, touching the screen or hovering it. Provides the [KPen] state.
+    PenMove(KPen),
+
+    /// Happens when a pen is lifted from the screen. Provides the [KPen] state.
+    PenUp(KPen),
+}
+
+impl std::fmt::Debug for KEventTouch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Begin(arg0) => f.debug_tuple("Begin").field(arg0).finish(),
+            Self::Move(arg0) => f.debug_tuple("Move").field(arg0).finish(),
+            Self::End(arg0) => f.debug_tuple("End").field(arg0).finish(),
+            Self::Cancel(arg0) => f.debug_tuple("Cancel").field(arg0).finish(),
+            Self::PenDown(arg0) => f.debug_tuple("PenDown").field(arg0).finish(),
+            Self::PenMove(arg0) => f.debug_tuple("PenMove").field(arg0).finish(),
+            Self::PenUp(arg0) => f.debug_tuple("PenUp").field(arg0).finish(),
+        }
+    }
+}
+
+/// A finger touching the screen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KTouch {
+    /// Finger id, unique while the finger touches the screen. Ids may be reused after [KEventTouch::End].
+    pub id : u64,
+
+    /// Normalized position (x, y) of the finger.
+    pub position : (f32, f32),
+}
+
+impl KTouch {
+    /// Create a new [KTouch].
+    pub fn new(id : u64, position : (f32, f32)) -> KTouch {
+        KTouch { id, position }
+    }
+}
+
+/// State of a pen (stylus).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KPen {
+    /// Normalized position (x, y) of the pen tip.
+    pub position : (f32, f32),
+
+    /// Pressure of the tip, from 0.0 (hovering) to 1.0.
+    pub pressure : f32,
+
+    /// Tilt (x, y) of the pen in degrees, from -90.0 to 90.0. (0.0, 0.0) is perpendicular to the screen.
+    pub tilt : (f32, f32),
+
+    /// True if the eraser end of the pen is used.
+    pub eraser : bool,
+}
+
+impl KPen {
+    /// Create a new [KPen].
+    pub fn new(position : (f32, f32), pressure : f32, tilt : (f32, f32), eraser : bool) -> KPen {
+        KPen { position, pressure, tilt, eraser }
+    }
+}
+
+/// Enumeration of gestures recognized by [KTouchGestureRecognizer].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum KTouchGesture {
+    /// 2 fingers moved apart or closer. Provides the scale since the pinch began (greater than 1.0 when moving apart) and
+    /// the normalized center (x, y) between fingers.
+    Pinch(f32, (f32, f32)),
+
+    /// 2 fingers pinch ended. Provides the final scale.
+    PinchEnd(f32),
+
+    /// Fingers moved in a direction then were lifted. Provides the direction and the count of fingers.
+    Swipe(KTouchSwipeDirection, u8),
+}
+
+/// Enumeration of swipe directions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KTouchSwipeDirection {
+    /// Fingers moved up.
+    Up,
+
+    /// Fingers moved down.
+    Down,
+
+    /// Fingers moved left.
+    Left,
+
+    /// Fingers moved right.
+    Right,
+}
+
+/// ##### Pinch and swipe gestures recognized from [KEventTouch].
+///
+/// [KTouchGestureRecognizer] is a [KEventReceiver] that never handles [KEvent], like [KInputState](super::KInputState).
+/// Recognized [KTouchGesture] are queued until popped with [KTouchGestureRecognizer::pop_gesture()].
+///
+/// A pinch is sent while exactly 2 fingers touch the screen and their distance changed by more than [KTOUCH_PINCH_THRESHOLD].
+/// A swipe is sent when all fingers are lifted after moving together by more than [KTOUCH_SWIPE_DISTANCE] without pinching.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::display::event::{KEventTouch, KTouch, KTouchGesture, KTouchGestureRecognizer, KTouchSwipeDirection};
+///
+/// let mut recognizer = KTouchGestureRecognizer::new();
+/// recognizer.handle_touch(&KEventTouch::Begin(KTouch::new(1, (0.2, 0.5))));
+/// recognizer.handle_touch(&KEventTouch::Move(KTouch::new(1, (0.6, 0.5))));
+/// recognizer.handle_touch(&KEventTouch::End(KTouch::new(1, (0.6, 0.5))));
+///
+/// assert_eq!(recognizer.pop_gesture(), Some(KTouchGesture::Swipe(KTouchSwipeDirection::Right, 1)));
+/// ```
+pub struct KTouchGestureRecognizer {
+    /// If False, no [KEvent] is received.
+    enabled : bool,
+
+    /// Fingers touching by id with their start and current positions.
+    touches : HashMap<u64, KTouchPath>,
+
+    /// Start and end positions of fingers lifted since first finger began.
+    lifted : Vec<KTouchPath>,
+
+    /// Distance between fingers when pinch began. None if no pinch is possible.
+    pinch_start : Option<f32>,
+
+    /// Last scale of the pinch. None if not pinching.
+    pinch_scale : Option<f32>,
+
+    /// True if fingers pinched or a touch was cancelled since first finger began. No swipe is recognized.
+    swipe_cancelled : bool,
+
+    /// Gestures waiting to be popped.
+    gestures : VecDeque<KTouchGesture>,
+}
+
+impl KTouchGestureRecognizer {
+    /// Create a new enabled [KTouchGestureRecognizer] without fingers.
+    pub fn new() -> KTouchGestureRecognizer {
+        KTouchGestureRecognizer { enabled: true, touches: HashMap::new(), lifted: Vec::new(), pinch_start: None, pinch_scale: None,
+            swipe_cancelled: false, gestures: VecDeque::new() }
+    }
+
+    /// Enable or disable the [KTouchGestureRecognizer]. A disabled [KTouchGestureRecognizer] doesn't receive [KEvent].
+    pub fn set_enabled(&mut self, enabled : bool) {
+        self.enabled = enabled;
+    }
+
+    /// Update fingers with a [KEventTouch] and queue recognized gestures. Pen events are ignored.
+    pub fn handle_touch(&mut self, event : &KEventTouch) {
+        match event {
+            KEventTouch::Begin(touch) => {
+                if self.touches.is_empty() {
+                    self.lifted.clear();
+                    self.swipe_cancelled = false;
+                }
+                self.touches.insert(touch.id, (touch.position, touch.position));
+                self.end_pinch();
+                self.pinch_start = self.get_distance();
+            },
+            KEventTouch::Move(touch) => {
+                if let Some((_, position)) = self.touches.get_mut(&touch.id) {
+                    *position = touch.position;
+                    self.update_pinch();
+                }
+            },
+            KEventTouch::End(touch) | KEventTouch::Cancel(touch) => {
+                if let Some((start, _)) = self.touches.remove(&touch.id) {
+                    self.lifted.push((start, touch.position));
+                    self.swipe_cancelled |= matches!(event, KEventTouch::Cancel(_));
+                    self.end_pinch();
+                    self.pinch_start = self.get_distance();
+
+                    // Swipe is recognized when the last finger is lifted, using all fingers motion.
+                    if self.touches.is_empty() && !self.swipe_cancelled {
+                        if let Some(direction) = self.get_swipe_direction() {
+                            self.gestures.push_back(KTouchGesture::Swipe(direction, self.lifted.len().min(u8::MAX as usize) as u8));
+                        }
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Pop the oldest [KTouchGesture] or [None] if no gesture was recognized.
+    pub fn pop_gesture(&mut self) -> Option<KTouchGesture> {
+        self.gestures.pop_front()
+    }
+
+    /// Returns the count of fingers touching.
+    pub fn get_touch_count(&self) -> usize {
+        self.touches.len()
+    }
+
+    /// Queue a pinch if 2 fingers distance changed enough.
+    fn update_pinch(&mut self) {
+        if let (Some(start), Some(distance)) = (self.pinch_start, self.get_distance()) {
+            if self.pinch_scale.is_some() || (distance - start).abs() > KTOUCH_PINCH_THRESHOLD {
+                let scale = if start > 0.0 { distance / start } else { 1.0 };
+                let mut positions = self.touches.values().map(|(_, position)| *position);
+                let (a, b) = (positions.next().unwrap(), positions.next().unwrap());
+
+                self.pinch_scale = Some(scale);
+                self.swipe_cancelled = true;
+                self.gestures.push_back(KTouchGesture::Pinch(scale, ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)));
+            }
+        }
+    }
+
+    /// Queue a pinch end if pinching.
+    fn end_pinch(&mut self) {
+        if let Some(scale) = self.pinch_scale.take() {
+            self.gestures.push_back(KTouchGesture::PinchEnd(scale));
+        }
+    }
+
+    /// Returns the distance between fingers if exactly 2 fingers touch.
+    fn get_distance(&self) -> Option<f32> {
+        if self.touches.len() == 2 {
+            let mut positions = self.touches.values().map(|(_, position)| *position);
+            let (a, b) = (positions.next().unwrap(), positions.next().unwrap());
+            Some(((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the direction of the average motion of lifted fingers if long enough.
+    fn get_swipe_direction(&self) -> Option<KTouchSwipeDirection> {
+        let count = self.lifted.len() as f32;
+        let (x, y) = self.lifted.iter().fold((0.0, 0.0), |motion, (start, end)|
+            (motion.0 + (end.0 - start.0) / count, motion.1 + (end.1 - start.1) / count));
+
+        if x.abs().max(y.abs()) < KTOUCH_SWIPE_DISTANCE {
+            None
+        } else if x.abs() > y.abs() {
+            Some(if x > 0.0 { KTouchSwipeDirection::Right } else { KTouchSwipeDirection::Left })
+        } else {
+            Some(if y > 0.0 { KTouchSwipeDirection::Down } else { KTouchSwipeDirection::Up })
+        }
+    }
+}
+
+impl Default for KTouchGestureRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KEventReceiver for KTouchGestureRecognizer {
+    /// Recognize gestures from [KEventTouch]. Always returns False so other receivers get the [KEvent].
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        if let KEvent::Touch(event) = entry.get_event() {
+            self.handle_touch(event);
+        }
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
@@ -0,0 +1,132 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+#[allow(unused_imports)]
+use super::{KEvent, KEventDispatcher};
+
+#[allow(unused_imports)]
+use super::super::KWindow;
+
+/// Size in bytes of [KEventUser] payload.
+pub const KEVENT_USER_PAYLOAD_SIZE : usize = 16;
+
+/// ##### User event posted by the application through the [KEvent] pipeline.
+///
+/// [KEvent] is [Copy] so [KEventUser] carries an id chosen by the application and a fixed size payload of [KEVENT_USER_PAYLOAD_SIZE] bytes.
+/// Larger data should be kept by the application and referenced with a key (ie. an entity id or an index) in the payload.
+///
+/// [KEventUser] is posted with [KWindow::post_user_event()], with a [KEventUserPoster] or dispatched directly with [KEventDispatcher::dispatch()].
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::display::event::{KEvent, KEventUser};
+///
+/// // Application defined ids.
+/// const LEVEL_LOADED : u32 = 1;
+/// const PLAYER_DIED : u32 = 2;
+///
+/// let event = KEvent::User(KEventUser::from_u64(PLAYER_DIED, 42));
+///
+/// if let KEvent::User(user) = event {
+///     assert_eq!(user.get_id(), PLAYER_DIED);
+///     assert_eq!(user.get_u64(), 42);
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KEventUser {
+    /// Id of the event, chosen by the application.
+    id : u32,
+
+    /// Payload of the event.
+    payload : [u8; KEVENT_USER_PAYLOAD_SIZE],
+}
+
+impl KEventUser {
+    /// Create a new [KEventUser] with an id and a payload.
+    pub fn new(id : u32, payload : [u8; KEVENT_USER_PAYLOAD_SIZE]) -> KEventUser {
+        KEventUser { id, payload }
+    }
+
+    /// Create a new [KEventUser] with an id and a u64 in the first 8 bytes of payload.
+    pub fn from_u64(id : u32, value : u64) -> KEventUser {
+        let mut payload = [0; KEVENT_USER_PAYLOAD_SIZE];
+        payload[..8].copy_from_slice(&value.to_le_bytes());
+        KEventUser { id, payload }
+    }
+
+    /// Create a new [KEventUser] with an id and a f64 in the first 8 bytes of payload.
+    pub fn from_f64(id : u32, value : f64) -> KEventUser {
+        KEventUser::from_u64(id, value.to_bits())
+    }
+
+    /// Returns the id of the event.
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the payload of the event.
+    pub fn get_payload(&self) -> &[u8; KEVENT_USER_PAYLOAD_SIZE] {
+        &self.payload
+    }
+
+    /// Returns the first 8 bytes of payload as a u64.
+    pub fn get_u64(&self) -> u64 {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.payload[..8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Returns the first 8 bytes of payload as a f64.
+    pub fn get_f64(&self) -> f64 {
+        f64::from_bits(self.get_u64())
+    }
+}
+
+/// ##### Handle posting [KEventUser] to a [KWindow] without holding the window.
+///
+/// Obtained with [KWindow::get_user_event_poster()] and cloned to gameplay systems. [KEventUser] posted by any clone are
+/// queued with those of [KWindow::post_user_event()] and dispatched at next [KWindow::dispatch_events()], in the order they were posted.
+///
+/// [KEventUser] posted after the [KWindow] is dropped are never dispatched.
+///
+/// # Example(s)
+/// ```no_run
+/// use olympus::kleio::display::{KWindow, event::{KEventDispatcher, KEventUser}, linux::server::KLinuxDisplayServerProvider};
+///
+/// const PLAYER_DIED : u32 = 2;
+///
+/// let mut window = KWindow::new(640, 480, KLinuxDisplayServerProvider::Default).unwrap();
+/// let mut dispatcher = KEventDispatcher::new(false);
+///
+/// // Gameplay system keeps its own poster.
+/// let poster = window.get_user_event_poster();
+/// poster.post(KEventUser::from_u64(PLAYER_DIED, 42));
+///
+/// window.dispatch_events(&mut dispatcher, true);
+/// ```
+#[derive(Clone)]
+pub struct KEventUserPoster {
+    /// Queue of [KEventUser] posted, shared with the [KWindow].
+    queue : Rc<RefCell<VecDeque<KEventUser>>>,
+}
+
+impl KEventUserPoster {
+    /// Create a new poster with an empty queue. Used by [KWindow].
+    pub(crate) fn new() -> KEventUserPoster {
+        KEventUserPoster { queue: Rc::new(RefCell::new(VecDeque::new())) }
+    }
+
+    /// Post a [KEventUser] dispatched at next [KWindow::dispatch_events()].
+    pub fn post(&self, event : KEventUser) {
+        self.queue.borrow_mut().push_back(event);
+    }
+
+    /// Returns the count of [KEventUser] posted and waiting for [KWindow::dispatch_events()].
+    pub fn get_posted_count(&self) -> usize {
+        self.queue.borrow().len()
+    }
+
+    /// Pop the oldest [KEventUser] posted. Used by [KWindow::dispatch_events()].
+    pub(crate) fn pop(&self) -> Option<KEventUser> {
+        self.queue.borrow_mut().pop_front()
+    }
+}
@@ -0,0 +1,263 @@
+// Contains bindings for Linux evdev and inotify
+use std::os::raw::{c_char, c_int, c_long, c_ulong};
+
+/// Open file without blocking on read.
+pub const O_NONBLOCK : c_int = 0o4000;
+
+/// Inotify file descriptor doesn't block on read.
+pub const IN_NONBLOCK : c_int = O_NONBLOCK;
+
+/// Inotify file descriptor is closed on exec.
+pub const IN_CLOEXEC : c_int = 0o2000000;
+
+/// Metadata changed (ie. permissions set by udev after creation).
+pub const IN_ATTRIB : u32 = 0x00000004;
+
+/// File created in watched directory.
+pub const IN_CREATE : u32 = 0x00000100;
+
+/// File deleted from watched directory.
+pub const IN_DELETE : u32 = 0x00000200;
+
+/// Size of inotify_event header without name.
+pub const INOTIFY_EVENT_SIZE : usize = 16;
+
+// Event types
+pub const EV_SYN : u16 = 0x00;
+pub const EV_KEY : u16 = 0x01;
+pub const EV_ABS : u16 = 0x03;
+pub const EV_FF : u16 = 0x15;
+
+// Synchronization events
+pub const SYN_REPORT : u16 = 0;
+pub const SYN_DROPPED : u16 = 3;
+
+// Buttons
+pub const BTN_MISC : u16 = 0x100;
+pub const BTN_JOYSTICK : u16 = 0x120;
+pub const BTN_GAMEPAD : u16 = 0x130;
+pub const BTN_SOUTH : u16 = 0x130;
+pub const BTN_EAST : u16 = 0x131;
+pub const BTN_C : u16 = 0x132;
+pub const BTN_NORTH : u16 = 0x133;
+pub const BTN_WEST : u16 = 0x134;
+pub const BTN_Z : u16 = 0x135;
+pub const BTN_TL : u16 = 0x136;
+pub const BTN_TR : u16 = 0x137;
+pub const BTN_TL2 : u16 = 0x138;
+pub const BTN_TR2 : u16 = 0x139;
+pub const BTN_SELECT : u16 = 0x13a;
+pub const BTN_START : u16 = 0x13b;
+pub const BTN_MODE : u16 = 0x13c;
+pub const BTN_THUMBL : u16 = 0x13d;
+pub const BTN_THUMBR : u16 = 0x13e;
+pub const BTN_DIGI : u16 = 0x140;
+pub const BTN_DPAD_UP : u16 = 0x220;
+pub const BTN_DPAD_DOWN : u16 = 0x221;
+pub const BTN_DPAD_LEFT : u16 = 0x222;
+pub const BTN_DPAD_RIGHT : u16 = 0x223;
+pub const BTN_TRIGGER_HAPPY : u16 = 0x2c0;
+pub const KEY_MAX : u16 = 0x2ff;
+
+// Absolute axes
+pub const ABS_X : u16 = 0x00;
+pub const ABS_Y : u16 = 0x01;
+pub const ABS_Z : u16 = 0x02;
+pub const ABS_RX : u16 = 0x03;
+pub const ABS_RY : u16 = 0x04;
+pub const ABS_RZ : u16 = 0x05;
+pub const ABS_GAS : u16 = 0x09;
+pub const ABS_BRAKE : u16 = 0x0a;
+pub const ABS_HAT0X : u16 = 0x10;
+pub const ABS_HAT0Y : u16 = 0x11;
+pub const ABS_HAT3Y : u16 = 0x17;
+pub const ABS_MAX : u16 = 0x3f;
+
+// Force feedback effects
+pub const FF_RUMBLE : u16 = 0x50;
+pub const FF_MAX : u16 = 0x7f;
+
+/// Time of an input event.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct timeval {
+    pub tv_sec : c_long,
+    pub tv_usec : c_long,
+}
+
+/// Event read from an evdev device.
+/// 
+/// # Reference(s)
+/// <https://www.kernel.org/doc/html/latest/input/input.html#event-interface>
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct input_event {
+    pub time : timeval,
+    pub _type : u16,
+    pub code : u16,
+    pub value : i32,
+}
+
+impl input_event {
+    /// Create a new input_event without time.
+    pub fn new(_type : u16, code : u16, value : i32) -> input_event {
+        input_event { time: timeval::default(), _type, code, value }
+    }
+}
+
+/// Identity of an evdev device.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct input_id {
+    pub bustype : u16,
+    pub vendor : u16,
+    pub product : u16,
+    pub version : u16,
+}
+
+/// Range and state of an absolute axis.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct input_absinfo {
+    pub value : i32,
+    pub minimum : i32,
+    pub maximum : i32,
+    pub fuzz : i32,
+    pub flat : i32,
+    pub resolution : i32,
+}
+
+impl input_absinfo {
+    /// Create a new input_absinfo with range only.
+    pub fn new(minimum : i32, maximum : i32) -> input_absinfo {
+        input_absinfo { value: 0, minimum, maximum, fuzz: 0, flat: 0, resolution: 0 }
+    }
+}
+
+/// Force feedback effect trigger.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ff_trigger {
+    pub button : u16,
+    pub interval : u16,
+}
+
+/// Force feedback effect scheduling in milliseconds.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ff_replay {
+    pub length : u16,
+    pub delay : u16,
+}
+
+/// Rumble effect magnitudes of strong (low frequency) and weak (high frequency) motors.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ff_rumble_effect {
+    pub strong_magnitude : u16,
+    pub weak_magnitude : u16,
+}
+
+/// Union of effects parameters. Only rumble is used, padding keeps the size of the largest effect.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union ff_effect_union {
+    pub rumble : ff_rumble_effect,
+    pub padding : [u64; 4],
+}
+
+/// Force feedback effect uploaded to a device.
+/// 
+/// # Reference(s)
+/// <https://www.kernel.org/doc/html/latest/input/ff.html>
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ff_effect {
+    pub _type : u16,
+    pub id : i16,
+    pub direction : u16,
+    pub trigger : ff_trigger,
+    pub replay : ff_replay,
+    pub u : ff_effect_union,
+}
+
+impl ff_effect {
+    /// Create a new rumble effect. Id -1 asks the device for a new effect id.
+    pub fn rumble(id : i16, strong_magnitude : u16, weak_magnitude : u16, length : u16) -> ff_effect {
+        ff_effect { _type: FF_RUMBLE, id, direction: 0, trigger: ff_trigger::default(), replay: ff_replay { length, delay: 0 },
+            u: ff_effect_union { padding: [0; 4] } }.with_rumble(strong_magnitude, weak_magnitude)
+    }
+
+    /// Set rumble magnitudes.
+    fn with_rumble(mut self, strong_magnitude : u16, weak_magnitude : u16) -> ff_effect {
+        self.u.rumble = ff_rumble_effect { strong_magnitude, weak_magnitude };
+        self
+    }
+}
+
+/// Read direction of ioctl request.
+const IOC_READ : c_ulong = 2;
+
+/// Write direction of ioctl request.
+const IOC_WRITE : c_ulong = 1;
+
+/// Create an ioctl request number for evdev 'E' type.
+const fn evioc(dir : c_ulong, nr : c_ulong, size : usize) -> c_ulong {
+    (dir << 30) | ((size as c_ulong) << 16) | ((b'E' as c_ulong) << 8) | nr
+}
+
+/// Create a read ioctl request number for evdev 'E' type.
+const fn eviocg(nr : c_ulong, size : usize) -> c_ulong {
+    evioc(IOC_READ, nr, size)
+}
+
+/// Get device identity.
+pub const fn EVIOCGID() -> c_ulong {
+    eviocg(0x02, std::mem::size_of::<input_id>())
+}
+
+/// Get device name.
+pub const fn EVIOCGNAME(len : usize) -> c_ulong {
+    eviocg(0x06, len)
+}
+
+/// Get event bits of event type.
+pub const fn EVIOCGBIT(ev : u16, len : usize) -> c_ulong {
+    eviocg(0x20 + ev as c_ulong, len)
+}
+
+/// Get absolute axis range and state.
+pub const fn EVIOCGABS(abs : u16) -> c_ulong {
+    eviocg(0x40 + abs as c_ulong, std::mem::size_of::<input_absinfo>())
+}
+
+/// Upload a force feedback effect to the device.
+pub const fn EVIOCSFF() -> c_ulong {
+    evioc(IOC_WRITE, 0x80, std::mem::size_of::<ff_effect>())
+}
+
+/// Erase a force feedback effect from the device.
+pub const fn EVIOCRMFF() -> c_ulong {
+    evioc(IOC_WRITE, 0x81, std::mem::size_of::<c_int>())
+}
+
+#[allow(dead_code)]
+extern "C" {
+    /// The ioctl() system call manipulates the underlying device parameters of special files.
+    /// 
+    /// # Reference(s)
+    /// <https://man7.org/linux/man-pages/man2/ioctl.2.html>
+    pub(crate) fn ioctl(fd : c_int, request : c_ulong, ...) -> c_int;
+
+    /// Initializes a new inotify instance and returns a file descriptor associated with a new inotify event queue.
+    /// 
+    /// # Reference(s)
+    /// <https://man7.org/linux/man-pages/man2/inotify_init1.2.html>
+    pub(crate) fn inotify_init1(flags : c_int) -> c_int;
+
+    /// Adds a new watch, or modifies an existing watch, for the file whose location is specified in pathname.
+    /// 
+    /// # Reference(s)
+    /// <https://man7.org/linux/man-pages/man2/inotify_add_watch.2.html>
+    pub(crate) fn inotify_add_watch(fd : c_int, pathname : *const c_char, mask : u32) -> c_int;
+}
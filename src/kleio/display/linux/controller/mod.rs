@@ -0,0 +1,867 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::CString;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use debug_print::debug_println;
+
+use crate::error::{OlympusError, KControllerError};
+use crate::kleio::display::{KControllerBattery, KControllerBatteryState};
+use crate::kleio::display::event::{KEvent, KEventController, KControllerButton, KControllerAxis, KCONTROLLER_RAW_BUTTON_FIRST, KCONTROLLER_RAW_AXIS_FIRST,
+    KControllerGuid, KControllerMapping, KControllerMappingDatabase, KControllerMappingSource, KControllerMappingTarget, KControllerAxisRange};
+
+use self::bind::{input_event, input_id, input_absinfo, ff_effect, ioctl, inotify_init1, inotify_add_watch, EVIOCGBIT, EVIOCGABS, EVIOCGNAME, EVIOCGID, EVIOCSFF,
+    EV_FF, FF_RUMBLE, FF_MAX,
+    O_NONBLOCK, IN_NONBLOCK, IN_CLOEXEC, IN_ATTRIB, IN_CREATE, IN_DELETE, INOTIFY_EVENT_SIZE, EV_KEY, EV_ABS, KEY_MAX, ABS_MAX,
+    BTN_JOYSTICK, BTN_DIGI, BTN_TRIGGER_HAPPY, BTN_SOUTH, BTN_EAST, BTN_NORTH, BTN_WEST, BTN_TL, BTN_TR, BTN_TL2, BTN_TR2,
+    BTN_SELECT, BTN_START, BTN_MODE, BTN_THUMBL, BTN_THUMBR, BTN_DPAD_UP, BTN_DPAD_DOWN, BTN_DPAD_LEFT, BTN_DPAD_RIGHT,
+    ABS_X, ABS_Y, ABS_Z, ABS_RX, ABS_RY, ABS_RZ, ABS_GAS, ABS_BRAKE, ABS_HAT0X, ABS_HAT3Y};
+
+/// Contains evdev and inotify C functions Bind
+#[allow(unused)]                    // Remove unused variable notification
+#[allow(non_camel_case_types)]      // Imported C types aren't formatted according to convention.
+#[allow(non_snake_case)]            // Imported C macros aren't formatted according to convention.
+pub mod bind;
+
+/// Directory of evdev devices watched for controllers.
+pub const KLINUX_INPUT_DIRECTORY : &str = "/dev/input";
+
+/// Maximum count of controllers connected at the same time, limited by controller id.
+pub const KLINUX_CONTROLLER_MAX : usize = 256;
+
+/// Directory of evdev devices in sysfs, used to find LEDs and battery of a device.
+pub const KLINUX_SYSFS_INPUT_DIRECTORY : &str = "/sys/class/input";
+
+/// Size of evdev device name buffer.
+const DEVICE_NAME_SIZE : usize = 256;
+
+/// Count of input_event read from a device at once.
+const READ_EVENT_COUNT : usize = 32;
+
+/// ##### Linux evdev controllers connected, with hotplug detection.
+///
+/// Devices of [KLINUX_INPUT_DIRECTORY] with joystick or gamepad buttons are opened as [KLinuxController]. The directory is
+/// watched with inotify so controllers connected after creation are detected.
+///
+/// Controllers are translated with the mapping of their GUID found in the [KControllerMappingDatabase] of the list.
+///
+/// [KEventController] read from controllers are queued until popped with [KLinuxControllerList::pop_event()].
+pub struct KLinuxControllerList {
+    /// Inotify instance watching the input directory. None if inotify isn't available.
+    inotify : Option<File>,
+
+    /// Controllers by id. None are free ids.
+    controllers : Vec<Option<KLinuxController>>,
+
+    /// Controller events waiting to be popped.
+    events : VecDeque<KEvent>,
+
+    /// Mappings applied to controllers.
+    mappings : KControllerMappingDatabase,
+}
+
+impl KLinuxControllerList {
+    /// Create a new [KLinuxControllerList] with controllers already connected. A [KEventController::Connected] is queued for each.
+    pub fn new() -> KLinuxControllerList {
+        let mut list = KLinuxControllerList { inotify: Self::create_inotify(), controllers: Vec::new(), events: VecDeque::new(),
+            mappings: KControllerMappingDatabase::new() };
+
+        // Open devices already connected, sorted by event number so ids stay the same between runs.
+        if let Ok(entries) = std::fs::read_dir(KLINUX_INPUT_DIRECTORY) {
+            let mut paths : Vec<PathBuf> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path())
+                .filter(|path| Self::is_event_device(path)).collect();
+            paths.sort_by_key(|path| path.file_name().and_then(|name| name.to_str()).and_then(|name| name[5..].parse::<u32>().ok()));
+
+            for path in paths {
+                list.connect(&path);
+            }
+        }
+
+        list
+    }
+
+    /// Add a [KLinuxController] to the list and queue a [KEventController::Connected]. The mapping of its GUID is applied if any.
+    ///
+    /// Returns the controller id given or [None] if [KLINUX_CONTROLLER_MAX] controllers are already connected.
+    pub fn add_controller(&mut self, mut controller : KLinuxController) -> Option<u8> {
+        let id = match self.controllers.iter().position(|c| c.is_none()) {
+            Some(id) => id,
+            None if self.controllers.len() < KLINUX_CONTROLLER_MAX => {
+                self.controllers.push(None);
+                self.controllers.len() - 1
+            },
+            None => return None,
+        };
+
+        controller.set_mapping(self.mappings.get_mapping(controller.get_guid()));
+        debug_println!("Controller {} connected : {} ({})", id, controller.get_name(), controller.get_guid());
+        self.controllers[id] = Some(controller);
+        self.events.push_back(KEvent::Controller(KEventController::Connected(id as u8)));
+        Some(id as u8)
+    }
+
+    /// Remove a controller from the list and queue a [KEventController::Disconnected].
+    ///
+    /// Returns the removed [KLinuxController] or [None] if id isn't connected.
+    pub fn remove_controller(&mut self, id : u8) -> Option<KLinuxController> {
+        let controller = self.controllers.get_mut(id as usize).and_then(|c| c.take());
+        if controller.is_some() {
+            debug_println!("Controller {} disconnected", id);
+            self.events.push_back(KEvent::Controller(KEventController::Disconnected(id)));
+        }
+        controller
+    }
+
+    /// Returns the [KLinuxController] of an id or [None] if id isn't connected.
+    pub fn get_controller(&self, id : u8) -> Option<&KLinuxController> {
+        self.controllers.get(id as usize).and_then(|c| c.as_ref())
+    }
+
+    /// Returns the mutable [KLinuxController] of an id or [None] if id isn't connected.
+    pub fn get_controller_mut(&mut self, id : u8) -> Option<&mut KLinuxController> {
+        self.controllers.get_mut(id as usize).and_then(|c| c.as_mut())
+    }
+
+    /// Returns the ids of connected controllers.
+    pub fn get_controllers(&self) -> Vec<u8> {
+        self.controllers.iter().enumerate().filter(|(_, c)| c.is_some()).map(|(id, _)| id as u8).collect()
+    }
+
+    /// Parse mappings in SDL game controller database format and apply them to connected controllers.
+    ///
+    /// Returns the count of mappings added.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::InvalidMapping)] with the line number if a line is invalid.
+    pub fn add_mappings(&mut self, mappings : &str) -> Result<usize, OlympusError> {
+        let count = self.mappings.parse(mappings)?;
+        self.apply_mappings();
+        Ok(count)
+    }
+
+    /// Load mappings from a file in SDL game controller database format and apply them to connected controllers.
+    ///
+    /// Returns the count of mappings added.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::FileError)] if file can't be read.
+    ///
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::InvalidMapping)] with the line number if a line is invalid.
+    pub fn load_mappings(&mut self, path : &Path) -> Result<usize, OlympusError> {
+        let count = self.mappings.load(path)?;
+        self.apply_mappings();
+        Ok(count)
+    }
+
+    /// Add a [KControllerMapping] and apply it to connected controllers with the same GUID.
+    pub fn add_mapping(&mut self, mapping : KControllerMapping) {
+        self.mappings.add_mapping(mapping);
+        self.apply_mappings();
+    }
+
+    /// Detect connected and disconnected devices, then read controllers input into the events queue.
+    pub fn update(&mut self) {
+        self.read_inotify_events();
+
+        let mut disconnected = Vec::new();
+        for (id, controller) in self.controllers.iter_mut().enumerate() {
+            if let Some(controller) = controller {
+                if !controller.read_input_events(id as u8, &mut self.events) {
+                    disconnected.push(id as u8);
+                }
+            }
+        }
+
+        for id in disconnected {
+            self.remove_controller(id);
+        }
+    }
+
+    /// Get the count of controller events waiting to be popped.
+    pub fn get_event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Pop the oldest controller event or [None] if queue is empty.
+    pub fn pop_event(&mut self) -> Option<KEvent> {
+        self.events.pop_front()
+    }
+
+    /// Create an inotify instance watching the input directory.
+    fn create_inotify() -> Option<File> {
+        unsafe {
+            let fd = inotify_init1(IN_NONBLOCK | IN_CLOEXEC);
+            if fd < 0 {
+                return None;
+            }
+
+            // File takes ownership of fd so it is closed on drop.
+            let inotify = File::from_raw_fd(fd);
+            let directory = CString::new(KLINUX_INPUT_DIRECTORY).unwrap();
+            if inotify_add_watch(fd, directory.as_ptr(), IN_CREATE | IN_ATTRIB | IN_DELETE) < 0 {
+                None
+            } else {
+                Some(inotify)
+            }
+        }
+    }
+
+    /// Read devices created and deleted since last update.
+    fn read_inotify_events(&mut self) {
+        let mut buffer = [0u8; 4096];
+
+        loop {
+            let length = match self.inotify.as_mut().map(|inotify| inotify.read(&mut buffer)) {
+                Some(Ok(length)) if length > 0 => length,
+                _ => break,     // No inotify, no more events or error.
+            };
+
+            let mut offset = 0;
+            while offset + INOTIFY_EVENT_SIZE <= length {
+                // Header is wd : i32, mask : u32, cookie : u32, len : u32 followed by a nul padded name.
+                let mask = u32::from_ne_bytes(buffer[offset + 4..offset + 8].try_into().unwrap());
+                let name_length = u32::from_ne_bytes(buffer[offset + 12..offset + 16].try_into().unwrap()) as usize;
+                let name_end = (offset + INOTIFY_EVENT_SIZE + name_length).min(length);
+                let name = &buffer[offset + INOTIFY_EVENT_SIZE..name_end];
+                let name = String::from_utf8_lossy(&name[..name.iter().position(|b| *b == 0).unwrap_or(name.len())]).into_owned();
+                let path = Path::new(KLINUX_INPUT_DIRECTORY).join(name);
+
+                if Self::is_event_device(&path) {
+                    if mask & IN_DELETE != 0 {
+                        if let Some(id) = self.get_controller_id(&path) {
+                            self.remove_controller(id);
+                        }
+                    } else {
+                        // Permissions are usually set by udev after creation so attributes change is tried too.
+                        self.connect(&path);
+                    }
+                }
+
+                offset = name_end;
+            }
+        }
+    }
+
+    /// Apply the mapping of their GUID to connected controllers.
+    fn apply_mappings(&mut self) {
+        for controller in self.controllers.iter_mut().flatten() {
+            controller.set_mapping(self.mappings.get_mapping(controller.get_guid()));
+        }
+    }
+
+    /// Open device as controller if not opened yet.
+    fn connect(&mut self, path : &Path) {
+        if self.get_controller_id(path).is_none() {
+            if let Some(controller) = KLinuxController::open(path) {
+                self.add_controller(controller);
+            }
+        }
+    }
+
+    /// Returns the id of the controller opened from path.
+    fn get_controller_id(&self, path : &Path) -> Option<u8> {
+        self.controllers.iter().position(|c| c.as_ref().is_some_and(|c| c.get_path() == path)).map(|id| id as u8)
+    }
+
+    /// Returns True if path is an evdev event device (eventX).
+    fn is_event_device(path : &Path) -> bool {
+        path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with("event") && name[5..].parse::<u32>().is_ok())
+    }
+}
+
+impl Default for KLinuxControllerList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Raw absolute axis of a device.
+#[derive(Debug, Copy, Clone)]
+struct KLinuxControllerAxis {
+    /// Raw axis index.
+    index : u8,
+
+    /// Minimum value of the device.
+    minimum : i32,
+
+    /// Maximum value of the device.
+    maximum : i32,
+}
+
+impl KLinuxControllerAxis {
+    /// Normalize device value to -32768..32767.
+    fn normalize(&self, value : i32) -> i16 {
+        let range = (self.maximum - self.minimum) as i64;
+        let value = (value.clamp(self.minimum, self.maximum) - self.minimum) as i64;
+        (value * u16::MAX as i64 / range + i16::MIN as i64) as i16
+    }
+}
+
+/// Raw hat of a device.
+#[derive(Debug, Copy, Clone)]
+struct KLinuxControllerHat {
+    /// Raw hat index.
+    index : u8,
+
+    /// Last values (x, y).
+    value : (i32, i32),
+}
+
+impl KLinuxControllerHat {
+    /// Returns the hat direction mask : 1 up, 2 right, 4 down and 8 left.
+    fn get_mask(&self) -> u8 {
+        let x = match self.value.0.signum() { -1 => 8, 1 => 2, _ => 0 };
+        let y = match self.value.1.signum() { -1 => 1, 1 => 4, _ => 0 };
+        x | y
+    }
+}
+
+/// Standard layout state sent, used to send only changes.
+#[derive(Debug, Clone, Default)]
+struct KLinuxControllerState {
+    /// Buttons down by id.
+    buttons : HashSet<u8>,
+
+    /// Last axes values by id.
+    axes : HashMap<u8, i16>,
+}
+
+impl KLinuxControllerState {
+    /// Set a button state and push an event if changed.
+    fn set_button(&mut self, controller : u8, button : u8, pressed : bool, events : &mut VecDeque<KEvent>) {
+        if pressed && self.buttons.insert(button) {
+            events.push_back(KEvent::Controller(KEventController::ButtonDown(controller, button)));
+        } else if !pressed && self.buttons.remove(&button) {
+            events.push_back(KEvent::Controller(KEventController::ButtonUp(controller, button)));
+        }
+    }
+
+    /// Set an axis value and push an event if changed.
+    fn set_axis(&mut self, controller : u8, axis : u8, value : i16, events : &mut VecDeque<KEvent>) {
+        if self.axes.insert(axis, value).unwrap_or(0) != value {
+            events.push_back(KEvent::Controller(KEventController::Axis(controller, axis, value)));
+        }
+    }
+
+    /// Set a mapping target from a fraction of its range, 0.0 being released and 1.0 fully pressed.
+    fn set_target(&mut self, controller : u8, target : KControllerMappingTarget, fraction : f64, events : &mut VecDeque<KEvent>) {
+        match target {
+            KControllerMappingTarget::Button(button) => self.set_button(controller, button as u8, fraction >= 0.5, events),
+            KControllerMappingTarget::Axis(axis, range) => {
+                let (minimum, maximum) = get_target_range(axis, range);
+                let value = (minimum as f64 + fraction * (maximum - minimum) as f64).round() as i16;
+
+                // A released half axis doesn't reset the value written by the other half.
+                let last = self.axes.get(&(axis as u8)).copied().unwrap_or(0);
+                if range == KControllerAxisRange::Full || value != 0 || last == 0 || last.signum() as i32 == maximum.signum() {
+                    self.set_axis(controller, axis as u8, value, events);
+                }
+            },
+        }
+    }
+}
+
+/// ##### Linux evdev controller translated to the standard layout.
+///
+/// Raw buttons, axes and hats are indexed like SDL then translated with a [KControllerMapping]. Without mapping from a
+/// [KControllerMappingDatabase], buttons and axes are translated according to the Linux gamepad specification and the first
+/// hat to directional pad buttons.
+///
+/// Buttons and axes not mapped are sent with raw ids (see [KCONTROLLER_RAW_BUTTON_FIRST] and [KCONTROLLER_RAW_AXIS_FIRST]).
+///
+/// # Reference(s)
+/// <https://www.kernel.org/doc/html/latest/input/gamepad.html>
+pub struct KLinuxController {
+    /// Device file. None if controller wasn't opened from a device.
+    file : Option<File>,
+
+    /// Device path.
+    path : PathBuf,
+
+    /// Device name.
+    name : String,
+
+    /// Device identity.
+    input_id : input_id,
+
+    /// Device GUID used to find mapping.
+    guid : KControllerGuid,
+
+    /// Raw button index by evdev code.
+    buttons : HashMap<u16, u8>,
+
+    /// Raw axes by evdev code.
+    axes : HashMap<u16, KLinuxControllerAxis>,
+
+    /// Raw hats by evdev code of hat x axis.
+    hats : HashMap<u16, KLinuxControllerHat>,
+
+    /// Mapping according to Linux gamepad specification.
+    default_mapping : KControllerMapping,
+
+    /// Mapping used to translate raw inputs.
+    mapping : KControllerMapping,
+
+    /// Standard layout state sent.
+    state : KLinuxControllerState,
+
+    /// True if device supports rumble effect.
+    force_feedback : bool,
+
+    /// Id of rumble effect uploaded to device. None until first rumble.
+    rumble_effect : Option<i16>,
+
+    /// Sysfs directory of the physical device, containing leds and power_supply directories. Empty if unknown.
+    sysfs_path : PathBuf,
+}
+
+impl KLinuxController {
+    /// Create a new [KLinuxController] from device capabilities. Buttons are evdev key codes and axes are evdev absolute codes with their range.
+    ///
+    /// Returns [None] if device doesn't have joystick or gamepad buttons.
+    pub fn new(name : &str, input_id : input_id, buttons : &[u16], axes : &[(u16, input_absinfo)]) -> Option<KLinuxController> {
+        if !buttons.iter().any(|code| (BTN_JOYSTICK..BTN_DIGI).contains(code) || *code >= BTN_TRIGGER_HAPPY) {
+            return None;
+        }
+
+        // Buttons ordered like SDL : joystick and gamepad buttons first, then other keys.
+        let mut buttons : Vec<u16> = buttons.iter().copied().filter(|code| *code <= KEY_MAX).collect();
+        buttons.sort_by_key(|code| (*code < BTN_JOYSTICK, *code));
+        buttons.dedup();
+        let buttons : HashMap<u16, u8> = buttons.iter().enumerate().map(|(index, code)| (*code, index.min(u8::MAX as usize) as u8)).collect();
+
+        // Axes and hats ordered by code.
+        let mut axes : Vec<(u16, input_absinfo)> = axes.iter().copied().filter(|(code, info)| *code <= ABS_MAX && info.maximum > info.minimum).collect();
+        axes.sort_by_key(|(code, _)| *code);
+        let mut hats = HashMap::new();
+        for code in axes.iter().map(|(code, _)| *code & !1).filter(|code| (ABS_HAT0X..=ABS_HAT3Y).contains(code)) {
+            let index = hats.len() as u8;
+            hats.entry(code).or_insert(KLinuxControllerHat { index, value: (0, 0) });
+        }
+        let axes : HashMap<u16, KLinuxControllerAxis> = axes.iter().filter(|(code, _)| !(ABS_HAT0X..=ABS_HAT3Y).contains(code)).enumerate()
+            .map(|(index, (code, info))| (*code, KLinuxControllerAxis { index: index as u8, minimum: info.minimum, maximum: info.maximum })).collect();
+
+        let guid = KControllerGuid::from_device(input_id.bustype, input_id.vendor, input_id.product, input_id.version, name);
+        let default_mapping = get_default_mapping(guid, name, &buttons, &axes, &hats);
+
+        Some(KLinuxController { file: None, path: PathBuf::new(), name: String::from(name), input_id, guid, buttons, axes, hats,
+            mapping: default_mapping.clone(), default_mapping, state: KLinuxControllerState::default(), force_feedback: false,
+            rumble_effect: None, sysfs_path: PathBuf::new() })
+    }
+
+    /// Open an evdev device as [KLinuxController].
+    ///
+    /// Device is opened for writing when permitted so rumble effects can be played.
+    ///
+    /// Returns [None] if device can't be opened or isn't a controller.
+    pub fn open(path : &Path) -> Option<KLinuxController> {
+        let file = OpenOptions::new().read(true).write(true).custom_flags(O_NONBLOCK).open(path)
+            .or_else(|_| OpenOptions::new().read(true).custom_flags(O_NONBLOCK).open(path)).ok()?;
+        let fd = file.as_raw_fd();
+
+        unsafe {
+            // Event types supported. Must support keys.
+            let mut types = [0u8; 4];
+            if ioctl(fd, EVIOCGBIT(0, types.len()), types.as_mut_ptr()) < 0 || !is_bit_set(&types, EV_KEY) {
+                return None;
+            }
+
+            let mut keys = [0u8; KEY_MAX as usize / 8 + 1];
+            if ioctl(fd, EVIOCGBIT(EV_KEY, keys.len()), keys.as_mut_ptr()) < 0 {
+                return None;
+            }
+            let buttons : Vec<u16> = (0..=KEY_MAX).filter(|code| is_bit_set(&keys, *code)).collect();
+
+            let mut abs = [0u8; ABS_MAX as usize / 8 + 1];
+            let mut axes = Vec::new();
+            if is_bit_set(&types, EV_ABS) && ioctl(fd, EVIOCGBIT(EV_ABS, abs.len()), abs.as_mut_ptr()) >= 0 {
+                for code in (0..=ABS_MAX).filter(|code| is_bit_set(&abs, *code)) {
+                    let mut info = input_absinfo::default();
+                    if ioctl(fd, EVIOCGABS(code), &mut info as *mut input_absinfo) >= 0 {
+                        axes.push((code, info));
+                    }
+                }
+            }
+
+            let mut name = [0u8; DEVICE_NAME_SIZE];
+            ioctl(fd, EVIOCGNAME(name.len()), name.as_mut_ptr());
+            let name = String::from_utf8_lossy(&name[..name.iter().position(|b| *b == 0).unwrap_or(name.len())]).into_owned();
+
+            let mut input_id = input_id::default();
+            ioctl(fd, EVIOCGID(), &mut input_id as *mut input_id);
+
+            let mut effects = [0u8; FF_MAX as usize / 8 + 1];
+            let force_feedback = is_bit_set(&types, EV_FF) && ioctl(fd, EVIOCGBIT(EV_FF, effects.len()), effects.as_mut_ptr()) >= 0
+                && is_bit_set(&effects, FF_RUMBLE);
+
+            let mut controller = KLinuxController::new(&name, input_id, &buttons, &axes)?;
+            controller.file = Some(file);
+            controller.path = path.to_path_buf();
+            controller.force_feedback = force_feedback;
+
+            // eventX/device is the input device and its parent the physical device (ie. HID or USB).
+            if let Some(event) = path.file_name() {
+                controller.sysfs_path = Path::new(KLINUX_SYSFS_INPUT_DIRECTORY).join(event).join("device").join("device");
+            }
+            Some(controller)
+        }
+    }
+
+    /// Returns the device name.
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Returns the device path. Empty if controller wasn't opened from a device.
+    pub fn get_path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Returns the device identity (bus, vendor, product and version).
+    pub fn get_input_id(&self) -> input_id {
+        self.input_id
+    }
+
+    /// Returns the device GUID used to find its mapping.
+    pub fn get_guid(&self) -> KControllerGuid {
+        self.guid
+    }
+
+    /// Returns the mapping used to translate raw inputs.
+    pub fn get_mapping(&self) -> &KControllerMapping {
+        &self.mapping
+    }
+
+    /// Set the mapping used to translate raw inputs. [None] restores the mapping of the Linux gamepad specification.
+    pub fn set_mapping(&mut self, mapping : Option<&KControllerMapping>) {
+        self.mapping = mapping.unwrap_or(&self.default_mapping).clone();
+    }
+
+    /// Returns the sysfs directory of the physical device. Empty if unknown.
+    pub fn get_sysfs_path(&self) -> &Path {
+        self.sysfs_path.as_path()
+    }
+
+    /// Set the sysfs directory of the physical device, containing leds and power_supply directories.
+    pub fn set_sysfs_path(&mut self, path : &Path) {
+        self.sysfs_path = path.to_path_buf();
+    }
+
+    /// Upload and play a rumble effect. Magnitudes range from 0.0 to 1.0.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotSupported)] if device doesn't support rumble.
+    ///
+    /// Returns [OlympusError::KController(KControllerError::DeviceError)] if effect can't be uploaded or played.
+    pub fn rumble(&mut self, strong : f32, weak : f32, duration : Duration) -> Result<(), OlympusError> {
+        let fd = self.get_force_feedback_fd()?;
+        let mut effect = ff_effect::rumble(self.rumble_effect.unwrap_or(-1), get_magnitude(strong), get_magnitude(weak),
+            duration.as_millis().min(u16::MAX as u128) as u16);
+
+        // Upload replaces the effect already uploaded since its id is reused.
+        if unsafe { ioctl(fd, EVIOCSFF(), &mut effect as *mut ff_effect) } < 0 {
+            return Err(OlympusError::KController(KControllerError::DeviceError));
+        }
+        self.rumble_effect = Some(effect.id);
+        self.play_effect(effect.id, 1)
+    }
+
+    /// Stop the rumble effect playing.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotSupported)] if device doesn't support rumble.
+    ///
+    /// Returns [OlympusError::KController(KControllerError::DeviceError)] if effect can't be stopped.
+    pub fn stop_rumble(&mut self) -> Result<(), OlympusError> {
+        self.get_force_feedback_fd()?;
+        match self.rumble_effect {
+            Some(id) => self.play_effect(id, 0),
+            None => Ok(()),     // Never played
+        }
+    }
+
+    /// Light the LED of a player index, starting at 1. Player 0 turns player LEDs off.
+    ///
+    /// LEDs named `*player-N` (ie. DualSense) and Xbox 360 `xpadN` LEDs are supported.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotSupported)] if device has no player LED.
+    ///
+    /// Returns [OlympusError::KController(KControllerError::DeviceError)] if LED brightness can't be written.
+    pub fn set_player_led(&mut self, player : u8) -> Result<(), OlympusError> {
+        let leds : Vec<PathBuf> = match std::fs::read_dir(self.sysfs_path.join("leds")) {
+            Ok(entries) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect(),
+            Err(_) => Vec::new(),
+        };
+
+        let mut brightness : Vec<(&PathBuf, u8)> = Vec::new();
+        for led in leds.iter() {
+            let name = led.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            if let Some((_, index)) = name.rsplit_once("player-") {
+                if let Ok(index) = index.parse::<u8>() {
+                    brightness.push((led, (index == player) as u8));
+                }
+            } else if name.starts_with("xpad") && name[4..].parse::<u32>().is_ok() {
+                // Xbox 360 patterns : 0 is off, 6 to 9 lights player 1 to 4.
+                brightness.push((led, if player == 0 { 0 } else { 6 + (player - 1) % 4 }));
+            }
+        }
+
+        if brightness.is_empty() {
+            return Err(OlympusError::KController(KControllerError::NotSupported));
+        }
+
+        for (led, value) in brightness {
+            if std::fs::write(led.join("brightness"), value.to_string()).is_err() {
+                return Err(OlympusError::KController(KControllerError::DeviceError));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the battery level and charging state read from the device power supply.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotSupported)] if device has no power supply.
+    pub fn get_battery(&self) -> Result<KControllerBattery, OlympusError> {
+        let supply = std::fs::read_dir(self.sysfs_path.join("power_supply")).ok()
+            .and_then(|mut entries| entries.find_map(|entry| entry.ok()))
+            .map(|entry| entry.path());
+
+        match supply {
+            Some(supply) => {
+                let read = |name : &str| std::fs::read_to_string(supply.join(name)).map(|value| value.trim().to_string()).ok();
+
+                // Devices without capacity may report a level name instead.
+                let level = read("capacity").and_then(|value| value.parse::<u8>().ok()).or_else(|| match read("capacity_level").as_deref() {
+                    Some("Critical") => Some(5),
+                    Some("Low") => Some(20),
+                    Some("Normal") => Some(60),
+                    Some("High") => Some(80),
+                    Some("Full") => Some(100),
+                    _ => None,
+                });
+
+                let state = match read("status").as_deref() {
+                    Some("Discharging") => KControllerBatteryState::Discharging,
+                    Some("Charging") => KControllerBatteryState::Charging,
+                    Some("Full") => KControllerBatteryState::Full,
+                    Some("Not charging") => KControllerBatteryState::NotCharging,
+                    _ => KControllerBatteryState::Unknown,
+                };
+
+                Ok(KControllerBattery { level, state })
+            },
+            None => Err(OlympusError::KController(KControllerError::NotSupported)),
+        }
+    }
+
+    /// Translate an evdev input event into [KEventController] pushed to events.
+    pub fn handle_input_event(&mut self, controller : u8, event : &input_event, events : &mut VecDeque<KEvent>) {
+        match event._type {
+            EV_KEY if event.value != 2 => if let Some(index) = self.buttons.get(&event.code) {     // Auto-repeat is ignored
+                let fraction = if event.value == 0 { 0.0 } else { 1.0 };
+                let mut mapped = false;
+                for (_, target) in self.mapping.get_entries().iter().filter(|(source, _)| *source == KControllerMappingSource::Button(*index)) {
+                    self.state.set_target(controller, *target, fraction, events);
+                    mapped = true;
+                }
+
+                if !mapped {
+                    self.state.set_button(controller, KCONTROLLER_RAW_BUTTON_FIRST.saturating_add(*index), event.value != 0, events);
+                }
+            },
+            EV_ABS if (ABS_HAT0X..=ABS_HAT3Y).contains(&event.code) => if let Some(hat) = self.hats.get_mut(&(event.code & !1)) {
+                if event.code & 1 == 0 { hat.value.0 = event.value } else { hat.value.1 = event.value };
+                let (index, mask) = (hat.index, hat.get_mask());
+
+                // Directions released are sent before directions pressed.
+                for pressed in [false, true] {
+                    for (source, target) in self.mapping.get_entries().iter() {
+                        if let KControllerMappingSource::Hat(hat, direction) = source {
+                            if *hat == index && (mask & direction != 0) == pressed {
+                                self.state.set_target(controller, *target, if pressed { 1.0 } else { 0.0 }, events);
+                            }
+                        }
+                    }
+                }
+            },
+            EV_ABS => if let Some(axis) = self.axes.get(&event.code) {
+                let value = axis.normalize(event.value);
+                let mut mapped = false;
+                for (source, target) in self.mapping.get_entries().iter() {
+                    if let KControllerMappingSource::Axis(index, range, inverted) = source {
+                        if *index == axis.index {
+                            let (minimum, maximum) = get_source_range(*range);
+                            let fraction = ((value as f64 - minimum as f64) / (maximum - minimum) as f64).clamp(0.0, 1.0);
+                            self.state.set_target(controller, *target, if *inverted { 1.0 - fraction } else { fraction }, events);
+                            mapped = true;
+                        }
+                    }
+                }
+
+                if !mapped {
+                    self.state.set_axis(controller, KCONTROLLER_RAW_AXIS_FIRST.saturating_add(axis.index), value, events);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Returns the device file descriptor if rumble is supported.
+    fn get_force_feedback_fd(&self) -> Result<i32, OlympusError> {
+        match &self.file {
+            Some(file) if self.force_feedback => Ok(file.as_raw_fd()),
+            _ => Err(OlympusError::KController(KControllerError::NotSupported)),
+        }
+    }
+
+    /// Play (value 1) or stop (value 0) an uploaded effect.
+    fn play_effect(&mut self, id : i16, value : i32) -> Result<(), OlympusError> {
+        let event = input_event::new(EV_FF, id as u16, value);
+        let bytes = unsafe { std::slice::from_raw_parts(&event as *const input_event as *const u8, std::mem::size_of::<input_event>()) };
+
+        // Write fails if device was opened read-only.
+        match self.file.as_mut().map(|file| file.write(bytes)) {
+            Some(Ok(_)) => Ok(()),
+            _ => Err(OlympusError::KController(KControllerError::DeviceError)),
+        }
+    }
+
+    /// Read pending input events of device.
+    ///
+    /// Returns False if device was disconnected.
+    fn read_input_events(&mut self, controller : u8, events : &mut VecDeque<KEvent>) -> bool {
+        let mut buffer = [input_event::default(); READ_EVENT_COUNT];
+
+        loop {
+            let result = match self.file.as_mut() {
+                Some(file) => unsafe {
+                    // Evdev only returns whole input_event.
+                    file.read(std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, std::mem::size_of_val(&buffer)))
+                },
+                None => return true,
+            };
+
+            match result {
+                Ok(0) => return false,
+                Ok(length) => {
+                    for event in buffer.iter().take(length / std::mem::size_of::<input_event>()) {
+                        self.handle_input_event(controller, event, events);
+                    }
+                },
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return true,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {},
+                Err(_) => return false,     // ENODEV when device is unplugged
+            }
+        }
+    }
+}
+
+/// Create the mapping of a device according to Linux gamepad specification.
+fn get_default_mapping(guid : KControllerGuid, name : &str, buttons : &HashMap<u16, u8>, axes : &HashMap<u16, KLinuxControllerAxis>,
+    hats : &HashMap<u16, KLinuxControllerHat>) -> KControllerMapping {
+    let mut entries : Vec<(KControllerMappingSource, KControllerMappingTarget)> = Vec::new();
+
+    let mut axes : Vec<(&u16, &KLinuxControllerAxis)> = axes.iter().collect();
+    axes.sort_by_key(|(_, axis)| axis.index);
+    for (code, axis) in axes {
+        if let Some(target) = get_evdev_axis(*code) {
+            if !entries.iter().any(|(_, t)| matches!(t, KControllerMappingTarget::Axis(a, _) if *a == target)) {
+                entries.push((KControllerMappingSource::Axis(axis.index, KControllerAxisRange::Full, false), KControllerMappingTarget::Axis(target, KControllerAxisRange::Full)));
+            }
+        }
+    }
+
+    let mut buttons : Vec<(&u16, &u8)> = buttons.iter().collect();
+    buttons.sort_by_key(|(_, index)| **index);
+    for (code, index) in buttons {
+        let target = match get_evdev_button(*code) {
+            Some(button) => KControllerMappingTarget::Button(button),
+            // Digital triggers are used as trigger axes only when there is no analog trigger.
+            None if *code == BTN_TL2 => KControllerMappingTarget::Axis(KControllerAxis::TriggerLeft, KControllerAxisRange::Full),
+            None if *code == BTN_TR2 => KControllerMappingTarget::Axis(KControllerAxis::TriggerRight, KControllerAxisRange::Full),
+            None => continue,
+        };
+        if !entries.iter().any(|(_, t)| *t == target) {
+            entries.push((KControllerMappingSource::Button(*index), target));
+        }
+    }
+
+    // First hat is the directional pad.
+    if hats.values().any(|hat| hat.index == 0) {
+        entries.push((KControllerMappingSource::Hat(0, 1), KControllerMappingTarget::Button(KControllerButton::DPadUp)));
+        entries.push((KControllerMappingSource::Hat(0, 2), KControllerMappingTarget::Button(KControllerButton::DPadRight)));
+        entries.push((KControllerMappingSource::Hat(0, 4), KControllerMappingTarget::Button(KControllerButton::DPadDown)));
+        entries.push((KControllerMappingSource::Hat(0, 8), KControllerMappingTarget::Button(KControllerButton::DPadLeft)));
+    }
+
+    KControllerMapping::new(guid, name, &entries)
+}
+
+/// Get the normalized range (start, end) of a mapping source. Negative half starts at 0 and ends at -32768.
+fn get_source_range(range : KControllerAxisRange) -> (i32, i32) {
+    match range {
+        KControllerAxisRange::Full => (i16::MIN as i32, i16::MAX as i32),
+        KControllerAxisRange::Positive => (0, i16::MAX as i32),
+        KControllerAxisRange::Negative => (0, i16::MIN as i32),
+    }
+}
+
+/// Get the range (released, fully pressed) of a mapping target. Triggers range from 0 to 32767.
+fn get_target_range(axis : KControllerAxis, range : KControllerAxisRange) -> (i32, i32) {
+    match axis {
+        KControllerAxis::TriggerLeft | KControllerAxis::TriggerRight => (0, i16::MAX as i32),
+        _ => get_source_range(range),
+    }
+}
+
+/// Get the standard [KControllerButton] of an evdev button code.
+///
+/// North and west follow the Xbox driver where BTN_NORTH is X (left) and BTN_WEST is Y (top).
+fn get_evdev_button(code : u16) -> Option<KControllerButton> {
+    match code {
+        BTN_SOUTH => Some(KControllerButton::A),
+        BTN_EAST => Some(KControllerButton::B),
+        BTN_NORTH => Some(KControllerButton::X),
+        BTN_WEST => Some(KControllerButton::Y),
+        BTN_SELECT => Some(KControllerButton::Back),
+        BTN_MODE => Some(KControllerButton::Guide),
+        BTN_START => Some(KControllerButton::Start),
+        BTN_THUMBL => Some(KControllerButton::LeftStick),
+        BTN_THUMBR => Some(KControllerButton::RightStick),
+        BTN_TL => Some(KControllerButton::LeftShoulder),
+        BTN_TR => Some(KControllerButton::RightShoulder),
+        BTN_DPAD_UP => Some(KControllerButton::DPadUp),
+        BTN_DPAD_DOWN => Some(KControllerButton::DPadDown),
+        BTN_DPAD_LEFT => Some(KControllerButton::DPadLeft),
+        BTN_DPAD_RIGHT => Some(KControllerButton::DPadRight),
+        _ => None,
+    }
+}
+
+/// Get the standard [KControllerAxis] of an evdev absolute axis code.
+fn get_evdev_axis(code : u16) -> Option<KControllerAxis> {
+    match code {
+        ABS_X => Some(KControllerAxis::LeftX),
+        ABS_Y => Some(KControllerAxis::LeftY),
+        ABS_RX => Some(KControllerAxis::RightX),
+        ABS_RY => Some(KControllerAxis::RightY),
+        ABS_Z | ABS_BRAKE => Some(KControllerAxis::TriggerLeft),
+        ABS_RZ | ABS_GAS => Some(KControllerAxis::TriggerRight),
+        _ => None,
+    }
+}
+
+/// Get the force feedback magnitude of a strength from 0.0 to 1.0.
+fn get_magnitude(strength : f32) -> u16 {
+    (strength.clamp(0.0, 1.0) * u16::MAX as f32) as u16
+}
+
+/// Returns True if bit is set in an evdev bits array.
+fn is_bit_set(bits : &[u8], bit : u16) -> bool {
+    bits.get(bit as usize / 8).is_some_and(|byte| byte & (1 << (bit % 8)) != 0)
+}
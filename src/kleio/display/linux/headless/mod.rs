@@ -0,0 +1,152 @@
+use std::collections::VecDeque;
+
+use crate::kleio::display::{KWindow, KWindowFullscreenMode, event::{KEvent, KEventWindow}, screen::{KScreen, KScreenResolution, KScreenListError}};
+
+/// Resolution (width, height) of the headless virtual screen.
+pub const KLINUX_HEADLESS_SCREEN_RESOLUTION : (u32, u32) = (1920, 1080);
+
+/// Refresh rate of the headless virtual screen (60hz).
+pub const KLINUX_HEADLESS_SCREEN_REFRESH_RATE : u32 = 6000;
+
+/// Headless display server properties.
+pub struct KLinuxDisplayServerHeadlessProperty {
+    /// Events synthesized or injected, waiting to be polled.
+    pub(crate) events : VecDeque<KEvent>,
+
+    /// Position and size for restoring window. None if window is neither maximized nor fullscreen.
+    pub(crate) restoration_position_size : Option<((i32, i32), (u32, u32))>,
+}
+
+impl KLinuxDisplayServerHeadlessProperty {
+    /// Create headless properties with the events of a newly shown window.
+    pub fn new() -> KLinuxDisplayServerHeadlessProperty {
+        KLinuxDisplayServerHeadlessProperty { events: VecDeque::from([KEvent::Window(KEventWindow::Shown()), KEvent::Window(KEventWindow::Focus())]),
+            restoration_position_size: None }
+    }
+}
+
+impl Default for KLinuxDisplayServerHeadlessProperty {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Private function that gives the headless virtual screen.
+pub(crate) fn get_headless_screen() -> Result<Vec<KScreen>, KScreenListError> {
+    let mut resolution = KScreenResolution::new(KLINUX_HEADLESS_SCREEN_RESOLUTION.0, KLINUX_HEADLESS_SCREEN_RESOLUTION.1);
+    resolution.add_refresh_rate(KLINUX_HEADLESS_SCREEN_REFRESH_RATE);
+
+    Ok(vec![KScreen::new(String::from("HEADLESS-1"), (0, 0), KLINUX_HEADLESS_SCREEN_RESOLUTION, KLINUX_HEADLESS_SCREEN_REFRESH_RATE, true, vec![resolution])])
+}
+
+/// Implementation of privates elements relatives to headless display server
+#[doc(hidden)]
+impl KWindow {
+
+    /// Returns the position of a new window centered in the headless screen.
+    #[inline(always)]
+    pub(super) fn get_headless_window_position(width : u32, height : u32) -> (i32, i32) {
+        ((KLINUX_HEADLESS_SCREEN_RESOLUTION.0 as i32 - width as i32) / 2, (KLINUX_HEADLESS_SCREEN_RESOLUTION.1 as i32 - height as i32) / 2)
+    }
+
+    // Pop an event from the queue and apply window states like a display server would.
+    #[inline(always)]
+    pub(super) fn headless_poll_event(&mut self) -> KEvent {
+        let event = self.display_server.headless_property.events.pop_front().unwrap_or(KEvent::None);
+
+        if let KEvent::Window(window_event) = event {
+            match window_event {
+                KEventWindow::Minimized() => self.property.minimized = true,
+                KEventWindow::Maximized() | KEventWindow::Fullscreen() => {
+                    // Keep normal position and size for restoration.
+                    if self.display_server.headless_property.restoration_position_size.is_none() {
+                        self.display_server.headless_property.restoration_position_size = Some((self.property.position, self.property.size));
+                    }
+                    self.property.maximized |= matches!(window_event, KEventWindow::Maximized());
+                    self.property.fullscreen |= matches!(window_event, KEventWindow::Fullscreen());
+                },
+                KEventWindow::Restored() => {
+                    self.property.minimized = false;
+                    self.property.maximized = false;
+                    self.property.fullscreen = false;
+                },
+                _ => {},
+            }
+        }
+        event
+    }
+
+    /// Get the count of events that need handling.
+    #[inline(always)]
+    pub(super) fn headless_get_event_count(&self) -> usize {
+        self.display_server.headless_property.events.len()
+    }
+
+    /// Queue an event given by user.
+    #[inline(always)]
+    pub(super) fn headless_inject_event(&mut self, event : KEvent) {
+        self.display_server.headless_property.events.push_back(event);
+    }
+
+    /// Set a size of [KWindow].
+    #[inline(always)]
+    pub(super) fn headless_set_size(&mut self) {
+        self.display_server.headless_property.events.push_back(KEvent::Window(KEventWindow::Resized(self.property.size)));
+    }
+
+    /// Set a position of [KWindow].
+    #[inline(always)]
+    pub(super) fn headless_set_position(&mut self) {
+        self.display_server.headless_property.events.push_back(KEvent::Window(KEventWindow::Moved(self.property.position)));
+    }
+
+    /// Set the [KWindow] as fullscreen, covering screens according to mode.
+    #[inline(always)]
+    pub(super) fn headless_set_fullscreen(&mut self, mode : KWindowFullscreenMode) {
+        let screens : Vec<&KScreen> = match mode {
+            KWindowFullscreenMode::CurrentScreen => {
+                let center = (self.property.position.0 + self.property.size.0 as i32 / 2, self.property.position.1 + self.property.size.1 as i32 / 2);
+                self.screen_list.get_screen_list().iter().filter(|s| is_in_screen(s, center)).take(1).collect()
+            },
+            KWindowFullscreenMode::PrimaryScreen => self.screen_list.get_primary_screen().into_iter().collect(),
+            KWindowFullscreenMode::ExtendedScreen(reference) => self.screen_list.get_screen_list().iter()
+                .filter(|s| s.get_current_resolution() == reference.get_current_resolution()).collect(),
+            KWindowFullscreenMode::SelectScreen(screen) => vec![screen],
+        };
+
+        // Use primary screen if window isn't in a screen.
+        let screens = if screens.is_empty() { self.screen_list.get_primary_screen().into_iter().collect() } else { screens };
+
+        if let Some(first) = screens.first() {
+            let mut area = (first.get_extended_position(), (first.get_extended_position().0 + first.get_current_resolution().0 as i32,
+                first.get_extended_position().1 + first.get_current_resolution().1 as i32));
+            for screen in screens.iter() {
+                let (position, resolution) = (screen.get_extended_position(), screen.get_current_resolution());
+                area.0 = (area.0.0.min(position.0), area.0.1.min(position.1));
+                area.1 = (area.1.0.max(position.0 + resolution.0 as i32), area.1.1.max(position.1 + resolution.1 as i32));
+            }
+
+            let size = ((area.1.0 - area.0.0) as u32, (area.1.1 - area.0.1) as u32);
+            self.display_server.headless_property.events.push_back(KEvent::Window(KEventWindow::Fullscreen()));
+            self.display_server.headless_property.events.push_back(KEvent::Window(KEventWindow::MovedResized(area.0, size)));
+        }
+    }
+
+    /// Restore the [KWindow], undoing any minimized, maximized and/or fullscreen status.
+    #[inline(always)]
+    pub(super) fn headless_restore(&mut self) {
+        if self.property.minimized || self.property.maximized || self.property.fullscreen {
+            self.display_server.headless_property.events.push_back(KEvent::Window(KEventWindow::Restored()));
+        }
+
+        if let Some((position, size)) = self.display_server.headless_property.restoration_position_size.take() {
+            self.display_server.headless_property.events.push_back(KEvent::Window(KEventWindow::MovedResized(position, size)));
+        }
+    }
+}
+
+/// Returns True if position is within the screen.
+fn is_in_screen(screen : &KScreen, position : (i32, i32)) -> bool {
+    let (origin, resolution) = (screen.get_extended_position(), screen.get_current_resolution());
+    position.0 >= origin.0 && position.1 >= origin.1 && position.0 < origin.0 + resolution.0 as i32 && position.1 < origin.1 + resolution.1 as i32
+}
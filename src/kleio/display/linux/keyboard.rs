@@ -0,0 +1,139 @@
+use crate::kleio::display::event::{KScancode, KKey};
+
+/// Get the [KScancode] of a Linux evdev key code.
+///
+/// X11 keycodes are evdev key codes + 8 and Wayland keycodes are evdev key codes.
+///
+/// # Reference(s)
+/// <https://github.com/torvalds/linux/blob/master/include/uapi/linux/input-event-codes.h>
+pub fn get_evdev_scancode(code : u32) -> KScancode {
+    match code {
+        1 => KScancode::Escape,
+        2 => KScancode::Digit1, 3 => KScancode::Digit2, 4 => KScancode::Digit3, 5 => KScancode::Digit4, 6 => KScancode::Digit5,
+        7 => KScancode::Digit6, 8 => KScancode::Digit7, 9 => KScancode::Digit8, 10 => KScancode::Digit9, 11 => KScancode::Digit0,
+        12 => KScancode::Minus,
+        13 => KScancode::Equal,
+        14 => KScancode::Backspace,
+        15 => KScancode::Tab,
+        16 => KScancode::Q, 17 => KScancode::W, 18 => KScancode::E, 19 => KScancode::R, 20 => KScancode::T,
+        21 => KScancode::Y, 22 => KScancode::U, 23 => KScancode::I, 24 => KScancode::O, 25 => KScancode::P,
+        26 => KScancode::LeftBracket,
+        27 => KScancode::RightBracket,
+        28 => KScancode::Enter,
+        29 => KScancode::LeftControl,
+        30 => KScancode::A, 31 => KScancode::S, 32 => KScancode::D, 33 => KScancode::F, 34 => KScancode::G,
+        35 => KScancode::H, 36 => KScancode::J, 37 => KScancode::K, 38 => KScancode::L,
+        39 => KScancode::Semicolon,
+        40 => KScancode::Apostrophe,
+        41 => KScancode::Grave,
+        42 => KScancode::LeftShift,
+        43 => KScancode::Backslash,
+        44 => KScancode::Z, 45 => KScancode::X, 46 => KScancode::C, 47 => KScancode::V, 48 => KScancode::B,
+        49 => KScancode::N, 50 => KScancode::M,
+        51 => KScancode::Comma,
+        52 => KScancode::Period,
+        53 => KScancode::Slash,
+        54 => KScancode::RightShift,
+        55 => KScancode::KeypadMultiply,
+        56 => KScancode::LeftAlt,
+        57 => KScancode::Space,
+        58 => KScancode::CapsLock,
+        59..=68 => KScancode::Function((code - 58) as u8),     // F1 to F10
+        69 => KScancode::NumLock,
+        70 => KScancode::ScrollLock,
+        71 => KScancode::Keypad7, 72 => KScancode::Keypad8, 73 => KScancode::Keypad9,
+        74 => KScancode::KeypadMinus,
+        75 => KScancode::Keypad4, 76 => KScancode::Keypad5, 77 => KScancode::Keypad6,
+        78 => KScancode::KeypadPlus,
+        79 => KScancode::Keypad1, 80 => KScancode::Keypad2, 81 => KScancode::Keypad3,
+        82 => KScancode::Keypad0,
+        83 => KScancode::KeypadPeriod,
+        86 => KScancode::NonUsBackslash,
+        87 => KScancode::Function(11),
+        88 => KScancode::Function(12),
+        96 => KScancode::KeypadEnter,
+        97 => KScancode::RightControl,
+        98 => KScancode::KeypadDivide,
+        99 => KScancode::PrintScreen,
+        100 => KScancode::RightAlt,
+        102 => KScancode::Home,
+        103 => KScancode::Up,
+        104 => KScancode::PageUp,
+        105 => KScancode::Left,
+        106 => KScancode::Right,
+        107 => KScancode::End,
+        108 => KScancode::Down,
+        109 => KScancode::PageDown,
+        110 => KScancode::Insert,
+        111 => KScancode::Delete,
+        117 => KScancode::KeypadEqual,
+        119 => KScancode::Pause,
+        125 => KScancode::LeftSuper,
+        126 => KScancode::RightSuper,
+        127 => KScancode::Menu,
+        183..=194 => KScancode::Function((code - 170) as u8),  // F13 to F24
+        _ => KScancode::Unknown(code),
+    }
+}
+
+/// Get the [KKey] of an X keysym. Keysyms are shared by X11 and Wayland (xkbcommon).
+///
+/// # Reference(s)
+/// <https://gitlab.freedesktop.org/xorg/proto/xorgproto/-/blob/master/include/X11/keysymdef.h>
+pub fn get_keysym_key(keysym : u32) -> KKey {
+    match keysym {
+        0xff08 => KKey::Backspace,
+        0xff09 => KKey::Tab,
+        0xff0d | 0xff8d => KKey::Enter,     // Return, KP_Enter
+        0xff13 => KKey::Pause,
+        0xff14 => KKey::ScrollLock,
+        0xff1b => KKey::Escape,
+        0xff50 | 0xff95 => KKey::Home,
+        0xff51 | 0xff96 => KKey::Left,
+        0xff52 | 0xff97 => KKey::Up,
+        0xff53 | 0xff98 => KKey::Right,
+        0xff54 | 0xff99 => KKey::Down,
+        0xff55 | 0xff9a => KKey::PageUp,
+        0xff56 | 0xff9b => KKey::PageDown,
+        0xff57 | 0xff9c => KKey::End,
+        0xff61 => KKey::PrintScreen,
+        0xff63 | 0xff9e => KKey::Insert,
+        0xff67 => KKey::Menu,
+        0xff7f => KKey::NumLock,
+        0xffbe..=0xffd5 => KKey::Function((keysym - 0xffbd) as u8),    // F1 to F24
+        0xffe1 => KKey::LeftShift,
+        0xffe2 => KKey::RightShift,
+        0xffe3 => KKey::LeftControl,
+        0xffe4 => KKey::RightControl,
+        0xffe5 => KKey::CapsLock,
+        0xffe9 => KKey::LeftAlt,
+        0xffea | 0xfe03 => KKey::RightAlt,  // Alt_R, ISO_Level3_Shift (AltGr)
+        0xffeb => KKey::LeftSuper,
+        0xffec => KKey::RightSuper,
+        0xffff | 0xff9f => KKey::Delete,
+        _ => match get_keysym_char(keysym) {
+            Some(character) => KKey::Character(character),
+            None => KKey::Unknown(keysym),
+        },
+    }
+}
+
+/// Get the character written by an X keysym or [None] if keysym doesn't write a character.
+///
+/// Only Latin-1, Unicode and keypad keysyms are converted.
+pub fn get_keysym_char(keysym : u32) -> Option<char> {
+    match keysym {
+        0x20..=0x7e | 0xa0..=0xff => char::from_u32(keysym),                 // Latin-1 keysyms are their code point
+        0x0100_0100..=0x0110_ffff => char::from_u32(keysym - 0x0100_0000),  // Unicode keysyms
+        0xff80 => Some(' '),
+        0xffaa => Some('*'),
+        0xffab => Some('+'),
+        0xffac => Some(','),
+        0xffad => Some('-'),
+        0xffae => Some('.'),
+        0xffaf => Some('/'),
+        0xffb0..=0xffb9 => char::from_digit(keysym - 0xffb0, 10),
+        0xffbd => Some('='),
+        _ => None,
+    }
+}
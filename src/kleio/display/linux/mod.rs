@@ -1,8 +1,10 @@
-use crate::error::{OlympusError, KWindowError};
+use std::cell::RefMut;
+
+use crate::error::{OlympusError, KWindowError, KControllerError};
 
 use self::x11::{attributes::XWindowAttributes, bind::XGetWindowAttributes};
 
-use super::{ event::KEvent, KWindow, screen::KScreenList, KCursorProperty, KWindowProperty, KWindowFullscreenMode };
+use super::{ event::{KEvent, KEventUserPoster}, KWindow, KController, KDisplayContext, screen::KScreenList, KCursorProperty, KWindowProperty, KWindowFullscreenMode };
 use debug_print::debug_println;
 use server::KLinuxDisplayServerProvider;
 
@@ -15,8 +17,18 @@ pub mod x11;
 /// Linux display server details
 pub mod server;
 
+/// Linux keyboard keycodes and keysyms conversion
+pub mod keyboard;
+
+/// Linux evdev controllers
+pub mod controller;
+
+
+/// Headless virtual display server
+pub mod headless;
 
-/// Macro shortcut to execute either wayland or x11 function.
+
+/// Macro shortcut to execute either wayland or x11 function, or headless function if given.
 #[doc(hidden)]
 #[macro_export]
 macro_rules! wayland_or_x11 {
@@ -25,33 +37,62 @@ macro_rules! wayland_or_x11 {
             KLinuxDisplayServerProvider::Wayland => $if_wayland,
             _ => $else,
         }
-    }
+    };
+    ($provider:expr, $if_wayland:block, $else:block, $if_headless:block) => {
+        match $provider {
+            KLinuxDisplayServerProvider::Wayland => $if_wayland,
+            KLinuxDisplayServerProvider::Headless => $if_headless,
+            _ => $else,
+        }
+    };
 }
 
 
 /// Implementation of privates elements relatives to linux distributions
 #[doc(hidden)]
 impl KWindow {
-    /// Create new KWindow
+    /// Create new KWindow with its own display context
     pub(super) fn __new(width:u32, height:u32, provider : super::linux::server::KLinuxDisplayServerProvider) -> Result<KWindow, OlympusError> {
+        match provider {
+            // Fall back to X11 if Wayland connection or window failed.
+            KLinuxDisplayServerProvider::Default => {
+                match KWindow::__new(width, height, KLinuxDisplayServerProvider::Wayland) {
+                    Ok(window) => Ok(window),
+                    Err(_) => KWindow::__new(width, height, KLinuxDisplayServerProvider::X11),
+                }
+            },
+            _ => KWindow::__from_context(&KDisplayContext::new(provider)?, width, height),
+        }
+    }
+
+    /// Create new KWindow sharing display context connection
+    pub(super) fn __from_context(context : &KDisplayContext, width:u32, height:u32) -> Result<KWindow, OlympusError> {
         // Default cursor.
         let cursor = KCursorProperty { mode: super::KCursorMode::Pointer, position: (0,0), visible: true, confined: false };
         
         // Default center position
         let center = ((width as i32 / 2), (height as i32 / 2));
 
-        match  super::linux::server::KLinuxDisplayServer::new(width, height, provider){
+        match  super::linux::server::KLinuxDisplayServer::new(width, height, context.connection.clone()){
             Ok(display_server) => {
                 match KScreenList::new(display_server.provider){
                     Ok(screen_list) => {
-                        let mut property = KWindowProperty { title : String::from(""), cursor, position: (0,0), size: (width, height), center, minimized: false, maximized: false, fullscreen: false };
+                        let mut property = KWindowProperty { id: KWindow::get_next_id(), title : String::from(""), cursor, position: (0,0), size: (width, height), center, minimized: false, maximized: false, fullscreen: false, posted: KEventUserPoster::new() };
                         match display_server.provider {     // Fetch window position according to provider
-                            KLinuxDisplayServerProvider::Wayland => todo!(),
+                            KLinuxDisplayServerProvider::Wayland => {
+                                // Wayland doesn't give window position to clients.
+                                Ok(KWindow { screen_list, property, display_server })
+                            },
                             KLinuxDisplayServerProvider::X11 => {
                                 // Set correct x11 window position
                                 property.position = KWindow::get_x11_window_position(display_server.display, display_server.window);
                                 Ok(KWindow { screen_list, property, display_server })
                             },
+                            KLinuxDisplayServerProvider::Headless => {
+                                // Headless window is centered in virtual screen
+                                property.position = KWindow::get_headless_window_position(width, height);
+                                Ok(KWindow { screen_list, property, display_server })
+                            },
                             _ => Err(OlympusError::KWindow(KWindowError::NoDisplayServer)),
                         }
                     },
@@ -63,13 +104,20 @@ impl KWindow {
     }
         
 
-    // Pop an event from the queue
+    // Pop an event from the queue. Controller events are popped first.
     #[inline(always)]
     pub(super) fn __poll_event(&mut self) -> KEvent {
+        let controller_event = self.display_server.connection.borrow_mut().controllers.pop_event();
+        if let Some(event) = controller_event {
+            return event;
+        }
+
         wayland_or_x11!{self.display_server.provider, { 
                 self.wayland_poll_event() 
             }, { 
                 self.x11_poll_event() 
+            }, { 
+                self.headless_poll_event()
             }
         }
     }
@@ -81,6 +129,8 @@ impl KWindow {
                 self.wayland_sync_events();
             }, { 
                 self.x11_sync_events();
+            }, {
+                // Nothing to do without display.
             }
         }
     }
@@ -88,14 +138,61 @@ impl KWindow {
     /// Get the count of events that need handling.
     #[inline(always)]
     pub(super) fn __get_event_count(&self) -> usize {
-        wayland_or_x11!{self.display_server.provider, { 
+        self.display_server.connection.borrow().controllers.get_event_count() + wayland_or_x11!{self.display_server.provider, { 
                 self.wayland_get_event_count() 
             }, { 
                 self.x11_get_event_count() 
+            }, { 
+                self.headless_get_event_count()
             }
         }
     }
 
+    /// Detect controllers connected or disconnected and read their input. Controllers are shared by windows of the display context.
+    #[inline(always)]
+    pub(super) fn __update_controllers(&mut self) {
+        // Headless only receives injected controller events.
+        if self.display_server.provider != KLinuxDisplayServerProvider::Headless {
+            self.display_server.connection.borrow_mut().controllers.update();
+        }
+    }
+
+    /// Queue an event as if sent by display server.
+    #[inline(always)]
+    pub(super) fn __inject_event(&mut self, event : KEvent) -> Result<(), OlympusError> {
+        match self.display_server.provider {
+            KLinuxDisplayServerProvider::Headless => { self.headless_inject_event(event); Ok(()) },
+            _ => Err(OlympusError::KWindow(KWindowError::NotSupported)),
+        }
+    }
+
+    /// Get a handle of a connected controller.
+    #[inline(always)]
+    pub(super) fn __get_controller(&mut self, id : u8) -> Result<KController<'_>, OlympusError> {
+        match RefMut::filter_map(self.display_server.connection.borrow_mut(), |connection| connection.controllers.get_controller_mut(id)) {
+            Ok(controller) => Ok(KController { id, controller }),
+            Err(_) => Err(OlympusError::KController(KControllerError::NotConnected)),
+        }
+    }
+
+    /// Get the ids of connected controllers.
+    #[inline(always)]
+    pub(super) fn __get_controllers(&self) -> Vec<u8> {
+        self.display_server.connection.borrow().controllers.get_controllers()
+    }
+
+    /// Add controller mappings in SDL format.
+    #[inline(always)]
+    pub(super) fn __add_controller_mappings(&mut self, mappings : &str) -> Result<usize, OlympusError> {
+        self.display_server.connection.borrow_mut().controllers.add_mappings(mappings)
+    }
+
+    /// Load controller mappings from a file in SDL format.
+    #[inline(always)]
+    pub(super) fn __load_controller_mappings(&mut self, path : &std::path::Path) -> Result<usize, OlympusError> {
+        self.display_server.connection.borrow_mut().controllers.load_mappings(path)
+    }
+
     /// Set the cursor position
     #[inline(always)]
     pub(super) fn __set_cursor_position(&mut self, position : (i32, i32)){
@@ -104,6 +201,8 @@ impl KWindow {
                 
             }, { 
                 self.x11_set_cursor_position(position);
+            }, {
+                // Nothing to do without display.
             }
         }
     }
@@ -116,6 +215,8 @@ impl KWindow {
             
             }, { 
                 self.x11_hide_cursor();
+            }, {
+                // Nothing to do without display.
             }
         }
     }
@@ -128,6 +229,8 @@ impl KWindow {
             
             }, { 
                 self.x11_show_cursor();
+            }, {
+                // Nothing to do without display.
             }
         }
     }
@@ -140,6 +243,8 @@ impl KWindow {
             
             }, { 
                 self.x11_confine_cursor();
+            }, {
+                // Nothing to do without display.
             }
         }
     }
@@ -153,6 +258,22 @@ impl KWindow {
             
             }, { 
                 self.x11_release_cursor();
+            }, {
+                // Nothing to do without display.
+            }
+        }
+    }
+
+    /// Apply cursor mode change to display server.
+    #[inline(always)]
+    pub(super) fn __set_cursor_mode(&mut self) {
+        wayland_or_x11!{self.display_server.provider, { 
+            self.wayland_set_cursor_mode();
+            
+            }, { 
+                // X11 motion events are translated according to cursor mode.
+            }, {
+                // Nothing to do without display.
             }
         }
     }
@@ -166,6 +287,8 @@ impl KWindow {
             
             }, { 
                 self.x11_restore();
+            }, { 
+                self.headless_restore();
             }
         }
     }
@@ -178,6 +301,8 @@ impl KWindow {
             
             }, { 
                 self.x11_set_title();
+            }, {
+                // Nothing to do without display.
             }
         }
     }
@@ -190,6 +315,8 @@ impl KWindow {
             
             }, { 
                 self.x11_set_size();
+            }, { 
+                self.headless_set_size();
             }
         }
     }
@@ -202,6 +329,8 @@ impl KWindow {
             
             }, { 
                 self.x11_set_position();
+            }, { 
+                self.headless_set_position();
             }
         }
     }
@@ -214,6 +343,8 @@ impl KWindow {
             
             }, { 
                 self.x11_set_fullscreen(mode);
+            }, { 
+                self.headless_set_fullscreen(mode);
             }
         }
     }
@@ -1,9 +1,10 @@
-use std::{os::raw::{ c_char, c_ulong }, ffi::{CString}};
+use std::{os::raw::{ c_char, c_int, c_ulong }, ffi::{CString}, rc::Rc, cell::RefCell, collections::{HashMap, VecDeque}};
 use debug_print::debug_println;
 
-use crate::{kleio::display::{KWindow, event::window}, wayland_or_x11, error::{OlympusError, KWindowError}};
+use crate::{kleio::display::{KWindow, event::{window, KEvent}}, wayland_or_x11, error::{OlympusError, KWindowError}};
 
-use super::{x11::{event::{XEvent, Atom}, bind::{XCloseDisplay, XInternAtom}}};
+use super::{x11::{event::{XEvent, Atom}, constant::GenericEvent, bind::{XCloseDisplay, XInternAtom, XIM, XIC, XDestroyIC, XCloseIM, XDestroyWindow, XFreeEventData, XFlush}},
+    headless::KLinuxDisplayServerHeadlessProperty, wayland::{KLinuxDisplayServerWaylandProperty, KLinuxDisplayServerWaylandConnection}, controller::KLinuxControllerList};
 
 /// Type used for display server window pointer.
 pub type Window = c_ulong;
@@ -41,46 +42,56 @@ pub enum KLinuxDisplayServerProvider {
 
     /// [X Window System](https://en.wikipedia.org/wiki/X_Window_System) display server.
     X11,
+
+    /// Virtual display server kept in memory, without any display. Never chosen by [KLinuxDisplayServerProvider::Default].
+    /// 
+    /// Used to test game logic and receivers without display (ie. continuous integration). Window operations
+    /// synthesize the [KEventWindow](crate::kleio::display::event::KEventWindow) a display server would send, and
+    /// input is given with [KWindow::inject_event()].
+    Headless,
 }
 
 
-/// Contains elements relatives to X11 and Wayland display server.
-pub struct KLinuxDisplayServer {
+/// Display server connection shared by the [KWindow] created from the same [KDisplayContext](crate::kleio::display::KDisplayContext).
+///
+/// Controllers belong to the connection, so their events are dispatched once by whichever window dispatches first.
+pub struct KLinuxDisplayConnection {
 
     /// Used to determine which provider is used
     pub provider : KLinuxDisplayServerProvider,
 
-    /// X11 only properties
-    pub x11_property : KLinuxDisplayServerX11Property,
-
     /// Display connection pointer
     pub display : *mut Display,
 
-    /// Window handle pointer
-    pub window : *mut Window,
+    /// X11 only connection properties
+    pub x11_connection : KLinuxDisplayServerX11Connection,
 
+    /// Wayland only connection. Null for other providers.
+    pub wayland_connection : *mut KLinuxDisplayServerWaylandConnection,
 
+    /// Linux evdev controllers
+    pub controllers : KLinuxControllerList,
 }
 
-
-impl KLinuxDisplayServer {
-    /// Create a new KLinuxDisplayServer according to provider.
-    pub fn new(width:u32, height:u32, provider : KLinuxDisplayServerProvider) -> Result<KLinuxDisplayServer, OlympusError> {
+impl KLinuxDisplayConnection {
+    /// Create a new KLinuxDisplayConnection according to provider.
+    pub fn new(provider : KLinuxDisplayServerProvider) -> Result<KLinuxDisplayConnection, OlympusError> {
         match provider {
             KLinuxDisplayServerProvider::Default => {
 
-                match KLinuxDisplayServer::new(width, height, KLinuxDisplayServerProvider::Wayland) {
-                    Ok(klds) => Ok(klds),
-                    Err(_) => KLinuxDisplayServer::new(width, height, KLinuxDisplayServerProvider::X11),
+                match KLinuxDisplayConnection::new(KLinuxDisplayServerProvider::Wayland) {
+                    Ok(kldc) => Ok(kldc),
+                    Err(_) => KLinuxDisplayConnection::new(KLinuxDisplayServerProvider::X11),
                 }
             },
             KLinuxDisplayServerProvider::Wayland => {
                 if KWindow::wayland_supported() {
-                    let provider = KLinuxDisplayServerProvider::Wayland;
-                    let dis_win = KWindow::create_wayland_window(width, height);
-
-                    Ok(KLinuxDisplayServer{ provider, x11_property : KLinuxDisplayServerX11Property::empty(), display : dis_win.0, window : dis_win.1 })
+                    let wayland_connection = KWindow::create_wayland_connection()?;
 
+                    // Display handle is the wl_display.
+                    let display = unsafe { (*wayland_connection).display as *mut Display };
+                    Ok(KLinuxDisplayConnection { provider, display, x11_connection : KLinuxDisplayServerX11Connection::empty(), wayland_connection,
+                        controllers : KLinuxControllerList::new() })
                 } else {
                     // No wayland support.
                     Err(OlympusError::KWindow(KWindowError::NotSupported))
@@ -88,16 +99,164 @@ impl KLinuxDisplayServer {
             },
             KLinuxDisplayServerProvider::X11 => {
                 if KWindow::x11_supported() {
-                    let provider = KLinuxDisplayServerProvider::X11;
-
-                    let prop_dis = KWindow::create_x11_display_connection();
-                    let window = KWindow::create_x11_window(prop_dis.1, &prop_dis.0, (0,0), (width, height), false);
-                    Ok(KLinuxDisplayServer{ provider, x11_property : prop_dis.0, display : prop_dis.1, window : window })
+                    let (x11_connection, display) = KWindow::create_x11_display_connection();
+                    Ok(KLinuxDisplayConnection { provider, display, x11_connection, wayland_connection : std::ptr::null_mut(),
+                        controllers : KLinuxControllerList::new() })
                 } else {
                     // No x11 support.
                     Err(OlympusError::KWindow(KWindowError::NotSupported))
                 }
             },
+            KLinuxDisplayServerProvider::Headless => {
+                // No connection needed.
+                Ok(KLinuxDisplayConnection { provider, display : std::ptr::null_mut(), x11_connection : KLinuxDisplayServerX11Connection::empty(),
+                    wayland_connection : std::ptr::null_mut(), controllers : KLinuxControllerList::new() })
+            },
+        }
+    }
+}
+
+impl Drop for KLinuxDisplayConnection {
+    fn drop(&mut self) {
+        wayland_or_x11!{self.provider, {
+            unsafe {
+                // Connection releases globals and disconnects when dropped.
+                drop(Box::from_raw(self.wayland_connection));
+            }
+        } , {
+            unsafe {
+                // Close input method before connection.
+                if !self.x11_connection.x_im.is_null() {
+                    XCloseIM(self.x11_connection.x_im);
+                }
+
+                // Close display server connection.
+                XCloseDisplay(self.display);
+            }
+        }, {
+            // Nothing to close.
+        }}
+    }
+}
+
+/// X11 properties shared by windows of a connection.
+pub struct KLinuxDisplayServerX11Connection {
+    /// Input method used to compose text. Null if none available.
+    pub(crate) x_im : XIM,
+
+    /// True if the server doesn't send KeyRelease for auto-repeated KeyPress.
+    pub(crate) x_detectable_repeat : bool,
+
+    /// Major opcode of XInput2 extension used to read raw motion. None if XInput2 isn't available.
+    pub(crate) x_xi_opcode : Option<c_int>,
+
+    /// True if XInput2 touch events are supported (XInput2 2.2).
+    pub(crate) x_xi_touch : bool,
+
+    /// XInput2 pen (stylus) devices by device id. Queried again when devices are plugged or unplugged.
+    pub(crate) x_xi_pens : HashMap<c_int, KLinuxDisplayServerX11Pen>,
+
+    /// Events read from connection, queued by window until polled. XInput2 events keep their claimed data.
+    pub(crate) x_queues : HashMap<Window, VecDeque<XEvent>>,
+
+    /// Window having focus, receiving raw motion selected on root window. 0 if no window has focus.
+    pub(crate) x_focus : Window,
+}
+
+impl KLinuxDisplayServerX11Connection {
+    /// Empty X11 connection properties.
+    pub fn empty() -> KLinuxDisplayServerX11Connection {
+        KLinuxDisplayServerX11Connection { x_im : std::ptr::null_mut(), x_detectable_repeat : false, x_xi_opcode : None, x_xi_touch : false,
+            x_xi_pens : HashMap::new(), x_queues : HashMap::new(), x_focus : 0 }
+    }
+}
+
+/// XInput2 pen (stylus) device with its valuators and last state.
+///
+/// Device events only carry valuators that changed, so last pressure and tilt are kept.
+pub struct KLinuxDisplayServerX11Pen {
+    /// Number, minimum and maximum of the pressure valuator.
+    pub(crate) pressure_valuator : (usize, f64, f64),
+
+    /// Numbers of the tilt (x, y) valuators. None if pen doesn't report tilt.
+    pub(crate) tilt_valuators : (Option<usize>, Option<usize>),
+
+    /// True if device is the eraser end of a pen.
+    pub(crate) eraser : bool,
+
+    /// Last pressure, from 0.0 to 1.0.
+    pub(crate) pressure : f32,
+
+    /// Last tilt (x, y) in degrees.
+    pub(crate) tilt : (f32, f32),
+}
+
+
+/// Contains elements relatives to X11 and Wayland display server.
+pub struct KLinuxDisplayServer {
+
+    /// Used to determine which provider is used
+    pub provider : KLinuxDisplayServerProvider,
+
+    /// X11 only properties
+    pub x11_property : KLinuxDisplayServerX11Property,
+
+    /// Headless only properties
+    pub headless_property : KLinuxDisplayServerHeadlessProperty,
+
+    /// Wayland only properties. Null for other providers.
+    pub wayland_property : *mut KLinuxDisplayServerWaylandProperty,
+
+    /// Display connection pointer
+    pub display : *mut Display,
+
+    /// Window handle pointer
+    pub window : *mut Window,
+
+    /// Connection shared with other windows of the same context
+    pub connection : Rc<RefCell<KLinuxDisplayConnection>>,
+
+}
+
+
+impl KLinuxDisplayServer {
+    /// Create a new KLinuxDisplayServer window on connection.
+    pub fn new(width:u32, height:u32, connection : Rc<RefCell<KLinuxDisplayConnection>>) -> Result<KLinuxDisplayServer, OlympusError> {
+        let (provider, display, wayland_connection) = {
+            let connection = connection.borrow();
+            (connection.provider, connection.display, connection.wayland_connection)
+        };
+
+        match provider {
+            KLinuxDisplayServerProvider::Wayland => {
+                let wayland_property = KWindow::create_wayland_window(wayland_connection, width, height)?;
+
+                // Window handle is the wl_surface.
+                let window = unsafe { (*wayland_property).surface as *mut Window };
+                Ok(KLinuxDisplayServer{ provider, x11_property : KLinuxDisplayServerX11Property::empty(), headless_property : KLinuxDisplayServerHeadlessProperty::new(),
+                    wayland_property, display, window, connection })
+            },
+            KLinuxDisplayServerProvider::X11 => {
+                let mut x11_property = KLinuxDisplayServerX11Property::new(display);
+                let window = {
+                    let mut connection = connection.borrow_mut();
+                    let window = KWindow::create_x11_window(display, &x11_property, &connection.x11_connection, (0,0), (width, height), false);
+                    KWindow::create_x11_input_context(window, connection.x11_connection.x_im, &mut x11_property);
+
+                    // Events of window are queued until polled.
+                    connection.x11_connection.x_queues.insert(window as Window, VecDeque::new());
+                    window
+                };
+                Ok(KLinuxDisplayServer{ provider, x11_property, headless_property : KLinuxDisplayServerHeadlessProperty::new(),
+                    wayland_property : std::ptr::null_mut(), display, window, connection })
+            },
+            KLinuxDisplayServerProvider::Headless => {
+                // No window handle needed.
+                Ok(KLinuxDisplayServer{ provider, x11_property : KLinuxDisplayServerX11Property::empty(), headless_property : KLinuxDisplayServerHeadlessProperty::new(),
+                    wayland_property : std::ptr::null_mut(), display, window : std::ptr::null_mut(), connection })
+            },
+            // Connection provider is never default.
+            KLinuxDisplayServerProvider::Default => Err(OlympusError::KWindow(KWindowError::NoDisplayServer)),
         }
 
     }
@@ -107,12 +266,29 @@ impl KLinuxDisplayServer {
 impl Drop for KLinuxDisplayServer {
     fn drop(&mut self) {
         wayland_or_x11!{self.provider, {
-            todo!()
+            unsafe {
+                // Properties destroy window when dropped. Connection is closed with the last window of context.
+                drop(Box::from_raw(self.wayland_property));
+            }
         } , {
             unsafe {
-                // Close display server connection.
-                XCloseDisplay(self.display);
+                // Destroy input context before window.
+                if !self.x11_property.x_ic.is_null() {
+                    XDestroyIC(self.x11_property.x_ic);
+                }
+                XDestroyWindow(self.display, self.window);
+
+                // Free data claimed by XInput2 events not polled.
+                let mut connection = self.connection.borrow_mut();
+                for mut xevent in connection.x11_connection.x_queues.remove(&(self.window as Window)).unwrap_or_default() {
+                    if xevent._type == GenericEvent {
+                        XFreeEventData(self.display, &mut xevent._xcookie);
+                    }
+                }
+                XFlush(self.display);
             }
+        }, {
+            // Nothing to close.
         }}
     }
 }
@@ -150,6 +326,18 @@ macro_rules! x11_server_property {
 
             /// Position and size for restoring window.
             pub(crate) restoration_position_size : ((i32,i32),(u32,u32)),
+
+            /// Input context of the window. Null if no input method.
+            pub(crate) x_ic : XIC,
+
+            /// Keys currently pressed by keycode, used to flag auto-repeat.
+            pub(crate) x_pressed_keys : [bool; 256],
+
+            /// Event generated by the previous event, returned at next poll (ie. text input following a key press).
+            pub(crate) x_pending_event : Option<KEvent>,
+
+            /// True if window has focus. Raw motion is received by root window even when not focused.
+            pub(crate) x_focused : bool,
         }
 
         impl KLinuxDisplayServerX11Property{
@@ -161,7 +349,8 @@ macro_rules! x11_server_property {
                     _NET_WM_STATE_REMOVE : 0, _NET_WM_STATE_ADD : 1, _NET_WM_STATE_TOGGLE : 2,
                     $atom :  XInternAtom(display, CString::new(stringify!($atom)).unwrap().as_ptr(), true),
                     $($atoms : XInternAtom(display, CString::new(stringify!($atoms)).unwrap().as_ptr(), true),)*
-                    xa_atom : 4, x_hide_cursor_flag : false, restoration_position_size : ((0,0),(0,0)) };
+                    xa_atom : 4, x_hide_cursor_flag : false, restoration_position_size : ((0,0),(0,0)),
+                    x_ic : std::ptr::null_mut(), x_pressed_keys : [false; 256], x_pending_event : None, x_focused : false };
 
                     // Make sure that all Atoms have value > 0.
                     assert_ne!(x11_prop.$atom, 0, "Atom [{}] value must NOT be 0.", stringify!($atom));
@@ -178,7 +367,8 @@ macro_rules! x11_server_property {
                     $atom :  0,
                     $($atoms : 0,)*
                     xa_atom : 4, x_hide_cursor_flag : false,
-                    restoration_position_size : ((0,0),(0,0)) }
+                    restoration_position_size : ((0,0),(0,0)),
+                    x_ic : std::ptr::null_mut(), x_pressed_keys : [false; 256], x_pending_event : None, x_focused : false }
             }
         }
 
@@ -1,8 +1,103 @@
-use std::os::raw::{c_char, c_int, c_void};
+use std::os::raw::{c_char, c_int, c_uint, c_ulong, c_void};
 
 pub enum wl_proxy {}
 pub enum wl_display {}
 pub enum wl_event_queue {}
+pub enum xkb_context {}
+pub enum xkb_keymap {}
+pub enum xkb_state {}
+
+/// Destroy proxy after marshalling request.
+pub const WL_MARSHAL_FLAG_DESTROY : u32 = 1;
+
+/// 32-bit RGB format with alpha.
+pub const WL_SHM_FORMAT_ARGB8888 : u32 = 0;
+
+/// 32-bit RGB format with unused alpha.
+pub const WL_SHM_FORMAT_XRGB8888 : u32 = 1;
+
+/// Seat has a pointer.
+pub const WL_SEAT_CAPABILITY_POINTER : u32 = 1;
+
+/// Seat has a keyboard.
+pub const WL_SEAT_CAPABILITY_KEYBOARD : u32 = 2;
+
+/// Key or button is pressed.
+pub const WL_PRESSED : u32 = 1;
+
+/// Vertical scroll axis.
+pub const WL_POINTER_AXIS_VERTICAL_SCROLL : u32 = 0;
+
+/// Mode is the current output mode.
+pub const WL_OUTPUT_MODE_CURRENT : u32 = 1;
+
+/// Keymap is a libxkbcommon compatible keymap.
+pub const WL_KEYBOARD_KEYMAP_FORMAT_XKB_V1 : u32 = 1;
+
+/// xdg_toplevel is maximized.
+pub const XDG_TOPLEVEL_STATE_MAXIMIZED : u32 = 1;
+
+/// xdg_toplevel is fullscreen.
+pub const XDG_TOPLEVEL_STATE_FULLSCREEN : u32 = 2;
+
+/// Close memory file descriptor on exec.
+pub const MFD_CLOEXEC : c_uint = 1;
+
+/// There is data to read.
+pub const POLLIN : i16 = 1;
+
+/// Keymap from a text string.
+pub const XKB_KEYMAP_FORMAT_TEXT_V1 : c_int = 1;
+
+/// Modifiers currently in effect.
+pub const XKB_STATE_MODS_EFFECTIVE : c_int = 1 << 3;
+
+/// Offset between evdev key codes and xkb key codes.
+pub const XKB_EVDEV_OFFSET : u32 = 8;
+
+/// Dynamic array of wayland events (ie. toplevel states).
+#[repr(C)]
+pub struct wl_array {
+    pub size : usize,
+    pub alloc : usize,
+    pub data : *mut c_void,
+}
+
+/// Description of a wayland request or event.
+#[repr(C)]
+pub struct wl_message {
+    pub name : *const c_char,
+    pub signature : *const c_char,
+    pub types : *const *const wl_interface,
+}
+
+/// Description of a wayland interface.
+#[repr(C)]
+pub struct wl_interface {
+    pub name : *const c_char,
+    pub version : c_int,
+    pub method_count : c_int,
+    pub methods : *const wl_message,
+    pub event_count : c_int,
+    pub events : *const wl_message,
+}
+
+/// Types of message arguments. Null types aren't checked by libwayland.
+#[repr(C)]
+pub struct wl_types(pub [*const wl_interface; 8]);
+
+// Interfaces are immutable C data shared with libwayland.
+unsafe impl Sync for wl_message {}
+unsafe impl Sync for wl_interface {}
+unsafe impl Sync for wl_types {}
+
+/// File descriptor polled for events.
+#[repr(C)]
+pub struct pollfd {
+    pub fd : c_int,
+    pub events : i16,
+    pub revents : i16,
+}
 
 #[link(name = "wayland-client")]
 extern {
@@ -11,4 +106,62 @@ extern {
     pub fn wl_display_connect(name : *const c_char) -> *mut wl_display;
     pub fn wl_display_disconnect(display : *mut wl_display) -> ();
     pub fn wl_display_get_fd(display : *mut wl_display) -> c_int;
+
+    // events reading and dispatch
+    pub fn wl_display_dispatch_pending(display : *mut wl_display) -> c_int;
+    pub fn wl_display_flush(display : *mut wl_display) -> c_int;
+    pub fn wl_display_prepare_read(display : *mut wl_display) -> c_int;
+    pub fn wl_display_read_events(display : *mut wl_display) -> c_int;
+    pub fn wl_display_cancel_read(display : *mut wl_display) -> ();
+    pub fn wl_display_roundtrip(display : *mut wl_display) -> c_int;
+
+    // proxies
+    pub fn wl_proxy_marshal_flags(proxy : *mut wl_proxy, opcode : u32, interface : *const wl_interface, version : u32, flags : u32, ...) -> *mut wl_proxy;
+    pub fn wl_proxy_add_listener(proxy : *mut wl_proxy, implementation : *const c_void, data : *mut c_void) -> c_int;
+    pub fn wl_proxy_destroy(proxy : *mut wl_proxy) -> ();
+    pub fn wl_proxy_get_version(proxy : *mut wl_proxy) -> u32;
+
+    // core interfaces
+    pub static wl_registry_interface : wl_interface;
+    pub static wl_compositor_interface : wl_interface;
+    pub static wl_surface_interface : wl_interface;
+    pub static wl_region_interface : wl_interface;
+    pub static wl_shm_interface : wl_interface;
+    pub static wl_shm_pool_interface : wl_interface;
+    pub static wl_buffer_interface : wl_interface;
+    pub static wl_seat_interface : wl_interface;
+    pub static wl_pointer_interface : wl_interface;
+    pub static wl_keyboard_interface : wl_interface;
+    pub static wl_output_interface : wl_interface;
+}
+
+#[link(name = "xkbcommon")]
+extern "C" {
+    pub fn xkb_context_new(flags : c_int) -> *mut xkb_context;
+    pub fn xkb_context_unref(context : *mut xkb_context) -> ();
+    pub fn xkb_keymap_new_from_string(context : *mut xkb_context, string : *const c_char, format : c_int, flags : c_int) -> *mut xkb_keymap;
+    pub fn xkb_keymap_unref(keymap : *mut xkb_keymap) -> ();
+    pub fn xkb_keymap_key_repeats(keymap : *mut xkb_keymap, key : u32) -> c_int;
+    pub fn xkb_keymap_key_get_syms_by_level(keymap : *mut xkb_keymap, key : u32, layout : u32, level : u32, syms_out : *mut *const u32) -> c_int;
+    pub fn xkb_state_new(keymap : *mut xkb_keymap) -> *mut xkb_state;
+    pub fn xkb_state_unref(state : *mut xkb_state) -> ();
+    pub fn xkb_state_update_mask(state : *mut xkb_state, depressed_mods : u32, latched_mods : u32, locked_mods : u32,
+        depressed_layout : u32, latched_layout : u32, locked_layout : u32) -> c_int;
+    pub fn xkb_state_key_get_layout(state : *mut xkb_state, key : u32) -> u32;
+    pub fn xkb_state_key_get_utf8(state : *mut xkb_state, key : u32, buffer : *mut c_char, size : usize) -> c_int;
+    pub fn xkb_state_mod_name_is_active(state : *mut xkb_state, name : *const c_char, kind : c_int) -> c_int;
+}
+
+extern "C" {
+    /// Creates an anonymous file in memory, used for shared buffers.
+    ///
+    /// # Reference(s)
+    /// <https://man7.org/linux/man-pages/man2/memfd_create.2.html>
+    pub fn memfd_create(name : *const c_char, flags : c_uint) -> c_int;
+
+    /// Waits for one of a set of file descriptors to become ready.
+    ///
+    /// # Reference(s)
+    /// <https://man7.org/linux/man-pages/man2/poll.2.html>
+    pub fn poll(fds : *mut pollfd, nfds : c_ulong, timeout : c_int) -> c_int;
 }
@@ -0,0 +1,408 @@
+use std::{ffi::CStr, fs::File, os::{raw::{c_char, c_void}, unix::{fs::FileExt, io::FromRawFd}}, ptr::null_mut, time::{Instant, Duration}};
+
+use crate::kleio::display::event::{KEvent, KEventWindow, KEventMouse, KMouseButton};
+
+use super::{KLinuxDisplayServerWaylandConnection, KLinuxDisplayServerWaylandProperty, protocol::*, bind::*, screen::{KWaylandOutput, KWAYLAND_OUTPUT_LISTENER}};
+
+/// Scroll distance of a wheel step when device doesn't give discrete steps (ie. touchpad).
+const KWAYLAND_WHEEL_STEP : f64 = 10.0;
+
+/// Returns the connection given as listener data of globals and seat devices.
+#[inline(always)]
+unsafe fn get_connection<'a>(data : *mut c_void) -> &'a mut KLinuxDisplayServerWaylandConnection {
+    &mut *(data as *mut KLinuxDisplayServerWaylandConnection)
+}
+
+/// Returns the window properties given as listener data of window objects.
+#[inline(always)]
+unsafe fn get_property<'a>(data : *mut c_void) -> &'a mut KLinuxDisplayServerWaylandProperty {
+    &mut *(data as *mut KLinuxDisplayServerWaylandProperty)
+}
+
+/// Convert a wl_fixed (24.8 signed fixed point) to f64.
+#[inline(always)]
+pub(super) fn wl_fixed_to_f64(fixed : i32) -> f64 {
+    fixed as f64 / 256.0
+}
+
+/// Bind a global interface from registry.
+pub(super) unsafe fn wl_registry_bind(registry : *mut wl_proxy, name : u32, interface : &wl_interface, version : u32) -> *mut wl_proxy {
+    wl_proxy_marshal_flags(registry, WL_REGISTRY_BIND, interface, version, 0, name, interface.name, version, null_mut::<c_void>())
+}
+
+/**********
+* REGISTRY *
+**********/
+#[repr(C)]
+pub(super) struct KWaylandRegistryListener {
+    global : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, *const c_char, u32),
+    global_remove : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+}
+
+pub(super) static KWAYLAND_REGISTRY_LISTENER : KWaylandRegistryListener = KWaylandRegistryListener { global: registry_global, global_remove: registry_global_remove };
+
+unsafe extern "C" fn registry_global(data : *mut c_void, registry : *mut wl_proxy, name : u32, interface : *const c_char, version : u32) {
+    let connection = get_connection(data);
+
+    match CStr::from_ptr(interface).to_bytes() {
+        b"wl_compositor" => connection.compositor = wl_registry_bind(registry, name, &wl_compositor_interface, version.min(4)),
+        b"wl_shm" => connection.shm = wl_registry_bind(registry, name, &wl_shm_interface, 1),
+        b"xdg_wm_base" => {
+            connection.wm_base = wl_registry_bind(registry, name, &xdg_wm_base_interface, 1);
+            wl_proxy_add_listener(connection.wm_base, &KWAYLAND_WM_BASE_LISTENER as *const _ as *const c_void, data);
+        },
+        // Only the first seat is used.
+        b"wl_seat" if connection.seat.is_null() => {
+            connection.seat = wl_registry_bind(registry, name, &wl_seat_interface, version.min(5));
+            wl_proxy_add_listener(connection.seat, &KWAYLAND_SEAT_LISTENER as *const _ as *const c_void, data);
+            connection.create_tablet_seat();
+        },
+        b"zwp_pointer_constraints_v1" => connection.pointer_constraints = wl_registry_bind(registry, name, &zwp_pointer_constraints_v1_interface, 1),
+        b"zwp_relative_pointer_manager_v1" => connection.relative_pointer_manager = wl_registry_bind(registry, name, &zwp_relative_pointer_manager_v1_interface, 1),
+        b"wp_cursor_shape_manager_v1" => connection.cursor_shape_manager = wl_registry_bind(registry, name, &wp_cursor_shape_manager_v1_interface, 1),
+        b"zwp_tablet_manager_v2" => {
+            connection.tablet_manager = wl_registry_bind(registry, name, &zwp_tablet_manager_v2_interface, 1);
+            connection.create_tablet_seat();
+        },
+        b"wl_output" => {
+            let mut output = Box::new(KWaylandOutput::new(name, wl_registry_bind(registry, name, &wl_output_interface, version.min(4))));
+            wl_proxy_add_listener(output.proxy, &KWAYLAND_OUTPUT_LISTENER as *const _ as *const c_void, output.as_mut() as *mut KWaylandOutput as *mut c_void);
+            connection.outputs.push(output);
+        },
+        _ => {},
+    }
+}
+
+unsafe extern "C" fn registry_global_remove(data : *mut c_void, _ : *mut wl_proxy, name : u32) {
+    let connection = get_connection(data);
+
+    if let Some(index) = connection.outputs.iter().position(|output| output.name == name) {
+        KLinuxDisplayServerWaylandConnection::release_output(connection.outputs.remove(index).proxy);
+    }
+}
+
+/*************
+* XDG_WM_BASE *
+*************/
+#[repr(C)]
+pub(super) struct KWaylandWmBaseListener {
+    ping : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+}
+
+pub(super) static KWAYLAND_WM_BASE_LISTENER : KWaylandWmBaseListener = KWaylandWmBaseListener { ping: wm_base_ping };
+
+unsafe extern "C" fn wm_base_ping(_ : *mut c_void, wm_base : *mut wl_proxy, serial : u32) {
+    // Compositor checks that client is responsive.
+    wl_proxy_marshal_flags(wm_base, XDG_WM_BASE_PONG, std::ptr::null(), wl_proxy_get_version(wm_base), 0, serial);
+}
+
+/*************
+* XDG_SURFACE *
+*************/
+#[repr(C)]
+pub(super) struct KWaylandXdgSurfaceListener {
+    configure : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+}
+
+pub(super) static KWAYLAND_XDG_SURFACE_LISTENER : KWaylandXdgSurfaceListener = KWaylandXdgSurfaceListener { configure: xdg_surface_configure };
+
+unsafe extern "C" fn xdg_surface_configure(data : *mut c_void, xdg_surface : *mut wl_proxy, serial : u32) {
+    let property = get_property(data);
+    wl_proxy_marshal_flags(xdg_surface, XDG_SURFACE_ACK_CONFIGURE, std::ptr::null(), wl_proxy_get_version(xdg_surface), 0, serial);
+    property.apply_configure();
+}
+
+/**************
+* XDG_TOPLEVEL *
+**************/
+#[repr(C)]
+pub(super) struct KWaylandToplevelListener {
+    configure : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32, i32, *mut wl_array),
+    close : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+}
+
+pub(super) static KWAYLAND_TOPLEVEL_LISTENER : KWaylandToplevelListener = KWaylandToplevelListener { configure: toplevel_configure, close: toplevel_close };
+
+unsafe extern "C" fn toplevel_configure(data : *mut c_void, _ : *mut wl_proxy, width : i32, height : i32, states : *mut wl_array) {
+    let property = get_property(data);
+    let states = std::slice::from_raw_parts((*states).data as *const u32, (*states).size / std::mem::size_of::<u32>());
+
+    // Size and states are applied on xdg_surface configure.
+    property.pending_size = (width, height);
+    property.pending_maximized = states.contains(&XDG_TOPLEVEL_STATE_MAXIMIZED);
+    property.pending_fullscreen = states.contains(&XDG_TOPLEVEL_STATE_FULLSCREEN);
+}
+
+unsafe extern "C" fn toplevel_close(data : *mut c_void, _ : *mut wl_proxy) {
+    get_property(data).events.push_back(KEvent::Window(KEventWindow::Close()));
+}
+
+/*********
+* WL_SEAT *
+*********/
+#[repr(C)]
+pub(super) struct KWaylandSeatListener {
+    capabilities : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    name : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *const c_char),
+}
+
+pub(super) static KWAYLAND_SEAT_LISTENER : KWaylandSeatListener = KWaylandSeatListener { capabilities: seat_capabilities, name: seat_name };
+
+unsafe extern "C" fn seat_capabilities(data : *mut c_void, seat : *mut wl_proxy, capabilities : u32) {
+    let connection = get_connection(data);
+    let version = wl_proxy_get_version(seat);
+
+    if capabilities & WL_SEAT_CAPABILITY_POINTER != 0 && connection.pointer.is_null() {
+        connection.pointer = wl_proxy_marshal_flags(seat, WL_SEAT_GET_POINTER, &wl_pointer_interface, version, 0, null_mut::<c_void>());
+        wl_proxy_add_listener(connection.pointer, &KWAYLAND_POINTER_LISTENER as *const _ as *const c_void, data);
+
+        // Constraints requested by windows before pointer was available.
+        connection.update_pointer_constraints();
+    } else if capabilities & WL_SEAT_CAPABILITY_POINTER == 0 && !connection.pointer.is_null() {
+        connection.destroy_pointer_objects();
+        KLinuxDisplayServerWaylandConnection::release_device(connection.pointer, WL_POINTER_RELEASE);
+        connection.pointer = null_mut();
+    }
+
+    if capabilities & WL_SEAT_CAPABILITY_KEYBOARD != 0 && connection.keyboard.is_null() {
+        connection.keyboard = wl_proxy_marshal_flags(seat, WL_SEAT_GET_KEYBOARD, &wl_keyboard_interface, version, 0, null_mut::<c_void>());
+        wl_proxy_add_listener(connection.keyboard, &KWAYLAND_KEYBOARD_LISTENER as *const _ as *const c_void, data);
+    } else if capabilities & WL_SEAT_CAPABILITY_KEYBOARD == 0 && !connection.keyboard.is_null() {
+        KLinuxDisplayServerWaylandConnection::release_device(connection.keyboard, WL_KEYBOARD_RELEASE);
+        connection.keyboard = null_mut();
+        connection.repeat_key = None;
+    }
+}
+
+unsafe extern "C" fn seat_name(_ : *mut c_void, _ : *mut wl_proxy, _ : *const c_char) {}
+
+/************
+* WL_POINTER *
+************/
+#[repr(C)]
+pub(super) struct KWaylandPointerListener {
+    enter : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, *mut wl_proxy, i32, i32),
+    leave : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, *mut wl_proxy),
+    motion : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, i32, i32),
+    button : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32, u32, u32),
+    axis : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32, i32),
+    frame : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    axis_source : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    axis_stop : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32),
+    axis_discrete : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, i32),
+}
+
+pub(super) static KWAYLAND_POINTER_LISTENER : KWaylandPointerListener = KWaylandPointerListener { enter: pointer_enter, leave: pointer_leave,
+    motion: pointer_motion, button: pointer_button, axis: pointer_axis, frame: pointer_frame, axis_source: pointer_axis_source,
+    axis_stop: pointer_axis_stop, axis_discrete: pointer_axis_discrete };
+
+unsafe extern "C" fn pointer_enter(data : *mut c_void, _ : *mut wl_proxy, serial : u32, surface : *mut wl_proxy, x : i32, y : i32) {
+    let connection = get_connection(data);
+
+    // Pointer events go to the entered window until leave. Surfaces of other clients or cursor are ignored.
+    connection.pointer_focus = connection.get_window(surface);
+
+    // Serial is needed to change cursor image. Image is undefined until set.
+    connection.pointer_serial = serial;
+    connection.set_cursor(true);
+    connection.pointer_position = (wl_fixed_to_f64(x) as i32, wl_fixed_to_f64(y) as i32);
+    connection.push_pointer_event(KEvent::Window(KEventWindow::CursorEnter()));
+    connection.push_pointer_event(KEvent::Mouse(KEventMouse::Moved(connection.pointer_position)));
+}
+
+unsafe extern "C" fn pointer_leave(data : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : *mut wl_proxy) {
+    let connection = get_connection(data);
+    connection.push_pointer_event(KEvent::Window(KEventWindow::CursorLeave()));
+    connection.pointer_focus = null_mut();
+}
+
+unsafe extern "C" fn pointer_motion(data : *mut c_void, _ : *mut wl_proxy, _ : u32, x : i32, y : i32) {
+    let connection = get_connection(data);
+    connection.pointer_position = (wl_fixed_to_f64(x) as i32, wl_fixed_to_f64(y) as i32);
+    connection.push_pointer_event(KEvent::Mouse(KEventMouse::Moved(connection.pointer_position)));
+}
+
+unsafe extern "C" fn pointer_button(data : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : u32, button : u32, state : u32) {
+    let connection = get_connection(data);
+
+    // Linux evdev button codes.
+    let button = match button {
+        0x110 => KMouseButton::Left,
+        0x111 => KMouseButton::Right,
+        0x112 => KMouseButton::Middle,
+        0x113 => KMouseButton::Back,
+        0x114 => KMouseButton::Forward,
+        _ => KMouseButton::Other(button as u8),
+    };
+
+    connection.push_pointer_event(KEvent::Mouse(if state == WL_PRESSED {
+        KEventMouse::ButtonDown(button, connection.pointer_position)
+    } else {
+        KEventMouse::ButtonUp(button, connection.pointer_position)
+    }));
+}
+
+unsafe extern "C" fn pointer_axis(data : *mut c_void, pointer : *mut wl_proxy, _ : u32, axis : u32, value : i32) {
+    let connection = get_connection(data);
+
+    if axis == WL_POINTER_AXIS_VERTICAL_SCROLL {
+        connection.wheel_distance.1 += wl_fixed_to_f64(value);
+    } else {
+        connection.wheel_distance.0 += wl_fixed_to_f64(value);
+    }
+
+    // Pointer without frame event (version < 5) sends axis alone.
+    if wl_proxy_get_version(pointer) < 5 {
+        pointer_frame(data, pointer);
+    }
+}
+
+unsafe extern "C" fn pointer_frame(data : *mut c_void, _ : *mut wl_proxy) {
+    let connection = get_connection(data);
+
+    // Discrete steps are preferred. Otherwise, distance is converted to steps and remainder is kept.
+    let mut steps = connection.wheel_steps;
+    if steps == (0, 0) {
+        steps = ((connection.wheel_distance.0 / KWAYLAND_WHEEL_STEP) as i32, (connection.wheel_distance.1 / KWAYLAND_WHEEL_STEP) as i32);
+        connection.wheel_distance.0 -= steps.0 as f64 * KWAYLAND_WHEEL_STEP;
+        connection.wheel_distance.1 -= steps.1 as f64 * KWAYLAND_WHEEL_STEP;
+    } else {
+        connection.wheel_distance = (0.0, 0.0);
+    }
+    connection.wheel_steps = (0, 0);
+
+    // Wayland vertical axis is positive toward user.
+    if steps != (0, 0) {
+        connection.push_pointer_event(KEvent::Mouse(KEventMouse::Wheel(steps.0, -steps.1)));
+    }
+}
+
+unsafe extern "C" fn pointer_axis_source(_ : *mut c_void, _ : *mut wl_proxy, _ : u32) {}
+
+unsafe extern "C" fn pointer_axis_stop(_ : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : u32) {}
+
+unsafe extern "C" fn pointer_axis_discrete(data : *mut c_void, _ : *mut wl_proxy, axis : u32, discrete : i32) {
+    let connection = get_connection(data);
+
+    if axis == WL_POINTER_AXIS_VERTICAL_SCROLL {
+        connection.wheel_steps.1 += discrete;
+    } else {
+        connection.wheel_steps.0 += discrete;
+    }
+}
+
+/**********************
+* ZWP_RELATIVE_POINTER *
+**********************/
+#[repr(C)]
+pub(super) struct KWaylandRelativePointerListener {
+    relative_motion : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32, i32, i32, i32, i32),
+}
+
+pub(super) static KWAYLAND_RELATIVE_POINTER_LISTENER : KWaylandRelativePointerListener = KWaylandRelativePointerListener { relative_motion: relative_pointer_motion };
+
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn relative_pointer_motion(data : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : u32, dx : i32, dy : i32, dx_unaccel : i32, dy_unaccel : i32) {
+    let property = get_property(data);
+
+    // Relative motion is given for pointer, whatever window it's in. Only the window under pointer receives it.
+    if !std::ptr::eq((*property.connection).pointer_focus, property) {
+        return;
+    }
+
+    // Relative pointer only exists in acceleration mode. Moved gives accelerated motion in whole pixels, keeping fractions for next motion.
+    let motion = (property.relative_remainder.0 + wl_fixed_to_f64(dx), property.relative_remainder.1 + wl_fixed_to_f64(dy));
+    let pixels = (motion.0.trunc() as i32, motion.1.trunc() as i32);
+    property.relative_remainder = (motion.0 - pixels.0 as f64, motion.1 - pixels.1 as f64);
+
+    if pixels != (0, 0) {
+        property.events.push_back(KEvent::Mouse(KEventMouse::Moved(pixels)));
+    }
+    property.events.push_back(KEvent::Mouse(KEventMouse::RawMotion(wl_fixed_to_f64(dx_unaccel), wl_fixed_to_f64(dy_unaccel))));
+}
+
+/*************
+* WL_KEYBOARD *
+*************/
+#[repr(C)]
+pub(super) struct KWaylandKeyboardListener {
+    keymap : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, i32, u32),
+    enter : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, *mut wl_proxy, *mut wl_array),
+    leave : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, *mut wl_proxy),
+    key : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32, u32, u32),
+    modifiers : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32, u32, u32, u32),
+    repeat_info : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32, i32),
+}
+
+pub(super) static KWAYLAND_KEYBOARD_LISTENER : KWaylandKeyboardListener = KWaylandKeyboardListener { keymap: keyboard_keymap, enter: keyboard_enter,
+    leave: keyboard_leave, key: keyboard_key, modifiers: keyboard_modifiers, repeat_info: keyboard_repeat_info };
+
+unsafe extern "C" fn keyboard_keymap(data : *mut c_void, _ : *mut wl_proxy, format : u32, fd : i32, size : u32) {
+    let connection = get_connection(data);
+
+    // File descriptor is closed when file is dropped.
+    let file = File::from_raw_fd(fd);
+
+    if format == WL_KEYBOARD_KEYMAP_FORMAT_XKB_V1 {
+        let mut keymap = vec![0u8; size as usize];
+        if file.read_exact_at(&mut keymap, 0).is_ok() {
+            // Keymap string must be null terminated.
+            if keymap.last() != Some(&0) {
+                keymap.push(0);
+            }
+            connection.set_keymap(keymap.as_ptr() as *const c_char);
+        }
+    }
+}
+
+unsafe extern "C" fn keyboard_enter(data : *mut c_void, _ : *mut wl_proxy, _ : u32, surface : *mut wl_proxy, _ : *mut wl_array) {
+    let connection = get_connection(data);
+
+    // Keyboard events go to the focused window until leave.
+    connection.keyboard_focus = connection.get_window(surface);
+    connection.push_keyboard_event(KEvent::Window(KEventWindow::Focus()));
+}
+
+unsafe extern "C" fn keyboard_leave(data : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : *mut wl_proxy) {
+    let connection = get_connection(data);
+
+    // Keys held are no longer received.
+    connection.repeat_key = None;
+    connection.push_keyboard_event(KEvent::Window(KEventWindow::Blur()));
+    connection.keyboard_focus = null_mut();
+}
+
+unsafe extern "C" fn keyboard_key(data : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : u32, key : u32, state : u32) {
+    let connection = get_connection(data);
+
+    if state == WL_PRESSED {
+        connection.push_key_event(key, true, false);
+
+        // Wayland doesn't repeat keys. Repeat is done by client according to repeat info.
+        if connection.repeat_rate > 0 && !connection.xkb_keymap.is_null() && xkb_keymap_key_repeats(connection.xkb_keymap, key + XKB_EVDEV_OFFSET) != 0 {
+            connection.repeat_key = Some((key, Instant::now() + Duration::from_millis(connection.repeat_delay as u64)));
+        }
+    } else {
+        if matches!(connection.repeat_key, Some((repeat, _)) if repeat == key) {
+            connection.repeat_key = None;
+        }
+        connection.push_key_event(key, false, false);
+    }
+}
+
+unsafe extern "C" fn keyboard_modifiers(data : *mut c_void, _ : *mut wl_proxy, _ : u32, depressed : u32, latched : u32, locked : u32, group : u32) {
+    let connection = get_connection(data);
+
+    if !connection.xkb_state.is_null() {
+        xkb_state_update_mask(connection.xkb_state, depressed, latched, locked, 0, 0, group);
+        connection.update_modifiers();
+    }
+}
+
+unsafe extern "C" fn keyboard_repeat_info(data : *mut c_void, _ : *mut wl_proxy, rate : i32, delay : i32) {
+    let connection = get_connection(data);
+
+    // Rate of 0 disables repeat.
+    connection.repeat_rate = rate.max(0);
+    connection.repeat_delay = delay.max(0);
+}
@@ -1,12 +1,11 @@
-use std::panic::catch_unwind;
+use std::{panic::catch_unwind, collections::VecDeque, ffi::CString, fs::File, os::{raw::{c_char, c_void}, unix::{fs::FileExt, io::FromRawFd}}, ptr::{null, null_mut}, time::{Instant, Duration}};
 
-use crate::kleio::display::{KWindow, event::KEvent, KWindowFullscreenMode};
-
-use self::bind::{wl_display_connect, wl_display_disconnect};
-
-use super::server::{Display, Window};
+use crate::{kleio::display::{KWindow, KCursorMode, event::{KEvent, KEventWindow, KEventKeyboard, KKeyInput, KKeyModifiers, KTextInput, KTEXT_INPUT_MAX}, KWindowFullscreenMode},
+    error::{OlympusError, KWindowError}};
 
+use self::{bind::*, protocol::*, listener::*, screen::KWaylandOutput, tablet::{KWaylandTabletTool, KWaylandTabletPad, KWAYLAND_TABLET_SEAT_LISTENER}};
 
+use super::keyboard::{get_evdev_scancode, get_keysym_key};
 
 /// Waylind C function binds
 #[allow(unused)]                    // Remove unused variable notification
@@ -14,91 +13,931 @@ use super::server::{Display, Window};
 #[allow(non_camel_case_types)]    // Imported C global aren't formatted according to convention.
 pub mod bind;
 
+/// Wayland protocols interfaces not provided by libwayland-client (ie. xdg-shell).
+#[allow(unused)]                    // Remove unused variable notification
+#[allow(non_upper_case_globals)]    // Interfaces are named like C generated code.
+pub mod protocol;
+
+/// Wayland events listeners
+pub mod listener;
+
 /// Contains Wayland screen fetch function
 pub mod screen;
 
+/// Wayland tablet tools (ie. pen) giving pen events, with tablets and pads
+pub mod tablet;
+
+/// Size of the arrow cursor drawn when compositor doesn't provide cursor shapes.
+const KWAYLAND_ARROW_SIZE : (u32, u32) = (11, 16);
+
+/// Wayland display server connection, shared by the windows created on it.
+///
+/// Connection is boxed since its address is given to listeners. Input is routed to the window having pointer or keyboard focus.
+pub struct KLinuxDisplayServerWaylandConnection {
+    /// Display connection.
+    pub(crate) display : *mut wl_display,
+
+    // Globals bound from registry.
+    pub(crate) registry : *mut wl_proxy,
+    pub(crate) compositor : *mut wl_proxy,
+    pub(crate) shm : *mut wl_proxy,
+    pub(crate) wm_base : *mut wl_proxy,
+    pub(crate) seat : *mut wl_proxy,
+
+    // Optional globals. Null if compositor doesn't support the protocol.
+    pub(crate) pointer_constraints : *mut wl_proxy,
+    pub(crate) relative_pointer_manager : *mut wl_proxy,
+    pub(crate) cursor_shape_manager : *mut wl_proxy,
+    pub(crate) tablet_manager : *mut wl_proxy,
+
+    /// Outputs (screens) bound from registry. Boxed since their address is given to listeners.
+    #[allow(clippy::vec_box)]
+    pub(crate) outputs : Vec<Box<KWaylandOutput>>,
+
+    // Seat devices. Null if seat doesn't have the capability.
+    pub(crate) pointer : *mut wl_proxy,
+    pub(crate) keyboard : *mut wl_proxy,
+
+    /// Tablets, tools and pads of seat. Null if compositor doesn't support tablets.
+    pub(crate) tablet_seat : *mut wl_proxy,
+
+    /// Tablets of seat, destroyed when removed.
+    pub(crate) tablets : Vec<*mut wl_proxy>,
+
+    /// Tools of tablets. Boxed since their address is given to listeners.
+    #[allow(clippy::vec_box)]
+    pub(crate) tablet_tools : Vec<Box<KWaylandTabletTool>>,
+
+    /// Pads of tablets. Boxed since their address is given to listeners.
+    #[allow(clippy::vec_box)]
+    pub(crate) tablet_pads : Vec<Box<KWaylandTabletPad>>,
+
+    /// Cursor shape of pointer. Null until needed.
+    pub(crate) cursor_shape_device : *mut wl_proxy,
+
+    // Arrow cursor used without cursor shapes. Null until needed.
+    pub(crate) cursor_surface : *mut wl_proxy,
+    pub(crate) cursor_buffer : *mut wl_proxy,
+
+    /// Windows created on this connection.
+    pub(crate) windows : Vec<*mut KLinuxDisplayServerWaylandProperty>,
+
+    /// Window under pointer. Null if pointer is outside windows.
+    pub(crate) pointer_focus : *mut KLinuxDisplayServerWaylandProperty,
+
+    /// Window receiving keyboard input. Null if no window has focus.
+    pub(crate) keyboard_focus : *mut KLinuxDisplayServerWaylandProperty,
+
+    /// Serial of the last pointer enter, needed to set cursor.
+    pub(crate) pointer_serial : u32,
+
+    /// Last pointer position in surface.
+    pub(crate) pointer_position : (i32, i32),
+
+    /// Wheel discrete steps accumulated until pointer frame.
+    pub(crate) wheel_steps : (i32, i32),
+
+    /// Wheel distance accumulated for devices without discrete steps.
+    pub(crate) wheel_distance : (f64, f64),
+
+    // Keyboard layout. Null until keymap is received.
+    pub(crate) xkb_context : *mut xkb_context,
+    pub(crate) xkb_keymap : *mut xkb_keymap,
+    pub(crate) xkb_state : *mut xkb_state,
+
+    /// Keyboard modifiers currently active.
+    pub(crate) modifiers : KKeyModifiers,
+
+    /// Keys repeated per second. 0 disables repeat.
+    pub(crate) repeat_rate : i32,
+
+    /// Delay in milliseconds before a held key repeats.
+    pub(crate) repeat_delay : i32,
+
+    /// Evdev code of the key held with the instant of its next repeat.
+    pub(crate) repeat_key : Option<(u32, Instant)>,
+}
+
+/// Wayland display server window properties.
+///
+/// Properties are boxed since their address is given to listeners.
+pub struct KLinuxDisplayServerWaylandProperty {
+    /// Connection the window was created on.
+    pub(crate) connection : *mut KLinuxDisplayServerWaylandConnection,
+
+    // Window surface roles and content.
+    pub(crate) surface : *mut wl_proxy,
+    pub(crate) xdg_surface : *mut wl_proxy,
+    pub(crate) toplevel : *mut wl_proxy,
+    pub(crate) buffer : *mut wl_proxy,
+
+    // Pointer constraints and relative motion of window. Null when inactive.
+    pub(crate) confined_pointer : *mut wl_proxy,
+    pub(crate) locked_pointer : *mut wl_proxy,
+    pub(crate) relative_pointer : *mut wl_proxy,
+
+    /// Pointer confined to window requested.
+    pub(crate) cursor_confined : bool,
+
+    /// Pointer locked for acceleration requested. Lock replaces confinement since a surface can only have one constraint.
+    pub(crate) cursor_locked : bool,
+
+    /// Last cursor position hint given to compositor while locked.
+    pub(crate) cursor_hint : Option<(i32, i32)>,
+
+    /// Fraction of relative motion not yet given with [KEventMouse::Moved](crate::kleio::display::event::KEventMouse::Moved).
+    pub(crate) relative_remainder : (f64, f64),
+
+    /// Events received by listeners, waiting to be polled.
+    pub(crate) events : VecDeque<KEvent>,
+
+    /// Size of the surface buffer.
+    pub(crate) size : (u32, u32),
+
+    /// Size for restoring window. None if window is neither maximized nor fullscreen.
+    pub(crate) restoration_size : Option<(u32, u32)>,
+
+    /// True once the first configure is received. Surface can't be attached before.
+    pub(crate) configured : bool,
+
+    // States applied with last configure.
+    pub(crate) maximized : bool,
+    pub(crate) fullscreen : bool,
+
+    // Toplevel configure applied on next surface configure. Size of 0 lets client choose.
+    pub(crate) pending_size : (i32, i32),
+    pub(crate) pending_maximized : bool,
+    pub(crate) pending_fullscreen : bool,
+
+    /// C-compatible string for window title
+    pub(crate) title : CString,
+}
+
+impl KLinuxDisplayServerWaylandConnection {
+    /// Create a display connection, without any global bound.
+    fn new(display : *mut wl_display) -> KLinuxDisplayServerWaylandConnection {
+        KLinuxDisplayServerWaylandConnection { display, registry: null_mut(), compositor: null_mut(), shm: null_mut(), wm_base: null_mut(), seat: null_mut(),
+            pointer_constraints: null_mut(), relative_pointer_manager: null_mut(), cursor_shape_manager: null_mut(), tablet_manager: null_mut(),
+            outputs: Vec::new(), pointer: null_mut(), keyboard: null_mut(), tablet_seat: null_mut(), tablets: Vec::new(), tablet_tools: Vec::new(),
+            tablet_pads: Vec::new(), cursor_shape_device: null_mut(), cursor_surface: null_mut(), cursor_buffer: null_mut(),
+            windows: Vec::new(), pointer_focus: null_mut(), keyboard_focus: null_mut(), pointer_serial: 0, pointer_position: (0, 0),
+            wheel_steps: (0, 0), wheel_distance: (0.0, 0.0), xkb_context: null_mut(), xkb_keymap: null_mut(), xkb_state: null_mut(),
+            modifiers: KKeyModifiers::NONE, repeat_rate: 0, repeat_delay: 0, repeat_key: None }
+    }
+
+    /// Connect to Wayland display server and bind globals from registry.
+    ///
+    /// # Error(s)
+    /// Returns `OlympusError::KWindow(KWindowError::NoDisplayServer)` if connection failed.
+    pub(crate) unsafe fn connect() -> Result<Box<KLinuxDisplayServerWaylandConnection>, OlympusError> {
+        let display = wl_display_connect(null());
+        if display.is_null() {
+            return Err(OlympusError::KWindow(KWindowError::NoDisplayServer));
+        }
+
+        let mut connection = Box::new(KLinuxDisplayServerWaylandConnection::new(display));
+        let data = connection.as_mut() as *mut KLinuxDisplayServerWaylandConnection as *mut c_void;
+
+        connection.registry = wl_proxy_marshal_flags(display as *mut wl_proxy, WL_DISPLAY_GET_REGISTRY, &wl_registry_interface,
+            wl_proxy_get_version(display as *mut wl_proxy), 0, null_mut::<c_void>());
+        wl_proxy_add_listener(connection.registry, &KWAYLAND_REGISTRY_LISTENER as *const _ as *const c_void, data);
+
+        // Globals are announced on first roundtrip. Connection is closed when dropped.
+        if wl_display_roundtrip(display) < 0 {
+            return Err(OlympusError::KWindow(KWindowError::NoDisplayServer));
+        }
+
+        Ok(connection)
+    }
+
+    /// Release an output proxy.
+    pub(crate) unsafe fn release_output(output : *mut wl_proxy) {
+        if wl_proxy_get_version(output) >= 3 {
+            wl_proxy_marshal_flags(output, WL_OUTPUT_RELEASE, null(), wl_proxy_get_version(output), WL_MARSHAL_FLAG_DESTROY);
+        } else {
+            wl_proxy_destroy(output);
+        }
+    }
+
+    /// Release a seat device (pointer or keyboard) with its release opcode.
+    pub(crate) unsafe fn release_device(device : *mut wl_proxy, opcode : u32) {
+        if wl_proxy_get_version(device) >= 3 {
+            wl_proxy_marshal_flags(device, opcode, null(), wl_proxy_get_version(device), WL_MARSHAL_FLAG_DESTROY);
+        } else {
+            wl_proxy_destroy(device);
+        }
+    }
+
+    /// Returns the window of a surface. Null if surface isn't a window of this connection.
+    pub(crate) unsafe fn get_window(&self, surface : *mut wl_proxy) -> *mut KLinuxDisplayServerWaylandProperty {
+        self.windows.iter().find(|window| (***window).surface == surface).copied().unwrap_or(null_mut())
+    }
+
+    /// Queue an event to the window under pointer. Event is dropped if pointer is outside windows.
+    pub(crate) unsafe fn push_pointer_event(&mut self, event : KEvent) {
+        if !self.pointer_focus.is_null() {
+            (*self.pointer_focus).events.push_back(event);
+        }
+    }
+
+    /// Queue an event to the window having keyboard focus. Event is dropped if no window has focus.
+    pub(crate) unsafe fn push_keyboard_event(&mut self, event : KEvent) {
+        if !self.keyboard_focus.is_null() {
+            (*self.keyboard_focus).events.push_back(event);
+        }
+    }
+
+    /// Create a shared memory buffer filled with pixels, or zero filled (black) if None. Returns null if memory couldn't be allocated.
+    unsafe fn create_buffer(&self, size : (u32, u32), format : u32, pixels : Option<&[u32]>) -> *mut wl_proxy {
+        let stride = size.0 as i32 * 4;
+        let length = stride * size.1 as i32;
+
+        let fd = memfd_create(c"olympus-kwindow".as_ptr(), MFD_CLOEXEC);
+        if fd < 0 {
+            return null_mut();
+        }
+
+        // Compositor keeps its own file descriptor. Ours is closed when file is dropped.
+        let file = File::from_raw_fd(fd);
+        if file.set_len(length as u64).is_err() {
+            return null_mut();
+        }
+        if let Some(pixels) = pixels {
+            let bytes : Vec<u8> = pixels.iter().flat_map(|pixel| pixel.to_le_bytes()).collect();
+            if file.write_all_at(&bytes, 0).is_err() {
+                return null_mut();
+            }
+        }
+
+        let pool = wl_proxy_marshal_flags(self.shm, WL_SHM_CREATE_POOL, &wl_shm_pool_interface, wl_proxy_get_version(self.shm), 0,
+            null_mut::<c_void>(), fd, length);
+        let buffer = wl_proxy_marshal_flags(pool, WL_SHM_POOL_CREATE_BUFFER, &wl_buffer_interface, wl_proxy_get_version(pool), 0,
+            null_mut::<c_void>(), 0i32, size.0 as i32, size.1 as i32, stride, format);
+        wl_proxy_marshal_flags(pool, WL_SHM_POOL_DESTROY, null(), wl_proxy_get_version(pool), WL_MARSHAL_FLAG_DESTROY);
+
+        buffer
+    }
+
+    /// Set the cursor image of pointer from last enter. Hidden cursor has no image.
+    pub(crate) unsafe fn set_cursor(&mut self, visible : bool) {
+        if self.pointer.is_null() {
+            return;
+        }
+        let version = wl_proxy_get_version(self.pointer);
+
+        if !visible {
+            wl_proxy_marshal_flags(self.pointer, WL_POINTER_SET_CURSOR, null(), version, 0, self.pointer_serial, null_mut::<wl_proxy>(), 0i32, 0i32);
+            return;
+        }
+
+        // Cursor shape gives the default cursor of compositor theme.
+        if self.cursor_shape_device.is_null() && !self.cursor_shape_manager.is_null() {
+            self.cursor_shape_device = wl_proxy_marshal_flags(self.cursor_shape_manager, WP_CURSOR_SHAPE_MANAGER_V1_GET_POINTER,
+                &wp_cursor_shape_device_v1_interface, 1, 0, null_mut::<c_void>(), self.pointer);
+        }
+        if !self.cursor_shape_device.is_null() {
+            wl_proxy_marshal_flags(self.cursor_shape_device, WP_CURSOR_SHAPE_DEVICE_V1_SET_SHAPE, null(), 1, 0, self.pointer_serial,
+                WP_CURSOR_SHAPE_DEVICE_V1_SHAPE_DEFAULT);
+            return;
+        }
+
+        // Otherwise, client must draw its own cursor.
+        if self.cursor_surface.is_null() {
+            self.cursor_buffer = self.create_buffer(KWAYLAND_ARROW_SIZE, WL_SHM_FORMAT_ARGB8888, Some(&get_arrow_pixels()));
+            if self.cursor_buffer.is_null() {
+                return;
+            }
+            self.cursor_surface = wl_proxy_marshal_flags(self.compositor, WL_COMPOSITOR_CREATE_SURFACE, &wl_surface_interface,
+                wl_proxy_get_version(self.compositor), 0, null_mut::<c_void>());
+            let surface_version = wl_proxy_get_version(self.cursor_surface);
+            wl_proxy_marshal_flags(self.cursor_surface, WL_SURFACE_ATTACH, null(), surface_version, 0, self.cursor_buffer, 0i32, 0i32);
+            wl_proxy_marshal_flags(self.cursor_surface, WL_SURFACE_DAMAGE, null(), surface_version, 0, 0i32, 0i32,
+                KWAYLAND_ARROW_SIZE.0 as i32, KWAYLAND_ARROW_SIZE.1 as i32);
+            wl_proxy_marshal_flags(self.cursor_surface, WL_SURFACE_COMMIT, null(), surface_version, 0);
+        }
+        wl_proxy_marshal_flags(self.pointer, WL_POINTER_SET_CURSOR, null(), version, 0, self.pointer_serial, self.cursor_surface, 0i32, 0i32);
+    }
+
+    /// Create pointer constraints requested by windows. Called once pointer is available.
+    pub(crate) unsafe fn update_pointer_constraints(&mut self) {
+        for window in self.windows.clone() {
+            (*window).update_pointer_constraints();
+        }
+    }
+
+    /// Destroy objects created from pointer, including constraints of windows. Must be called before releasing pointer.
+    pub(crate) unsafe fn destroy_pointer_objects(&mut self) {
+        for window in self.windows.clone() {
+            (*window).destroy_pointer_constraints();
+        }
+        if !self.cursor_shape_device.is_null() {
+            wl_proxy_marshal_flags(self.cursor_shape_device, WP_CURSOR_SHAPE_DEVICE_V1_DESTROY, null(), 1, WL_MARSHAL_FLAG_DESTROY);
+            self.cursor_shape_device = null_mut();
+        }
+        self.pointer_focus = null_mut();
+    }
+
+    /// Get the tablet seat once both seat and tablet manager are bound.
+    pub(crate) unsafe fn create_tablet_seat(&mut self) {
+        if self.tablet_seat.is_null() && !self.seat.is_null() && !self.tablet_manager.is_null() {
+            self.tablet_seat = wl_proxy_marshal_flags(self.tablet_manager, ZWP_TABLET_MANAGER_V2_GET_TABLET_SEAT, &zwp_tablet_seat_v2_interface,
+                1, 0, null_mut::<c_void>(), self.seat);
+            wl_proxy_add_listener(self.tablet_seat, &KWAYLAND_TABLET_SEAT_LISTENER as *const _ as *const c_void,
+                self as *mut KLinuxDisplayServerWaylandConnection as *mut c_void);
+        }
+    }
+
+    /// Destroy tools, pads and tablets, then tablet seat.
+    pub(crate) unsafe fn destroy_tablet_objects(&mut self) {
+        for tool in self.tablet_tools.drain(..) {
+            wl_proxy_marshal_flags(tool.proxy, ZWP_TABLET_TOOL_V2_DESTROY, null(), 1, WL_MARSHAL_FLAG_DESTROY);
+        }
+        for mut pad in self.tablet_pads.drain(..) {
+            pad.destroy();
+        }
+        for tablet in self.tablets.drain(..) {
+            wl_proxy_marshal_flags(tablet, ZWP_TABLET_V2_DESTROY, null(), 1, WL_MARSHAL_FLAG_DESTROY);
+        }
+        if !self.tablet_seat.is_null() {
+            wl_proxy_marshal_flags(self.tablet_seat, ZWP_TABLET_SEAT_V2_DESTROY, null(), 1, WL_MARSHAL_FLAG_DESTROY);
+            self.tablet_seat = null_mut();
+        }
+    }
+
+    /// Replace keyboard layout with keymap string.
+    pub(crate) unsafe fn set_keymap(&mut self, keymap : *const c_char) {
+        if self.xkb_context.is_null() {
+            self.xkb_context = xkb_context_new(0);
+            if self.xkb_context.is_null() {
+                return;
+            }
+        }
+
+        let xkb_keymap = xkb_keymap_new_from_string(self.xkb_context, keymap, XKB_KEYMAP_FORMAT_TEXT_V1, 0);
+        if xkb_keymap.is_null() {
+            return;
+        }
+
+        if !self.xkb_state.is_null() {
+            xkb_state_unref(self.xkb_state);
+        }
+        if !self.xkb_keymap.is_null() {
+            xkb_keymap_unref(self.xkb_keymap);
+        }
+        self.xkb_keymap = xkb_keymap;
+        self.xkb_state = xkb_state_new(xkb_keymap);
+    }
+
+    /// Update keyboard modifiers from xkb state.
+    pub(crate) unsafe fn update_modifiers(&mut self) {
+        let modifiers = [(c"Shift", KKeyModifiers::SHIFT), (c"Control", KKeyModifiers::CONTROL), (c"Mod1", KKeyModifiers::ALT),
+            (c"Mod4", KKeyModifiers::SUPER), (c"Lock", KKeyModifiers::CAPS_LOCK), (c"Mod2", KKeyModifiers::NUM_LOCK)];
+
+        self.modifiers = KKeyModifiers::NONE;
+        for (name, modifier) in modifiers {
+            if xkb_state_mod_name_is_active(self.xkb_state, name.as_ptr(), XKB_STATE_MODS_EFFECTIVE) > 0 {
+                self.modifiers |= modifier;
+            }
+        }
+    }
+
+    /// Queue the [KEventKeyboard] of an evdev key pressed or released to the focused window. Key pressed is followed by its text input if any.
+    pub(crate) unsafe fn push_key_event(&mut self, key : u32, pressed : bool, repeat : bool) {
+        let code = key + XKB_EVDEV_OFFSET;
+
+        // Logical key is the keysym without modifiers applied.
+        let mut keysym : u32 = 0;
+        if !self.xkb_keymap.is_null() {
+            let mut syms : *const u32 = null();
+            let layout = xkb_state_key_get_layout(self.xkb_state, code);
+            if xkb_keymap_key_get_syms_by_level(self.xkb_keymap, code, layout, 0, &mut syms) > 0 {
+                keysym = *syms;
+            }
+        }
+
+        let input = KKeyInput::new(get_evdev_scancode(key), get_keysym_key(keysym), self.modifiers, repeat);
+        if !pressed {
+            self.push_keyboard_event(KEvent::Keyboard(KEventKeyboard::KeyUp(input)));
+            return;
+        }
+        self.push_keyboard_event(KEvent::Keyboard(KEventKeyboard::KeyDown(input)));
+
+        if !self.xkb_state.is_null() {
+            let mut buffer = [0 as c_char; KTEXT_INPUT_MAX];
+            let length = xkb_state_key_get_utf8(self.xkb_state, code, buffer.as_mut_ptr(), buffer.len());
+            if length > 0 && (length as usize) < buffer.len() {
+                let bytes = std::slice::from_raw_parts(buffer.as_ptr() as *const u8, length as usize);
+                let text = String::from_utf8_lossy(bytes);
+                if !text.chars().any(char::is_control) {
+                    self.push_keyboard_event(KEvent::Keyboard(KEventKeyboard::TextInput(KTextInput::new(&text))));
+                }
+            }
+        }
+    }
+
+    /// Read and dispatch events from display server to windows without blocking, then repeat held key if due.
+    pub(crate) unsafe fn dispatch(connection : *mut KLinuxDisplayServerWaylandConnection) {
+        let display = (*connection).display;
+
+        // Events already queued must be dispatched before reading.
+        while wl_display_prepare_read(display) != 0 {
+            wl_display_dispatch_pending(display);
+        }
+        wl_display_flush(display);
+
+        let mut fd = pollfd { fd: wl_display_get_fd(display), events: POLLIN, revents: 0 };
+        if poll(&mut fd, 1, 0) > 0 {
+            wl_display_read_events(display);
+        } else {
+            wl_display_cancel_read(display);
+        }
+        wl_display_dispatch_pending(display);
+
+        let connection = &mut *connection;
+        if let Some((key, next)) = connection.repeat_key {
+            let now = Instant::now();
+            if now >= next {
+                connection.repeat_key = Some((key, now + Duration::from_millis(1000 / connection.repeat_rate.max(1) as u64)));
+                connection.push_key_event(key, true, true);
+            }
+        }
+    }
+}
+
+impl Drop for KLinuxDisplayServerWaylandConnection {
+    fn drop(&mut self) {
+        unsafe {
+            // Windows are dropped before their connection. Pointer and tablet objects are destroyed before pointer and seat.
+            self.destroy_pointer_objects();
+            self.destroy_tablet_objects();
+            for (proxy, opcode) in [(self.cursor_surface, WL_SURFACE_DESTROY), (self.cursor_buffer, WL_BUFFER_DESTROY), (self.wm_base, XDG_WM_BASE_DESTROY),
+                (self.pointer_constraints, ZWP_POINTER_CONSTRAINTS_V1_DESTROY), (self.relative_pointer_manager, ZWP_RELATIVE_POINTER_MANAGER_V1_DESTROY),
+                (self.cursor_shape_manager, WP_CURSOR_SHAPE_MANAGER_V1_DESTROY), (self.tablet_manager, ZWP_TABLET_MANAGER_V2_DESTROY)] {
+                if !proxy.is_null() {
+                    wl_proxy_marshal_flags(proxy, opcode, null(), wl_proxy_get_version(proxy), WL_MARSHAL_FLAG_DESTROY);
+                }
+            }
+            if !self.pointer.is_null() {
+                Self::release_device(self.pointer, WL_POINTER_RELEASE);
+            }
+            if !self.keyboard.is_null() {
+                Self::release_device(self.keyboard, WL_KEYBOARD_RELEASE);
+            }
+            for output in self.outputs.drain(..) {
+                Self::release_output(output.proxy);
+            }
+            for proxy in [self.seat, self.shm, self.compositor, self.registry] {
+                if !proxy.is_null() {
+                    wl_proxy_destroy(proxy);
+                }
+            }
+
+            // Keyboard layout.
+            if !self.xkb_state.is_null() {
+                xkb_state_unref(self.xkb_state);
+            }
+            if !self.xkb_keymap.is_null() {
+                xkb_keymap_unref(self.xkb_keymap);
+            }
+            if !self.xkb_context.is_null() {
+                xkb_context_unref(self.xkb_context);
+            }
+
+            // Send destroy requests before closing connection.
+            wl_display_flush(self.display);
+            wl_display_disconnect(self.display);
+        }
+    }
+}
+
+impl KLinuxDisplayServerWaylandProperty {
+    /// Create properties of a window on connection, without any surface.
+    fn new(connection : *mut KLinuxDisplayServerWaylandConnection) -> KLinuxDisplayServerWaylandProperty {
+        KLinuxDisplayServerWaylandProperty { connection, surface: null_mut(), xdg_surface: null_mut(), toplevel: null_mut(), buffer: null_mut(),
+            confined_pointer: null_mut(), locked_pointer: null_mut(), relative_pointer: null_mut(), cursor_confined: false, cursor_locked: false,
+            cursor_hint: None, relative_remainder: (0.0, 0.0), events: VecDeque::new(), size: (0, 0), restoration_size: None, configured: false,
+            maximized: false, fullscreen: false, pending_size: (0, 0), pending_maximized: false, pending_fullscreen: false, title: CString::default() }
+    }
+
+    /// Create a window surface with xdg-shell roles on connection and wait for the first configure.
+    ///
+    /// # Error(s)
+    /// Returns `OlympusError::KWindow(KWindowError::NotSupported)` if compositor lacks xdg-shell or shared memory.
+    unsafe fn create(connection : *mut KLinuxDisplayServerWaylandConnection, width : u32, height : u32) -> Result<Box<KLinuxDisplayServerWaylandProperty>, OlympusError> {
+        let (compositor, wm_base) = ((*connection).compositor, (*connection).wm_base);
+        if compositor.is_null() || (*connection).shm.is_null() || wm_base.is_null() {
+            return Err(OlympusError::KWindow(KWindowError::NotSupported));
+        }
+
+        // Window receives input once registered in connection. It is unregistered when dropped.
+        let mut window = Box::new(KLinuxDisplayServerWaylandProperty::new(connection));
+        let data = window.as_mut() as *mut KLinuxDisplayServerWaylandProperty;
+        (*connection).windows.push(data);
+        window.size = (width, height);
+
+        window.surface = wl_proxy_marshal_flags(compositor, WL_COMPOSITOR_CREATE_SURFACE, &wl_surface_interface,
+            wl_proxy_get_version(compositor), 0, null_mut::<c_void>());
+        window.xdg_surface = wl_proxy_marshal_flags(wm_base, XDG_WM_BASE_GET_XDG_SURFACE, &xdg_surface_interface,
+            wl_proxy_get_version(wm_base), 0, null_mut::<c_void>(), window.surface);
+        wl_proxy_add_listener(window.xdg_surface, &KWAYLAND_XDG_SURFACE_LISTENER as *const _ as *const c_void, data as *mut c_void);
+        window.toplevel = wl_proxy_marshal_flags(window.xdg_surface, XDG_SURFACE_GET_TOPLEVEL, &xdg_toplevel_interface,
+            wl_proxy_get_version(window.xdg_surface), 0, null_mut::<c_void>());
+        wl_proxy_add_listener(window.toplevel, &KWAYLAND_TOPLEVEL_LISTENER as *const _ as *const c_void, data as *mut c_void);
+        wl_proxy_marshal_flags(window.toplevel, XDG_TOPLEVEL_SET_TITLE, null(), wl_proxy_get_version(window.toplevel), 0, window.title.as_ptr());
+
+        // Commit without buffer asks compositor for the first configure.
+        wl_proxy_marshal_flags(window.surface, WL_SURFACE_COMMIT, null(), wl_proxy_get_version(window.surface), 0);
+        loop {
+            if wl_display_roundtrip((*connection).display) < 0 {
+                return Err(OlympusError::KWindow(KWindowError::NotSupported));
+            }
+
+            // Set by xdg_surface listener.
+            if window.configured {
+                return Ok(window);
+            }
+        }
+    }
+
+    /// Apply toplevel configure received, synthesizing [KEventWindow] of state changes.
+    pub(crate) unsafe fn apply_configure(&mut self) {
+        if !self.configured {
+            self.configured = true;
+            self.events.push_back(KEvent::Window(KEventWindow::Shown()));
+        }
+
+        // Keep normal size for restoration.
+        let was_normal = !self.maximized && !self.fullscreen;
+        if was_normal && (self.pending_maximized || self.pending_fullscreen) {
+            self.restoration_size = Some(self.size);
+        }
+        if self.pending_fullscreen && !self.fullscreen {
+            self.events.push_back(KEvent::Window(KEventWindow::Fullscreen()));
+        }
+        if self.pending_maximized && !self.maximized {
+            self.events.push_back(KEvent::Window(KEventWindow::Maximized()));
+        }
+        let restored = !was_normal && !self.pending_maximized && !self.pending_fullscreen;
+        if restored {
+            self.events.push_back(KEvent::Window(KEventWindow::Restored()));
+        }
+        self.maximized = self.pending_maximized;
+        self.fullscreen = self.pending_fullscreen;
+
+        // Size of 0 lets client choose its size.
+        let size = if self.pending_size.0 > 0 && self.pending_size.1 > 0 {
+            (self.pending_size.0 as u32, self.pending_size.1 as u32)
+        } else if restored {
+            self.restoration_size.take().unwrap_or(self.size)
+        } else {
+            self.size
+        };
+
+        if size != self.size || self.buffer.is_null() {
+            if size != self.size {
+                self.events.push_back(KEvent::Window(KEventWindow::Resized(size)));
+            }
+            self.attach_buffer(size);
+        }
+    }
+
+    /// Attach a new black buffer of size to the surface, replacing the previous one.
+    pub(crate) unsafe fn attach_buffer(&mut self, size : (u32, u32)) {
+        let buffer = (*self.connection).create_buffer(size, WL_SHM_FORMAT_XRGB8888, None);
+        if buffer.is_null() {
+            return;
+        }
+
+        wl_proxy_marshal_flags(self.surface, WL_SURFACE_ATTACH, null(), wl_proxy_get_version(self.surface), 0, buffer, 0i32, 0i32);
+        wl_proxy_marshal_flags(self.surface, WL_SURFACE_DAMAGE, null(), wl_proxy_get_version(self.surface), 0, 0i32, 0i32, size.0 as i32, size.1 as i32);
+        wl_proxy_marshal_flags(self.surface, WL_SURFACE_COMMIT, null(), wl_proxy_get_version(self.surface), 0);
+
+        if !self.buffer.is_null() {
+            wl_proxy_marshal_flags(self.buffer, WL_BUFFER_DESTROY, null(), wl_proxy_get_version(self.buffer), WL_MARSHAL_FLAG_DESTROY);
+        }
+        self.buffer = buffer;
+        self.size = size;
+        self.events.push_back(KEvent::Window(KEventWindow::Exposed((0, 0), size)));
+    }
+
+    /// Create or destroy pointer constraints and relative pointer according to lock and confinement requested.
+    ///
+    /// Nothing is done if compositor doesn't support pointer constraints.
+    pub(crate) unsafe fn update_pointer_constraints(&mut self) {
+        let connection = &*self.connection;
+        if connection.pointer.is_null() || connection.pointer_constraints.is_null() {
+            return;
+        }
+        let lock = self.cursor_locked;
+        let confine = self.cursor_confined && !self.cursor_locked;
+
+        // Previous constraint must be destroyed before creating another one.
+        if !lock && !self.locked_pointer.is_null() {
+            wl_proxy_marshal_flags(self.locked_pointer, ZWP_LOCKED_POINTER_V1_DESTROY, null(), 1, WL_MARSHAL_FLAG_DESTROY);
+            self.locked_pointer = null_mut();
+            self.cursor_hint = None;
+        }
+        if !confine && !self.confined_pointer.is_null() {
+            wl_proxy_marshal_flags(self.confined_pointer, ZWP_CONFINED_POINTER_V1_DESTROY, null(), 1, WL_MARSHAL_FLAG_DESTROY);
+            self.confined_pointer = null_mut();
+        }
+
+        // Persistent constraints are activated again by compositor when window gets focus back.
+        if lock && self.locked_pointer.is_null() {
+            self.locked_pointer = wl_proxy_marshal_flags(connection.pointer_constraints, ZWP_POINTER_CONSTRAINTS_V1_LOCK_POINTER, &zwp_locked_pointer_v1_interface,
+                1, 0, null_mut::<c_void>(), self.surface, connection.pointer, null_mut::<wl_proxy>(), ZWP_POINTER_CONSTRAINTS_V1_LIFETIME_PERSISTENT);
+        }
+        if confine && self.confined_pointer.is_null() {
+            self.confined_pointer = wl_proxy_marshal_flags(connection.pointer_constraints, ZWP_POINTER_CONSTRAINTS_V1_CONFINE_POINTER, &zwp_confined_pointer_v1_interface,
+                1, 0, null_mut::<c_void>(), self.surface, connection.pointer, null_mut::<wl_proxy>(), ZWP_POINTER_CONSTRAINTS_V1_LIFETIME_PERSISTENT);
+        }
+
+        // Locked pointer doesn't move. Motion is given by relative pointer.
+        if lock && self.relative_pointer.is_null() && !connection.relative_pointer_manager.is_null() {
+            self.relative_pointer = wl_proxy_marshal_flags(connection.relative_pointer_manager, ZWP_RELATIVE_POINTER_MANAGER_V1_GET_RELATIVE_POINTER,
+                &zwp_relative_pointer_v1_interface, 1, 0, null_mut::<c_void>(), connection.pointer);
+            wl_proxy_add_listener(self.relative_pointer, &KWAYLAND_RELATIVE_POINTER_LISTENER as *const _ as *const c_void,
+                self as *mut KLinuxDisplayServerWaylandProperty as *mut c_void);
+        } else if !lock && !self.relative_pointer.is_null() {
+            wl_proxy_marshal_flags(self.relative_pointer, ZWP_RELATIVE_POINTER_V1_DESTROY, null(), 1, WL_MARSHAL_FLAG_DESTROY);
+            self.relative_pointer = null_mut();
+            self.relative_remainder = (0.0, 0.0);
+        }
+    }
+
+    /// Destroy pointer constraints and relative pointer of window. Requests are kept to create them again with a new pointer.
+    pub(crate) unsafe fn destroy_pointer_constraints(&mut self) {
+        for (proxy, opcode) in [(&mut self.locked_pointer, ZWP_LOCKED_POINTER_V1_DESTROY), (&mut self.confined_pointer, ZWP_CONFINED_POINTER_V1_DESTROY),
+            (&mut self.relative_pointer, ZWP_RELATIVE_POINTER_V1_DESTROY)] {
+            if !proxy.is_null() {
+                wl_proxy_marshal_flags(*proxy, opcode, null(), 1, WL_MARSHAL_FLAG_DESTROY);
+                *proxy = null_mut();
+            }
+        }
+        self.cursor_hint = None;
+    }
+}
+
+impl Drop for KLinuxDisplayServerWaylandProperty {
+    fn drop(&mut self) {
+        unsafe {
+            // Destroy constraints and window roles before surface.
+            self.destroy_pointer_constraints();
+            for (proxy, opcode) in [(self.toplevel, XDG_TOPLEVEL_DESTROY), (self.xdg_surface, XDG_SURFACE_DESTROY), (self.surface, WL_SURFACE_DESTROY),
+                (self.buffer, WL_BUFFER_DESTROY)] {
+                if !proxy.is_null() {
+                    wl_proxy_marshal_flags(proxy, opcode, null(), wl_proxy_get_version(proxy), WL_MARSHAL_FLAG_DESTROY);
+                }
+            }
+
+            // Window no longer receives input.
+            let connection = &mut *self.connection;
+            let window = self as *mut KLinuxDisplayServerWaylandProperty;
+            connection.windows.retain(|other| *other != window);
+            if connection.pointer_focus == window {
+                connection.pointer_focus = null_mut();
+            }
+            if connection.keyboard_focus == window {
+                connection.keyboard_focus = null_mut();
+                connection.repeat_key = None;
+            }
+            for tool in connection.tablet_tools.iter_mut().filter(|tool| tool.focus == window) {
+                tool.focus = null_mut();
+            }
+            wl_display_flush(connection.display);
+        }
+    }
+}
+
+/// Returns pixels of a black arrow with a white border, pointing at (0, 0).
+fn get_arrow_pixels() -> Vec<u32> {
+    let (width, height) = (KWAYLAND_ARROW_SIZE.0 as i32, KWAYLAND_ARROW_SIZE.1 as i32);
+    let inside = |x : i32, y : i32| x >= 0 && y >= 0 && y < height && x * 3 <= y * 2;
+
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.push(if !inside(x, y) {
+                0x00000000
+            } else if inside(x - 1, y) && inside(x + 1, y) && inside(x, y - 1) && inside(x, y + 1) {
+                0xFF000000
+            } else {
+                0xFFFFFFFF
+            });
+        }
+    }
+    pixels
+}
+
 /// Implementation of privates elements relatives to Wayland display server
 #[doc(hidden)]
 impl KWindow {
 
-    // Pop an event from the queue
+    /// Returns the Wayland properties of the window.
+    #[inline(always)]
+    fn get_wayland_property(&self) -> *mut KLinuxDisplayServerWaylandProperty {
+        self.display_server.wayland_property
+    }
+
+    // Pop an event from the queue and apply window states.
     #[inline(always)]
     pub(super) fn wayland_poll_event(&mut self) -> KEvent {
-        todo!()
+        let event = unsafe { (*self.get_wayland_property()).events.pop_front().unwrap_or(KEvent::None) };
+
+        if let KEvent::Window(window_event) = event {
+            match window_event {
+                KEventWindow::Maximized() => self.property.maximized = true,
+                KEventWindow::Fullscreen() => self.property.fullscreen = true,
+                KEventWindow::Restored() => {
+                    self.property.minimized = false;
+                    self.property.maximized = false;
+                    self.property.fullscreen = false;
+                },
+                _ => {},
+            }
+        }
+        event
     }
 
     // Sync an event from the queue
     #[inline(always)]
     pub(super) fn wayland_sync_events(&self) {
-        todo!()
+        unsafe {
+            wl_display_flush((*(*self.get_wayland_property()).connection).display);
+        }
     }
 
     /// Get the count of events that need handling.
+    ///
+    /// Events read for other windows of the connection are kept in their own queue.
     #[inline(always)]
     pub(super) fn wayland_get_event_count(&self) -> usize {
-        todo!()
+        unsafe {
+            KLinuxDisplayServerWaylandConnection::dispatch((*self.get_wayland_property()).connection);
+            (*self.get_wayland_property()).events.len()
+        }
     }
 
     /// Set the cursor position
+    ///
+    /// Wayland doesn't let clients warp the pointer. Position is given as a hint to compositor when pointer is locked
+    /// by [KCursorMode::Acceleration], where pointer reappears once unlocked.
     #[inline(always)]
     pub(super) fn wayland_set_cursor_position(&mut self, position : (i32, i32)){
-        todo!()
+        unsafe {
+            let property = &mut *self.get_wayland_property();
+
+            if !property.locked_pointer.is_null() && property.cursor_hint != Some(position) {
+                // Hint is applied on next surface commit.
+                wl_proxy_marshal_flags(property.locked_pointer, ZWP_LOCKED_POINTER_V1_SET_CURSOR_POSITION_HINT, null(), 1, 0,
+                    position.0 * 256, position.1 * 256);
+                wl_proxy_marshal_flags(property.surface, WL_SURFACE_COMMIT, null(), wl_proxy_get_version(property.surface), 0);
+                property.cursor_hint = Some(position);
+            }
+        }
     }
 
     /// Confine cursor to window, preventing it from exiting boundaries.
+    ///
+    /// Needs compositor support of pointer constraints.
     #[inline(always)]
     pub fn wayland_confine_cursor(&mut self) {
-        todo!()
+        unsafe {
+            let property = &mut *self.get_wayland_property();
+            property.cursor_confined = true;
+            property.update_pointer_constraints();
+            wl_display_flush((*property.connection).display);
+        }
     }
 
     /// Release cursor from window, allowing it to exit boundaries.
     #[inline(always)]
     pub fn wayland_release_cursor(&mut self) {
-        todo!()
+        unsafe {
+            let property = &mut *self.get_wayland_property();
+            property.cursor_confined = false;
+            property.update_pointer_constraints();
+            wl_display_flush((*property.connection).display);
+        }
     }
 
     /// Hide system default cursor.
+    ///
+    /// Cursor image belongs to pointer, so it is only changed while pointer is over this window.
     #[inline(always)]
     pub fn wayland_hide_cursor(&mut self) {
-        todo!()
+        unsafe {
+            let connection = &mut *(*self.get_wayland_property()).connection;
+            if connection.pointer_focus == self.get_wayland_property() {
+                connection.set_cursor(false);
+                wl_display_flush(connection.display);
+            }
+        }
     }
 
     /// Show system default cursor.
+    ///
+    /// Cursor comes from compositor theme when cursor shapes are supported. Otherwise, a plain arrow is drawn.
     #[inline(always)]
     pub fn wayland_show_cursor(&mut self) {
-        todo!()
+        unsafe {
+            let connection = &mut *(*self.get_wayland_property()).connection;
+            if connection.pointer_focus == self.get_wayland_property() {
+                connection.set_cursor(true);
+                wl_display_flush(connection.display);
+            }
+        }
+    }
+
+    /// Lock pointer in [KCursorMode::Acceleration], giving motion from relative pointer.
+    ///
+    /// Needs compositor support of pointer constraints and relative pointer.
+    #[inline(always)]
+    pub(super) fn wayland_set_cursor_mode(&mut self) {
+        unsafe {
+            let property = &mut *self.get_wayland_property();
+            property.cursor_locked = self.property.cursor.mode == KCursorMode::Acceleration;
+            property.update_pointer_constraints();
+            wl_display_flush((*property.connection).display);
+        }
     }
 
     /// Restore the [KWindow], undoing any minimized, maximized and/or fullscreen status.
+    ///
+    /// Compositor answers with a configure giving back the normal size.
     #[inline(always)]
     pub fn wayland_restore(&mut self) {
-        todo!()
+        unsafe {
+            let property = &mut *self.get_wayland_property();
+            let version = wl_proxy_get_version(property.toplevel);
+
+            if property.fullscreen {
+                wl_proxy_marshal_flags(property.toplevel, XDG_TOPLEVEL_UNSET_FULLSCREEN, null(), version, 0);
+            }
+            if property.maximized {
+                wl_proxy_marshal_flags(property.toplevel, XDG_TOPLEVEL_UNSET_MAXIMIZED, null(), version, 0);
+            }
+            wl_display_flush((*property.connection).display);
+        }
     }
 
     /// Set a new title for the [KWindow].
     #[inline(always)]
     pub(super) fn wayland_set_title(&mut self) {
-        todo!()
+        unsafe {
+            let property = &mut *self.get_wayland_property();
+
+            // Title is cut at first null character.
+            property.title = CString::new(self.property.title.split('\0').next().unwrap_or_default()).unwrap_or_default();
+            wl_proxy_marshal_flags(property.toplevel, XDG_TOPLEVEL_SET_TITLE, null(), wl_proxy_get_version(property.toplevel), 0, property.title.as_ptr());
+        }
     }
 
     /// Set the [KWindow] as fullscreen.
+    ///
+    /// Wayland fullscreen covers a single output. [KWindowFullscreenMode::ExtendedScreen] uses the output of the reference screen and
+    /// [KWindowFullscreenMode::CurrentScreen] lets compositor choose the output.
     #[inline(always)]
     pub(super) fn wayland_set_fullscreen(&mut self, mode : KWindowFullscreenMode) {
-        todo!()
+        unsafe {
+            let property = &mut *self.get_wayland_property();
+            let connection = &*property.connection;
+
+            let identifier = match mode {
+                KWindowFullscreenMode::CurrentScreen => None,
+                KWindowFullscreenMode::PrimaryScreen => self.screen_list.get_primary_screen().map(|screen| screen.get_identifier()),
+                KWindowFullscreenMode::ExtendedScreen(screen) | KWindowFullscreenMode::SelectScreen(screen) => Some(screen.get_identifier()),
+            };
+            let output = identifier.and_then(|identifier| connection.outputs.iter().find(|output| output.identifier == *identifier))
+                .map_or(null_mut(), |output| output.proxy);
+
+            wl_proxy_marshal_flags(property.toplevel, XDG_TOPLEVEL_SET_FULLSCREEN, null(), wl_proxy_get_version(property.toplevel), 0, output);
+            wl_display_flush(connection.display);
+        }
     }
 
     /// Set a size of [KWindow].
+    ///
+    /// Size of a maximized or fullscreen window is given by compositor.
     #[inline(always)]
     pub(super) fn wayland_set_size(&mut self) {
-        todo!()
+        unsafe {
+            let property = &mut *self.get_wayland_property();
+
+            if !property.maximized && !property.fullscreen && property.size != self.property.size {
+                property.events.push_back(KEvent::Window(KEventWindow::Resized(self.property.size)));
+                property.attach_buffer(self.property.size);
+            }
+        }
     }
 
     /// Set a position of [KWindow].
+    ///
+    /// Wayland doesn't let clients position their windows. Position stays as given.
     #[inline(always)]
     pub(super) fn wayland_set_position(&mut self) {
-        todo!()
     }
-  
+
     /// Get if Wayland is supported.
     #[inline(always)]
     pub(crate) fn wayland_supported() -> bool {
@@ -106,7 +945,7 @@ impl KWindow {
             // Try to call C function with error handling.
             let result = catch_unwind(|| {
                 wl_display_connect(std::ptr::null())
-            }); 
+            });
             match result {
                 Ok(display) => {
                     if display == std::ptr::null_mut() {
@@ -125,9 +964,30 @@ impl KWindow {
         }
     }
 
-    /// Create connection to Wayland and window
+    /// Create connection to Wayland, shared by windows created on it.
+    ///
+    /// # Error(s)
+    /// Returns `OlympusError::KWindow(KWindowError::NoDisplayServer)` if connection failed.
+    #[inline(always)]
+    pub(crate) fn create_wayland_connection() -> Result<*mut KLinuxDisplayServerWaylandConnection, OlympusError> {
+        unsafe {
+            Ok(Box::into_raw(KLinuxDisplayServerWaylandConnection::connect()?))
+        }
+    }
+
+    /// Create a Wayland window on connection.
+    ///
+    /// # Error(s)
+    /// Returns `OlympusError::KWindow(KWindowError::NotSupported)` if compositor lacks xdg-shell.
     #[inline(always)]
-    pub(crate) fn create_wayland_window(width:u32, height:u32) -> (*mut Display, *mut Window) {
-        todo!()
+    pub(crate) fn create_wayland_window(connection : *mut KLinuxDisplayServerWaylandConnection, width:u32, height:u32) -> Result<*mut KLinuxDisplayServerWaylandProperty, OlympusError> {
+        unsafe {
+            let property = KLinuxDisplayServerWaylandProperty::create(connection, width, height)?;
+
+            // Keymap and seat devices are received after bind.
+            wl_display_roundtrip((*connection).display);
+
+            Ok(Box::into_raw(property))
+        }
     }
-}
\ No newline at end of file
+}
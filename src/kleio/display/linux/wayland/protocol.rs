@@ -0,0 +1,422 @@
+use super::bind::{wl_interface, wl_message, wl_types};
+
+/// Argument types of messages. Left null since proxies are created with their interface.
+static WL_NULL_TYPES : wl_types = wl_types([std::ptr::null(); 8]);
+
+/// Macro that construct a [wl_message] from name, signature and types if message has a new object argument.
+macro_rules! wl_message {
+    ($name:literal, $signature:literal) => {
+        wl_message { name: $name.as_ptr(), signature: $signature.as_ptr(), types: WL_NULL_TYPES.0.as_ptr() }
+    };
+    ($name:literal, $signature:literal, $types:ident) => {
+        wl_message { name: $name.as_ptr(), signature: $signature.as_ptr(), types: $types.0.as_ptr() }
+    };
+}
+
+/// Returns the argument types of an event creating an object of interface. libwayland needs the interface of objects created by compositor.
+const fn wl_new_id_types(interface : &'static wl_interface) -> wl_types {
+    let mut types : [*const wl_interface; 8] = [std::ptr::null(); 8];
+    types[0] = interface;
+    wl_types(types)
+}
+
+/// Macro that construct a [wl_interface] from name, version, requests and events.
+macro_rules! wl_interface {
+    ($name:literal, $version:literal, $requests:ident, $events:ident) => {
+        wl_interface { name: $name.as_ptr(), version: $version, method_count: $requests.len() as i32, methods: $requests.as_ptr(),
+            event_count: $events.len() as i32, events: $events.as_ptr() }
+    };
+}
+
+/*********
+* WL_CORE *
+*********/
+// Opcodes of core requests. Interfaces are given by libwayland-client.
+pub const WL_DISPLAY_GET_REGISTRY : u32 = 1;
+pub const WL_REGISTRY_BIND : u32 = 0;
+pub const WL_COMPOSITOR_CREATE_SURFACE : u32 = 0;
+pub const WL_SURFACE_DESTROY : u32 = 0;
+pub const WL_SURFACE_ATTACH : u32 = 1;
+pub const WL_SURFACE_DAMAGE : u32 = 2;
+pub const WL_SURFACE_COMMIT : u32 = 6;
+pub const WL_SHM_CREATE_POOL : u32 = 0;
+pub const WL_SHM_POOL_CREATE_BUFFER : u32 = 0;
+pub const WL_SHM_POOL_DESTROY : u32 = 1;
+pub const WL_BUFFER_DESTROY : u32 = 0;
+pub const WL_SEAT_GET_POINTER : u32 = 0;
+pub const WL_SEAT_GET_KEYBOARD : u32 = 1;
+pub const WL_SEAT_RELEASE : u32 = 3;
+pub const WL_POINTER_SET_CURSOR : u32 = 0;
+pub const WL_POINTER_RELEASE : u32 = 1;
+pub const WL_KEYBOARD_RELEASE : u32 = 0;
+pub const WL_OUTPUT_RELEASE : u32 = 0;
+
+/*************
+* XDG_WM_BASE *
+*************/
+pub const XDG_WM_BASE_DESTROY : u32 = 0;
+pub const XDG_WM_BASE_GET_XDG_SURFACE : u32 = 2;
+pub const XDG_WM_BASE_PONG : u32 = 3;
+
+static XDG_WM_BASE_REQUESTS : [wl_message; 4] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"create_positioner", c"n"),
+    wl_message!(c"get_xdg_surface", c"no"),
+    wl_message!(c"pong", c"u"),
+];
+
+static XDG_WM_BASE_EVENTS : [wl_message; 1] = [
+    wl_message!(c"ping", c"u"),
+];
+
+/// Global interface used to create desktop-style surfaces.
+pub static xdg_wm_base_interface : wl_interface = wl_interface!(c"xdg_wm_base", 1, XDG_WM_BASE_REQUESTS, XDG_WM_BASE_EVENTS);
+
+/*************
+* XDG_SURFACE *
+*************/
+pub const XDG_SURFACE_DESTROY : u32 = 0;
+pub const XDG_SURFACE_GET_TOPLEVEL : u32 = 1;
+pub const XDG_SURFACE_ACK_CONFIGURE : u32 = 4;
+
+static XDG_SURFACE_REQUESTS : [wl_message; 5] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"get_toplevel", c"n"),
+    wl_message!(c"get_popup", c"n?oo"),
+    wl_message!(c"set_window_geometry", c"iiii"),
+    wl_message!(c"ack_configure", c"u"),
+];
+
+static XDG_SURFACE_EVENTS : [wl_message; 1] = [
+    wl_message!(c"configure", c"u"),
+];
+
+/// Desktop user interface surface base interface.
+pub static xdg_surface_interface : wl_interface = wl_interface!(c"xdg_surface", 1, XDG_SURFACE_REQUESTS, XDG_SURFACE_EVENTS);
+
+/**************
+* XDG_TOPLEVEL *
+**************/
+pub const XDG_TOPLEVEL_DESTROY : u32 = 0;
+pub const XDG_TOPLEVEL_SET_TITLE : u32 = 2;
+pub const XDG_TOPLEVEL_SET_APP_ID : u32 = 3;
+pub const XDG_TOPLEVEL_SET_MAXIMIZED : u32 = 9;
+pub const XDG_TOPLEVEL_UNSET_MAXIMIZED : u32 = 10;
+pub const XDG_TOPLEVEL_SET_FULLSCREEN : u32 = 11;
+pub const XDG_TOPLEVEL_UNSET_FULLSCREEN : u32 = 12;
+pub const XDG_TOPLEVEL_SET_MINIMIZED : u32 = 13;
+
+static XDG_TOPLEVEL_REQUESTS : [wl_message; 14] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"set_parent", c"?o"),
+    wl_message!(c"set_title", c"s"),
+    wl_message!(c"set_app_id", c"s"),
+    wl_message!(c"show_window_menu", c"ouii"),
+    wl_message!(c"move", c"ou"),
+    wl_message!(c"resize", c"ouu"),
+    wl_message!(c"set_max_size", c"ii"),
+    wl_message!(c"set_min_size", c"ii"),
+    wl_message!(c"set_maximized", c""),
+    wl_message!(c"unset_maximized", c""),
+    wl_message!(c"set_fullscreen", c"?o"),
+    wl_message!(c"unset_fullscreen", c""),
+    wl_message!(c"set_minimized", c""),
+];
+
+static XDG_TOPLEVEL_EVENTS : [wl_message; 2] = [
+    wl_message!(c"configure", c"iia"),
+    wl_message!(c"close", c""),
+];
+
+/// Toplevel surface interface (ie. a window).
+pub static xdg_toplevel_interface : wl_interface = wl_interface!(c"xdg_toplevel", 1, XDG_TOPLEVEL_REQUESTS, XDG_TOPLEVEL_EVENTS);
+
+/**************************
+* ZWP_POINTER_CONSTRAINTS *
+**************************/
+pub const ZWP_POINTER_CONSTRAINTS_V1_DESTROY : u32 = 0;
+pub const ZWP_POINTER_CONSTRAINTS_V1_LOCK_POINTER : u32 = 1;
+pub const ZWP_POINTER_CONSTRAINTS_V1_CONFINE_POINTER : u32 = 2;
+
+/// Constraint is destroyed once deactivated (ie. on focus lost).
+pub const ZWP_POINTER_CONSTRAINTS_V1_LIFETIME_ONESHOT : u32 = 1;
+
+/// Constraint is activated again when conditions are met (ie. on focus).
+pub const ZWP_POINTER_CONSTRAINTS_V1_LIFETIME_PERSISTENT : u32 = 2;
+
+static ZWP_POINTER_CONSTRAINTS_V1_REQUESTS : [wl_message; 3] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"lock_pointer", c"noo?ou"),
+    wl_message!(c"confine_pointer", c"noo?ou"),
+];
+
+static ZWP_POINTER_CONSTRAINTS_V1_EVENTS : [wl_message; 0] = [];
+
+/// Global interface used to lock or confine pointer to a surface.
+pub static zwp_pointer_constraints_v1_interface : wl_interface = wl_interface!(c"zwp_pointer_constraints_v1", 1, ZWP_POINTER_CONSTRAINTS_V1_REQUESTS, ZWP_POINTER_CONSTRAINTS_V1_EVENTS);
+
+pub const ZWP_LOCKED_POINTER_V1_DESTROY : u32 = 0;
+pub const ZWP_LOCKED_POINTER_V1_SET_CURSOR_POSITION_HINT : u32 = 1;
+
+static ZWP_LOCKED_POINTER_V1_REQUESTS : [wl_message; 3] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"set_cursor_position_hint", c"ff"),
+    wl_message!(c"set_region", c"?o"),
+];
+
+static ZWP_LOCKED_POINTER_V1_EVENTS : [wl_message; 2] = [
+    wl_message!(c"locked", c""),
+    wl_message!(c"unlocked", c""),
+];
+
+/// Pointer locked in place. Motion is only given by relative pointer.
+pub static zwp_locked_pointer_v1_interface : wl_interface = wl_interface!(c"zwp_locked_pointer_v1", 1, ZWP_LOCKED_POINTER_V1_REQUESTS, ZWP_LOCKED_POINTER_V1_EVENTS);
+
+pub const ZWP_CONFINED_POINTER_V1_DESTROY : u32 = 0;
+
+static ZWP_CONFINED_POINTER_V1_REQUESTS : [wl_message; 2] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"set_region", c"?o"),
+];
+
+static ZWP_CONFINED_POINTER_V1_EVENTS : [wl_message; 2] = [
+    wl_message!(c"confined", c""),
+    wl_message!(c"unconfined", c""),
+];
+
+/// Pointer confined to surface.
+pub static zwp_confined_pointer_v1_interface : wl_interface = wl_interface!(c"zwp_confined_pointer_v1", 1, ZWP_CONFINED_POINTER_V1_REQUESTS, ZWP_CONFINED_POINTER_V1_EVENTS);
+
+/**********************
+* ZWP_RELATIVE_POINTER *
+**********************/
+pub const ZWP_RELATIVE_POINTER_MANAGER_V1_DESTROY : u32 = 0;
+pub const ZWP_RELATIVE_POINTER_MANAGER_V1_GET_RELATIVE_POINTER : u32 = 1;
+
+static ZWP_RELATIVE_POINTER_MANAGER_V1_REQUESTS : [wl_message; 2] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"get_relative_pointer", c"no"),
+];
+
+static ZWP_RELATIVE_POINTER_MANAGER_V1_EVENTS : [wl_message; 0] = [];
+
+/// Global interface used to get relative pointer motion.
+pub static zwp_relative_pointer_manager_v1_interface : wl_interface = wl_interface!(c"zwp_relative_pointer_manager_v1", 1,
+    ZWP_RELATIVE_POINTER_MANAGER_V1_REQUESTS, ZWP_RELATIVE_POINTER_MANAGER_V1_EVENTS);
+
+pub const ZWP_RELATIVE_POINTER_V1_DESTROY : u32 = 0;
+
+static ZWP_RELATIVE_POINTER_V1_REQUESTS : [wl_message; 1] = [
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_RELATIVE_POINTER_V1_EVENTS : [wl_message; 1] = [
+    wl_message!(c"relative_motion", c"uuffff"),
+];
+
+/// Relative motion of pointer, not limited by screen edges.
+pub static zwp_relative_pointer_v1_interface : wl_interface = wl_interface!(c"zwp_relative_pointer_v1", 1, ZWP_RELATIVE_POINTER_V1_REQUESTS, ZWP_RELATIVE_POINTER_V1_EVENTS);
+
+/*****************
+* WP_CURSOR_SHAPE *
+*****************/
+pub const WP_CURSOR_SHAPE_MANAGER_V1_DESTROY : u32 = 0;
+pub const WP_CURSOR_SHAPE_MANAGER_V1_GET_POINTER : u32 = 1;
+
+static WP_CURSOR_SHAPE_MANAGER_V1_REQUESTS : [wl_message; 3] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"get_pointer", c"no"),
+    wl_message!(c"get_tablet_tool_v2", c"no"),
+];
+
+static WP_CURSOR_SHAPE_MANAGER_V1_EVENTS : [wl_message; 0] = [];
+
+/// Global interface used to set cursor from compositor cursor theme.
+pub static wp_cursor_shape_manager_v1_interface : wl_interface = wl_interface!(c"wp_cursor_shape_manager_v1", 1,
+    WP_CURSOR_SHAPE_MANAGER_V1_REQUESTS, WP_CURSOR_SHAPE_MANAGER_V1_EVENTS);
+
+pub const WP_CURSOR_SHAPE_DEVICE_V1_DESTROY : u32 = 0;
+pub const WP_CURSOR_SHAPE_DEVICE_V1_SET_SHAPE : u32 = 1;
+
+/// Default cursor shape (usually an arrow).
+pub const WP_CURSOR_SHAPE_DEVICE_V1_SHAPE_DEFAULT : u32 = 1;
+
+static WP_CURSOR_SHAPE_DEVICE_V1_REQUESTS : [wl_message; 2] = [
+    wl_message!(c"destroy", c""),
+    wl_message!(c"set_shape", c"uu"),
+];
+
+static WP_CURSOR_SHAPE_DEVICE_V1_EVENTS : [wl_message; 0] = [];
+
+/// Cursor shape of a pointer.
+pub static wp_cursor_shape_device_v1_interface : wl_interface = wl_interface!(c"wp_cursor_shape_device_v1", 1,
+    WP_CURSOR_SHAPE_DEVICE_V1_REQUESTS, WP_CURSOR_SHAPE_DEVICE_V1_EVENTS);
+
+/************
+* ZWP_TABLET *
+************/
+pub const ZWP_TABLET_MANAGER_V2_GET_TABLET_SEAT : u32 = 0;
+pub const ZWP_TABLET_MANAGER_V2_DESTROY : u32 = 1;
+
+static ZWP_TABLET_MANAGER_V2_REQUESTS : [wl_message; 2] = [
+    wl_message!(c"get_tablet_seat", c"no"),
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_TABLET_MANAGER_V2_EVENTS : [wl_message; 0] = [];
+
+/// Global interface used to get the tablets, tools and pads of a seat.
+pub static zwp_tablet_manager_v2_interface : wl_interface = wl_interface!(c"zwp_tablet_manager_v2", 1,
+    ZWP_TABLET_MANAGER_V2_REQUESTS, ZWP_TABLET_MANAGER_V2_EVENTS);
+
+pub const ZWP_TABLET_SEAT_V2_DESTROY : u32 = 0;
+
+static ZWP_TABLET_SEAT_V2_REQUESTS : [wl_message; 1] = [
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_TABLET_SEAT_V2_TABLET_TYPES : wl_types = wl_new_id_types(&zwp_tablet_v2_interface);
+static ZWP_TABLET_SEAT_V2_TOOL_TYPES : wl_types = wl_new_id_types(&zwp_tablet_tool_v2_interface);
+static ZWP_TABLET_SEAT_V2_PAD_TYPES : wl_types = wl_new_id_types(&zwp_tablet_pad_v2_interface);
+
+static ZWP_TABLET_SEAT_V2_EVENTS : [wl_message; 3] = [
+    wl_message!(c"tablet_added", c"n", ZWP_TABLET_SEAT_V2_TABLET_TYPES),
+    wl_message!(c"tool_added", c"n", ZWP_TABLET_SEAT_V2_TOOL_TYPES),
+    wl_message!(c"pad_added", c"n", ZWP_TABLET_SEAT_V2_PAD_TYPES),
+];
+
+/// Tablets, tools and pads of a seat, announced when added.
+pub static zwp_tablet_seat_v2_interface : wl_interface = wl_interface!(c"zwp_tablet_seat_v2", 1, ZWP_TABLET_SEAT_V2_REQUESTS, ZWP_TABLET_SEAT_V2_EVENTS);
+
+pub const ZWP_TABLET_TOOL_V2_DESTROY : u32 = 1;
+
+/// Tool is the eraser end of a pen.
+pub const ZWP_TABLET_TOOL_V2_TYPE_ERASER : u32 = 0x141;
+
+static ZWP_TABLET_TOOL_V2_REQUESTS : [wl_message; 2] = [
+    wl_message!(c"set_cursor", c"u?oii"),
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_TABLET_TOOL_V2_EVENTS : [wl_message; 19] = [
+    wl_message!(c"type", c"u"),
+    wl_message!(c"hardware_serial", c"uu"),
+    wl_message!(c"hardware_id_wacom", c"uu"),
+    wl_message!(c"capability", c"u"),
+    wl_message!(c"done", c""),
+    wl_message!(c"removed", c""),
+    wl_message!(c"proximity_in", c"uoo"),
+    wl_message!(c"proximity_out", c""),
+    wl_message!(c"down", c"u"),
+    wl_message!(c"up", c""),
+    wl_message!(c"motion", c"ff"),
+    wl_message!(c"pressure", c"u"),
+    wl_message!(c"distance", c"u"),
+    wl_message!(c"tilt", c"ff"),
+    wl_message!(c"rotation", c"f"),
+    wl_message!(c"slider", c"i"),
+    wl_message!(c"wheel", c"fi"),
+    wl_message!(c"button", c"uuu"),
+    wl_message!(c"frame", c"u"),
+];
+
+/// Physical tool of a tablet (ie. pen, eraser).
+pub static zwp_tablet_tool_v2_interface : wl_interface = wl_interface!(c"zwp_tablet_tool_v2", 1, ZWP_TABLET_TOOL_V2_REQUESTS, ZWP_TABLET_TOOL_V2_EVENTS);
+
+pub const ZWP_TABLET_V2_DESTROY : u32 = 0;
+
+static ZWP_TABLET_V2_REQUESTS : [wl_message; 1] = [
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_TABLET_V2_EVENTS : [wl_message; 5] = [
+    wl_message!(c"name", c"s"),
+    wl_message!(c"id", c"uu"),
+    wl_message!(c"path", c"s"),
+    wl_message!(c"done", c""),
+    wl_message!(c"removed", c""),
+];
+
+/// Graphics tablet device.
+pub static zwp_tablet_v2_interface : wl_interface = wl_interface!(c"zwp_tablet_v2", 1, ZWP_TABLET_V2_REQUESTS, ZWP_TABLET_V2_EVENTS);
+
+pub const ZWP_TABLET_PAD_RING_V2_DESTROY : u32 = 1;
+
+static ZWP_TABLET_PAD_RING_V2_REQUESTS : [wl_message; 2] = [
+    wl_message!(c"set_feedback", c"su"),
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_TABLET_PAD_RING_V2_EVENTS : [wl_message; 4] = [
+    wl_message!(c"source", c"u"),
+    wl_message!(c"angle", c"f"),
+    wl_message!(c"stop", c""),
+    wl_message!(c"frame", c"u"),
+];
+
+/// Ring of a tablet pad.
+pub static zwp_tablet_pad_ring_v2_interface : wl_interface = wl_interface!(c"zwp_tablet_pad_ring_v2", 1,
+    ZWP_TABLET_PAD_RING_V2_REQUESTS, ZWP_TABLET_PAD_RING_V2_EVENTS);
+
+pub const ZWP_TABLET_PAD_STRIP_V2_DESTROY : u32 = 1;
+
+static ZWP_TABLET_PAD_STRIP_V2_REQUESTS : [wl_message; 2] = [
+    wl_message!(c"set_feedback", c"su"),
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_TABLET_PAD_STRIP_V2_EVENTS : [wl_message; 4] = [
+    wl_message!(c"source", c"u"),
+    wl_message!(c"position", c"u"),
+    wl_message!(c"stop", c""),
+    wl_message!(c"frame", c"u"),
+];
+
+/// Strip of a tablet pad.
+pub static zwp_tablet_pad_strip_v2_interface : wl_interface = wl_interface!(c"zwp_tablet_pad_strip_v2", 1,
+    ZWP_TABLET_PAD_STRIP_V2_REQUESTS, ZWP_TABLET_PAD_STRIP_V2_EVENTS);
+
+pub const ZWP_TABLET_PAD_GROUP_V2_DESTROY : u32 = 0;
+
+static ZWP_TABLET_PAD_GROUP_V2_REQUESTS : [wl_message; 1] = [
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_TABLET_PAD_GROUP_V2_RING_TYPES : wl_types = wl_new_id_types(&zwp_tablet_pad_ring_v2_interface);
+static ZWP_TABLET_PAD_GROUP_V2_STRIP_TYPES : wl_types = wl_new_id_types(&zwp_tablet_pad_strip_v2_interface);
+
+static ZWP_TABLET_PAD_GROUP_V2_EVENTS : [wl_message; 6] = [
+    wl_message!(c"buttons", c"a"),
+    wl_message!(c"ring", c"n", ZWP_TABLET_PAD_GROUP_V2_RING_TYPES),
+    wl_message!(c"strip", c"n", ZWP_TABLET_PAD_GROUP_V2_STRIP_TYPES),
+    wl_message!(c"modes", c"u"),
+    wl_message!(c"done", c""),
+    wl_message!(c"mode_switch", c"uuu"),
+];
+
+/// Group of buttons, rings and strips of a tablet pad.
+pub static zwp_tablet_pad_group_v2_interface : wl_interface = wl_interface!(c"zwp_tablet_pad_group_v2", 1,
+    ZWP_TABLET_PAD_GROUP_V2_REQUESTS, ZWP_TABLET_PAD_GROUP_V2_EVENTS);
+
+pub const ZWP_TABLET_PAD_V2_DESTROY : u32 = 1;
+
+static ZWP_TABLET_PAD_V2_REQUESTS : [wl_message; 2] = [
+    wl_message!(c"set_feedback", c"usu"),
+    wl_message!(c"destroy", c""),
+];
+
+static ZWP_TABLET_PAD_V2_GROUP_TYPES : wl_types = wl_new_id_types(&zwp_tablet_pad_group_v2_interface);
+
+static ZWP_TABLET_PAD_V2_EVENTS : [wl_message; 8] = [
+    wl_message!(c"group", c"n", ZWP_TABLET_PAD_V2_GROUP_TYPES),
+    wl_message!(c"path", c"s"),
+    wl_message!(c"buttons", c"u"),
+    wl_message!(c"done", c""),
+    wl_message!(c"button", c"uuu"),
+    wl_message!(c"enter", c"uoo"),
+    wl_message!(c"leave", c"uo"),
+    wl_message!(c"removed", c""),
+];
+
+/// Buttons, rings and strips of a tablet. Pads aren't used but must be destroyed when removed.
+pub static zwp_tablet_pad_v2_interface : wl_interface = wl_interface!(c"zwp_tablet_pad_v2", 1, ZWP_TABLET_PAD_V2_REQUESTS, ZWP_TABLET_PAD_V2_EVENTS);
@@ -1,6 +1,121 @@
-use crate::kleio::display::screen::{KScreen, KScreenListError};
+use std::{ffi::CStr, os::raw::{c_char, c_void}};
+
+use crate::kleio::display::screen::{KScreen, KScreenResolution, KScreenListError};
+
+use super::{KLinuxDisplayServerWaylandConnection, bind::{wl_proxy, wl_display_roundtrip, WL_OUTPUT_MODE_CURRENT}};
+
+/// Wayland output (screen) bound from registry.
+pub(crate) struct KWaylandOutput {
+    /// Registry name of output, used when output is removed.
+    pub(crate) name : u32,
+
+    /// wl_output proxy.
+    pub(crate) proxy : *mut wl_proxy,
+
+    /// Connector name (ie. DP-1) or make and model if compositor doesn't give names.
+    pub(crate) identifier : String,
+
+    /// True if identifier is the connector name.
+    pub(crate) named : bool,
+
+    /// Position in compositor global space.
+    pub(crate) position : (i32, i32),
+
+    /// Current resolution.
+    pub(crate) resolution : (u32, u32),
+
+    /// Current refresh rate.
+    pub(crate) refresh_rate : u32,
+
+    /// Supported resolutions.
+    pub(crate) supported : Vec<KScreenResolution>,
+}
+
+impl KWaylandOutput {
+    /// Create a new output with registry name and proxy. Details are filled by output events.
+    pub(crate) fn new(name : u32, proxy : *mut wl_proxy) -> KWaylandOutput {
+        KWaylandOutput { name, proxy, identifier: String::new(), named: false, position: (0, 0), resolution: (0, 0), refresh_rate: 0, supported: Vec::new() }
+    }
+}
+
+/// wl_output events.
+#[repr(C)]
+pub(crate) struct KWaylandOutputListener {
+    geometry : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32, i32, i32, i32, i32, *const c_char, *const c_char, i32),
+    mode : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, i32, i32, i32),
+    done : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    scale : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32),
+    name : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *const c_char),
+    description : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *const c_char),
+}
+
+/// Listener of [KWaylandOutput]. Data is a pointer to the [KWaylandOutput].
+pub(crate) static KWAYLAND_OUTPUT_LISTENER : KWaylandOutputListener = KWaylandOutputListener { geometry: output_geometry, mode: output_mode,
+    done: output_ignore, scale: output_scale, name: output_name, description: output_description };
+
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn output_geometry(data : *mut c_void, _ : *mut wl_proxy, x : i32, y : i32, _ : i32, _ : i32, _ : i32, make : *const c_char, model : *const c_char, _ : i32) {
+    let output = &mut *(data as *mut KWaylandOutput);
+    output.position = (x, y);
+
+    // Connector name is preferred over make and model.
+    if !output.named {
+        output.identifier = format!("{} {}", CStr::from_ptr(make).to_string_lossy(), CStr::from_ptr(model).to_string_lossy());
+    }
+}
+
+unsafe extern "C" fn output_mode(data : *mut c_void, _ : *mut wl_proxy, flags : u32, width : i32, height : i32, refresh : i32) {
+    let output = &mut *(data as *mut KWaylandOutput);
+    let (width, height) = (width.max(0) as u32, height.max(0) as u32);
+
+    // Wayland refresh rate is in mHz.
+    let refresh_rate = refresh.max(0) as u32 / 10;
+
+    if flags & WL_OUTPUT_MODE_CURRENT != 0 {
+        output.resolution = (width, height);
+        output.refresh_rate = refresh_rate;
+    }
+
+    match output.supported.iter_mut().find(|r| r.get_width() == width && r.get_height() == height) {
+        Some(resolution) => resolution.add_refresh_rate(refresh_rate),
+        None => {
+            let mut resolution = KScreenResolution::new(width, height);
+            resolution.add_refresh_rate(refresh_rate);
+            output.supported.push(resolution);
+        },
+    }
+}
+
+unsafe extern "C" fn output_ignore(_ : *mut c_void, _ : *mut wl_proxy) {}
+
+unsafe extern "C" fn output_scale(_ : *mut c_void, _ : *mut wl_proxy, _ : i32) {}
+
+unsafe extern "C" fn output_name(data : *mut c_void, _ : *mut wl_proxy, name : *const c_char) {
+    let output = &mut *(data as *mut KWaylandOutput);
+    output.identifier = CStr::from_ptr(name).to_string_lossy().into_owned();
+    output.named = true;
+}
+
+unsafe extern "C" fn output_description(_ : *mut c_void, _ : *mut wl_proxy, _ : *const c_char) {}
+
+/// Returns the [KScreen] of an output. First output bound is the primary screen.
+pub(crate) fn get_wayland_output_screen(output : &KWaylandOutput, primary : bool) -> KScreen {
+    KScreen::new(output.identifier.clone(), output.position, output.resolution, output.refresh_rate, primary, output.supported.clone())
+}
 
 /// Private function that fetch Wayland display server screens.
 pub(crate) fn get_wayland_screen() -> Result<Vec<KScreen>, KScreenListError>{
-    Err(KScreenListError::FetchScreenListError)
-}
\ No newline at end of file
+    unsafe {
+        match KLinuxDisplayServerWaylandConnection::connect() {
+            Ok(connection) => {
+                // Second roundtrip receives the events of outputs bound during the first.
+                if wl_display_roundtrip(connection.display) < 0 {
+                    return Err(KScreenListError::FetchScreenListError);
+                }
+
+                Ok(connection.outputs.iter().enumerate().map(|(index, output)| get_wayland_output_screen(output, index == 0)).collect())
+            },
+            Err(_) => Err(KScreenListError::FetchScreenListError),
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use std::{os::raw::{c_char, c_void}, ptr::{null, null_mut}};
+
+use crate::kleio::display::event::{KEvent, KEventTouch, KPen};
+
+use super::{KLinuxDisplayServerWaylandConnection, KLinuxDisplayServerWaylandProperty, listener::wl_fixed_to_f64, protocol::*,
+    bind::{wl_proxy, wl_array, wl_proxy_add_listener, wl_proxy_marshal_flags, wl_proxy_get_version, WL_MARSHAL_FLAG_DESTROY}};
+
+/// Pressure given by tablet tools at full pressure.
+const KWAYLAND_TABLET_PRESSURE_MAX : f32 = 65535.0;
+
+/// Tablet tool (ie. pen, eraser) of the tablet seat, with its state accumulated until frame.
+pub(crate) struct KWaylandTabletTool {
+    /// zwp_tablet_tool_v2 proxy.
+    pub(crate) proxy : *mut wl_proxy,
+
+    /// Connection the tool was added to.
+    pub(crate) connection : *mut KLinuxDisplayServerWaylandConnection,
+
+    /// Window the tool is in proximity of. Null if tool is away from windows.
+    pub(crate) focus : *mut KLinuxDisplayServerWaylandProperty,
+
+    /// True if tool is the eraser end of a pen.
+    pub(crate) eraser : bool,
+
+    /// Position of the tool tip in surface.
+    pub(crate) position : (f64, f64),
+
+    /// Pressure of the tool tip, from 0.0 to 1.0.
+    pub(crate) pressure : f32,
+
+    /// Tilt (x, y) of the tool in degrees.
+    pub(crate) tilt : (f32, f32),
+
+    /// Contact changed since last frame. True if tool touched the surface, False if it was lifted.
+    pub(crate) contact : Option<bool>,
+
+    /// True if position, pressure or tilt changed since last frame.
+    pub(crate) changed : bool,
+
+    /// True if tool left proximity. Focus is cleared once frame is sent.
+    pub(crate) left : bool,
+}
+
+impl KWaylandTabletTool {
+    /// Create a new tool of connection with proxy. Tool type is given by tool events.
+    pub(crate) fn new(proxy : *mut wl_proxy, connection : *mut KLinuxDisplayServerWaylandConnection) -> KWaylandTabletTool {
+        KWaylandTabletTool { proxy, connection, focus: null_mut(), eraser: false, position: (0.0, 0.0), pressure: 0.0, tilt: (0.0, 0.0),
+            contact: None, changed: false, left: false }
+    }
+}
+
+/// Tablet pad with its groups, rings and strips, destroyed together when pad is removed.
+pub(crate) struct KWaylandTabletPad {
+    /// zwp_tablet_pad_v2 proxy.
+    pub(crate) proxy : *mut wl_proxy,
+
+    /// Connection the pad was added to.
+    pub(crate) connection : *mut KLinuxDisplayServerWaylandConnection,
+
+    /// Groups, rings and strips of pad in order of creation, with their destroy opcode.
+    pub(crate) objects : Vec<(*mut wl_proxy, u32)>,
+}
+
+impl KWaylandTabletPad {
+    /// Create a new pad of connection with proxy. Groups are given by pad events.
+    pub(crate) fn new(proxy : *mut wl_proxy, connection : *mut KLinuxDisplayServerWaylandConnection) -> KWaylandTabletPad {
+        KWaylandTabletPad { proxy, connection, objects: Vec::new() }
+    }
+
+    /// Destroy pad objects, then pad.
+    pub(crate) unsafe fn destroy(&mut self) {
+        for (proxy, opcode) in self.objects.drain(..).rev() {
+            wl_proxy_marshal_flags(proxy, opcode, null(), wl_proxy_get_version(proxy), WL_MARSHAL_FLAG_DESTROY);
+        }
+        wl_proxy_marshal_flags(self.proxy, ZWP_TABLET_PAD_V2_DESTROY, null(), wl_proxy_get_version(self.proxy), WL_MARSHAL_FLAG_DESTROY);
+    }
+}
+
+/// Returns the connection given as listener data of tablet seat and tablets.
+#[inline(always)]
+unsafe fn get_connection<'a>(data : *mut c_void) -> &'a mut KLinuxDisplayServerWaylandConnection {
+    &mut *(data as *mut KLinuxDisplayServerWaylandConnection)
+}
+
+/// Returns the tool given as listener data of tools.
+#[inline(always)]
+unsafe fn get_tool<'a>(data : *mut c_void) -> &'a mut KWaylandTabletTool {
+    &mut *(data as *mut KWaylandTabletTool)
+}
+
+/// Returns the pad given as listener data of pads and their groups.
+#[inline(always)]
+unsafe fn get_pad<'a>(data : *mut c_void) -> &'a mut KWaylandTabletPad {
+    &mut *(data as *mut KWaylandTabletPad)
+}
+
+unsafe extern "C" fn tablet_ignore(_ : *mut c_void, _ : *mut wl_proxy) {}
+
+unsafe extern "C" fn tablet_ignore_u(_ : *mut c_void, _ : *mut wl_proxy, _ : u32) {}
+
+unsafe extern "C" fn tablet_ignore_uu(_ : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : u32) {}
+
+unsafe extern "C" fn tablet_ignore_uuu(_ : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : u32, _ : u32) {}
+
+unsafe extern "C" fn tablet_ignore_i(_ : *mut c_void, _ : *mut wl_proxy, _ : i32) {}
+
+unsafe extern "C" fn tablet_ignore_ii(_ : *mut c_void, _ : *mut wl_proxy, _ : i32, _ : i32) {}
+
+unsafe extern "C" fn tablet_ignore_s(_ : *mut c_void, _ : *mut wl_proxy, _ : *const c_char) {}
+
+/********************
+* ZWP_TABLET_SEAT_V2 *
+********************/
+/// zwp_tablet_seat_v2 events.
+#[repr(C)]
+pub(crate) struct KWaylandTabletSeatListener {
+    tablet_added : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *mut wl_proxy),
+    tool_added : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *mut wl_proxy),
+    pad_added : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *mut wl_proxy),
+}
+
+/// Listener of tablet seat. Data is a pointer to the [KLinuxDisplayServerWaylandConnection].
+pub(crate) static KWAYLAND_TABLET_SEAT_LISTENER : KWaylandTabletSeatListener = KWaylandTabletSeatListener { tablet_added: tablet_seat_tablet_added,
+    tool_added: tablet_seat_tool_added, pad_added: tablet_seat_pad_added };
+
+unsafe extern "C" fn tablet_seat_tablet_added(data : *mut c_void, _ : *mut wl_proxy, tablet : *mut wl_proxy) {
+    // Tablets are only kept to be destroyed when removed.
+    get_connection(data).tablets.push(tablet);
+    wl_proxy_add_listener(tablet, &KWAYLAND_TABLET_LISTENER as *const _ as *const c_void, data);
+}
+
+unsafe extern "C" fn tablet_seat_tool_added(data : *mut c_void, _ : *mut wl_proxy, tool : *mut wl_proxy) {
+    let connection = get_connection(data);
+    let mut tool = Box::new(KWaylandTabletTool::new(tool, connection));
+    wl_proxy_add_listener(tool.proxy, &KWAYLAND_TABLET_TOOL_LISTENER as *const _ as *const c_void, tool.as_mut() as *mut KWaylandTabletTool as *mut c_void);
+    connection.tablet_tools.push(tool);
+}
+
+unsafe extern "C" fn tablet_seat_pad_added(data : *mut c_void, _ : *mut wl_proxy, pad : *mut wl_proxy) {
+    let connection = get_connection(data);
+    let mut pad = Box::new(KWaylandTabletPad::new(pad, connection));
+    wl_proxy_add_listener(pad.proxy, &KWAYLAND_TABLET_PAD_LISTENER as *const _ as *const c_void, pad.as_mut() as *mut KWaylandTabletPad as *mut c_void);
+    connection.tablet_pads.push(pad);
+}
+
+/***************
+* ZWP_TABLET_V2 *
+***************/
+/// zwp_tablet_v2 events.
+#[repr(C)]
+pub(crate) struct KWaylandTabletListener {
+    name : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *const c_char),
+    id : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32),
+    path : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *const c_char),
+    done : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    removed : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+}
+
+/// Listener of tablets. Data is a pointer to the [KLinuxDisplayServerWaylandConnection].
+pub(crate) static KWAYLAND_TABLET_LISTENER : KWaylandTabletListener = KWaylandTabletListener { name: tablet_ignore_s, id: tablet_ignore_uu,
+    path: tablet_ignore_s, done: tablet_ignore, removed: tablet_removed };
+
+unsafe extern "C" fn tablet_removed(data : *mut c_void, tablet : *mut wl_proxy) {
+    get_connection(data).tablets.retain(|other| *other != tablet);
+    wl_proxy_marshal_flags(tablet, ZWP_TABLET_V2_DESTROY, null(), wl_proxy_get_version(tablet), WL_MARSHAL_FLAG_DESTROY);
+}
+
+/********************
+* ZWP_TABLET_TOOL_V2 *
+********************/
+/// zwp_tablet_tool_v2 events.
+#[repr(C)]
+pub(crate) struct KWaylandTabletToolListener {
+    tool_type : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    hardware_serial : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32),
+    hardware_id_wacom : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32),
+    capability : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    done : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    removed : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    proximity_in : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, *mut wl_proxy, *mut wl_proxy),
+    proximity_out : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    down : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    up : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    motion : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32, i32),
+    pressure : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    distance : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    tilt : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32, i32),
+    rotation : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32),
+    slider : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32),
+    wheel : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, i32, i32),
+    button : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32, u32),
+    frame : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+}
+
+/// Listener of tools. Data is a pointer to the [KWaylandTabletTool].
+pub(crate) static KWAYLAND_TABLET_TOOL_LISTENER : KWaylandTabletToolListener = KWaylandTabletToolListener { tool_type: tool_tool_type,
+    hardware_serial: tablet_ignore_uu, hardware_id_wacom: tablet_ignore_uu, capability: tablet_ignore_u, done: tablet_ignore, removed: tool_removed,
+    proximity_in: tool_proximity_in, proximity_out: tool_proximity_out, down: tool_down, up: tool_up, motion: tool_motion, pressure: tool_pressure,
+    distance: tablet_ignore_u, tilt: tool_tilt, rotation: tablet_ignore_i, slider: tablet_ignore_i, wheel: tablet_ignore_ii, button: tablet_ignore_uuu,
+    frame: tool_frame };
+
+unsafe extern "C" fn tool_tool_type(data : *mut c_void, _ : *mut wl_proxy, tool_type : u32) {
+    get_tool(data).eraser = tool_type == ZWP_TABLET_TOOL_V2_TYPE_ERASER;
+}
+
+unsafe extern "C" fn tool_removed(data : *mut c_void, proxy : *mut wl_proxy) {
+    let tool = data as *mut KWaylandTabletTool;
+    wl_proxy_marshal_flags(proxy, ZWP_TABLET_TOOL_V2_DESTROY, null(), wl_proxy_get_version(proxy), WL_MARSHAL_FLAG_DESTROY);
+
+    // Tool is dropped, nothing else must be done with it.
+    (*(*tool).connection).tablet_tools.retain(|other| !std::ptr::eq(other.as_ref(), tool));
+}
+
+unsafe extern "C" fn tool_proximity_in(data : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : *mut wl_proxy, surface : *mut wl_proxy) {
+    let tool = get_tool(data);
+
+    // Tool events go to the window in proximity until proximity out. Surfaces of other clients are ignored.
+    tool.focus = (*tool.connection).get_window(surface);
+    tool.left = false;
+}
+
+unsafe extern "C" fn tool_proximity_out(data : *mut c_void, _ : *mut wl_proxy) {
+    get_tool(data).left = true;
+}
+
+unsafe extern "C" fn tool_down(data : *mut c_void, _ : *mut wl_proxy, _ : u32) {
+    get_tool(data).contact = Some(true);
+}
+
+unsafe extern "C" fn tool_up(data : *mut c_void, _ : *mut wl_proxy) {
+    get_tool(data).contact = Some(false);
+}
+
+unsafe extern "C" fn tool_motion(data : *mut c_void, _ : *mut wl_proxy, x : i32, y : i32) {
+    let tool = get_tool(data);
+    tool.position = (wl_fixed_to_f64(x), wl_fixed_to_f64(y));
+    tool.changed = true;
+}
+
+unsafe extern "C" fn tool_pressure(data : *mut c_void, _ : *mut wl_proxy, pressure : u32) {
+    let tool = get_tool(data);
+    tool.pressure = (pressure as f32 / KWAYLAND_TABLET_PRESSURE_MAX).min(1.0);
+    tool.changed = true;
+}
+
+unsafe extern "C" fn tool_tilt(data : *mut c_void, _ : *mut wl_proxy, x : i32, y : i32) {
+    let tool = get_tool(data);
+    tool.tilt = ((wl_fixed_to_f64(x) as f32).clamp(-90.0, 90.0), (wl_fixed_to_f64(y) as f32).clamp(-90.0, 90.0));
+    tool.changed = true;
+}
+
+unsafe extern "C" fn tool_frame(data : *mut c_void, _ : *mut wl_proxy, _ : u32) {
+    let tool = get_tool(data);
+
+    // A frame gives a single event with the tool state. Position is normalized to window size.
+    if !tool.focus.is_null() && (tool.changed || tool.contact.is_some()) {
+        let window = &mut *tool.focus;
+        let position = ((tool.position.0 / window.size.0.max(1) as f64) as f32, (tool.position.1 / window.size.1.max(1) as f64) as f32);
+        let pen = KPen::new(position, tool.pressure, tool.tilt, tool.eraser);
+
+        window.events.push_back(KEvent::Touch(match tool.contact {
+            Some(true) => KEventTouch::PenDown(pen),
+            Some(false) => KEventTouch::PenUp(pen),
+            None => KEventTouch::PenMove(pen),
+        }));
+    }
+
+    if tool.left {
+        tool.focus = null_mut();
+        tool.left = false;
+    }
+    tool.contact = None;
+    tool.changed = false;
+}
+
+/*******************
+* ZWP_TABLET_PAD_V2 *
+*******************/
+/// zwp_tablet_pad_v2 events.
+#[repr(C)]
+pub(crate) struct KWaylandTabletPadListener {
+    group : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *mut wl_proxy),
+    path : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *const c_char),
+    buttons : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    done : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    button : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32, u32),
+    enter : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, *mut wl_proxy, *mut wl_proxy),
+    leave : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, *mut wl_proxy),
+    removed : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+}
+
+/// Listener of pads. Data is a pointer to the [KWaylandTabletPad].
+pub(crate) static KWAYLAND_TABLET_PAD_LISTENER : KWaylandTabletPadListener = KWaylandTabletPadListener { group: pad_group, path: tablet_ignore_s,
+    buttons: tablet_ignore_u, done: tablet_ignore, button: tablet_ignore_uuu, enter: pad_enter, leave: pad_leave, removed: pad_removed };
+
+unsafe extern "C" fn pad_group(data : *mut c_void, _ : *mut wl_proxy, group : *mut wl_proxy) {
+    get_pad(data).objects.push((group, ZWP_TABLET_PAD_GROUP_V2_DESTROY));
+    wl_proxy_add_listener(group, &KWAYLAND_TABLET_PAD_GROUP_LISTENER as *const _ as *const c_void, data);
+}
+
+unsafe extern "C" fn pad_enter(_ : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : *mut wl_proxy, _ : *mut wl_proxy) {}
+
+unsafe extern "C" fn pad_leave(_ : *mut c_void, _ : *mut wl_proxy, _ : u32, _ : *mut wl_proxy) {}
+
+unsafe extern "C" fn pad_removed(data : *mut c_void, _ : *mut wl_proxy) {
+    let pad = data as *mut KWaylandTabletPad;
+    (*pad).destroy();
+
+    // Pad is dropped, nothing else must be done with it.
+    (*(*pad).connection).tablet_pads.retain(|other| !std::ptr::eq(other.as_ref(), pad));
+}
+
+/*************************
+* ZWP_TABLET_PAD_GROUP_V2 *
+*************************/
+/// zwp_tablet_pad_group_v2 events. Rings and strips are only kept to be destroyed with their pad.
+#[repr(C)]
+pub(crate) struct KWaylandTabletPadGroupListener {
+    buttons : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *mut wl_array),
+    ring : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *mut wl_proxy),
+    strip : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, *mut wl_proxy),
+    modes : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32),
+    done : unsafe extern "C" fn(*mut c_void, *mut wl_proxy),
+    mode_switch : unsafe extern "C" fn(*mut c_void, *mut wl_proxy, u32, u32, u32),
+}
+
+/// Listener of pad groups. Data is a pointer to the [KWaylandTabletPad] of group.
+pub(crate) static KWAYLAND_TABLET_PAD_GROUP_LISTENER : KWaylandTabletPadGroupListener = KWaylandTabletPadGroupListener { buttons: pad_group_buttons,
+    ring: pad_group_ring, strip: pad_group_strip, modes: tablet_ignore_u, done: tablet_ignore, mode_switch: tablet_ignore_uuu };
+
+unsafe extern "C" fn pad_group_buttons(_ : *mut c_void, _ : *mut wl_proxy, _ : *mut wl_array) {}
+
+unsafe extern "C" fn pad_group_ring(data : *mut c_void, _ : *mut wl_proxy, ring : *mut wl_proxy) {
+    get_pad(data).objects.push((ring, ZWP_TABLET_PAD_RING_V2_DESTROY));
+}
+
+unsafe extern "C" fn pad_group_strip(data : *mut c_void, _ : *mut wl_proxy, strip : *mut wl_proxy) {
+    get_pad(data).objects.push((strip, ZWP_TABLET_PAD_STRIP_V2_DESTROY));
+}
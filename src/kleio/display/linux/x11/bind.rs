@@ -3,7 +3,13 @@ use std::os::raw::{c_uchar, c_char, c_int, c_long, c_uint, c_ulong, c_void};
 
 use super::attributes::{XWindowAttributes, Visual, XSetWindowAttributes, Screen};
 use super::{ Display, Window };
-use super::event::{ XEvent, Atom, XClientMessageEvent};
+use super::event::{ XEvent, Atom, XClientMessageEvent, XKeyEvent, KeySym, XGenericEventCookie, XIEventMask, XIDeviceInfo};
+
+/// Input method opaque pointer.
+pub type XIM = *mut c_void;
+
+/// Input context opaque pointer.
+pub type XIC = *mut c_void;
 
 
 #[link(name = "X11")]
@@ -228,6 +234,113 @@ extern {
     /// Reference(s)
     /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XScreenOfDisplay>
     pub(crate) fn XScreenOfDisplay(display : *mut Display, screen_number : c_int) -> *const Screen;
+
+    /// The XPeekEvent function returns the first event from the event queue, but it does not remove the event from the queue.
+    /// If the queue is empty, XPeekEvent flushes the output buffer and blocks until an event is received.
+    /// 
+    /// Reference(s)
+    /// <https://tronche.com/gui/x/xlib/event-handling/manipulating-event-queue/XPeekEvent.html>
+    pub(crate) fn XPeekEvent(display : *mut Display, event_return : *mut XEvent);
+
+    /// The XLookupKeysym function uses a given keyboard event and the index you specified to return the KeySym from the list
+    /// that corresponds to the KeyCode member in the XKeyPressedEvent or XKeyReleasedEvent structure.
+    /// 
+    /// Reference(s)
+    /// <https://tronche.com/gui/x/xlib/utilities/keyboard/XLookupKeysym.html>
+    pub(crate) fn XLookupKeysym(key_event : *mut XKeyEvent, index : c_int) -> KeySym;
+
+    /// The XLookupString function translates a key event to a KeySym and a Latin-1 string.
+    /// 
+    /// Reference(s)
+    /// <https://tronche.com/gui/x/xlib/utilities/XLookupString.html>
+    pub(crate) fn XLookupString(event_struct : *mut XKeyEvent, buffer_return : *mut c_char, bytes_buffer : c_int, 
+        keysym_return : *mut KeySym, status_in_out : *mut c_void) -> c_int;
+
+    /// The XSetLocaleModifiers function sets the X modifiers for the current locale setting. An empty string
+    /// loads the modifiers of the XMODIFIERS environment variable.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XSetLocaleModifiers>
+    pub(crate) fn XSetLocaleModifiers(modifier_list : *const c_char) -> *mut c_char;
+
+    /// The XOpenIM function opens an input method, matching the current locale and modifiers specification.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XOpenIM>
+    pub(crate) fn XOpenIM(display : *mut Display, db : *mut c_void, res_name : *mut c_char, res_class : *mut c_char) -> XIM;
+
+    /// The XCloseIM function closes the specified input method.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XCloseIM>
+    pub(crate) fn XCloseIM(im : XIM) -> c_int;
+
+    /// The XCreateIC function creates a context within the specified input method. Arguments are a NULL
+    /// terminated list of name and value pairs.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XCreateIC>
+    pub(crate) fn XCreateIC(im : XIM, ...) -> XIC;
+
+    /// The XDestroyIC function destroys the specified input context.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XDestroyIC>
+    pub(crate) fn XDestroyIC(ic : XIC);
+
+    /// The XSetICFocus function allows a client to notify an input method that the focus window attached to the specified input 
+    /// context has received keyboard focus.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XSetICFocus>
+    pub(crate) fn XSetICFocus(ic : XIC);
+
+    /// The XUnsetICFocus function allows a client to notify an input method that the specified input context has lost the keyboard focus.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XUnsetICFocus>
+    pub(crate) fn XUnsetICFocus(ic : XIC);
+
+    /// The Xutf8LookupString function returns the UTF-8 string composed in the input context, and the KeySym, if any.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#Xutf8LookupString>
+    pub(crate) fn Xutf8LookupString(ic : XIC, event : *mut XKeyEvent, buffer_return : *mut c_char, bytes_buffer : c_int,
+        keysym_return : *mut KeySym, status_return : *mut c_int) -> c_int;
+
+    /// If an input method is used, the XFilterEvent function passes the event to the input method. Returns True if the 
+    /// event was consumed by the input method and must be ignored by the client.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XFilterEvent>
+    pub(crate) fn XFilterEvent(event : *mut XEvent, w : Window) -> bool;
+
+    /// The XkbSetDetectableAutoRepeat function sets if the server sends a KeyRelease event for each auto-repeated KeyPress. 
+    /// When detectable, only KeyPress events are sent while a key is held.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/XKB/xkblib.html#Detectable_Autorepeat>
+    pub(crate) fn XkbSetDetectableAutoRepeat(display : *mut Display, detectable : bool, supported_rtrn : *mut bool) -> bool;
+
+    /// The XQueryExtension function determines if the named extension is present. If so, the major opcode, first event
+    /// and first error of the extension are returned.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#XQueryExtension>
+    pub(crate) fn XQueryExtension(display : *mut Display, name : *const c_char, major_opcode_return : *mut c_int, 
+        first_event_return : *mut c_int, first_error_return : *mut c_int) -> bool;
+
+    /// The XGetEventData function retrieves the data of a GenericEvent cookie. Data must be freed with XFreeEventData.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#Generic_Event_Cookies>
+    pub(crate) fn XGetEventData(display : *mut Display, cookie : *mut XGenericEventCookie) -> bool;
+
+    /// The XFreeEventData function frees the data retrieved by XGetEventData.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/X11R7.7/doc/libX11/libX11/libX11.html#Generic_Event_Cookies>
+    pub(crate) fn XFreeEventData(display : *mut Display, cookie : *mut XGenericEventCookie);
     
 }
 
@@ -245,4 +358,32 @@ extern {
     /// Reference(s)
     /// <https://www.x.org/releases/current/doc/fixesproto/fixesproto.txt>
     pub fn XFixesShowCursor(display : *mut Display, window : *mut Window);
-}
\ No newline at end of file
+}
+
+// XInput2 bindings.
+#[link(name = "Xi")]
+extern "C" {
+    /// Announce the XInput2 version supported by the client. Returns the version supported by the server.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/current/doc/man/man3/XIQueryVersion.3.xhtml>
+    pub(crate) fn XIQueryVersion(display : *mut Display, major_version_inout : *mut c_int, minor_version_inout : *mut c_int) -> c_int;
+
+    /// Select XInput2 events of devices on a window. Raw events can only be selected on the root window.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/current/doc/man/man3/XISelectEvents.3.xhtml>
+    pub(crate) fn XISelectEvents(display : *mut Display, window : *mut Window, masks : *mut XIEventMask, num_masks : c_int) -> c_int;
+
+    /// Returns information about devices, with their classes. Must be freed with XIFreeDeviceInfo.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/current/doc/man/man3/XIQueryDevice.3.xhtml>
+    pub(crate) fn XIQueryDevice(display : *mut Display, deviceid : c_int, ndevices_return : *mut c_int) -> *mut XIDeviceInfo;
+
+    /// Free information returned by XIQueryDevice.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/current/doc/man/man3/XIQueryDevice.3.xhtml>
+    pub(crate) fn XIFreeDeviceInfo(info : *mut XIDeviceInfo);
+}
@@ -456,4 +456,41 @@ pub const TrueColor:c_int=4;
 pub const DirectColor:c_int=5;
 /* Byte order  used in imageByteOrder and bitmapBitOrder */
 pub const LSBFirst:c_int=0;
-pub const MSBFirst:c_int=1;
\ No newline at end of file
+pub const MSBFirst:c_int=1;/*****************************************************************
+* INPUT METHOD (Xlib.h)
+*****************************************************************/
+/* Input context attributes names */
+pub const XNInputStyle:&[u8]=b"inputStyle\0";
+pub const XNClientWindow:&[u8]=b"clientWindow\0";
+pub const XNFocusWindow:&[u8]=b"focusWindow\0";
+/* Input styles */
+pub const XIMPreeditNothing:c_long=0x0008;
+pub const XIMStatusNothing:c_long=0x0400;
+/* Lookup status */
+pub const XBufferOverflow:c_int=-1;
+pub const XLookupNone:c_int=1;
+pub const XLookupChars:c_int=2;
+pub const XLookupKeySym:c_int=3;
+pub const XLookupBoth:c_int=4;
+
+/*****************************************************************
+* XINPUT2 (XI2.h)
+*****************************************************************/
+/* Name of the extension */
+pub const XInputExtension:&[u8]=b"XInputExtension\0";
+/* Devices */
+pub const XIAllDevices:c_int=0;
+pub const XIAllMasterDevices:c_int=1;
+/* Device use */
+pub const XISlavePointer:c_int=3;
+/* Device classes */
+pub const XIValuatorClass:c_int=2;
+/* Event types */
+pub const XI_ButtonPress:c_int=4;
+pub const XI_ButtonRelease:c_int=5;
+pub const XI_Motion:c_int=6;
+pub const XI_HierarchyChanged:c_int=11;
+pub const XI_RawMotion:c_int=17;
+pub const XI_TouchBegin:c_int=18;
+pub const XI_TouchUpdate:c_int=19;
+pub const XI_TouchEnd:c_int=20;
@@ -10,6 +10,7 @@ pub type XID = c_ulong;
 pub type Atom = XID;
 pub type Colormap = XID;
 pub type Drawable = XID;
+pub type KeySym = XID;
 
 /// Union 'data' of XClientMessageEvent struct.
 #[repr(C)]
@@ -461,9 +462,7 @@ pub struct XAnyEvent {
 	pub _type:c_int,
 	pub _serial:c_ulong,
 	pub _send_event:bool,
-	pub _Display:Display,
-	pub _the:Display,
-	pub _event:Display,
+	pub _display:*mut Display,
 	pub _window:Window,
 }
 
@@ -530,3 +529,124 @@ pub union XEvent {
 	pub _xcookie:XGenericEventCookie,
 	pub _pad:[c_long; 24],
 }
+
+/// Mask of XInput2 events selected for a device.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIEventMask {
+	pub _deviceid:c_int,
+	pub _mask_len:c_int,
+	pub _mask:*mut c_uchar,
+}
+
+/// Class of an XInput2 device, cast to its type-specific class (ie. XIValuatorClassInfo).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIAnyClassInfo {
+	pub _type:c_int,
+	pub _sourceid:c_int,
+}
+
+/// Valuator (axis) class of an XInput2 device.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIValuatorClassInfo {
+	pub _type:c_int,
+	pub _sourceid:c_int,
+	pub _number:c_int,
+	pub _label:Atom,
+	pub _min:f64,
+	pub _max:f64,
+	pub _value:f64,
+	pub _resolution:c_int,
+	pub _mode:c_int,
+}
+
+/// XInput2 device returned by XIQueryDevice.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIDeviceInfo {
+	pub _deviceid:c_int,
+	pub _name:*mut c_char,
+	pub _use:c_int,
+	pub _attachment:c_int,
+	pub _enabled:c_int,
+	pub _num_classes:c_int,
+	pub _classes:*mut *mut XIAnyClassInfo,
+}
+
+/// Valuators of an XInput2 event. Values are packed for each bit set in mask.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIValuatorState {
+	pub _mask_len:c_int,
+	pub _mask:*mut c_uchar,
+	pub _values:*mut f64,
+}
+
+/// XInput2 raw device event, read from XGenericEventCookie data.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIRawEvent {
+	pub _type:c_int,
+	pub _serial:c_ulong,
+	pub _send_event:bool,
+	pub _display:*mut Display,
+	pub _extension:c_int,
+	pub _evtype:c_int,
+	pub _time:Time,
+	pub _deviceid:c_int,
+	pub _sourceid:c_int,
+	pub _detail:c_int,
+	pub _flags:c_int,
+	pub _valuators:XIValuatorState,
+	pub _raw_values:*mut f64,
+}
+
+/// Buttons held during an XInput2 device event.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIButtonState {
+	pub _mask_len:c_int,
+	pub _mask:*mut c_uchar,
+}
+
+/// Modifiers or group state of an XInput2 device event.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIModifierState {
+	pub _base:c_int,
+	pub _latched:c_int,
+	pub _locked:c_int,
+	pub _effective:c_int,
+}
+
+pub type XIGroupState = XIModifierState;
+
+/// XInput2 device event (ie. touch), read from XGenericEventCookie data.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XIDeviceEvent {
+	pub _type:c_int,
+	pub _serial:c_ulong,
+	pub _send_event:bool,
+	pub _display:*mut Display,
+	pub _extension:c_int,
+	pub _evtype:c_int,
+	pub _time:Time,
+	pub _deviceid:c_int,
+	pub _sourceid:c_int,
+	pub _detail:c_int,
+	pub _root:Window,
+	pub _event:Window,
+	pub _child:Window,
+	pub _root_x:f64,
+	pub _root_y:f64,
+	pub _event_x:f64,
+	pub _event_y:f64,
+	pub _flags:c_int,
+	pub _buttons:XIButtonState,
+	pub _valuators:XIValuatorState,
+	pub _mods:XIModifierState,
+	pub _group:XIGroupState,
+}
@@ -1,11 +1,14 @@
 use std::ffi::{CStr, CString};
 use std::os::raw::{ c_int, c_long, c_uint, c_ulong, c_char, c_uchar, c_short, c_void };
 use std::ptr::null_mut;
+use std::collections::{HashMap, VecDeque};
 use std::{panic::catch_unwind};
 use debug_print::debug_println;
 
 
 use crate::kleio::display::linux::x11::bind::{XFree, XGetAtomName, XFlush, XScreenOfDisplay};
+use crate::kleio::display::linux::keyboard::{get_evdev_scancode, get_keysym_key, get_keysym_char};
+use crate::kleio::display::event::{KKeyInput, KKeyModifiers, KTextInput, KTEXT_INPUT_MAX, KEventTouch, KTouch, KPen};
 use crate::kleio::display::{KWindow, KCursorMode, KWindowFullscreenMode};
 use crate::kleio::display::event::KEventWindow;
 use crate::kleio::display::linux::x11::constant::{GrabModeAsync};
@@ -14,17 +17,22 @@ use crate::kleio::display::{linux::x11::{bind::{XDefaultRootWindow, XCreateSimpl
     ButtonRelease, EnterNotify, FocusIn, FocusOut, KeymapNotify, Expose, GraphicsExpose, NoExpose, VisibilityNotify, 
     CreateNotify, DestroyNotify, UnmapNotify, MapNotify, MapRequest, ReparentNotify, ConfigureNotify, ConfigureRequest, 
     GravityNotify, CirculateNotify, CirculateRequest, PropertyNotify, SelectionClear, SelectionRequest, SelectionNotify, 
-    ColormapNotify, ClientMessage, MappingNotify, GenericEvent}}, event::KEvent, event::KEventMouse, event::KEventKeyboard};
+    ColormapNotify, ClientMessage, MappingNotify, GenericEvent}}, event::KEvent, event::KEventMouse, event::KMouseButton, event::KEventKeyboard};
 
 use self::attributes::{XWindowAttributes};
-use self::bind::{XWarpPointer, XFixesHideCursor, XGrabPointer, XFixesShowCursor, XUngrabPointer, XGetWindowProperty, XStoreName, 
+use self::bind::{XIM, XWarpPointer, XFixesHideCursor, XGrabPointer, XFixesShowCursor, XUngrabPointer, XGetWindowProperty, XStoreName, 
     XChangeProperty, XGetWindowAttributes, XTranslateCoordinates, 
-    XResizeWindow, XMoveWindow, XDestroyWindow};
-use self::constant::{CurrentTime, VisibilityUnobscured, PropModeReplace};
-use self::event::{Atom};
+    XResizeWindow, XMoveWindow, XDestroyWindow, XLookupKeysym, XLookupString, XSetLocaleModifiers, XOpenIM, XCreateIC, 
+    XDestroyIC, XSetICFocus, XUnsetICFocus, Xutf8LookupString, XFilterEvent, XkbSetDetectableAutoRepeat, XQueryExtension, XGetEventData, 
+    XFreeEventData, XIQueryVersion, XISelectEvents, XIQueryDevice, XIFreeDeviceInfo, XInternAtom};
+use self::constant::{CurrentTime, VisibilityUnobscured, PropModeReplace, ShiftMask, LockMask, ControlMask, Mod1Mask, Mod2Mask, Mod4Mask,
+    XNInputStyle, XNClientWindow, XNFocusWindow, XIMPreeditNothing, XIMStatusNothing, XLookupChars, XLookupBoth, XInputExtension, 
+    XIAllDevices, XIAllMasterDevices, XISlavePointer, XIValuatorClass, XI_RawMotion, XI_TouchBegin, XI_TouchUpdate, XI_TouchEnd, 
+    XI_ButtonPress, XI_ButtonRelease, XI_Motion, XI_HierarchyChanged};
+use self::event::{Atom, XEvent, XKeyEvent, KeySym, XIEventMask, XIRawEvent, XIDeviceEvent, XIValuatorClassInfo, XIValuatorState};
 use self::{ bind::{XOpenDisplay, XCloseDisplay, XNextEvent}, constant::{KeyReleaseMask, ButtonReleaseMask, LeaveWindowMask, EnterWindowMask, Button1MotionMask, PointerMotionMask, Button3MotionMask, Button2MotionMask, Button5MotionMask, Button4MotionMask, ButtonMotionMask, StructureNotifyMask, ResizeRedirectMask, VisibilityChangeMask, FocusChangeMask, PropertyChangeMask}};
 
-use super::server::{ Display, Window, KLinuxDisplayServerX11Property };
+use super::server::{ Display, Window, KLinuxDisplayServerX11Property, KLinuxDisplayServerX11Connection, KLinuxDisplayServerX11Pen };
 
 /// Contains X11 contants definition
 #[allow(unused)]                    // Remove unused variable notification
@@ -47,7 +55,7 @@ pub mod screen;
 
 /// Event mask used with x11 to capture and dispatch event.
 const EVENT_MASK : i64 =    KeyPressMask | KeyReleaseMask |             // Keyboard Button Down and Up
-                            ButtonPressMask | ButtonReleaseMask |       // Controller button??? TBD 
+                            ButtonPressMask | ButtonReleaseMask |       // Mouse buttons and wheel
                             EnterWindowMask | LeaveWindowMask |         // Window focus, blur
                             PointerMotionMask | Button1MotionMask | 
                             Button2MotionMask | Button3MotionMask |
@@ -73,6 +81,64 @@ macro_rules! x11_change_property {
 }
 
 
+/// Routing of events read from an X11 connection shared by windows.
+impl KLinuxDisplayServerX11Connection {
+    /// Read events from connection without blocking and queue them to their window.
+    ///
+    /// XInput2 data is claimed when read since it is only kept until next event. Raw motion selected on root window
+    /// goes to the window having focus. Events of unknown windows are dropped.
+    ///
+    /// Pen devices are queried again and selected on windows when devices are plugged or unplugged.
+    #[allow(non_upper_case_globals)]            // Imported C members aren't formatted according to convention.
+    pub(crate) fn route_events(&mut self, display : *mut Display) {
+        unsafe {
+            while XEventsQueued(display, 0) > 0 {
+                let mut xevent = XEvent { _type: 0 };
+                XNextEvent(display, &mut xevent);
+
+                let window = match xevent._type {
+                    GenericEvent => {
+                        if Some(xevent._xcookie._extension) != self.x_xi_opcode || !XGetEventData(display, &mut xevent._xcookie) {
+                            debug_println!("KLinuxDisplayServerX11Connection, GenericEvent({})", xevent._xcookie._extension);
+                            continue;
+                        }
+                        match xevent._xcookie._evtype {
+                            XI_RawMotion => self.x_focus,
+                            XI_HierarchyChanged => {
+                                self.x_xi_pens = KWindow::query_x11_pens(display);
+                                for window in self.x_queues.keys() {
+                                    KWindow::select_x11_pen_events(display, *window as *mut Window, &self.x_xi_pens);
+                                }
+                                XFreeEventData(display, &mut xevent._xcookie);
+                                continue;
+                            },
+                            _ => (*(xevent._xcookie._data as *const XIDeviceEvent))._event,
+                        }
+                    },
+                    FocusIn => {
+                        self.x_focus = xevent._xfocus._window;
+                        xevent._xany._window
+                    },
+                    FocusOut => {
+                        if self.x_focus == xevent._xfocus._window {
+                            self.x_focus = 0;
+                        }
+                        xevent._xany._window
+                    },
+                    _ => xevent._xany._window,
+                };
+
+                match self.x_queues.get_mut(&window) {
+                    Some(queue) => queue.push_back(xevent),
+                    None => if xevent._type == GenericEvent {
+                        XFreeEventData(display, &mut xevent._xcookie);
+                    },
+                }
+            }
+        }
+    }
+}
+
 /// Implementation of privates elements relatives to X11 display server
 #[doc(hidden)]
 impl KWindow {
@@ -86,11 +152,16 @@ impl KWindow {
     }
 
     /// Get the count of events that need handling.
+    ///
+    /// Events read for other windows of the connection are kept in their own queue.
     #[inline(always)]
     pub(super) fn x11_get_event_count(&self) -> usize {
-        unsafe {
-            XEventsQueued(self.display_server.display, 0).try_into().unwrap()
-        }   
+        let mut connection = self.display_server.connection.borrow_mut();
+        connection.x11_connection.route_events(self.display_server.display);
+
+        // Pending event generated by previous event is counted with queued events.
+        let queued = connection.x11_connection.x_queues.get(&(self.display_server.window as Window)).map_or(0, |queue| queue.len());
+        queued + self.display_server.x11_property.x_pending_event.is_some() as usize
     }
 
     /// Set the cursor position
@@ -173,7 +244,6 @@ impl KWindow {
     /// <https://specifications.freedesktop.org/wm-spec/1.3/ar01s05.html>
     #[inline(always)]
     pub(super) fn x11_set_fullscreen(&mut self, mode : KWindowFullscreenMode) {
-        unsafe {
 
             /*
             let xwa = Self::get_x11_window_attributes(self.display_server.display, self.display_server.window);
@@ -193,18 +263,11 @@ impl KWindow {
             }
             */
 
-            // Save window properties for restoration
-            self.display_server.x11_property.restoration_position_size = (self.get_position(), self.get_size());
+        // Save window properties for restoration
+        self.display_server.x11_property.restoration_position_size = (self.get_position(), self.get_size());
 
-            // Destroy current window
-            XDestroyWindow(self.display_server.display, self.display_server.window);
-
-            // Recreate window as fullscreen
-            self.display_server.window = KWindow::create_x11_window(self.display_server.display, &self.display_server.x11_property, (0,0),
-                self.screen_list.get_primary_screen().unwrap().get_current_resolution(), true);           
-            
-            
-        }
+        // Recreate window as fullscreen
+        self.recreate_x11_window((0,0), self.screen_list.get_primary_screen().unwrap().get_current_resolution(), true);
     }
         
     /// Get if x11 display server is supported.
@@ -236,23 +299,135 @@ impl KWindow {
 
     /// Create connection to X11 display server
     #[inline(always)]
-    pub(super) fn create_x11_display_connection() -> (KLinuxDisplayServerX11Property, *mut Display) {
+    pub(crate) fn create_x11_display_connection() -> (KLinuxDisplayServerX11Connection, *mut Display) {
         unsafe {
             // Create display connection
             let display = XOpenDisplay(std::ptr::null());
 
-            // Create x11 Properties
-            let x11_prop = KLinuxDisplayServerX11Property::new(display);
+            // Create x11 connection properties
+            let mut x11_prop = KLinuxDisplayServerX11Connection::empty();
+
+            // Ask server to not send KeyRelease for auto-repeated keys.
+            let mut supported = false;
+            XkbSetDetectableAutoRepeat(display, true, &mut supported);
+            x11_prop.x_detectable_repeat = supported;
 
-            // Return properties and display connection
+            // Open input method used to compose text. Modifiers are read from XMODIFIERS.
+            XSetLocaleModifiers(c"".as_ptr());
+            x11_prop.x_im = XOpenIM(display, null_mut(), null_mut(), null_mut());
+
+            // Select raw motion of master pointers used by acceleration cursor mode.
+            Self::query_x11_xinput(display, &mut x11_prop);
+
+            // Return connection properties and display connection
             (x11_prop, display)
         }
     }
 
+    /// Query XInput2 support and pen devices, then select raw motion and device changes on root window.
+    /// 
+    /// Raw motion and pen events need XInput2 2.0 and touch events XInput2 2.2. Nothing is selected if XInput2 isn't supported.
+    /// 
+    /// Reference(s)
+    /// <https://www.x.org/releases/current/doc/inputproto/XI2proto.txt>
+    fn query_x11_xinput(display : *mut Display, x11_prop : &mut KLinuxDisplayServerX11Connection) {
+        unsafe {
+            let (mut opcode, mut event, mut error) = (0, 0, 0);
+            if !XQueryExtension(display, XInputExtension.as_ptr() as *const c_char, &mut opcode, &mut event, &mut error) {
+                return;
+            }
+
+            // Server answers with the highest version it supports up to the version asked.
+            let (mut major, mut minor) = (2, 2);
+            if XIQueryVersion(display, &mut major, &mut minor) != 0 || major < 2 {
+                return;
+            }
+
+            if Self::select_x11_xinput_events(display, XDefaultRootWindow(display), XIAllMasterDevices, &[XI_RawMotion]) {
+                x11_prop.x_xi_opcode = Some(opcode);
+                x11_prop.x_xi_touch = minor >= 2;
+                x11_prop.x_xi_pens = Self::query_x11_pens(display);
+                Self::select_x11_xinput_events(display, XDefaultRootWindow(display), XIAllDevices, &[XI_HierarchyChanged]);
+            }
+        }
+    }
+
+    /// Query XInput2 pen (stylus) devices, recognized as slave pointers with a pressure valuator.
+    /// 
+    /// Drivers (ie. wacom, libinput) add the eraser end of a pen as another device with "eraser" in its name. Tilt valuators
+    /// of these drivers are in degrees.
+    pub(crate) fn query_x11_pens(display : *mut Display) -> HashMap<c_int, KLinuxDisplayServerX11Pen> {
+        unsafe {
+            let mut pens = HashMap::new();
+            let labels = [c"Abs Pressure", c"Abs Tilt X", c"Abs Tilt Y"].map(|label| XInternAtom(display, label.as_ptr(), true));
+            if labels[0] == 0 {
+                // No device ever reported pressure.
+                return pens;
+            }
+
+            let mut count : c_int = 0;
+            let devices = XIQueryDevice(display, XIAllDevices, &mut count);
+            if devices.is_null() {
+                return pens;
+            }
+
+            for device in std::slice::from_raw_parts(devices, count.max(0) as usize) {
+                if device._use != XISlavePointer {
+                    continue;
+                }
+
+                let mut pressure = None;
+                let mut tilt = (None, None);
+                for class in std::slice::from_raw_parts(device._classes, device._num_classes.max(0) as usize) {
+                    if (**class)._type != XIValuatorClass {
+                        continue;
+                    }
+                    let valuator = &*(*class as *const XIValuatorClassInfo);
+                    let number = valuator._number.max(0) as usize;
+                    if valuator._label == labels[0] {
+                        pressure = Some((number, valuator._min, valuator._max));
+                    } else if valuator._label == labels[1] {
+                        tilt.0 = Some(number);
+                    } else if valuator._label == labels[2] {
+                        tilt.1 = Some(number);
+                    }
+                }
+
+                if let Some(pressure_valuator) = pressure {
+                    let eraser = !device._name.is_null() && CStr::from_ptr(device._name).to_string_lossy().to_lowercase().contains("eraser");
+                    pens.insert(device._deviceid, KLinuxDisplayServerX11Pen { pressure_valuator, tilt_valuators: tilt, eraser, pressure: 0.0, tilt: (0.0, 0.0) });
+                }
+            }
+
+            XIFreeDeviceInfo(devices);
+            pens
+        }
+    }
+
+    /// Select XInput2 tip and motion events of pen devices on a window.
+    pub(crate) fn select_x11_pen_events(display : *mut Display, window : *mut Window, pens : &HashMap<c_int, KLinuxDisplayServerX11Pen>) {
+        for device in pens.keys() {
+            Self::select_x11_xinput_events(display, window, *device, &[XI_ButtonPress, XI_ButtonRelease, XI_Motion]);
+        }
+    }
+
+    /// Select XInput2 events of a device (or all master devices) on a window. Returns True if selected.
+    fn select_x11_xinput_events(display : *mut Display, window : *mut Window, device : c_int, events : &[c_int]) -> bool {
+        unsafe {
+            // Mask is a bit array indexed by event type.
+            let mut mask = [0 as c_uchar; 4];
+            for event in events {
+                mask[*event as usize >> 3] |= 1 << (event & 7);
+            }
+            let mut event_mask = XIEventMask { _deviceid: device, _mask_len: mask.len() as c_int, _mask: mask.as_mut_ptr() };
+            XISelectEvents(display, window, &mut event_mask, 1) == 0
+        }
+    }
+
     /// Create x11 Window according to position, size and if fullscreen or not.
     #[inline(always)]
-    pub(super) fn create_x11_window(display : *mut Display, x11_prop : &KLinuxDisplayServerX11Property, position : (i32, i32), 
-        size : (u32,u32), fullscreen : bool) -> *mut Window {
+    pub(crate) fn create_x11_window(display : *mut Display, x11_prop : &KLinuxDisplayServerX11Property, x11_connection : &KLinuxDisplayServerX11Connection,
+        position : (i32, i32), size : (u32,u32), fullscreen : bool) -> *mut Window {
         unsafe {
             let window = XCreateSimpleWindow(display, XDefaultRootWindow(display), position.0,position.1,
                     size.0, size.1, 4, 0, 0);
@@ -277,6 +452,10 @@ impl KWindow {
 
             // Mask of events to receive
             XSelectInput(display, window, EVENT_MASK);
+            if x11_connection.x_xi_touch {
+                Self::select_x11_xinput_events(display, window, XIAllMasterDevices, &[XI_TouchBegin, XI_TouchUpdate, XI_TouchEnd]);
+            }
+            Self::select_x11_pen_events(display, window, &x11_connection.x_xi_pens);
 
             // Flush buffer
             XFlush(display);
@@ -288,22 +467,60 @@ impl KWindow {
 
 
 
-    /// Restore the [KWindow], undoing any minimized, maximized and/or fullscreen status.
+    /// Create the input context of window used to compose text. Previous input context is destroyed.
+    /// 
+    /// No input context is created if no input method is available. Text is then written according to keysym only.
     #[inline(always)]
-    pub(super) fn x11_restore(&mut self) {
+    pub(crate) fn create_x11_input_context(window : *mut Window, x_im : XIM, x11_prop : &mut KLinuxDisplayServerX11Property) {
         unsafe {
-        let states = self.get_x11_window_states();
+            if !x11_prop.x_ic.is_null() {
+                XDestroyIC(x11_prop.x_ic);
+                x11_prop.x_ic = null_mut();
+            }
 
-            // Destroy current window
+            if !x_im.is_null() {
+                x11_prop.x_ic = XCreateIC(x_im, XNInputStyle.as_ptr(), (XIMPreeditNothing | XIMStatusNothing) as c_ulong,
+                    XNClientWindow.as_ptr(), window, XNFocusWindow.as_ptr(), window, null_mut::<c_void>());
+            }
+        }
+    }
+
+    /// Destroy current window and create it again with position, size and if fullscreen or not.
+    ///
+    /// Events queued for the destroyed window are dropped.
+    fn recreate_x11_window(&mut self, position : (i32, i32), size : (u32, u32), fullscreen : bool) {
+        unsafe {
+            // Destroy input context and current window
+            if !self.display_server.x11_property.x_ic.is_null() {
+                XDestroyIC(self.display_server.x11_property.x_ic);
+                self.display_server.x11_property.x_ic = null_mut();
+            }
             XDestroyWindow(self.display_server.display, self.display_server.window);
 
-            // Recreate window as normal
-            self.display_server.window = KWindow::create_x11_window(self.display_server.display, &self.display_server.x11_property, self.display_server.x11_property.restoration_position_size.0,
-                self.display_server.x11_property.restoration_position_size.1, false);   
+            let mut connection = self.display_server.connection.borrow_mut();
+            for mut xevent in connection.x11_connection.x_queues.remove(&(self.display_server.window as Window)).unwrap_or_default() {
+                if xevent._type == GenericEvent {
+                    XFreeEventData(self.display_server.display, &mut xevent._xcookie);
+                }
+            }
+
+            self.display_server.window = KWindow::create_x11_window(self.display_server.display, &self.display_server.x11_property, &connection.x11_connection,
+                position, size, fullscreen);
+            KWindow::create_x11_input_context(self.display_server.window, connection.x11_connection.x_im, &mut self.display_server.x11_property);
+            connection.x11_connection.x_queues.insert(self.display_server.window as Window, VecDeque::new());
+        }
+    }
 
-            self.set_position(self.display_server.x11_property.restoration_position_size.0);
-        }        
+    /// Restore the [KWindow], undoing any minimized, maximized and/or fullscreen status.
+    #[inline(always)]
+    pub(super) fn x11_restore(&mut self) {
+        let states = self.get_x11_window_states();
+
+        // Recreate window as normal
+        self.recreate_x11_window(self.display_server.x11_property.restoration_position_size.0,
+            self.display_server.x11_property.restoration_position_size.1, false);
 
+        self.set_position(self.display_server.x11_property.restoration_position_size.0);
     }
 
     // Pop an event from the queue
@@ -312,20 +529,40 @@ impl KWindow {
     pub(super) fn x11_poll_event(&mut self) -> KEvent {
         unsafe {
 
-            XNextEvent(self.display_server.display, &mut self.display_server.x11_property.x_event);
+            // Return event generated by previous event first.
+            if let Some(event) = self.display_server.x11_property.x_pending_event.take() {
+                return event;
+            }
+
+            // Events of window were read from connection when counted.
+            let queued = self.display_server.connection.borrow_mut().x11_connection.x_queues.get_mut(&(self.display_server.window as Window))
+                .and_then(|queue| queue.pop_front());
+            match queued {
+                Some(xevent) => self.display_server.x11_property.x_event = xevent,
+                None => return KEvent::None,
+            }
+
+            // Let the input method consume events used to compose text (ie. dead keys).
+            if XFilterEvent(&mut self.display_server.x11_property.x_event, 0) {
+                return KEvent::None;
+            }
+
             let xevent = self.display_server.x11_property.x_event; 
             
             match xevent._type {
 
                 // Keyboard key pressed
-                KeyPress => KEvent::Keyboard(KEventKeyboard::KeyDown(xevent._xkey._keycode)),
+                KeyPress => self.get_x11_key_down_event(xevent._xkey),
 
                 // Keyboard key release
-                KeyRelease=> KEvent::Keyboard(KEventKeyboard::KeyUp(xevent._xkey._keycode)),
+                KeyRelease=> self.get_x11_key_up_event(xevent._xkey),
+
 
+                // Mouse button pressed or wheel scrolled
+                ButtonPress=> Self::get_x11_button_event(xevent._xbutton._button, true, (xevent._xbutton._x, xevent._xbutton._y)),
 
-                ButtonPress=> { debug_println!("KWindow({:p}), ButtonPress({})", self, xevent._type); KEvent::Unknown },
-                ButtonRelease=> { debug_println!("KWindow({:p}), ButtonRelease({})", self, xevent._type); KEvent::Unknown },
+                // Mouse button released
+                ButtonRelease=> Self::get_x11_button_event(xevent._xbutton._button, false, (xevent._xbutton._x, xevent._xbutton._y)),
 
                 // Cursor moved
                 MotionNotify=> {    
@@ -343,10 +580,26 @@ impl KWindow {
                 LeaveNotify=> KEvent::Window(KEventWindow::CursorLeave()),
 
                 // Window got focus
-                FocusIn=> KEvent::Window(KEventWindow::Focus()),
+                FocusIn=> {
+                    if !self.display_server.x11_property.x_ic.is_null() {
+                        XSetICFocus(self.display_server.x11_property.x_ic);
+                    }
+                    self.display_server.x11_property.x_focused = true;
+                    KEvent::Window(KEventWindow::Focus())
+                },
 
                 // Window lost focus
-                FocusOut=> KEvent::Window(KEventWindow::Blur()),
+                FocusOut=> {
+                    if !self.display_server.x11_property.x_ic.is_null() {
+                        XUnsetICFocus(self.display_server.x11_property.x_ic);
+                    }
+
+                    self.display_server.x11_property.x_focused = false;
+
+                    // Keys released while not focused aren't received.
+                    self.display_server.x11_property.x_pressed_keys = [false; 256];
+                    KEvent::Window(KEventWindow::Blur())
+                },
 
                 KeymapNotify=> { debug_println!("KWindow({:p}), KeymapNotify({})", self, xevent._type); KEvent::Unknown },
 
@@ -444,12 +697,248 @@ impl KWindow {
                 ColormapNotify=> { debug_println!("KWindow({:p}), ColormapNotify({})", self, xevent._type); KEvent::Unknown },
                 ClientMessage=> { debug_println!("KWindow({:p}), ClientMessage({})", self, xevent._type); KEvent::Unknown },
                 MappingNotify=> { debug_println!("KWindow({:p}), MappingNotify({})", self, xevent._type); KEvent::Unknown },
-                GenericEvent=> { debug_println!("KWindow({:p}), GenericEvent({})", self, xevent._type); KEvent::Unknown },
+                // Extension events (ie. XInput2 raw motion)
+                GenericEvent=> self.get_x11_generic_event(),
                 _ => { debug_println!("KWindow({:p}), _({})", self, xevent._type); KEvent::Unknown },
             }
         }
     }
 
+    /// Get the [KEvent] of an X11 extension event.
+    /// 
+    /// XInput2 raw motion gives [KEventMouse::RawMotion] when cursor mode is [KCursorMode::Acceleration] and window has focus. 
+    /// Raw values are read before pointer acceleration is applied.
+    /// 
+    /// XInput2 touch events give [KEventTouch]. X11 doesn't cancel touches selected by the window.
+    /// 
+    /// XInput2 events of pen devices give [KEventTouch::PenDown], [KEventTouch::PenMove] and [KEventTouch::PenUp].
+    #[inline(always)]
+    #[allow(non_upper_case_globals)]            // Imported C members aren't formatted according to convention.
+    fn get_x11_generic_event(&mut self) -> KEvent {
+        unsafe {
+            // Only XInput2 events with claimed data are routed to windows.
+            let mut cookie = self.display_server.x11_property.x_event._xcookie;
+
+            let event = match cookie._evtype {
+                XI_RawMotion if self.display_server.x11_property.x_focused && self.property.cursor.mode == KCursorMode::Acceleration => {
+                    let motion = Self::get_x11_raw_motion(&*(cookie._data as *const XIRawEvent));
+                    if motion == (0.0, 0.0) {
+                        KEvent::None
+                    } else {
+                        KEvent::Mouse(KEventMouse::RawMotion(motion.0, motion.1))
+                    }
+                },
+                XI_TouchBegin | XI_TouchUpdate | XI_TouchEnd => {
+                    // Detail is the touch id. Position is normalized to window size.
+                    let device = &*(cookie._data as *const XIDeviceEvent);
+                    let touch = KTouch::new(device._detail as u32 as u64, ((device._event_x / self.property.size.0.max(1) as f64) as f32, 
+                        (device._event_y / self.property.size.1.max(1) as f64) as f32));
+                    KEvent::Touch(match cookie._evtype {
+                        XI_TouchBegin => KEventTouch::Begin(touch),
+                        XI_TouchUpdate => KEventTouch::Move(touch),
+                        _ => KEventTouch::End(touch),
+                    })
+                },
+                XI_ButtonPress | XI_ButtonRelease | XI_Motion => self.get_x11_pen_event(cookie._evtype, &*(cookie._data as *const XIDeviceEvent)),
+                _ => KEvent::None,
+            };
+
+            XFreeEventData(self.display_server.display, &mut cookie);
+            event
+        }
+    }
+
+    /// Get the [KEventTouch] of an XInput2 pen device event. Tip is button 1, other buttons (ie. barrel) are ignored.
+    /// 
+    /// Pressure is normalized from its valuator range. Last pressure and tilt are kept for valuators not in event.
+    #[allow(non_upper_case_globals)]            // Imported C members aren't formatted according to convention.
+    fn get_x11_pen_event(&mut self, evtype : c_int, device : &XIDeviceEvent) -> KEvent {
+        let mut connection = self.display_server.connection.borrow_mut();
+        let pen = match connection.x11_connection.x_xi_pens.get_mut(&device._deviceid) {
+            Some(pen) if evtype == XI_Motion || device._detail == 1 => pen,
+            _ => return KEvent::None,
+        };
+
+        let (number, min, max) = pen.pressure_valuator;
+        if let Some(value) = Self::get_x11_valuator(&device._valuators, number) {
+            pen.pressure = if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) as f32 } else { 0.0 };
+        }
+        if let Some(value) = pen.tilt_valuators.0.and_then(|number| Self::get_x11_valuator(&device._valuators, number)) {
+            pen.tilt.0 = (value as f32).clamp(-90.0, 90.0);
+        }
+        if let Some(value) = pen.tilt_valuators.1.and_then(|number| Self::get_x11_valuator(&device._valuators, number)) {
+            pen.tilt.1 = (value as f32).clamp(-90.0, 90.0);
+        }
+
+        // Position is normalized to window size.
+        let pen = KPen::new(((device._event_x / self.property.size.0.max(1) as f64) as f32, (device._event_y / self.property.size.1.max(1) as f64) as f32),
+            pen.pressure, pen.tilt, pen.eraser);
+        KEvent::Touch(match evtype {
+            XI_ButtonPress => KEventTouch::PenDown(pen),
+            XI_ButtonRelease => KEventTouch::PenUp(pen),
+            _ => KEventTouch::PenMove(pen),
+        })
+    }
+
+    /// Get the value of a valuator of an XInput2 device event. None if valuator isn't in event.
+    /// 
+    /// Values are packed : a value is only present for each valuator bit set in mask.
+    #[inline(always)]
+    fn get_x11_valuator(valuators : &XIValuatorState, number : usize) -> Option<f64> {
+        unsafe {
+            let mask = std::slice::from_raw_parts(valuators._mask, valuators._mask_len.max(0) as usize);
+            if number >= mask.len() * 8 || mask[number >> 3] & (1 << (number & 7)) == 0 {
+                return None;
+            }
+
+            // Index of value is the count of bits set before valuator.
+            let index = (0..number).filter(|bit| mask[bit >> 3] & (1 << (bit & 7)) != 0).count();
+            Some(*valuators._values.add(index))
+        }
+    }
+
+    /// Get the (x, y) motion of an XInput2 raw event. Valuators 0 and 1 are x and y. 
+    /// 
+    /// Raw values are packed : a value is only present for each valuator bit set in mask.
+    #[inline(always)]
+    fn get_x11_raw_motion(raw : &XIRawEvent) -> (f64, f64) {
+        unsafe {
+            let mask = std::slice::from_raw_parts(raw._valuators._mask, raw._valuators._mask_len.max(0) as usize);
+            let mut motion = (0.0, 0.0);
+            let mut value = raw._raw_values;
+
+            for valuator in 0..(mask.len() * 8).min(2) {
+                if mask[valuator >> 3] & (1 << (valuator & 7)) != 0 {
+                    match valuator {
+                        0 => motion.0 = *value,
+                        _ => motion.1 = *value,
+                    }
+                    value = value.add(1);
+                }
+            }
+
+            motion
+        }
+    }
+
+    /// Get the [KEvent] of an X11 key pressed.
+    /// 
+    /// Text composed by the key is kept as pending [KEventKeyboard::TextInput] returned at next poll. Control characters 
+    /// (ie. Enter, Backspace, Ctrl+C) don't compose text.
+    #[inline(always)]
+    fn get_x11_key_down_event(&mut self, mut xkey : XKeyEvent) -> KEvent {
+        unsafe {
+            let x11_property = &mut self.display_server.x11_property;
+
+            // Key already pressed is repeated.
+            let keycode = (xkey._keycode as usize).min(255);
+            let repeat = x11_property.x_pressed_keys[keycode];
+            x11_property.x_pressed_keys[keycode] = true;
+
+            // Compose text with input context if any, keysym otherwise.
+            let mut buffer = [0 as c_char; KTEXT_INPUT_MAX];
+            let mut keysym : KeySym = 0;
+            let text = if x11_property.x_ic.is_null() {
+                XLookupString(&mut xkey, buffer.as_mut_ptr(), buffer.len() as c_int, &mut keysym, null_mut());
+                get_keysym_char(keysym as u32).map(String::from).unwrap_or_default()
+            } else {
+                let mut status : c_int = 0;
+                let length = Xutf8LookupString(x11_property.x_ic, &mut xkey, buffer.as_mut_ptr(), buffer.len() as c_int, &mut keysym, &mut status);
+                if status == XLookupChars || status == XLookupBoth {
+                    let bytes = std::slice::from_raw_parts(buffer.as_ptr() as *const u8, length as usize);
+                    String::from_utf8_lossy(bytes).into_owned()
+                } else {
+                    String::new()
+                }
+            };
+
+            if !text.is_empty() && !text.chars().any(char::is_control) {
+                x11_property.x_pending_event = Some(KEvent::Keyboard(KEventKeyboard::TextInput(KTextInput::new(&text))));
+            }
+
+            KEvent::Keyboard(KEventKeyboard::KeyDown(Self::get_x11_key_input(&mut xkey, repeat)))
+        }
+    }
+
+    /// Get the [KEvent] of an X11 key released.
+    /// 
+    /// Without detectable auto-repeat, a held key sends a KeyRelease immediately followed by a KeyPress with the same time. 
+    /// That KeyRelease is ignored so the KeyPress is flagged as repeat.
+    #[inline(always)]
+    #[allow(non_upper_case_globals)]            // Imported C members aren't formatted according to convention.
+    fn get_x11_key_up_event(&mut self, mut xkey : XKeyEvent) -> KEvent {
+        unsafe {
+            let mut connection = self.display_server.connection.borrow_mut();
+            if !connection.x11_connection.x_detectable_repeat {
+                connection.x11_connection.route_events(self.display_server.display);
+                let next = connection.x11_connection.x_queues.get(&(self.display_server.window as Window)).and_then(|queue| queue.front());
+                if next.is_some_and(|next| next._type == KeyPress && next._xkey._keycode == xkey._keycode && next._xkey._time == xkey._time) {
+                    return KEvent::None;
+                }
+            }
+            drop(connection);
+
+            self.display_server.x11_property.x_pressed_keys[(xkey._keycode as usize).min(255)] = false;
+            KEvent::Keyboard(KEventKeyboard::KeyUp(Self::get_x11_key_input(&mut xkey, false)))
+        }
+    }
+
+    /// Get the [KKeyInput] of an X11 key event.
+    #[inline(always)]
+    fn get_x11_key_input(xkey : &mut XKeyEvent, repeat : bool) -> KKeyInput {
+        unsafe {
+            // X11 keycodes are evdev key codes + 8. Logical key is taken without shift (index 0).
+            let scancode = get_evdev_scancode(xkey._keycode.saturating_sub(8));
+            let key = get_keysym_key(XLookupKeysym(xkey, 0) as u32);
+
+            KKeyInput::new(scancode, key, Self::get_x11_key_modifiers(xkey._state), repeat)
+        }
+    }
+
+    /// Get the [KKeyModifiers] of an X11 key event state.
+    /// 
+    /// Mod1 is Alt, Mod2 is Num Lock and Mod4 is Super on most keyboard mappings.
+    #[inline(always)]
+    fn get_x11_key_modifiers(state : c_uint) -> KKeyModifiers {
+        let state = state as c_long;
+        let mut modifiers = KKeyModifiers::NONE;
+
+        for (mask, modifier) in [(ShiftMask, KKeyModifiers::SHIFT), (ControlMask, KKeyModifiers::CONTROL), (Mod1Mask, KKeyModifiers::ALT),
+            (Mod4Mask, KKeyModifiers::SUPER), (LockMask, KKeyModifiers::CAPS_LOCK), (Mod2Mask, KKeyModifiers::NUM_LOCK)] {
+            if state & mask != 0 {
+                modifiers |= modifier;
+            }
+        }
+
+        modifiers
+    }
+
+    /// Get the [KEvent] of an X11 button pressed or released at position.
+    /// 
+    /// X11 buttons 4 to 7 are wheel notches (up, down, left, right) sent as a press and a release. Only the press is kept as a
+    /// [KEventMouse::Wheel]. Buttons 8 and 9 are the back and forward side buttons.
+    #[inline(always)]
+    fn get_x11_button_event(button : c_uint, pressed : bool, position : (i32, i32)) -> KEvent {
+        let button = match button {
+            1 => KMouseButton::Left,
+            2 => KMouseButton::Middle,
+            3 => KMouseButton::Right,
+            4 => return if pressed { KEvent::Mouse(KEventMouse::Wheel(0, 1)) } else { KEvent::None },
+            5 => return if pressed { KEvent::Mouse(KEventMouse::Wheel(0, -1)) } else { KEvent::None },
+            6 => return if pressed { KEvent::Mouse(KEventMouse::Wheel(-1, 0)) } else { KEvent::None },
+            7 => return if pressed { KEvent::Mouse(KEventMouse::Wheel(1, 0)) } else { KEvent::None },
+            8 => KMouseButton::Back,
+            9 => KMouseButton::Forward,
+            button => KMouseButton::Other(button.min(u8::MAX as c_uint) as u8),
+        };
+
+        if pressed {
+            KEvent::Mouse(KEventMouse::ButtonDown(button, position))
+        } else {
+            KEvent::Mouse(KEventMouse::ButtonUp(button, position))
+        }
+    }
+
     /// Get the real, translated position of KWindow.
     /// 
     /// Reference(s)
@@ -5,6 +5,11 @@ pub use property::KWindowProperty as KWindowProperty;
 pub use property::KCursorMode as KCursorMode;
 pub use property::KCursorProperty as KCursorProperty;
 pub use property::KWindowFullscreenMode as KWindowFullscreenMode;
+pub use controller::KController as KController;
+pub use controller::KControllerBattery as KControllerBattery;
+pub use controller::KControllerBatteryState as KControllerBatteryState;
+#[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+pub use context::KDisplayContext as KDisplayContext;
 
 /// Window and cursor properties.
 #[doc(hidden)]
@@ -14,6 +19,15 @@ pub mod property;
 #[doc(hidden)]
 pub mod window;
 
+/// [KController] handle to controllers outputs and battery.
+#[doc(hidden)]
+pub mod controller;
+
+/// [KDisplayContext] display server connection shared by windows.
+#[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+#[doc(hidden)]
+pub mod context;
+
 /// Hardware screen details and supported resolutions.
 pub mod screen;
 
@@ -1,12 +1,15 @@
 use super::screen::KScreen;
 #[allow(unused_imports)]
-use super::{ KWindow, event::{ KEvent, KEventMouse }};
+use super::{ KWindow, event::{ KEvent, KEventMouse, KEventUser, KEventUserPoster }};
 
 /// [KWindow] properties.
 #[cfg(any(doc, any(target_os = "linux", target_os = "windows", target_os = "macos")))]
 #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))))]
 pub struct KWindowProperty {
 
+    /// Window unique id
+    pub(super) id : u64,
+
     /// Window title
     pub(super) title : String,
 
@@ -31,6 +34,9 @@ pub struct KWindowProperty {
     /// Window is fullscreen
     pub(super) fullscreen : bool,
 
+    /// [KEventUser] posted, dispatched at next [KWindow::dispatch_events()].
+    pub(super) posted : KEventUserPoster,
+
 }
 
 /// Enumeration of possible [KWindow] cursor mode used for [KEvent].
@@ -45,6 +51,8 @@ pub enum KCursorMode {
 
     /// [KEventMouse] events will give the (x,y) acceleration of the cursor instead of the position.
     /// 
+    /// Unaccelerated device motion is also sent as [KEventMouse::RawMotion] when the display server supports it (X11 XInput2, Wayland relative pointer).
+    /// 
     /// Usually used for 3d camera and direct mouse inputs.
     Acceleration,
 }
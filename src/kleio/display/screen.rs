@@ -32,7 +32,12 @@ impl KScreenList {
             super::linux::server::KLinuxDisplayServerProvider::Default => Err(KScreenListError::DefaultLinuxDisplayProviderError),
             super::linux::server::KLinuxDisplayServerProvider::Wayland => {
                 match super::linux::wayland::screen::get_wayland_screen() {
-                    Ok(screen_list) => Ok(KScreenList { screen_list, width: 0, height: 0 }),
+                    Ok(screen_list) => {
+                        // Desktop size is the bottom right corner of the farthest screen.
+                        let width = screen_list.iter().map(|s| (s.position.0 + s.resolution.0 as i32).max(0) as u32).max().unwrap_or(0);
+                        let height = screen_list.iter().map(|s| (s.position.1 + s.resolution.1 as i32).max(0) as u32).max().unwrap_or(0);
+                        Ok(KScreenList { screen_list, width, height })
+                    },
                     Err(err) => Err(err),
                 }
             },
@@ -42,6 +47,13 @@ impl KScreenList {
                     Err(err) => Err(err),
                 }
             },
+            super::linux::server::KLinuxDisplayServerProvider::Headless => {
+                match super::linux::headless::get_headless_screen() {
+                    Ok(screen_list) => Ok(KScreenList { screen_list, width: super::linux::headless::KLINUX_HEADLESS_SCREEN_RESOLUTION.0,
+                        height: super::linux::headless::KLINUX_HEADLESS_SCREEN_RESOLUTION.1 }),
+                    Err(err) => Err(err),
+                }
+            },
         }
         
     }
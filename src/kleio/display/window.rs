@@ -1,15 +1,24 @@
-use crate::kleio::display::event::{KEventWindow, KEventKeyboard, KEventController};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use crate::kleio::display::event::{KEventWindow, KEventKeyboard, KEventController, KEventUser, KEventUserPoster};
 use debug_print::debug_println;
 use crate::error::OlympusError;
 use crate::error::KWindowError;
 
-use super::{event::{ KEvent, KEventDispatcher}, screen::KScreenList, KWindowProperty};
+use super::{event::{ KEvent, KEventDispatcher}, screen::KScreenList, KWindowProperty, KController};
 use super::{ KCursorMode };
 use super::KWindowFullscreenMode;
 
+#[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+use super::KDisplayContext;
+
 #[allow(unused_imports)]
 use super::event::{ KEventMouse, KEventReceiver };
 
+#[allow(unused_imports)]
+use crate::error::{KControllerError, KControllerMappingError};
+
 /// Minimum [KWindow] width allowed.
 pub const KWINDOW_MIN_WIDTH : u32 = 1;
 
@@ -22,6 +31,9 @@ pub const KWINDOW_MAX_WIDTH : u32 = 65535;
 /// Maximum [KWindow] height allowed.
 pub const KWINDOW_MAX_HEIGHT : u32 = 65535;
 
+/// Id given to the next created [KWindow]. Id 0 is [KEVENT_NO_WINDOW](super::event::KEVENT_NO_WINDOW).
+static KWINDOW_NEXT_ID : AtomicU64 = AtomicU64::new(1);
+
 /// Create and manage a window frame for display.
 /// 
 /// [KWindow] broadcasts [KEvent] to multiple [KEventReceiver] via [KWindow::dispatch_events()].
@@ -66,6 +78,28 @@ impl KWindow {
 
     }
 
+    /// Create a new sized [KWindow] sharing the display server connection of a [KDisplayContext].
+    /// 
+    /// Windows of a context receive their own events and dispatch them with their own id (see [KWindow::get_id()]).
+    /// 
+    /// Return New [`KWindow`].
+    /// 
+    /// # Error(s)
+    /// Returns [OlympusError::KWindow(KWindowError::NotSupported)] if display server can't create the window.
+    /// 
+    /// Returns [OlympusError::KWindow(KWindowError::SizeError)] if width and/or height aren't within allowed boundaries.
+    #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+    pub fn from_context(context : &KDisplayContext, width:u32, height:u32) -> Result<KWindow, OlympusError> {
+        #![cfg_attr(docsrs, doc(cfg(any(target_os = "linux"))))]
+
+        // Make sure dimension are within boundaries.
+        if KWindow::is_size_within_boundaries(width, height) {
+            KWindow::__from_context(context, width, height)     // Private platform inline implementation
+        } else {
+            Err(OlympusError::KWindow(KWindowError::SizeError))
+        }
+    }
+
     /// Create a new sized [KWindow] in the middle of the main default screen.
     /// 
     /// Return New [`KWindow`].
@@ -100,6 +134,8 @@ impl KWindow {
 
     /// Dispatch [KEvent] to [KEventReceiver] using a [KEventDispatcher].
     /// 
+    /// Each [KEvent] is timestamped when polled and dispatched with the [KWindow] id (see [KEventEntry](super::event::KEventEntry)).
+    /// 
     /// # Note(s)
     /// After dispatching events, [KWindow::sync_events()] will be called automatically if parameter sync is true.
     /// 
@@ -121,17 +157,24 @@ impl KWindow {
     /// ```
     pub fn dispatch_events(&mut self, dispatcher : &mut KEventDispatcher, sync : bool) {
 
+        // Read controllers input since they aren't part of display server events.
+        self.__update_controllers();    // Private platform inline implementation
+
         // First get the event count to poll. This is important to prevent bloking.
         let event_count = self.__get_event_count();  // Private platform inline implementation
+        self.dispatch_polled_events(dispatcher, event_count);
 
-        for _ in 0..event_count {
-            // Fetch event
-            let event = self.__poll_event();    // Private platform inline implementation
+        // Events can generate other events while polled (ie. text input following key down). Count is read once more only,
+        // since continuous input (ie. mouse motion) would keep it rising and never return.
+        if event_count > 0 {
+            let event_count = self.__get_event_count();  // Private platform inline implementation
+            self.dispatch_polled_events(dispatcher, event_count);
+        }
 
-            // Let KWindow handle event first.
-            if !self.handle_kwindow_event(&event) {
-                // Then dispatch event via dispatcher of KWindow didn't handle it.
-                dispatcher.dispatch(&event);
+        // Dispatch user events posted before this call, after display server events.
+        for _ in 0..self.property.posted.get_posted_count() {
+            if let Some(event) = self.property.posted.pop() {
+                dispatcher.dispatch_from(&KEvent::User(event), Instant::now(), self.property.id);
             }
         }
 
@@ -141,6 +184,33 @@ impl KWindow {
         }
     }
 
+    /// Inject a [KEvent] as if sent by the display server. It is dispatched at next [KWindow::dispatch_events()].
+    /// 
+    /// Injected [KEventWindow] update the [KWindow] state like real ones (ie. [KEventWindow::Minimized] makes [KWindow::is_minimized()] true).
+    /// 
+    /// # Error(s)
+    /// Returns [OlympusError::KWindow(KWindowError::NotSupported)] if display server isn't headless.
+    pub fn inject_event(&mut self, event : KEvent) -> Result<(), OlympusError> {
+        self.__inject_event(event)    // Private platform inline implementation
+    }
+
+    /// Post a [KEventUser] that is dispatched with the [KWindow] id at next [KWindow::dispatch_events()], after display server events.
+    /// 
+    /// [KEventUser] are dispatched in the order they were posted.
+    pub fn post_user_event(&self, event : KEventUser) {
+        self.property.posted.post(event);
+    }
+
+    /// Get a [KEventUserPoster] that posts [KEventUser] to the [KWindow] without holding it (ie. given to gameplay systems).
+    pub fn get_user_event_poster(&self) -> KEventUserPoster {
+        self.property.posted.clone()
+    }
+
+    /// Returns the count of [KEventUser] posted and waiting for [KWindow::dispatch_events()].
+    pub fn get_posted_event_count(&self) -> usize {
+        self.property.posted.get_posted_count()
+    }
+
     /// Confine cursor to window, preventing it from exiting boundaries.
     pub fn confine_cursor(&mut self) {
         // Confined only if released.
@@ -150,13 +220,55 @@ impl KWindow {
         }
     }
 
-     /// Get the cursor position with as a pair (x,y).
+     /// Get a [KController] handle of a connected controller from the id given by [KEventController::Connected].
+    /// 
+    /// # Error(s)
+    /// Returns [OlympusError::KController(KControllerError::NotConnected)] if no controller is connected with id.
+    pub fn get_controller(&mut self, id : u8) -> Result<KController<'_>, OlympusError> {
+        self.__get_controller(id)    // Private platform inline implementation
+    }
+
+    /// Get the ids of connected controllers.
+    pub fn get_controllers(&self) -> Vec<u8> {
+        self.__get_controllers()    // Private platform inline implementation
+    }
+
+    /// Add controller mappings in SDL `gamecontrollerdb.txt` format. Mappings override built-in mappings and are applied to
+    /// connected controllers.
+    ///
+    /// Returns the count of mappings added. Mappings of other platforms are skipped.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::InvalidMapping)] with the line number if a line is invalid. No mapping is added.
+    pub fn add_controller_mappings(&mut self, mappings : &str) -> Result<usize, OlympusError> {
+        self.__add_controller_mappings(mappings)    // Private platform inline implementation
+    }
+
+    /// Load controller mappings from a SDL `gamecontrollerdb.txt` file. Mappings override built-in mappings and are applied to
+    /// connected controllers.
+    ///
+    /// Returns the count of mappings added. Mappings of other platforms are skipped.
+    ///
+    /// # Error(s)
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::FileError)] if file can't be read.
+    ///
+    /// Returns [OlympusError::KControllerMapping(KControllerMappingError::InvalidMapping)] with the line number if a line is invalid. No mapping is added.
+    pub fn load_controller_mappings(&mut self, path : &std::path::Path) -> Result<usize, OlympusError> {
+        self.__load_controller_mappings(path)    // Private platform inline implementation
+    }
+
+    /// Get the cursor position with as a pair (x,y).
      #[cfg(any(doc, all(not(target_family = "wasm"), any(target_os = "linux", target_os = "windows", target_os = "macos"))))]
      pub fn get_cursor_position(&self) -> (i32, i32) {
          #![cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))))]
          self.property.cursor.position
      }
 
+    /// Returns the [KWindow] unique id given to its dispatched [KEvent]. Ids are never reused.
+    pub fn get_id(&self) -> u64 {
+        self.property.id
+    }
+
     /// Get the display server provider identification.
     #[cfg(any(doc, all(not(target_family = "wasm"), any(target_os = "linux"))))]
     pub fn get_display_server_provider(&self) -> super::linux::server::KLinuxDisplayServerProvider{
@@ -164,6 +276,13 @@ impl KWindow {
        self.display_server.provider
     }
 
+    /// Get the [KDisplayContext] of the [KWindow], used to create other windows sharing its display server connection.
+    #[cfg(any(doc, all(not(target_family = "wasm"), target_os = "linux")))]
+    pub fn get_display_context(&self) -> KDisplayContext {
+        #![cfg_attr(docsrs, doc(cfg(any(target_os = "linux"))))]
+        KDisplayContext { connection : self.display_server.connection.clone() }
+    }
+
     /// Get the display server connection.
     #[cfg(any(doc, all(not(target_family = "wasm"), any(target_os = "linux"))))]
     pub fn get_display_server_connection(&self) -> *const super::linux::server::Display{
@@ -347,6 +466,7 @@ impl KWindow {
 
         if mode != self.property.cursor.mode {
             self.property.cursor.mode = mode;
+            self.__set_cursor_mode();   // Private platform inline implementation
             match self.property.cursor.mode {
                 KCursorMode::Pointer => {},
                 // Put cursor in center if acceleration
@@ -366,6 +486,11 @@ impl KWindow {
 /// Private KWindow members
 #[doc(hidden)]
 impl KWindow {
+    /// Returns a new unique [KWindow] id.
+    pub(super) fn get_next_id() -> u64 {
+        KWINDOW_NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    }
+
     /// Return True if width and size are between boundaries.
     fn is_size_within_boundaries(width:u32, height:u32) -> bool {
 
@@ -391,10 +516,27 @@ impl KWindow {
             KEvent::Keyboard(event) => self.handle_kwindow_keyboard_event(event),
             KEvent::Mouse(event) => self.handle_kwindow_mouse_event(event),
             KEvent::Controller(event) => self.handle_kwindow_controller_event(event),
+            KEvent::Touch(_) => false,
+            KEvent::User(_) => false,
             KEvent::Unknown => false,
         }
     }
 
+    /// Poll and dispatch event_count [KEvent] from the display server.
+    fn dispatch_polled_events(&mut self, dispatcher : &mut KEventDispatcher, event_count : usize) {
+        for _ in 0..event_count {
+            // Fetch event
+            let event = self.__poll_event();    // Private platform inline implementation
+            let timestamp = Instant::now();
+
+            // Let KWindow handle event first.
+            if !self.handle_kwindow_event(&event) {
+                // Then dispatch event via dispatcher of KWindow didn't handle it.
+                dispatcher.dispatch_from(&event, timestamp, self.property.id);
+            }
+        }
+    }
+
     /// Handle KEventWindow for KWindow.
     #[cfg(any(doc, all(not(target_family = "wasm"), any(target_os = "linux", target_os = "windows", target_os = "macos"))))]
     #[inline(always)]
@@ -462,6 +604,7 @@ impl KWindow {
         match event {
             KEventKeyboard::KeyDown(_) => {},
             KEventKeyboard::KeyUp(_) => {},
+            KEventKeyboard::TextInput(_) => {},
         }
         false
     }
@@ -0,0 +1,464 @@
+use std::{io::{Read, Write, ErrorKind}, time::{SystemTime, Duration, UNIX_EPOCH}};
+
+use super::{KJournalEntry, KJournalEntrySeverity, KJournalEntrySpan, KJournalFilter};
+
+#[allow(unused_imports)]
+use super::KJournalListenerBinary;
+
+/// Magic bytes at the start of every binary journal.
+pub const KJOURNAL_BINARY_MAGIC : [u8; 4] = *b"KJNL";
+
+/// Current version of the binary journal format.
+pub const KJOURNAL_BINARY_VERSION : u16 = 1;
+
+/// Maximum size of a record payload. Bigger records are considered corrupted.
+pub const KJOURNAL_BINARY_RECORD_MAX : u32 = 16 * 1024 * 1024;
+
+/// ##### Owned journal record read from a binary journal.
+///
+/// # Format
+/// A binary journal starts with [KJOURNAL_BINARY_MAGIC] followed by the format version as little-endian u16.
+/// Each record is a little-endian u32 payload length followed by the payload :
+/// * `u8` severity flag of [KJournalEntrySeverity].
+/// * `u64` seconds and `u32` nanoseconds since UNIX epoch of first occurrence.
+/// * `u64` seconds and `u32` nanoseconds since UNIX epoch of last occurrence.
+/// * `u32` repeat count.
+/// * String target, string description.
+/// * `u16` count of fields followed by string key and string value of each field.
+/// * `u8` 1 if the record has a span followed by `u64` span ID, `u64` parent ID (0 if none), `u16` depth
+///   and `u64` duration in nanoseconds (`u64::MAX` if none). Records written before spans end after fields.
+///
+/// Strings are a little-endian u32 length followed by UTF-8 bytes. Readers ignore payload bytes they don't know
+/// so that future versions can append data to records.
+///
+/// A record with a repeat count over 1 is an update of the record of its first occurrence written earlier, see
+/// [KJournalRecord::is_update()] and [merge_updates()].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KJournalRecord {
+    /// Severity of the record.
+    pub severity : KJournalEntrySeverity,
+
+    /// Date and time of first occurrence.
+    pub first_date_time : SystemTime,
+
+    /// Date and time of last occurrence.
+    pub date_time : SystemTime,
+
+    /// Count of consecutive occurrences collapsed in this record.
+    pub repeat_count : u32,
+
+    /// Hierarchical target. Empty if none.
+    pub target : String,
+
+    /// Record description.
+    pub description : String,
+
+    /// Structured fields as (key, value).
+    pub fields : Vec<(String, String)>,
+
+    /// Span the record was written in, if any.
+    pub span : Option<KJournalEntrySpan>,
+}
+
+/// Enumeration of possible binary journal errors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KJournalBinaryError {
+    /// Happens when the journal doesn't start with [KJOURNAL_BINARY_MAGIC].
+    InvalidHeader,
+
+    /// Happens when the journal version is newer than [KJOURNAL_BINARY_VERSION].
+    UnsupportedVersion,
+
+    /// Happens when a record is truncated or malformed.
+    CorruptedRecord,
+
+    /// Happens when a record payload is bigger than [KJOURNAL_BINARY_RECORD_MAX] and can't be written.
+    RecordTooLarge,
+
+    /// Happens when an IO error occurred while reading or writing.
+    IoError(ErrorKind),
+}
+
+impl From<&KJournalEntry> for KJournalRecord {
+    fn from(entry: &KJournalEntry) -> Self {
+        KJournalRecord { severity: entry.get_severity(), first_date_time: entry.get_first_date_time(), date_time: entry.get_date_time(),
+            repeat_count: entry.get_repeat_count(), target: entry.get_target().to_string(), description: entry.get_description().clone(),
+            fields: entry.get_fields().clone(), span: entry.get_span() }
+    }
+}
+
+impl KJournalRecord {
+
+    /// Returns True if record is an update of an earlier record of its first occurrence. See [KJournalEntry::is_update()].
+    pub fn is_update(&self) -> bool {
+        self.repeat_count > 1
+    }
+
+    /// Returns True if record updates other, which is the record of the same entry with an earlier repeat count.
+    pub fn is_update_of(&self, other : &KJournalRecord) -> bool {
+        self.repeat_count > other.repeat_count && self.first_date_time == other.first_date_time && self.severity == other.severity &&
+            self.target == other.target && self.description == other.description && self.fields == other.fields && self.span == other.span
+    }
+
+    /// Returns True if record passes the [KJournalFilter] for its target and severity.
+    pub fn is_enabled(&self, filter : &KJournalFilter) -> bool {
+        filter.is_enabled(&self.target, self.severity)
+    }
+
+    /// Returns the record as a line of text similar to [`super::KJournalListenerPrint`] with time as seconds since UNIX epoch.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("[{} {:.6}]", self.severity.get_symbol(), to_epoch(self.date_time).as_secs_f64());
+
+        if !self.target.is_empty() {
+            text.push_str(&format!(" {} :", self.target));
+        }
+        text.push(' ');
+
+        // Indent description according to span depth like KJournalListenerPrint.
+        if let Some(span) = &self.span {
+            text.push_str(&"  ".repeat(if span.is_exit() { span.get_depth().saturating_sub(1) } else { span.get_depth() }));
+        }
+        text.push_str(&self.description);
+
+        if let Some(duration) = self.span.and_then(|span| span.get_duration()) {
+            text.push_str(&format!(" [{:?}]", duration));
+        }
+
+        if !self.fields.is_empty() {
+            let fields : Vec<String> = self.fields.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            text.push_str(&format!(" {{{}}}", fields.join(", ")));
+        }
+
+        if self.repeat_count > 1 {
+            text.push_str(&format!(" (repeated {} times)", self.repeat_count));
+        }
+
+        text
+    }
+
+    /// Returns the record as a JSON object on a single line. Times are seconds since UNIX epoch.
+    pub fn to_json(&self) -> String {
+        let fields : Vec<String> = self.fields.iter().map(|(k, v)| format!("{}:{}", json_string(k), json_string(v))).collect();
+
+        let span = match &self.span {
+            Some(span) => format!(",\"span\":{{\"id\":{},\"parent\":{},\"depth\":{}{}}}", span.get_id(),
+                span.get_parent_id().map_or("null".to_string(), |id| id.to_string()), span.get_depth(),
+                span.get_duration().map_or(String::new(), |d| format!(",\"duration\":{:.6}", d.as_secs_f64()))),
+            None => String::new(),
+        };
+
+        format!("{{\"severity\":\"{}\",\"first_time\":{:.6},\"time\":{:.6},\"repeat\":{},\"target\":{},\"description\":{},\"fields\":{{{}}}{}}}",
+            self.severity, to_epoch(self.first_date_time).as_secs_f64(), to_epoch(self.date_time).as_secs_f64(), self.repeat_count,
+            json_string(&self.target), json_string(&self.description), fields.join(","), span)
+    }
+
+    /// Encode the record payload prefixed by its length.
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalBinaryError::RecordTooLarge]) if payload is bigger than [KJOURNAL_BINARY_RECORD_MAX], since readers would
+    /// consider it corrupted.
+    pub fn encode(&self) -> Result<Vec<u8>, KJournalBinaryError> {
+        let mut payload : Vec<u8> = Vec::new();
+
+        payload.push(self.severity.get_flag());
+        encode_time(&mut payload, self.first_date_time);
+        encode_time(&mut payload, self.date_time);
+        payload.extend_from_slice(&self.repeat_count.to_le_bytes());
+        encode_string(&mut payload, &self.target);
+        encode_string(&mut payload, &self.description);
+        payload.extend_from_slice(&(self.fields.len().min(u16::MAX as usize) as u16).to_le_bytes());
+        for (key, value) in self.fields.iter().take(u16::MAX as usize) {
+            encode_string(&mut payload, key);
+            encode_string(&mut payload, value);
+        }
+
+        match &self.span {
+            Some(span) => {
+                payload.push(1);
+                payload.extend_from_slice(&span.get_id().to_le_bytes());
+                payload.extend_from_slice(&span.get_parent_id().unwrap_or(0).to_le_bytes());
+                payload.extend_from_slice(&(span.get_depth().min(u16::MAX as usize) as u16).to_le_bytes());
+                payload.extend_from_slice(&span.get_duration().map_or(u64::MAX, |d| d.as_nanos().min(u64::MAX as u128 - 1) as u64).to_le_bytes());
+            },
+            None => payload.push(0),
+        }
+
+        if payload.len() > KJOURNAL_BINARY_RECORD_MAX as usize {
+            return Err(KJournalBinaryError::RecordTooLarge);
+        }
+
+        let mut record = (payload.len() as u32).to_le_bytes().to_vec();
+        record.extend(payload);
+        Ok(record)
+    }
+
+    /// Decode a record payload (without length prefix).
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalBinaryError::CorruptedRecord]) if payload is truncated or malformed.
+    pub fn decode(payload : &[u8]) -> Result<KJournalRecord, KJournalBinaryError> {
+        let mut cursor = KJournalBinaryCursor { data: payload, position: 0 };
+
+        let severity = KJournalEntrySeverity::from_flag(cursor.take(1)?[0]).ok_or(KJournalBinaryError::CorruptedRecord)?;
+        let first_date_time = cursor.time()?;
+        let date_time = cursor.time()?;
+        let repeat_count = cursor.u32()?;
+        let target = cursor.string()?;
+        let description = cursor.string()?;
+
+        let count = cursor.u16()?;
+        let mut fields = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            fields.push((cursor.string()?, cursor.string()?));
+        }
+
+        // Records written before spans end after fields.
+        let span = if cursor.is_end() || cursor.take(1)?[0] == 0 {
+            None
+        } else {
+            let id = cursor.u64()?;
+            let parent_id = cursor.u64()?;
+            let depth = cursor.u16()? as usize;
+            let duration = cursor.u64()?;
+            Some(KJournalEntrySpan::new(id, if parent_id == 0 { None } else { Some(parent_id) }, depth,
+                if duration == u64::MAX { None } else { Some(Duration::from_nanos(duration)) }))
+        };
+
+        // Remaining bytes are ignored for forward compatibility.
+        Ok(KJournalRecord { severity, first_date_time, date_time, repeat_count, target, description, fields, span })
+    }
+}
+
+/// Write the binary journal header.
+pub fn write_binary_header(writer : &mut dyn Write) -> Result<(), KJournalBinaryError> {
+    let mut header = KJOURNAL_BINARY_MAGIC.to_vec();
+    header.extend_from_slice(&KJOURNAL_BINARY_VERSION.to_le_bytes());
+    writer.write_all(&header).map_err(|err| KJournalBinaryError::IoError(err.kind()))
+}
+
+/// ##### Reader of binary journals written by [KJournalListenerBinary].
+///
+/// [KJournalBinaryReader] is an [Iterator] of `Result<`[KJournalRecord]`, `[KJournalBinaryError]`>`.
+///
+/// # Example(s)
+/// ```no_run
+/// use std::fs::File;
+/// use olympus::kleio::journal::binary::KJournalBinaryReader;
+///
+/// let reader = KJournalBinaryReader::new(File::open("soak.kjnl").unwrap()).unwrap();
+///
+/// for record in reader {
+///     println!("{}", record.unwrap().to_text());
+/// }
+/// ```
+pub struct KJournalBinaryReader<R : Read> {
+    /// Source of the binary journal.
+    source : R,
+
+    /// Version read from header.
+    version : u16,
+
+    /// True once the end or an error was reached.
+    done : bool,
+}
+
+impl<R : Read> KJournalBinaryReader<R> {
+    /// Create a new [KJournalBinaryReader] and read the header from source.
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalBinaryError::InvalidHeader]) if the source isn't a binary journal.
+    ///
+    /// Returns Err([KJournalBinaryError::UnsupportedVersion]) if the version is newer than [KJOURNAL_BINARY_VERSION].
+    pub fn new(mut source : R) -> Result<KJournalBinaryReader<R>, KJournalBinaryError> {
+        let mut header = [0u8; 6];
+        source.read_exact(&mut header).map_err(|_| KJournalBinaryError::InvalidHeader)?;
+
+        if header[0..4] != KJOURNAL_BINARY_MAGIC {
+            return Err(KJournalBinaryError::InvalidHeader);
+        }
+
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version > KJOURNAL_BINARY_VERSION {
+            return Err(KJournalBinaryError::UnsupportedVersion);
+        }
+
+        Ok(KJournalBinaryReader { source, version, done: false })
+    }
+
+    /// Get the format version of the binary journal.
+    pub fn get_version(&self) -> u16 {
+        self.version
+    }
+
+    /// Read the next record.
+    ///
+    /// Returns Ok(None) at the end of the journal.
+    fn read_record(&mut self) -> Result<Option<KJournalRecord>, KJournalBinaryError> {
+        let mut length = [0u8; 4];
+
+        // Clean end of journal only happens between records.
+        match self.source.read(&mut length[0..1]) {
+            Ok(0) => return Ok(None),
+            Ok(_) => {},
+            Err(err) => return Err(KJournalBinaryError::IoError(err.kind())),
+        }
+        self.source.read_exact(&mut length[1..4]).map_err(|_| KJournalBinaryError::CorruptedRecord)?;
+
+        let length = u32::from_le_bytes(length);
+        if length > KJOURNAL_BINARY_RECORD_MAX {
+            return Err(KJournalBinaryError::CorruptedRecord);
+        }
+
+        let mut payload = vec![0u8; length as usize];
+        self.source.read_exact(&mut payload).map_err(|_| KJournalBinaryError::CorruptedRecord)?;
+
+        KJournalRecord::decode(&payload).map(Some)
+    }
+}
+
+impl<R : Read> Iterator for KJournalBinaryReader<R> {
+    type Item = Result<KJournalRecord, KJournalBinaryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.read_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => { self.done = true; None },
+            Err(err) => { self.done = true; Some(Err(err)) },
+        }
+    }
+}
+
+/// Merge multiple lists of [KJournalRecord] into one list ordered by date and time.
+///
+/// Updates of each list are merged with [merge_updates()] first. Records with the same date and time keep the order of the lists given.
+pub fn merge_records(journals : Vec<Vec<KJournalRecord>>) -> Vec<KJournalRecord> {
+    let mut records : Vec<KJournalRecord> = journals.into_iter().flat_map(merge_updates).collect();
+    records.sort_by_key(|r| r.date_time);
+    records
+}
+
+/// Replace records of a journal by their updates so that each collapsed entry is kept once with its total repeat count.
+///
+/// Updates without an earlier record (ie. listener added during a run of duplicates) are kept as is.
+pub fn merge_updates(records : Vec<KJournalRecord>) -> Vec<KJournalRecord> {
+    let mut merged : Vec<KJournalRecord> = Vec::with_capacity(records.len());
+
+    for record in records {
+        if record.is_update() {
+            // Records older than the first occurrence can't be updated by this record.
+            let original = merged.iter().rposition(|r| r.date_time < record.first_date_time || record.is_update_of(r))
+                .filter(|index| record.is_update_of(&merged[*index]));
+
+            if let Some(index) = original {
+                merged.remove(index);
+            }
+        }
+        merged.push(record);
+    }
+
+    merged
+}
+
+/// Cursor used to decode a record payload.
+struct KJournalBinaryCursor<'a> {
+    /// Payload decoded.
+    data : &'a [u8],
+
+    /// Current position in payload.
+    position : usize,
+}
+
+impl<'a> KJournalBinaryCursor<'a> {
+    /// Take the next count bytes.
+    fn take(&mut self, count : usize) -> Result<&'a [u8], KJournalBinaryError> {
+        if self.position + count > self.data.len() {
+            return Err(KJournalBinaryError::CorruptedRecord);
+        }
+        let bytes = &self.data[self.position..self.position + count];
+        self.position += count;
+        Ok(bytes)
+    }
+
+    /// Returns True if all bytes were taken.
+    fn is_end(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    /// Take a little-endian u16.
+    fn u16(&mut self) -> Result<u16, KJournalBinaryError> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    /// Take a little-endian u32.
+    fn u32(&mut self) -> Result<u32, KJournalBinaryError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Take a little-endian u64.
+    fn u64(&mut self) -> Result<u64, KJournalBinaryError> {
+        let b = self.take(8)?;
+        Ok(u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+    }
+
+    /// Take a time as seconds and nanoseconds since UNIX epoch.
+    fn time(&mut self) -> Result<SystemTime, KJournalBinaryError> {
+        let secs = self.u64()?;
+        let nanos = self.u32()?;
+        if nanos >= 1_000_000_000 {
+            return Err(KJournalBinaryError::CorruptedRecord);
+        }
+        UNIX_EPOCH.checked_add(Duration::new(secs, nanos)).ok_or(KJournalBinaryError::CorruptedRecord)
+    }
+
+    /// Take a length-prefixed UTF-8 string.
+    fn string(&mut self) -> Result<String, KJournalBinaryError> {
+        let length = self.u32()? as usize;
+        match std::str::from_utf8(self.take(length)?) {
+            Ok(s) => Ok(s.to_string()),
+            Err(_) => Err(KJournalBinaryError::CorruptedRecord),
+        }
+    }
+}
+
+/// Returns duration since UNIX epoch. Times before epoch are 0.
+fn to_epoch(time : SystemTime) -> Duration {
+    time.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO)
+}
+
+/// Encode time as seconds and nanoseconds since UNIX epoch.
+fn encode_time(payload : &mut Vec<u8>, time : SystemTime) {
+    let duration = to_epoch(time);
+    payload.extend_from_slice(&duration.as_secs().to_le_bytes());
+    payload.extend_from_slice(&duration.subsec_nanos().to_le_bytes());
+}
+
+/// Encode a length-prefixed UTF-8 string.
+fn encode_string(payload : &mut Vec<u8>, s : &str) {
+    payload.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    payload.extend_from_slice(s.as_bytes());
+}
+
+/// Returns string quoted and escaped for JSON.
+fn json_string(s : &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
@@ -1,58 +1,148 @@
 use std::time::SystemTime;
 
+use super::{KJournalEntrySeverity, KJournalEntrySpan};
 
-/// ##### Journal entry. Contains the severity, the date and time of entry and a description.
+
+/// ##### Journal entry. Contains the severity, the date and time of entry, the target and a description.
 /// 
 /// # Example(s)
 /// ```
 /// // Import Journal entry and severity
-/// use olympus_kleio::journal::{ KJournalEntry, KJournalEntrySeverity};
+/// use olympus::kleio::journal::{ KJournalEntry, KJournalEntrySeverity};
 /// 
 /// // Create an entry with severity and description as String. (Date and time is added automatically upon creation).
-/// let mut j = KJournalEntry::new(KJournalEntrySeverity::ERROR, String::from("This is an example of an error entry!"));
+/// let mut j = KJournalEntry::new(KJournalEntrySeverity::Error, "This is an example of an error entry!");
 /// 
 /// // You can also recycle / update entries (ie. for circular buffer). (Date and time is added automatically updated).
-/// j.update(KJournalEntrySeverity::INFORMATION, String::from("This is now a recycle entry!"));
+/// j.update(KJournalEntrySeverity::Information, "This is now a recycle entry!");
 /// ```
 pub struct KJournalEntry {
-    /// Severity of the entry according to [`super::KJournalEntrySeverity`].
-    severity : u8,
+    /// Severity of the entry according to [`KJournalEntrySeverity`].
+    severity : KJournalEntrySeverity,
 
-    /// Date and time entry occurred.
+    /// Date and time entry occurred. Last occurrence if entry was repeated.
     date_time : SystemTime,
 
+    /// Date and time of the first occurrence of a repeated entry.
+    first_date_time : SystemTime,
+
+    /// Count of consecutive occurrences collapsed in this entry.
+    repeat_count : u32,
+
+    /// Hierarchical target that wrote the entry (ie. `kleio::display::x11`). Empty if none.
+    target : String,
+
     /// Entry description / Metadata.
     description : String,
+
+    /// Structured fields of the entry as (key, value).
+    fields : Vec<(String, String)>,
+
+    /// Span the entry was written in, if any.
+    span : Option<KJournalEntrySpan>,
 }
 
 
 impl KJournalEntry {
-    /// Create a new [`KJournalEntry`] from [severity][`super::KJournalEntrySeverity`] and description. Date and time will be added automatically.
+    /// Create a new [`KJournalEntry`] from [severity][`KJournalEntrySeverity`] and description. Date and time will be added automatically.
     /// 
     /// Return New [`KJournalEntry`] with new date and time.
-    pub fn new(severity : u8, description : &str) -> KJournalEntry {
-        KJournalEntry { severity, date_time: SystemTime::now(), description : description.to_string() }
+    pub fn new(severity : KJournalEntrySeverity, description : &str) -> KJournalEntry {
+        let date_time = SystemTime::now();
+        KJournalEntry { severity, date_time, first_date_time: date_time, repeat_count : 1, target : String::new(), description : description.to_string(), fields : Vec::new(), span : None }
+    }
+
+    /// Update Journal entry with a new [severity][`KJournalEntrySeverity`] and description. Date and time will be modified automatically.
+    /// 
+    /// The target of the entry is cleared.
+    pub fn update(&mut self, severity : KJournalEntrySeverity, description : &str){
+        self.update_target("", severity, description);
+    }
+
+    /// Update Journal entry with a new target, [severity][`KJournalEntrySeverity`] and description. Date and time will be modified automatically.
+    /// 
+    /// Structured fields of the entry are cleared.
+    pub fn update_target(&mut self, target : &str, severity : KJournalEntrySeverity, description : &str){
+        self.update_fields(target, severity, description, &[]);
     }
 
-    /// Update Journal entry with a new [severity][`super::KJournalEntrySeverity`] and description. Date and time will be modified automatically.
-    pub fn update(&mut self, severity : u8, description : &str){
+    /// Update Journal entry with a new target, [severity][`KJournalEntrySeverity`], description and structured fields as (key, value).
+    /// Date and time will be modified automatically. Span of the entry is cleared.
+    pub fn update_fields(&mut self, target : &str, severity : KJournalEntrySeverity, description : &str, fields : &[(&str, &str)]){
         self.date_time = SystemTime::now();
+        self.first_date_time = self.date_time;
+        self.repeat_count = 1;
         self.severity = severity;
+        self.target.clear();
+        self.target.push_str(target);
         self.description = description.to_string();
+        self.fields.clear();
+        self.fields.extend(fields.iter().map(|(k, v)| (k.to_string(), v.to_string())));
+        self.span = None;
+    }
+
+    /// Set the [span][KJournalEntrySpan] the entry was written in.
+    pub fn set_span(&mut self, span : Option<KJournalEntrySpan>) {
+        self.span = span;
+    }
+
+    /// Register a new occurrence of the same entry. Increment repeat count and update last date and time.
+    pub fn repeat(&mut self) {
+        self.date_time = SystemTime::now();
+        self.repeat_count = self.repeat_count.saturating_add(1);
+    }
+
+    /// Returns True if entry is an update of an entry listeners were already notified of.
+    /// 
+    /// Listeners are notified of the first occurrence of an entry with a repeat count of 1. When a run of collapsed duplicates ends,
+    /// they are notified once more with the total repeat count, which includes the first occurrence.
+    pub fn is_update(&self) -> bool {
+        self.repeat_count > 1
     }
 
-    /// Returns entry [severity][`super::KJournalEntrySeverity`].
-    pub fn get_severity(&self) -> u8{
+    /// Returns True if entry has the same target, [severity][`KJournalEntrySeverity`], description and structured fields.
+    pub fn is_same(&self, target : &str, severity : KJournalEntrySeverity, description : &str, fields : &[(&str, &str)]) -> bool {
+        self.severity == severity && self.target == target && self.description == description && 
+            self.fields.len() == fields.len() && self.fields.iter().zip(fields).all(|(a, b)| a.0 == b.0 && a.1 == b.1)
+    }
+
+    /// Returns entry [severity][`KJournalEntrySeverity`].
+    pub fn get_severity(&self) -> KJournalEntrySeverity{
         self.severity
     }
 
-    /// Returns entry date and time as [`SystemTime`].
+    /// Returns entry date and time as [`SystemTime`]. Last occurrence if entry was repeated.
     pub fn get_date_time(&self) -> SystemTime{
         self.date_time
     }
 
+    /// Returns entry first occurrence date and time as [`SystemTime`]. Same as [KJournalEntry::get_date_time()] if not repeated.
+    pub fn get_first_date_time(&self) -> SystemTime{
+        self.first_date_time
+    }
+
+    /// Returns count of consecutive occurrences collapsed in this entry. 1 if not repeated.
+    pub fn get_repeat_count(&self) -> u32 {
+        self.repeat_count
+    }
+
+    /// Returns entry target. Empty if written without target.
+    pub fn get_target(&self) -> &str {
+        &self.target
+    }
+
     /// Returns entry description / metadata.
     pub fn get_description(&self) -> &String {
         &self.description
     }
+
+    /// Returns entry structured fields as (key, value). Empty if written without fields.
+    pub fn get_fields(&self) -> &Vec<(String, String)> {
+        &self.fields
+    }
+
+    /// Returns the [span][KJournalEntrySpan] the entry was written in or [None] if written outside of spans.
+    pub fn get_span(&self) -> Option<KJournalEntrySpan> {
+        self.span
+    }
 }
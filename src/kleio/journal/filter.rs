@@ -0,0 +1,207 @@
+use std::path::Path;
+
+use super::{KJournalEntrySeverity, KJournalSeveritySet};
+
+#[allow(unused_imports)]
+use super::KJournal;
+
+/// Conventional name of the environment variable holding [KJournalFilter] directives, to give to [KJournalFilter::from_env()].
+pub const KJOURNAL_FILTER_ENV : &str = "OLYMPUS_JOURNAL";
+
+/// Separator used between each level of a hierarchical target (ie. `kleio::display::x11`).
+pub const KJOURNAL_TARGET_SEPARATOR : &str = "::";
+
+/// ##### Severity filter of a [KJournal] with per-target overrides.
+///
+/// Targets are hierarchical paths like `kleio::display::x11`. The severity of a target is the one of the
+/// most specific override matching it. `kleio::display` will match `kleio::display` and `kleio::display::x11` but not `kleio::displays`.
+/// Targets without override use the default severity.
+///
+/// # Syntax
+/// Filters are written as a list of directives separated by commas (or new lines for files).
+/// * `severity` sets the default severity.
+/// * `target=severity` sets the severity of a target and its children.
+///
+/// Severity can be a name or a [`KJournalSeveritySet`]. Names means that severity and above.
+/// * `debug`, `other`, `information` (or `info`), `warning` (or `warn`), `error`, `fatal`.
+/// * `all` for all severities including DEBUG, `none` (or `off`) to ignore everything.
+/// * Names separated by `|` (ie. `debug|error`) for exactly those severities, or a raw flags number.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::journal::{KJournalFilter, KJournalEntrySeverity, KJournalSeveritySet};
+///
+/// // Log warning and above, except x11 which log everything.
+/// let f = KJournalFilter::parse("warning, kleio::display::x11=debug").unwrap();
+///
+/// assert_eq!(f.get_target_severity("kleio::asset"), KJournalSeveritySet::at_least(KJournalEntrySeverity::Warning));
+/// assert_eq!(f.get_target_severity("kleio::display::x11::event"), KJournalSeveritySet::ALL_WITH_DEBUG);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KJournalFilter {
+    /// Severity used for targets without override.
+    severity : KJournalSeveritySet,
+
+    /// Severity overrides as (target, severity).
+    targets : Vec<(String, KJournalSeveritySet)>,
+}
+
+/// Enumeration of possible [KJournalFilter] errors.
+pub enum KJournalFilterError {
+    /// Happens when a severity name or number can't be parsed.
+    InvalidSeverity,
+
+    /// Happens when a directive target is empty or malformed.
+    InvalidTarget,
+
+    /// Happens when the environment variable is not set or not unicode.
+    EnvironmentVariableNotFound,
+
+    /// Happens when the filter file can't be read.
+    FileReadError,
+}
+
+impl std::fmt::Debug for KJournalFilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSeverity => write!(f, "InvalidSeverity"),
+            Self::InvalidTarget => write!(f, "InvalidTarget"),
+            Self::EnvironmentVariableNotFound => write!(f, "EnvironmentVariableNotFound"),
+            Self::FileReadError => write!(f, "FileReadError"),
+        }
+    }
+}
+
+impl KJournalFilter {
+
+    /// Create a new [KJournalFilter] with a default [severity set][`KJournalSeveritySet`] and no override.
+    pub fn new(severity : impl Into<KJournalSeveritySet>) -> KJournalFilter {
+        KJournalFilter { severity: severity.into(), targets: Vec::new() }
+    }
+
+    /// Parse a [KJournalFilter] from a list of directives. See [KJournalFilter] for syntax.
+    ///
+    /// The default severity is [`KJournalSeveritySet::ALL_WITH_DEBUG`] if no directive set it.
+    ///
+    /// Returns Ok([KJournalFilter]) if all directives are valid.
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalFilterError::InvalidSeverity]) if a severity can't be parsed.
+    ///
+    /// Returns Err([KJournalFilterError::InvalidTarget]) if a target is malformed.
+    pub fn parse(directives : &str) -> Result<KJournalFilter, KJournalFilterError> {
+        let mut filter = KJournalFilter::new(KJournalSeveritySet::ALL_WITH_DEBUG);
+
+        for directive in directives.split([',', '\n']).map(|d| d.trim()).filter(|d| !d.is_empty() && !d.starts_with('#')) {
+            match directive.split_once('=') {
+                Some((target, severity)) => {
+                    let target = target.trim();
+
+                    // Target must be non-empty and each level must have a name.
+                    if target.split(KJOURNAL_TARGET_SEPARATOR).any(|level| level.trim().is_empty()) {
+                        return Err(KJournalFilterError::InvalidTarget);
+                    }
+
+                    filter.set_target_severity(target, Self::parse_severity(severity)?);
+                },
+                None => filter.severity = Self::parse_severity(directive)?,
+            }
+        }
+
+        Ok(filter)
+    }
+
+    /// Parse a [KJournalFilter] from an environment variable like [KJOURNAL_FILTER_ENV].
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalFilterError::EnvironmentVariableNotFound]) if variable is not set.
+    ///
+    /// Returns the same errors as [KJournalFilter::parse()] otherwise.
+    pub fn from_env(name : &str) -> Result<KJournalFilter, KJournalFilterError> {
+        match std::env::var(name) {
+            Ok(directives) => Self::parse(&directives),
+            Err(_) => Err(KJournalFilterError::EnvironmentVariableNotFound),
+        }
+    }
+
+    /// Parse a [KJournalFilter] from a configuration file with a directive per line. Lines starting with `#` are comments.
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalFilterError::FileReadError]) if file can't be read.
+    ///
+    /// Returns the same errors as [KJournalFilter::parse()] otherwise.
+    pub fn from_file(path : &Path) -> Result<KJournalFilter, KJournalFilterError> {
+        match std::fs::read_to_string(path) {
+            Ok(directives) => Self::parse(&directives),
+            Err(_) => Err(KJournalFilterError::FileReadError),
+        }
+    }
+
+    /// Set the default [severity set][`KJournalSeveritySet`] used for targets without override.
+    pub fn set_severity(&mut self, severity : impl Into<KJournalSeveritySet>) {
+        self.severity = severity.into();
+    }
+
+    /// Get the default [severity set][`KJournalSeveritySet`] used for targets without override.
+    pub fn get_severity(&self) -> KJournalSeveritySet {
+        self.severity
+    }
+
+    /// Set the [severity set][`KJournalSeveritySet`] override of a target and its children. Replace previous override if any.
+    pub fn set_target_severity(&mut self, target : &str, severity : impl Into<KJournalSeveritySet>) {
+        let severity = severity.into();
+        match self.targets.iter_mut().find(|t| t.0 == target) {
+            Some(t) => t.1 = severity,
+            None => self.targets.push((target.to_string(), severity)),
+        }
+    }
+
+    /// Remove the severity override of a target.
+    ///
+    /// Returns True if an override was removed, false otherwise.
+    pub fn remove_target_severity(&mut self, target : &str) -> bool {
+        let count = self.targets.len();
+        self.targets.retain(|t| t.0 != target);
+        count != self.targets.len()
+    }
+
+    /// Get the [severity set][`KJournalSeveritySet`] of a target according to the most specific override matching it.
+    pub fn get_target_severity(&self, target : &str) -> KJournalSeveritySet {
+        let mut severity = self.severity;
+        let mut matched_len = 0;
+
+        for (t, s) in &self.targets {
+            if t.len() >= matched_len && Self::is_target_match(t, target) {
+                severity = *s;
+                matched_len = t.len();
+            }
+        }
+
+        severity
+    }
+
+    /// Returns True if an entry of severity written for target would pass the filter.
+    pub fn is_enabled(&self, target : &str, severity : KJournalEntrySeverity) -> bool {
+        self.get_target_severity(target).contains(severity)
+    }
+
+    /// Returns True if filter target is the same as target or one of its parent.
+    fn is_target_match(filter : &str, target : &str) -> bool {
+        match target.strip_prefix(filter) {
+            Some(rest) => rest.is_empty() || rest.starts_with(KJOURNAL_TARGET_SEPARATOR),
+            None => false,
+        }
+    }
+
+    /// Parse a severity name as that severity and above, or a [`KJournalSeveritySet`].
+    fn parse_severity(severity : &str) -> Result<KJournalSeveritySet, KJournalFilterError> {
+        if severity.trim().eq_ignore_ascii_case("off") {
+            return Ok(KJournalSeveritySet::NONE);
+        }
+
+        match severity.parse::<KJournalEntrySeverity>() {
+            Ok(severity) => Ok(KJournalSeveritySet::at_least(severity)),
+            Err(_) => severity.parse::<KJournalSeveritySet>().map_err(|_| KJournalFilterError::InvalidSeverity),
+        }
+    }
+}
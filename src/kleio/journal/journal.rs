@@ -1,4 +1,8 @@
-use super::{KJournalEntry, listener::KJournalListenerList, listener::{KJournalListener, KJournalListenerListError}, KJournalEntrySeverity};
+use super::{KJournalEntry, listener::KJournalListenerList, listener::{KJournalListener, KJournalListenerListError}, KJournalEntrySeverity, KJournalSeveritySet};
+use super::filter::{KJournalFilter, KJournalFilterError};
+use super::throttle::{KJournalThrottle, KJournalRateLimiter};
+use super::span::{KJournalEntrySpan, KJournalSpanFrame, KJournalSpanGuard};
+use std::time::{Duration, Instant};
 
 /// ##### Journal use for logging events and information.
 /// 
@@ -8,33 +12,78 @@ use super::{KJournalEntry, listener::KJournalListenerList, listener::{KJournalLi
 /// Create a new [KJournal] with minimum buffer size, with a listener to write entries.
 /// ```
 /// // Import needed modules
-/// use olympus_kleio::journal::{KJournal, KJournalEntrySeverity, KJournalListenerPrint, KJOURNAL_BUFFER_MIN};
+/// use olympus::kleio::journal::{KJournal, KJournalEntrySeverity, KJournalSeveritySet, KJournalListenerPrint, KJOURNAL_BUFFER_MIN};
 /// 
 /// // Create a journal that log any severity.
-/// let mut j = KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+/// let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
 /// 
 /// // Create a listener that will print each entry to the KJournal
-/// let l = KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
+/// let l = KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
 /// 
 /// // Add listener to journal
-/// j.add_listener(&l);
+/// assert!(j.add_listener(&l).is_ok());
 /// 
 /// // Write an entry to the KJournal that will also be listened by KJournalListenerPrint
-/// j.write(KJournalEntrySeverity::ERROR, "Error detected");
+/// j.write(KJournalEntrySeverity::Error, "Error detected");
 /// ```
+/// 
+/// # Target(s)
+/// Entries can be written for a hierarchical target with [KJournal::write_target()]. Each target can have its own
+/// severity according to the [KJournalFilter] of the journal, which can be reloaded at runtime with [KJournal::reload_filter()].
+/// ```
+/// use olympus::kleio::journal::{KJournal, KJournalEntrySeverity, KJournalSeveritySet, KJOURNAL_BUFFER_MIN};
+/// 
+/// let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_NO_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+/// 
+/// // Turn on debug output for x11 only.
+/// j.reload_filter("warning, kleio::display::x11=debug").unwrap();
+/// 
+/// j.write_target("kleio::display::x11", KJournalEntrySeverity::Debug, "Written");
+/// j.write_target("kleio::asset", KJournalEntrySeverity::Debug, "Ignored");
+/// ```
+/// 
+/// # Flood protection
+/// Repeated entries can be collapsed and rate limited per journal with a [KJournalThrottle] set via [KJournal::set_throttle()].
+/// When a run of collapsed duplicates ends, listeners are notified once more with the collapsed entry and its total repeat count.
+/// This second notification is an update of the first one ([KJournalEntry::is_update()]).
+/// 
+/// # Span(s)
+/// Spans measure how long a subsystem takes. Entries written inside a span are attached to it with a [KJournalEntrySpan]
+/// and an entry with the span duration is written when it exits. See [KJournal::span()] and [KJournalSpanGuard].
 pub struct KJournal<'a> {
 
     /// Name of the journal
     name : String,
 
-    /// Severities to log. KJournalEntrySeverity::ALL_WITH_DEBUG by default.
-    severity : u8,
+    /// Severities to log per target.
+    filter : KJournalFilter,
 
     /// List of listeners
     listeners : KJournalListenerList<'a>,
 
     /// Journal entries kept in circular buffer
     entries :  KJournalBuffer,
+
+    /// Deduplication and rate limiting configuration.
+    throttle : KJournalThrottle,
+
+    /// Rate limit counters per message key.
+    rate_limiter : KJournalRateLimiter,
+
+    /// Count of entries suppressed by rate limiting.
+    suppressed : u64,
+
+    /// True if latest entry collapsed duplicates that listeners weren't notified of yet.
+    repeat_pending : bool,
+
+    /// Spans currently entered, innermost last.
+    spans : Vec<KJournalSpanFrame>,
+
+    /// ID given to the next span entered.
+    next_span_id : u64,
+
+    /// Severity of entries written when spans exit.
+    span_severity : KJournalEntrySeverity,
 }
 
 /// Enumeration of possible [KJournal] errors.
@@ -67,25 +116,95 @@ impl<'a> KJournal<'a> {
     /// Returns Err([KJournalError::BufferSizeTooSmall]) if `max_entries` < [KJOURNAL_BUFFER_MIN].
     /// 
     /// Returns Err([KJournalError::BufferSizeTooBig]) if `max_entries` > [KJOURNAL_BUFFER_MAX].
-    pub fn new(name : &str, severity : u8, max_entries : usize) -> Result<KJournal, KJournalError> {
+    pub fn new(name : &str, severity : impl Into<KJournalSeveritySet>, max_entries : usize) -> Result<KJournal<'a>, KJournalError> {
 
         match  KJournalBuffer::new(max_entries) {
             Ok(buffer) => Ok( KJournal {
                 name: name.to_owned(), 
-                severity: severity, 
+                filter: KJournalFilter::new(severity.into()), 
                 listeners: KJournalListenerList::new(), 
-                entries: buffer  }),
+                entries: buffer,
+                throttle: KJournalThrottle::none(),
+                rate_limiter: KJournalRateLimiter::new(),
+                suppressed: 0,
+                repeat_pending: false,
+                spans: Vec::new(),
+                next_span_id: 1,
+                span_severity: KJournalEntrySeverity::Debug  }),
             Err(error) => Err(error),
         }
 
     }
 
     /// Write a new entry to [KJournal] with [`KJournalEntrySeverity`] and description if entry is not ignored.
-    pub fn write(&mut self, severity : u8, description : &str) {
+    /// 
+    /// Entry is written without target and is filtered by the default severity.
+    pub fn write(&mut self, severity : KJournalEntrySeverity, description : &str) {
+        self.write_target("", severity, description);
+    }
+
+    /// Write a new entry to [KJournal] for a hierarchical target (ie. `kleio::display::x11`) with [`KJournalEntrySeverity`] and description if entry is not ignored.
+    /// 
+    /// Entry is filtered by the [KJournalFilter] severity of the target.
+    pub fn write_target(&mut self, target : &str, severity : KJournalEntrySeverity, description : &str) {
+        self.write_fields(target, severity, description, &[]);
+    }
+
+    /// Write a new entry to [KJournal] for a hierarchical target with [`KJournalEntrySeverity`], description and structured fields as (key, value)
+    /// if entry is not ignored.
+    /// 
+    /// Entry is filtered by the [KJournalFilter] severity of the target.
+    /// 
+    /// Entry is attached to the current span if any.
+    pub fn write_fields(&mut self, target : &str, severity : KJournalEntrySeverity, description : &str, fields : &[(&str, &str)]) {
+        let span = self.spans.last().map(|frame| KJournalEntrySpan::new(frame.id, self.get_parent_span(), self.spans.len(), None));
+        self.write_span(target, severity, description, fields, span);
+    }
+
+    /// Write a new entry attached to a span if entry is not ignored.
+    fn write_span(&mut self, target : &str, severity : KJournalEntrySeverity, description : &str, fields : &[(&str, &str)], span : Option<KJournalEntrySpan>) {
         
-        // Only write and notify if journal log this severity.
-        if self.severity & severity > 0 {
-            self.listeners.notify(self.entries.write(severity, description));
+        // Only write and notify if journal log this severity for this target.
+        if self.filter.is_enabled(target, severity) {
+
+            // Collapse entry into latest written if they are the same. Done before rate limiting so repeat count includes every occurrence.
+            if self.throttle.is_deduplicate() {
+                if let Some(latest) = self.entries.peek_latest_mut() {
+                    if latest.is_same(target, severity, description, fields) && latest.get_span() == span {
+                        latest.repeat();
+                        self.repeat_pending = true;
+                        return;
+                    }
+                }
+            }
+
+            // Suppress entry if rate limit of this message is reached.
+            if let Some((max_entries, period)) = self.throttle.get_rate_limit() {
+                if !self.rate_limiter.allow(target, description, max_entries, period) {
+                    // Suppressed entry still ends the run of duplicates.
+                    self.flush();
+                    self.entries.seal_latest();
+                    self.suppressed += 1;
+                    return;
+                }
+            }
+
+            // Run of duplicates ended, notify listeners of collapsed entry.
+            self.flush();
+
+            self.listeners.notify(self.entries.write_span(target, severity, description, fields, span));
+        }
+    }
+
+    /// Notify listeners of the latest entry if it collapsed duplicates they weren't notified of yet.
+    /// 
+    /// This is called automatically when a different entry is written or when reading the journal.
+    pub fn flush(&mut self) {
+        if self.repeat_pending {
+            self.repeat_pending = false;
+            if let Some(latest) = self.entries.peek_latest_mut() {
+                self.listeners.notify(latest);
+            }
         }
     }
 
@@ -93,6 +212,7 @@ impl<'a> KJournal<'a> {
     /// 
     /// Returns [Some(KJournalEntry)](https://doc.rust-lang.org/beta/core/option/enum.Option.html#variant.Some) if any or [None] otherwise.
     pub fn read(&mut self) -> Option<&KJournalEntry> {
+        self.flush();
         self.entries.latest()
     }
 
@@ -103,9 +223,108 @@ impl<'a> KJournal<'a> {
 
     /// Clear the [KJournal] to 0 entries.
     pub fn clear(&mut self) {
+        self.repeat_pending = false;
         self.entries.clear()
     }
 
+    /// Set the [KJournalThrottle] used for deduplication and rate limiting. Rate limit counters are reset.
+    pub fn set_throttle(&mut self, throttle : KJournalThrottle) {
+        self.flush();
+        self.throttle = throttle;
+        self.rate_limiter.clear();
+    }
+
+    /// Get the [KJournalThrottle] used for deduplication and rate limiting.
+    pub fn get_throttle(&self) -> KJournalThrottle {
+        self.throttle
+    }
+
+    /// Get the count of entries suppressed by rate limiting since creation.
+    pub fn get_suppressed_count(&self) -> u64 {
+        self.suppressed
+    }
+
+    /// Enter a new span for a target. Entries written until the span exits are attached to it.
+    /// 
+    /// Prefer [KJournal::span()] which exits the span automatically.
+    /// 
+    /// Returns the ID of the span entered.
+    pub fn enter_span(&mut self, target : &str, name : &str) -> u64 {
+        let id = self.next_span_id;
+        self.next_span_id += 1;
+        self.spans.push(KJournalSpanFrame { id, target: target.to_string(), name: name.to_string(), start: Instant::now() });
+        id
+    }
+
+    /// Enter a new span for a target and returns a [KJournalSpanGuard] that exits the span when dropped.
+    /// 
+    /// The guard dereferences to the [KJournal], allowing entries to be written inside the span.
+    pub fn span(&mut self, target : &str, name : &str) -> KJournalSpanGuard<'_, 'a> {
+        let id = self.enter_span(target, name);
+        KJournalSpanGuard::new(self, id)
+    }
+
+    /// Exit the innermost span and write an entry with its name as description and its duration.
+    /// 
+    /// Returns Some(Duration) of the span or [None] if no span was entered.
+    pub fn exit_span(&mut self) -> Option<Duration> {
+        match self.spans.last() {
+            Some(frame) => self.exit_span_id(frame.id),
+            None => None,
+        }
+    }
+
+    /// Exit a span and the spans nested in it, writing an entry with the duration of each.
+    /// 
+    /// Returns Some(Duration) of the span or [None] if span isn't entered.
+    pub fn exit_span_id(&mut self, id : u64) -> Option<Duration> {
+        if !self.spans.iter().any(|frame| frame.id == id) {
+            return None;
+        }
+
+        loop {
+            let depth = self.spans.len();
+            let frame = self.spans.pop()?;
+            let duration = frame.start.elapsed();
+            let span = KJournalEntrySpan::new(frame.id, self.spans.last().map(|parent| parent.id), depth, Some(duration));
+
+            self.write_span(&frame.target, self.span_severity, &frame.name, &[], Some(span));
+
+            if frame.id == id {
+                return Some(duration);
+            }
+        }
+    }
+
+    /// Get the ID of the innermost span entered or [None] if outside of spans.
+    pub fn get_current_span(&self) -> Option<u64> {
+        self.spans.last().map(|frame| frame.id)
+    }
+
+    /// Get the count of spans currently entered.
+    pub fn get_span_depth(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Set the [`KJournalEntrySeverity`] of entries written when spans exit. Default is [`KJournalEntrySeverity::Debug`].
+    pub fn set_span_severity(&mut self, severity : KJournalEntrySeverity) {
+        self.span_severity = severity;
+    }
+
+    /// Get the [`KJournalEntrySeverity`] of entries written when spans exit.
+    pub fn get_span_severity(&self) -> KJournalEntrySeverity {
+        self.span_severity
+    }
+
+    /// Get the ID of the parent of the innermost span.
+    fn get_parent_span(&self) -> Option<u64> {
+        if self.spans.len() >= 2 {
+            Some(self.spans[self.spans.len() - 2].id)
+        } else {
+            None
+        }
+    }
+
     /// Add [KJournalListener] to the [KJournal].
     /// 
     /// Returns [OK(usize)][Ok] with index of new listener added.
@@ -129,14 +348,42 @@ impl<'a> KJournal<'a> {
     }
 
 
-    /// Set [`KJournalEntrySeverity`] flags to log in journal. Will ignore other severity and won't push them to listeners.
-    pub fn set_severity(&mut self, severity : u8) {
-        self.severity = severity;
+    /// Set [`KJournalSeveritySet`] to log in journal. Will ignore other severity and won't push them to listeners.
+    /// 
+    /// Only the default severity of the [KJournalFilter] is modified. Target overrides are kept.
+    pub fn set_severity(&mut self, severity : impl Into<KJournalSeveritySet>) {
+        self.filter.set_severity(severity.into());
     } 
 
-    /// Get [`KJournalEntrySeverity`] the [KJournal] listen to.
-    pub fn get_severity(&self) -> u8 {
-        self.severity
+    /// Get default [`KJournalSeveritySet`] the [KJournal] listen to.
+    pub fn get_severity(&self) -> KJournalSeveritySet {
+        self.filter.get_severity()
+    }
+
+    /// Replace the [KJournalFilter] used to filter entries per target.
+    pub fn set_filter(&mut self, filter : KJournalFilter) {
+        self.filter = filter;
+    }
+
+    /// Get an immutable reference to the [KJournalFilter] used to filter entries per target.
+    pub fn get_filter(&self) -> &KJournalFilter {
+        &self.filter
+    }
+
+    /// Get a mutable reference to the [KJournalFilter], allowing modification of target severity at runtime.
+    pub fn get_filter_mut(&mut self) -> &mut KJournalFilter {
+        &mut self.filter
+    }
+
+    /// Reload the [KJournalFilter] from directives without recreating the journal. See [KJournalFilter] for syntax.
+    /// 
+    /// Current filter is kept if directives are invalid.
+    /// 
+    /// # Error(s)
+    /// Returns the same errors as [KJournalFilter::parse()].
+    pub fn reload_filter(&mut self, directives : &str) -> Result<(), KJournalFilterError> {
+        self.filter = KJournalFilter::parse(directives)?;
+        Ok(())
     }
 
     /// Set the maximum entries kept in [KJournal].
@@ -157,6 +404,7 @@ impl<'a> KJournal<'a> {
             Ok(buffer) => 
                 {
                     // Store new maximum entries
+                    self.repeat_pending = false;
                     self.entries = buffer;
                     Ok(max_entries)
                 }
@@ -200,7 +448,8 @@ pub struct KJournalBuffer {
     /// Tail of the circular buffer
     tail : usize,
 
-    
+    /// True if entry at head is the latest written and wasn't read since.
+    written : bool,
 }
 
 impl KJournalBuffer {
@@ -234,12 +483,12 @@ impl KJournalBuffer {
 
         // Create all entries.
         for _ in 0..padded_size {
-            entries.push( KJournalEntry::new(KJournalEntrySeverity::OTHER, ""));
+            entries.push( KJournalEntry::new(KJournalEntrySeverity::Other, ""));
         }
 
         // Return KJournalBuffer. size is padded for head == tail conundrum
         Ok(KJournalBuffer {
-            entries, size : padded_size, head:0, tail:0
+            entries, size : padded_size, head:0, tail:0, written:false
         })
     }
 
@@ -260,22 +509,64 @@ impl KJournalBuffer {
     /// Clear the buffer.
     pub fn clear(&mut self){
         self.tail = self.head;
+        self.written = false;
     }
 
     /// Write a new entry to the buffer with severity and description.
     /// 
     /// Returns a reference to new entry written.
-    pub fn write(&mut self, severity : u8, description : &str) -> &KJournalEntry {
+    pub fn write(&mut self, severity : KJournalEntrySeverity, description : &str) -> &KJournalEntry {
+        self.write_target("", severity, description)
+    }
+
+    /// Write a new entry to the buffer with target, severity and description.
+    /// 
+    /// Returns a reference to new entry written.
+    pub fn write_target(&mut self, target : &str, severity : KJournalEntrySeverity, description : &str) -> &KJournalEntry {
+        self.write_fields(target, severity, description, &[])
+    }
+
+    /// Write a new entry to the buffer with target, severity, description and structured fields.
+    /// 
+    /// Returns a reference to new entry written.
+    pub fn write_fields(&mut self, target : &str, severity : KJournalEntrySeverity, description : &str, fields : &[(&str, &str)]) -> &KJournalEntry {
+        self.write_span(target, severity, description, fields, None)
+    }
+
+    /// Write a new entry to the buffer with target, severity, description, structured fields and span.
+    /// 
+    /// Returns a reference to new entry written.
+    pub fn write_span(&mut self, target : &str, severity : KJournalEntrySeverity, description : &str, fields : &[(&str, &str)], span : Option<KJournalEntrySpan>) -> &KJournalEntry {
 
         // Increment head.
         self.inc_head();
 
         // Write entry into buffer
-        self.entries[self.head].update(severity, description);
+        self.entries[self.head].update_fields(target, severity, description, fields);
+        self.entries[self.head].set_span(span);
+        self.written = true;
 
         &self.entries[self.head]
     }
 
+    /// Get a mutable reference to the latest [`KJournalEntry`] written in the buffer without reading it.
+    /// 
+    /// # Return
+    /// [Some(KJournalEntry)](https://doc.rust-lang.org/beta/core/option/enum.Option.html#variant.Some) if latest entry written
+    /// wasn't read since, [None] otherwise.
+    pub fn peek_latest_mut(&mut self) -> Option<&mut KJournalEntry> {
+        if !self.written || self.head == self.tail {
+            None
+        } else {
+            Some(&mut self.entries[self.head])
+        }
+    }
+
+    /// Prevent the latest [`KJournalEntry`] written from being peeked with [KJournalBuffer::peek_latest_mut()] until a new entry is written.
+    pub fn seal_latest(&mut self) {
+        self.written = false;
+    }
+
     /// Get the latest [`KJournalEntry`] in the buffer.
     /// 
     /// # Return
@@ -289,6 +580,9 @@ impl KJournalBuffer {
             // Get current entry position AKA head.
             let current = self.head;
 
+            // Entries under head were written before the latest, they can't be peeked anymore.
+            self.written = false;
+
             // Decrease head.
             self.dec_head();
 
@@ -1,18 +1,21 @@
-use super::KJournalEntry;
+use super::{KJournalEntry, KJournalSeveritySet};
 
 /// Implementing this trait is needed to listen to new [KJournalEntry].
 /// 
-/// Listeners are notified of new journal entry according to [severity][super::KJournalEntrySeverity] they listen
+/// Listeners are notified of new journal entry according to [severity set][KJournalSeveritySet] they listen
 /// to according to set_severity().
+/// 
+/// An entry collapsing duplicates is notified twice : once when first written and once when the run of duplicates ends.
+/// The second notification is an update of the first and must not be counted as a new entry (see [KJournalEntry::is_update()]).
 pub trait KJournalListener {
     /// Notification of new entry with an unmutable reference to it.
     fn notify(&self, entry : &KJournalEntry);
 
     /// Set the severity the listener will listen to.
-    fn set_severity(&mut self, severity:u8);
+    fn set_severity(&mut self, severity : KJournalSeveritySet);
 
     /// Get the severity the listener is listening to.
-    fn get_severity(&self) -> u8;
+    fn get_severity(&self) -> KJournalSeveritySet;
 }
 
 
@@ -47,7 +50,7 @@ impl<'a> KJournalListenerList<'a> {
     pub fn notify(&self, entry : &super::KJournalEntry) {
         for listener in &self.listeners {
             // Verify that listener is listening to this severity.
-            if listener.get_severity() & entry.get_severity() > 0 {
+            if listener.get_severity().contains(entry.get_severity()) {
                 listener.notify(entry);
             }
         }
@@ -0,0 +1,97 @@
+use std::{cell::RefCell, fs::File, io::{BufWriter, Write}, path::Path};
+
+use super::{KJournalListener, KJournalEntry, KJournalSeveritySet, binary::{KJournalRecord, KJournalBinaryError, write_binary_header}};
+
+/// Implementation of [KJournalListener] that write each new entry into a compact binary journal.
+///
+/// Binary journals can be read with [`super::binary::KJournalBinaryReader`]. See [KJournalRecord] for the format.
+///
+/// # Example(s)
+/// ```no_run
+/// use std::path::Path;
+/// use olympus::kleio::journal::{KJournal, KJournalEntrySeverity, KJournalSeveritySet, KJournalListenerBinary, KJOURNAL_BUFFER_MIN};
+///
+/// let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+/// let l = KJournalListenerBinary::create(Path::new("soak.kjnl"), KJournalSeveritySet::ALL_WITH_DEBUG).unwrap();
+/// assert!(j.add_listener(&l).is_ok());
+///
+/// j.write_fields("kleio::display", KJournalEntrySeverity::Information, "Window created", &[("width", "320"), ("height", "240")]);
+/// ```
+pub struct KJournalListenerBinary {
+    /// Severity to be written.
+    severity : KJournalSeveritySet,
+
+    /// Destination of records. Wrapped in [RefCell] since [KJournalListener::notify()] is immutable.
+    writer : RefCell<Box<dyn Write>>,
+
+    /// Latest error that occurred while writing, if any.
+    error : RefCell<Option<KJournalBinaryError>>,
+}
+
+impl KJournalListenerBinary {
+    /// Create a new [KJournalListenerBinary] that write the header and then records to writer if they match listened [severities][KJournalSeveritySet].
+    ///
+    /// Returns new [KJournalListenerBinary] created.
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalBinaryError::IoError]) if the header can't be written.
+    pub fn new(mut writer : Box<dyn Write>, severity : impl Into<KJournalSeveritySet>) -> Result<KJournalListenerBinary, KJournalBinaryError> {
+        write_binary_header(&mut writer)?;
+        Ok(KJournalListenerBinary { severity: severity.into(), writer: RefCell::new(writer), error: RefCell::new(None) })
+    }
+
+    /// Create a new [KJournalListenerBinary] writing into a new file at path. File is truncated if it exists.
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalBinaryError::IoError]) if the file can't be created.
+    pub fn create(path : &Path, severity : impl Into<KJournalSeveritySet>) -> Result<KJournalListenerBinary, KJournalBinaryError> {
+        match File::create(path) {
+            Ok(file) => Self::new(Box::new(BufWriter::new(file)), severity),
+            Err(err) => Err(KJournalBinaryError::IoError(err.kind())),
+        }
+    }
+
+    /// Flush records buffered to the writer.
+    ///
+    /// # Error(s)
+    /// Returns Err([KJournalBinaryError::IoError]) if flushing failed.
+    pub fn flush(&self) -> Result<(), KJournalBinaryError> {
+        self.writer.borrow_mut().flush().map_err(|err| KJournalBinaryError::IoError(err.kind()))
+    }
+
+    /// Take the latest error that occurred while writing a record, if any.
+    ///
+    /// [KJournalListener::notify()] can't return errors so they are kept until taken. Records bigger than
+    /// [KJOURNAL_BINARY_RECORD_MAX](super::binary::KJOURNAL_BINARY_RECORD_MAX) are skipped with [KJournalBinaryError::RecordTooLarge].
+    pub fn take_error(&self) -> Option<KJournalBinaryError> {
+        self.error.borrow_mut().take()
+    }
+}
+
+impl KJournalListener for KJournalListenerBinary {
+    fn notify(&self, new_entry : &KJournalEntry) {
+        // Records too large to be read back are skipped.
+        let record = match KJournalRecord::from(new_entry).encode() {
+            Ok(record) => record,
+            Err(err) => { self.error.replace(Some(err)); return },
+        };
+
+        if let Err(err) = self.writer.borrow_mut().write_all(&record) {
+            self.error.replace(Some(KJournalBinaryError::IoError(err.kind())));
+        }
+    }
+
+    fn set_severity(&mut self, severity : KJournalSeveritySet) {
+        self.severity = severity;
+    }
+
+    fn get_severity(&self) -> KJournalSeveritySet {
+        self.severity
+    }
+}
+
+impl Drop for KJournalListenerBinary {
+    fn drop(&mut self) {
+        let _ = self.writer.get_mut().flush();
+    }
+}
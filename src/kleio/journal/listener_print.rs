@@ -1,31 +1,59 @@
-use super::{KJournalListener, get_journal_severity_symbol};
+use super::{KJournalListener, KJournalSeveritySet};
 
 /// Implementation of [KJournalListener] that print new entry into console.
 pub struct KJournalListenerPrint {
     /// Severity to be printed.
-    severity : u8,
+    severity : KJournalSeveritySet,
 
 }
 
 impl KJournalListenerPrint {
-    /// Create a new instance of [KJournalListenerPrint] that print new entry to log if they match listened [severity][super::KJournalEntrySeverity].
+    /// Create a new instance of [KJournalListenerPrint] that print new entry to log if they match listened [severities][KJournalSeveritySet].
     /// 
     /// Returns new [KJournalListenerPrint] created.
-    pub fn new(severity : u8) -> KJournalListenerPrint {
-        KJournalListenerPrint { severity }
+    pub fn new(severity : impl Into<KJournalSeveritySet>) -> KJournalListenerPrint {
+        KJournalListenerPrint { severity: severity.into() }
     }
 }
 
 impl KJournalListener for KJournalListenerPrint {
     fn notify(&self, new_entry : &super::KJournalEntry) {
-        println!("[{} {:?}] {}", get_journal_severity_symbol(new_entry.get_severity()), new_entry.get_date_time(), new_entry.get_description())
+        // Indent description according to span depth. Span exit entries are aligned with their parent content.
+        let mut description = match new_entry.get_span() {
+            Some(span) if span.is_exit() => "  ".repeat(span.get_depth().saturating_sub(1)),
+            Some(span) => "  ".repeat(span.get_depth()),
+            None => String::new(),
+        };
+        description.push_str(new_entry.get_description());
+
+        // Add span duration to description of span exit entries.
+        if let Some(duration) = new_entry.get_span().and_then(|span| span.get_duration()) {
+            description.push_str(&format!(" [{:?}]", duration));
+        }
+
+        // Add structured fields to description.
+        if !new_entry.get_fields().is_empty() {
+            let fields : Vec<String> = new_entry.get_fields().iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            description.push_str(&format!(" {{{}}}", fields.join(", ")));
+        }
+
+        // Add repeat count to description of collapsed entries.
+        if new_entry.get_repeat_count() > 1 {
+            description.push_str(&format!(" (repeated {} times)", new_entry.get_repeat_count()));
+        }
+
+        if new_entry.get_target().is_empty() {
+            println!("[{} {:?}] {}", new_entry.get_severity().get_symbol(), new_entry.get_date_time(), description)
+        } else {
+            println!("[{} {:?}] {} : {}", new_entry.get_severity().get_symbol(), new_entry.get_date_time(), new_entry.get_target(), description)
+        }
     }
 
-    fn set_severity(&mut self, severity:u8) {
+    fn set_severity(&mut self, severity : KJournalSeveritySet) {
         self.severity = severity;
     }
 
-    fn get_severity(&self) -> u8 {
+    fn get_severity(&self) -> KJournalSeveritySet {
        self.severity
     }
 }
\ No newline at end of file
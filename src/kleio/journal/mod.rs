@@ -1,10 +1,22 @@
 /// # Re-export for Public API
 #[doc(inline)]
 pub use severity::KJournalEntrySeverity as KJournalEntrySeverity;
+pub use severity::KJournalSeveritySet as KJournalSeveritySet;
+pub use severity::KJournalSeverityError as KJournalSeverityError;
 pub use severity::get_journal_severity_symbol as get_journal_severity_symbol;
 pub use entry::KJournalEntry as KJournalEntry;
 pub use listener::KJournalListener as KJournalListener;
 pub use listener_print::KJournalListenerPrint as KJournalListenerPrint;
+pub use filter::KJournalFilter as KJournalFilter;
+pub use filter::KJournalFilterError as KJournalFilterError;
+pub use filter::KJOURNAL_FILTER_ENV as KJOURNAL_FILTER_ENV;
+pub use throttle::KJournalThrottle as KJournalThrottle;
+pub use span::KJournalEntrySpan as KJournalEntrySpan;
+pub use span::KJournalSpanGuard as KJournalSpanGuard;
+pub use listener_binary::KJournalListenerBinary as KJournalListenerBinary;
+pub use binary::KJournalRecord as KJournalRecord;
+pub use binary::KJournalBinaryReader as KJournalBinaryReader;
+pub use binary::KJournalBinaryError as KJournalBinaryError;
 pub use journal::KJournal as KJournal;
 pub use journal::KJOURNAL_BUFFER_MAX as KJOURNAL_BUFFER_MAX;
 pub use journal::KJOURNAL_BUFFER_MIN as KJOURNAL_BUFFER_MIN;
@@ -30,3 +42,22 @@ pub mod listener;
 #[doc(hidden)]
 pub mod listener_print;
 
+// Kleio journal per-target severity filter
+#[doc(hidden)]
+pub mod filter;
+
+// Kleio journal deduplication and rate limiting
+#[doc(hidden)]
+pub mod throttle;
+
+// Kleio journal spans and scoped timing
+#[doc(hidden)]
+pub mod span;
+
+// Kleio binary implementation of journal listener
+#[doc(hidden)]
+pub mod listener_binary;
+
+/// Binary journal format, reader and merge of journals.
+pub mod binary;
+
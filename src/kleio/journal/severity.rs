@@ -1,51 +1,303 @@
-/// ##### Enumeration of journal entry severities as bytes flags in order of severity.
-#[allow(non_snake_case)]
-pub mod KJournalEntrySeverity {
+use std::{fmt::Display, ops::{BitOr, BitOrAssign, BitAnd, BitAndAssign, Not}, str::FromStr};
 
+/// ##### Enumeration of journal entry severities in order of severity.
+///
+/// Severities are ordered from [KJournalEntrySeverity::Debug] (lowest) to [KJournalEntrySeverity::Fatal] (highest)
+/// so they can be compared (ie. `severity >= KJournalEntrySeverity::Warning`).
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::journal::KJournalEntrySeverity;
+///
+/// let severity : KJournalEntrySeverity = "warn".parse().unwrap();
+///
+/// assert_eq!(severity, KJournalEntrySeverity::Warning);
+/// assert!(KJournalEntrySeverity::Error > severity);
+/// assert_eq!(severity.to_string(), "WARNING");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum KJournalEntrySeverity {
     /// Lowest severity. Debug message. Listeners shouldn't listener to those by default.
-    pub const DEBUG: u8 = 1;
+    Debug = 1,
 
     /// Other message with low severity impact.
-    pub const OTHER: u8 = 2;
+    Other = 2,
 
     /// Information about event as they occur.
-    pub const INFORMATION: u8 = 4;
+    Information = 4,
 
     /// An occurred event that can be potentially severe.
-    pub const WARNING: u8 = 8;
+    Warning = 8,
 
     /// A severe error that occurred but didn't cause the program to crash.
-    pub const ERROR: u8 = 16;
+    Error = 16,
 
     /// Highest severity. Causes program to crash.
-    pub const FATAL: u8 = 32;
-    
-    /// Quick shortcut to all severity flags without DEBUG.
-    pub const ALL_NO_DEBUG: u8 = 62;
-
-    /// Quick shortcut to all severity flags including DEBUG.
-    pub const ALL_WITH_DEBUG: u8 = 63;
-}
-
-/// Returns a 1 character symbol of severity.
-/// 
-/// * DEBUG => 'D'
-/// * OTHER => 'O'
-/// * INFORMATION => 'I'
-/// * WARNING => 'W'
-/// * ERROR => 'E'
-/// * FATAL => 'F'
-/// * UNKNOWN => '?'
-pub fn get_journal_severity_symbol(severity : u8) -> char {
-
-    match severity {
-        KJournalEntrySeverity::DEBUG => 'D',
-        KJournalEntrySeverity::OTHER => 'O',
-        KJournalEntrySeverity::INFORMATION => 'I',
-        KJournalEntrySeverity::WARNING => 'W',
-        KJournalEntrySeverity::ERROR => 'E',
-        KJournalEntrySeverity::FATAL => 'F',
-        
-        _ => '?'
-    }
-}
\ No newline at end of file
+    Fatal = 32,
+}
+
+/// Enumeration of possible severity parsing errors.
+pub enum KJournalSeverityError {
+    /// Happens when a severity name or flags number can't be parsed.
+    InvalidSeverity,
+}
+
+impl std::fmt::Debug for KJournalSeverityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSeverity => write!(f, "InvalidSeverity"),
+        }
+    }
+}
+
+impl KJournalEntrySeverity {
+    /// All severities in order of severity.
+    pub const ALL : [KJournalEntrySeverity; 6] = [Self::Debug, Self::Other, Self::Information, Self::Warning, Self::Error, Self::Fatal];
+
+    /// Returns the severity flag used in [KJournalSeveritySet].
+    pub fn get_flag(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the severity of a single flag or [None] if flag isn't a single severity.
+    pub fn from_flag(flag : u8) -> Option<KJournalEntrySeverity> {
+        Self::ALL.into_iter().find(|s| s.get_flag() == flag)
+    }
+
+    /// Returns the upper case name of the severity.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Other => "OTHER",
+            Self::Information => "INFORMATION",
+            Self::Warning => "WARNING",
+            Self::Error => "ERROR",
+            Self::Fatal => "FATAL",
+        }
+    }
+
+    /// Returns a 1 character symbol of severity.
+    ///
+    /// * Debug => 'D'
+    /// * Other => 'O'
+    /// * Information => 'I'
+    /// * Warning => 'W'
+    /// * Error => 'E'
+    /// * Fatal => 'F'
+    pub fn get_symbol(&self) -> char {
+        match self {
+            Self::Debug => 'D',
+            Self::Other => 'O',
+            Self::Information => 'I',
+            Self::Warning => 'W',
+            Self::Error => 'E',
+            Self::Fatal => 'F',
+        }
+    }
+}
+
+impl Display for KJournalEntrySeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+impl FromStr for KJournalEntrySeverity {
+    type Err = KJournalSeverityError;
+
+    /// Parse a severity name, case insensitive. `info` and `warn` are accepted as aliases.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "debug" => Ok(Self::Debug),
+            "other" => Ok(Self::Other),
+            "information" | "info" => Ok(Self::Information),
+            "warning" | "warn" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            "fatal" => Ok(Self::Fatal),
+            _ => Err(KJournalSeverityError::InvalidSeverity),
+        }
+    }
+}
+
+/// ##### Set of [KJournalEntrySeverity] listened to by a [`super::KJournal`] or a [`super::KJournalListener`].
+///
+/// Sets are combined with `|`, `&` and `!` like flags. Combining 2 severities with `|` gives a set.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::journal::{KJournalEntrySeverity, KJournalSeveritySet};
+///
+/// // WARNING and above.
+/// let set = KJournalSeveritySet::at_least(KJournalEntrySeverity::Warning);
+///
+/// assert_eq!(set, KJournalEntrySeverity::Warning | KJournalEntrySeverity::Error | KJournalEntrySeverity::Fatal);
+/// assert!(set.contains(KJournalEntrySeverity::Error));
+/// assert!(!set.contains(KJournalEntrySeverity::Debug));
+/// assert_eq!(set.to_string(), "WARNING|ERROR|FATAL");
+/// assert_eq!("warning|error|fatal".parse::<KJournalSeveritySet>().unwrap(), set);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct KJournalSeveritySet {
+    /// Severity flags of the set.
+    flags : u8,
+}
+
+impl KJournalSeveritySet {
+    /// Empty set. Nothing is listened to.
+    pub const NONE : KJournalSeveritySet = KJournalSeveritySet { flags: 0 };
+
+    /// All severities without DEBUG.
+    pub const ALL_NO_DEBUG : KJournalSeveritySet = KJournalSeveritySet { flags: 62 };
+
+    /// All severities including DEBUG.
+    pub const ALL_WITH_DEBUG : KJournalSeveritySet = KJournalSeveritySet { flags: 63 };
+
+    /// Create a set of severities from flags.
+    ///
+    /// Returns [None] if flags contain bits that aren't a [KJournalEntrySeverity].
+    pub fn from_flags(flags : u8) -> Option<KJournalSeveritySet> {
+        if flags & !Self::ALL_WITH_DEBUG.flags == 0 {
+            Some(KJournalSeveritySet { flags })
+        } else {
+            None
+        }
+    }
+
+    /// Create a set of the given severity and all severities above it.
+    pub const fn at_least(severity : KJournalEntrySeverity) -> KJournalSeveritySet {
+        // Flags are powers of 2 in order, so all flags >= severity are the complement of the ones below.
+        KJournalSeveritySet { flags: Self::ALL_WITH_DEBUG.flags & !(severity as u8 - 1) }
+    }
+
+    /// Returns the severity flags of the set.
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Returns True if the set contains the severity.
+    pub fn contains(&self, severity : KJournalEntrySeverity) -> bool {
+        self.flags & severity.get_flag() > 0
+    }
+
+    /// Returns True if the set contains no severity.
+    pub fn is_empty(&self) -> bool {
+        self.flags == 0
+    }
+
+    /// Add a severity to the set.
+    pub fn insert(&mut self, severity : KJournalEntrySeverity) {
+        self.flags |= severity.get_flag();
+    }
+
+    /// Remove a severity from the set.
+    pub fn remove(&mut self, severity : KJournalEntrySeverity) {
+        self.flags &= !severity.get_flag();
+    }
+
+    /// Returns an iterator over the severities of the set in order of severity.
+    pub fn iter(&self) -> impl Iterator<Item = KJournalEntrySeverity> {
+        let set = *self;
+        KJournalEntrySeverity::ALL.into_iter().filter(move |s| set.contains(*s))
+    }
+}
+
+impl From<KJournalEntrySeverity> for KJournalSeveritySet {
+    fn from(severity: KJournalEntrySeverity) -> Self {
+        KJournalSeveritySet { flags: severity.get_flag() }
+    }
+}
+
+impl Display for KJournalSeveritySet {
+    /// Severity names separated by `|` or `NONE` if empty.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "NONE");
+        }
+
+        let names : Vec<&str> = self.iter().map(|s| s.get_name()).collect();
+        write!(f, "{}", names.join("|"))
+    }
+}
+
+impl FromStr for KJournalSeveritySet {
+    type Err = KJournalSeverityError;
+
+    /// Parse severity names separated by `|`, `all`, `none` or a flags number.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        match s.to_lowercase().as_str() {
+            "all" => return Ok(Self::ALL_WITH_DEBUG),
+            "none" => return Ok(Self::NONE),
+            _ => {},
+        }
+
+        if let Ok(flags) = s.parse::<u8>() {
+            return Self::from_flags(flags).ok_or(KJournalSeverityError::InvalidSeverity);
+        }
+
+        let mut set = Self::NONE;
+        for name in s.split('|') {
+            set.insert(name.parse()?);
+        }
+        Ok(set)
+    }
+}
+
+impl BitOr for KJournalSeveritySet {
+    type Output = KJournalSeveritySet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        KJournalSeveritySet { flags: self.flags | rhs.flags }
+    }
+}
+
+impl BitOr<KJournalEntrySeverity> for KJournalSeveritySet {
+    type Output = KJournalSeveritySet;
+
+    fn bitor(self, rhs: KJournalEntrySeverity) -> Self::Output {
+        KJournalSeveritySet { flags: self.flags | rhs.get_flag() }
+    }
+}
+
+impl BitOr for KJournalEntrySeverity {
+    type Output = KJournalSeveritySet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        KJournalSeveritySet { flags: self.get_flag() | rhs.get_flag() }
+    }
+}
+
+impl BitOrAssign for KJournalSeveritySet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.flags |= rhs.flags;
+    }
+}
+
+impl BitAnd for KJournalSeveritySet {
+    type Output = KJournalSeveritySet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        KJournalSeveritySet { flags: self.flags & rhs.flags }
+    }
+}
+
+impl BitAndAssign for KJournalSeveritySet {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.flags &= rhs.flags;
+    }
+}
+
+impl Not for KJournalSeveritySet {
+    type Output = KJournalSeveritySet;
+
+    fn not(self) -> Self::Output {
+        KJournalSeveritySet { flags: !self.flags & Self::ALL_WITH_DEBUG.flags }
+    }
+}
+
+/// Returns a 1 character symbol of severity. See [KJournalEntrySeverity::get_symbol()].
+pub fn get_journal_severity_symbol(severity : KJournalEntrySeverity) -> char {
+    severity.get_symbol()
+}
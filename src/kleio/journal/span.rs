@@ -0,0 +1,147 @@
+use std::{ops::{Deref, DerefMut}, time::{Duration, Instant}};
+
+use super::KJournal;
+
+#[allow(unused_imports)]
+use super::{KJournalEntry, KJournalListener};
+
+/// ##### Span information attached to a [KJournalEntry] written inside a journal span.
+///
+/// Entries written inside a span carry the span ID, its parent ID and its depth. The entry written when
+/// the span exits also carries the [duration][KJournalEntrySpan::get_duration()] of the span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KJournalEntrySpan {
+    /// Unique ID of the span within the journal. Never 0.
+    id : u64,
+
+    /// ID of the parent span if nested.
+    parent_id : Option<u64>,
+
+    /// Nesting level of the span. 1 for a span without parent.
+    depth : usize,
+
+    /// Duration of the span. Only set on the entry written when the span exits.
+    duration : Option<Duration>,
+}
+
+impl KJournalEntrySpan {
+    /// Create a new [KJournalEntrySpan] from ID, parent ID, depth and optional duration.
+    pub fn new(id : u64, parent_id : Option<u64>, depth : usize, duration : Option<Duration>) -> KJournalEntrySpan {
+        KJournalEntrySpan { id, parent_id, depth, duration }
+    }
+
+    /// Returns the ID of the span.
+    pub fn get_id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the ID of the parent span or [None] if span isn't nested.
+    pub fn get_parent_id(&self) -> Option<u64> {
+        self.parent_id
+    }
+
+    /// Returns the nesting level of the span. 1 for a span without parent.
+    ///
+    /// [KJournalListener] can use it to render nested indentation.
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the duration of the span if entry was written when the span exited, [None] otherwise.
+    pub fn get_duration(&self) -> Option<Duration> {
+        self.duration
+    }
+
+    /// Returns True if entry was written when the span exited.
+    pub fn is_exit(&self) -> bool {
+        self.duration.is_some()
+    }
+}
+
+/// Span currently entered in a [KJournal].
+pub(crate) struct KJournalSpanFrame {
+    /// ID of the span.
+    pub id : u64,
+
+    /// Target of the span.
+    pub target : String,
+
+    /// Name of the span, written as description of the exit entry.
+    pub name : String,
+
+    /// Instant the span was entered.
+    pub start : Instant,
+}
+
+/// ##### Guard of a [KJournal] span. The span exits when the guard is dropped.
+///
+/// The guard dereferences to the [KJournal] so entries can be written, and nested spans entered, while the span is active.
+///
+/// # Example(s)
+/// ```
+/// use olympus::kleio::journal::{KJournal, KJournalEntrySeverity, KJournalSeveritySet, KJOURNAL_BUFFER_MIN};
+///
+/// let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+///
+/// {
+///     let mut frame = j.span("kleio::display", "frame");
+///     frame.write(KJournalEntrySeverity::Information, "Frame started");
+///
+///     {
+///         let mut render = frame.span("kleio::display", "render");
+///         render.write(KJournalEntrySeverity::Information, "Rendering");
+///     }   // render exits here and writes its duration.
+/// }   // frame exits here and writes its duration.
+///
+/// let frame_exit = j.read().unwrap();
+/// assert_eq!(frame_exit.get_description(), "frame");
+/// assert!(frame_exit.get_span().unwrap().get_duration().is_some());
+/// ```
+pub struct KJournalSpanGuard<'b, 'a> {
+    /// Journal the span was entered in.
+    journal : &'b mut KJournal<'a>,
+
+    /// ID of the span guarded.
+    id : u64,
+}
+
+impl<'b, 'a> KJournalSpanGuard<'b, 'a> {
+    /// Create a guard of span ID entered in journal.
+    pub(crate) fn new(journal : &'b mut KJournal<'a>, id : u64) -> KJournalSpanGuard<'b, 'a> {
+        KJournalSpanGuard { journal, id }
+    }
+
+    /// Returns the ID of the span guarded.
+    pub fn get_id(&self) -> u64 {
+        self.id
+    }
+
+    /// Exit the span now instead of when dropped.
+    ///
+    /// Returns the duration of the span.
+    pub fn exit(self) -> Option<Duration> {
+        let mut guard = std::mem::ManuallyDrop::new(self);
+        let id = guard.id;
+        guard.journal.exit_span_id(id)
+    }
+}
+
+impl<'a> Deref for KJournalSpanGuard<'_, 'a> {
+    type Target = KJournal<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.journal
+    }
+}
+
+impl<'a> DerefMut for KJournalSpanGuard<'_, 'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.journal
+    }
+}
+
+impl Drop for KJournalSpanGuard<'_, '_> {
+    fn drop(&mut self) {
+        self.journal.exit_span_id(self.id);
+    }
+}
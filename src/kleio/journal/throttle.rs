@@ -0,0 +1,130 @@
+use std::{collections::HashMap, time::{Duration, Instant}};
+
+#[allow(unused_imports)]
+use super::{KJournal, KJournalEntry};
+
+/// Count of rate limit keys tracked before expired keys are purged.
+const KJOURNAL_THROTTLE_PURGE_COUNT : usize = 1024;
+
+/// ##### Configuration of [KJournal] flood protection.
+///
+/// * Deduplication collapses consecutive identical entries (same target, severity and description) into one [KJournalEntry]
+///   with a repeat count and first/last date and time.
+/// * Rate limiting allows at most `max_entries` entries with the same target and description per `period`. Other entries are suppressed.
+///   Duplicates collapsed into the latest entry are counted in its repeat count instead and aren't rate limited.
+///
+/// # Example(s)
+/// ```
+/// use std::time::Duration;
+/// use olympus::kleio::journal::{KJournal, KJournalEntrySeverity, KJournalSeveritySet, KJournalThrottle, KJOURNAL_BUFFER_MIN};
+///
+/// let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+///
+/// // Collapse duplicates and allow at most 5 identical messages per second.
+/// j.set_throttle(KJournalThrottle::new(true, Some((5, Duration::from_secs(1)))));
+///
+/// // Consecutive duplicates are collapsed into 1 entry repeated 100 times.
+/// for _ in 0..100 {
+///     j.write(KJournalEntrySeverity::Warning, "Flood");
+/// }
+/// assert_eq!(j.read().unwrap().get_repeat_count(), 100);
+///
+/// // Alternating messages aren't collapsed, only 5 of each are written.
+/// for _ in 0..100 {
+///     j.write(KJournalEntrySeverity::Warning, "Ping");
+///     j.write(KJournalEntrySeverity::Warning, "Pong");
+/// }
+/// assert_eq!(j.unread(), 10);
+/// assert_eq!(j.get_suppressed_count(), 190);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KJournalThrottle {
+    /// Collapse consecutive duplicates.
+    deduplicate : bool,
+
+    /// Maximum entries per key and period.
+    rate_limit : Option<(u32, Duration)>,
+}
+
+impl KJournalThrottle {
+    /// Create a new [KJournalThrottle] with deduplication flag and optional rate limit as (max_entries, period).
+    pub fn new(deduplicate : bool, rate_limit : Option<(u32, Duration)>) -> KJournalThrottle {
+        KJournalThrottle { deduplicate, rate_limit }
+    }
+
+    /// Create a [KJournalThrottle] without deduplication nor rate limit. This is the [KJournal] default.
+    pub fn none() -> KJournalThrottle {
+        KJournalThrottle { deduplicate: false, rate_limit: None }
+    }
+
+    /// Returns True if consecutive duplicates are collapsed.
+    pub fn is_deduplicate(&self) -> bool {
+        self.deduplicate
+    }
+
+    /// Returns the rate limit as (max_entries, period) if any.
+    pub fn get_rate_limit(&self) -> Option<(u32, Duration)> {
+        self.rate_limit
+    }
+}
+
+/// Counter of entries written for a key within a period.
+struct KJournalRateCounter {
+    /// Start of current period.
+    start : Instant,
+
+    /// Entries written in current period.
+    count : u32,
+}
+
+/// ##### Rate limiter keeping a counter per message key.
+pub struct KJournalRateLimiter {
+    /// Counters per message key.
+    counters : HashMap<String, KJournalRateCounter>,
+}
+
+impl KJournalRateLimiter {
+    /// Create a new empty [KJournalRateLimiter].
+    pub fn new() -> KJournalRateLimiter {
+        KJournalRateLimiter { counters: HashMap::new() }
+    }
+
+    /// Register an entry for target and description according to max_entries per period.
+    ///
+    /// Returns True if entry is allowed, false if it must be suppressed.
+    pub fn allow(&mut self, target : &str, description : &str, max_entries : u32, period : Duration) -> bool {
+        let now = Instant::now();
+
+        // Purge expired counters so keys don't accumulate forever.
+        if self.counters.len() >= KJOURNAL_THROTTLE_PURGE_COUNT {
+            self.counters.retain(|_, c| now.duration_since(c.start) < period);
+        }
+
+        let key = format!("{}\u{0}{}", target, description);
+        let counter = self.counters.entry(key).or_insert(KJournalRateCounter { start: now, count: 0 });
+
+        // Start a new period if current one expired.
+        if now.duration_since(counter.start) >= period {
+            counter.start = now;
+            counter.count = 0;
+        }
+
+        if counter.count < max_entries {
+            counter.count += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Clear all counters.
+    pub fn clear(&mut self) {
+        self.counters.clear();
+    }
+}
+
+impl Default for KJournalRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,177 @@
+use std::{rc::Rc, cell::RefCell};
+
+use olympus::{kleio::display::event::{KEvent, KEventDispatcher, KEventKeyboard, KEventMouse, KEventController, KEventReceiver, KInputActionMap, 
+    KInputBinding, KInput, KKey, KKeyInput, KKeyModifiers, KScancode, KMouseButton}, error::{OlympusError, KInputActionMapError}};
+
+use crate::{assert_err, assert_ok};
+
+/********
+* TESTS *
+********/
+#[test]
+/// Compute actions values from bindings.
+/// 
+/// # Verification(s)
+/// V1 | Button bindings give their scale when held and 0.0 otherwise.
+/// V2 | Action value is the binding with the highest absolute value.
+/// V3 | Axis bindings apply dead zone and scale.
+/// V4 | Chord bindings need all inputs held.
+/// V5 | Pressed and released edges are kept until KInputActionMap::reset_frame().
+/// V6 | KInputActionMap::set_controller() only uses inputs of that controller.
+fn kinput_action_map_values() {
+    let mut map = KInputActionMap::new();
+    map.add_binding("MoveX", KInput::Scancode(KScancode::D));
+    map.add_binding("MoveX", KInputBinding::new(&[KInput::Scancode(KScancode::A)], 0.0, -1.0));
+    map.add_binding("MoveX", KInputBinding::new(&[KInput::ControllerAxis(0)], 0.2, 2.0));
+    map.add_binding("Undo", KInputBinding::new(&[KInput::Key(KKey::LeftControl), KInput::Key(KKey::Character('z'))], 0.0, 1.0));
+    map.add_binding("Fire", KInput::MouseButton(KMouseButton::Left));
+    map.add_binding("Fire", KInput::ControllerButton(5));
+
+    // V1 | Button bindings give their scale when held and 0.0 otherwise.
+    assert_eq!(map.get_action_value("MoveX"), 0.0, "MoveX should be 0.0!");
+    map.handle_event(&key_down(KScancode::A, KKey::Character('q')).into());
+    assert_eq!(map.get_action_value("MoveX"), -1.0, "MoveX should be -1.0!");
+    assert!(map.is_action_down("MoveX"), "MoveX should be down!");
+
+    // V2 | Action value is the binding with the highest absolute value.
+    map.handle_event(&KEvent::Controller(KEventController::Connected(0)).into());
+    map.handle_event(&KEvent::Controller(KEventController::Axis(0, 0, i16::MAX)).into());
+    assert_eq!(map.get_action_value("MoveX"), 2.0, "MoveX should be axis value!");
+    map.handle_event(&key_up(KScancode::A, KKey::Character('q')).into());
+
+    // V3 | Axis bindings apply dead zone and scale.
+    map.handle_event(&KEvent::Controller(KEventController::Axis(0, 0, (i16::MAX as f32 * 0.1) as i16)).into());
+    assert_eq!(map.get_action_value("MoveX"), 0.0, "Axis within dead zone should be 0.0!");
+    map.handle_event(&KEvent::Controller(KEventController::Axis(0, 0, -(i16::MAX as f32 * 0.6) as i16)).into());
+    assert!((map.get_action_value("MoveX") + 1.0).abs() < 0.001, "Axis outside dead zone should be rescaled!");
+    map.handle_event(&KEvent::Controller(KEventController::Axis(0, 0, 0)).into());
+
+    // V4 | Chord bindings need all inputs held.
+    map.handle_event(&key_down(KScancode::Z, KKey::Character('z')).into());
+    assert!(!map.is_action_down("Undo"), "Undo shouldn't be down without control!");
+    map.handle_event(&key_down(KScancode::LeftControl, KKey::LeftControl).into());
+    assert!(map.is_action_down("Undo"), "Undo should be down with control!");
+
+    // V5 | Pressed and released edges are kept until KInputActionMap::reset_frame().
+    assert!(map.is_action_pressed("Undo") && map.is_action_released("MoveX"), "Edges error!");
+    map.reset_frame();
+    assert!(!map.is_action_pressed("Undo") && !map.is_action_released("MoveX"), "Edges should be cleared!");
+    map.handle_event(&key_up(KScancode::LeftControl, KKey::LeftControl).into());
+    assert!(map.is_action_released("Undo") && !map.is_action_down("Undo"), "Undo should be released!");
+    assert!(!map.is_action_pressed("Unknown") && map.get_action_value("Unknown") == 0.0, "Unknown action should be 0.0!");
+
+    // V6 | KInputActionMap::set_controller() only uses inputs of that controller.
+    map.set_controller(Some(1));
+    map.handle_event(&KEvent::Controller(KEventController::ButtonDown(0, 5)).into());
+    assert!(!map.is_action_down("Fire"), "Fire shouldn't be down for controller 0!");
+    map.handle_event(&KEvent::Controller(KEventController::ButtonDown(1, 5)).into());
+    assert!(map.is_action_down("Fire"), "Fire should be down for controller 1!");
+}
+
+#[test]
+/// Rebind actions by capturing next input.
+/// 
+/// # Verification(s)
+/// V1 | KInputActionMap::capture_next_input() with unknown action returns Err(OlympusError::KInputActionMap(KInputActionMapError::ActionNotFound)).
+/// V2 | Key captured is added as scancode and event is handled, masking it for other receivers.
+/// V3 | Captured input doesn't press the action, nor release it.
+/// V4 | Capture with replace replaces bindings and small axis moves aren't captured.
+/// V5 | Axis captured keeps the direction moved as scale.
+fn kinput_action_map_capture() {
+    let map = Rc::new(RefCell::new(KInputActionMap::new()));
+    let mut dispatcher = KEventDispatcher::new(true);
+    let _map_handle = assert_ok!(dispatcher.add_event_receiver(map.clone()));
+    map.borrow_mut().add_binding("Jump", KInput::Scancode(KScancode::Space));
+
+    // V1 | KInputActionMap::capture_next_input() with unknown action returns Err(OlympusError::KInputActionMap(KInputActionMapError::ActionNotFound)).
+    assert_err!(map.borrow_mut().capture_next_input("Unknown", false), OlympusError::KInputActionMap(KInputActionMapError::ActionNotFound));
+
+    // V2 | Key captured is added as scancode and event is handled, masking it for other receivers.
+    assert_ok!(map.borrow_mut().capture_next_input("Jump", false));
+    assert!(map.borrow().is_capturing(), "Map should be capturing!");
+    assert!(dispatcher.dispatch(&key_down(KScancode::W, KKey::Character('z'))), "Captured event should be handled!");
+    assert!(!map.borrow().is_capturing(), "Capture should be over!");
+    assert_eq!(map.borrow().get_bindings("Jump").unwrap(), &[KInput::Scancode(KScancode::Space).into(), KInput::Scancode(KScancode::W).into()], "Captured bindings error!");
+
+    // V3 | Captured input doesn't press the action, nor release it.
+    assert!(!map.borrow().is_action_pressed("Jump"), "Jump shouldn't be pressed by capture!");
+    assert!(!dispatcher.dispatch(&key_up(KScancode::W, KKey::Character('z'))), "Event shouldn't be handled after capture!");
+    assert!(!map.borrow().is_action_released("Jump"), "Jump shouldn't be released by captured key!");
+    map.borrow_mut().reset_frame();
+    dispatcher.dispatch(&key_down(KScancode::W, KKey::Character('z')));
+    assert!(map.borrow().is_action_pressed("Jump"), "Jump should be pressed by captured key!");
+
+    // V4 | Capture with replace replaces bindings and small axis moves aren't captured.
+    assert_ok!(map.borrow_mut().capture_next_input("Jump", true));
+    assert!(!dispatcher.dispatch(&KEvent::Controller(KEventController::Axis(0, 1, 1000))), "Small axis move shouldn't be captured!");
+    assert!(dispatcher.dispatch(&KEvent::Mouse(KEventMouse::ButtonDown(KMouseButton::Right, (0, 0)))), "Mouse button should be captured!");
+    assert_eq!(map.borrow().get_bindings("Jump").unwrap(), &[KInput::MouseButton(KMouseButton::Right).into()], "Replaced bindings error!");
+
+    // V5 | Axis captured keeps the direction moved as scale.
+    assert_ok!(map.borrow_mut().capture_next_input("Jump", true));
+    assert!(dispatcher.dispatch(&KEvent::Controller(KEventController::Axis(0, 1, -20000))), "Axis should be captured!");
+    assert_eq!(map.borrow().get_bindings("Jump").unwrap(), &[KInputBinding::new(&[KInput::ControllerAxis(1)], 0.0, -1.0)], "Axis binding error!");
+}
+
+#[test]
+/// Serialize bindings configuration.
+/// 
+/// # Verification(s)
+/// V1 | KInputActionMap::parse() reads actions, chords, dead zones, scales and quoted characters.
+/// V2 | KInputActionMap to_string() gives a configuration that parses to the same bindings.
+/// V3 | KInputActionMap::parse() returns Err(OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(line))) on invalid lines.
+/// V4 | KInputActionMap::save() and KInputActionMap::load() write and read a configuration file.
+fn kinput_action_map_serialization() {
+    let config = "# Game bindings\n\
+        Jump = Scancode(Space) | ControllerButton(0)\n\
+        \n\
+        MoveX = Scancode(D) | Scancode(A) scale=-1 | ControllerAxis(0) dead_zone=0.2 scale=1.5\n\
+        Undo = Key(LeftControl)+Key(Character('z'))\n\
+        Pipe = Key(Character('|')) | Key(Character(' ')) | Key(Character('\\'')) | Key(Function(12)) | MouseButton(Other(8))\n\
+        Unbound =\n";
+
+    // V1 | KInputActionMap::parse() reads actions, chords, dead zones, scales and quoted characters.
+    let map = assert_ok!(KInputActionMap::parse(config));
+    assert_eq!(map.get_actions(), vec!["Jump", "MoveX", "Undo", "Pipe", "Unbound"], "Actions error!");
+    assert_eq!(map.get_bindings("MoveX").unwrap()[2], KInputBinding::new(&[KInput::ControllerAxis(0)], 0.2, 1.5), "Axis binding error!");
+    assert_eq!(map.get_bindings("Undo").unwrap()[0].get_inputs(), &[KInput::Key(KKey::LeftControl), KInput::Key(KKey::Character('z'))], "Chord error!");
+    let pipe : Vec<KInput> = map.get_bindings("Pipe").unwrap().iter().map(|b| b.get_inputs()[0]).collect();
+    assert_eq!(pipe, vec![KInput::Key(KKey::Character('|')), KInput::Key(KKey::Character(' ')), KInput::Key(KKey::Character('\'')), 
+        KInput::Key(KKey::Function(12)), KInput::MouseButton(KMouseButton::Other(8))], "Quoted characters error!");
+    assert!(map.get_bindings("Unbound").unwrap().is_empty(), "Unbound should have no binding!");
+
+    // V2 | KInputActionMap to_string() gives a configuration that parses to the same bindings.
+    let serialized = map.to_string();
+    assert!(serialized.contains("MoveX = Scancode(D) | Scancode(A) scale=-1 | ControllerAxis(0) dead_zone=0.2 scale=1.5\n"), "Serialized format error!");
+    let parsed = assert_ok!(KInputActionMap::parse(&serialized));
+    for action in map.get_actions() {
+        assert_eq!(parsed.get_bindings(action), map.get_bindings(action), "Serialized bindings error for {}!", action);
+    }
+
+    // V3 | KInputActionMap::parse() returns Err(OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(line))) on invalid lines.
+    assert_err!(KInputActionMap::parse("Jump = Scancode(Space)\nMissing equal"), OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(2)));
+    assert_err!(KInputActionMap::parse("Jump = Scancode(Nothing)"), OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(1)));
+    assert_err!(KInputActionMap::parse("Jump = Scancode(Space) speed=2"), OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(1)));
+    assert_err!(KInputActionMap::parse(" = Scancode(Space)"), OlympusError::KInputActionMap(KInputActionMapError::InvalidBinding(1)));
+
+    // V4 | KInputActionMap::save() and KInputActionMap::load() write and read a configuration file.
+    let path = std::env::temp_dir().join("kinput_action_map_serialization.cfg");
+    assert_ok!(map.save(&path));
+    let loaded = assert_ok!(KInputActionMap::load(&path));
+    assert_eq!(loaded.get_bindings("Pipe"), map.get_bindings("Pipe"), "Loaded bindings error!");
+    let _ = std::fs::remove_file(&path);
+    assert_err!(KInputActionMap::load(&path), OlympusError::KInputActionMap(KInputActionMapError::FileError));
+}
+
+/************
+* FUNCTIONS * 
+************/
+/// Create a key down event.
+fn key_down(scancode : KScancode, key : KKey) -> KEvent {
+    KEvent::Keyboard(KEventKeyboard::KeyDown(KKeyInput::new(scancode, key, KKeyModifiers::NONE, false)))
+}
+
+/// Create a key up event.
+fn key_up(scancode : KScancode, key : KKey) -> KEvent {
+    KEvent::Keyboard(KEventKeyboard::KeyUp(KKeyInput::new(scancode, key, KKeyModifiers::NONE, false)))
+}
@@ -0,0 +1,215 @@
+use std::{collections::VecDeque, time::Duration};
+
+use olympus::{error::{OlympusError, KControllerError}, kleio::display::{KControllerBattery, KControllerBatteryState, event::{KEvent, KEventController, KControllerButton, KControllerAxis, 
+    KCONTROLLER_RAW_BUTTON_FIRST, KCONTROLLER_RAW_AXIS_FIRST}, 
+    linux::controller::{KLinuxController, KLinuxControllerList, bind::{input_event, input_id, input_absinfo, EV_KEY, EV_ABS, EV_SYN, SYN_REPORT,
+    BTN_SOUTH, BTN_EAST, BTN_C, BTN_NORTH, BTN_WEST, BTN_TL, BTN_TR, BTN_TL2, BTN_TR2, BTN_SELECT, BTN_START, BTN_MODE, ABS_X, ABS_Y, ABS_Z, ABS_RX, 
+    ABS_HAT0X, ABS_HAT0Y}}}};
+
+use crate::{assert_err, assert_ok};
+
+/********
+* TESTS *
+********/
+#[test]
+/// Translate recorded evdev streams into KEventController.
+/// 
+/// # Verification(s)
+/// V1 | KLinuxController::new() returns None for devices without joystick or gamepad buttons.
+/// V2 | Gamepad buttons are translated to KControllerButton, auto-repeat is ignored.
+/// V3 | Axes are normalized to -32768..32767 and triggers to 0..32767. Unchanged values aren't sent.
+/// V4 | First hat is translated to directional pad buttons.
+/// V5 | Buttons and axes outside the standard layout get raw ids.
+/// V6 | Digital triggers are sent as trigger axes when there is no analog trigger.
+fn klinux_controller_translate() {
+    // V1 | KLinuxController::new() returns None for devices without joystick or gamepad buttons.
+    assert!(KLinuxController::new("Keyboard", input_id::default(), &[30, 31, 32], &[]).is_none(), "Keyboard shouldn't be a controller!");
+
+    let buttons = [BTN_SOUTH, BTN_EAST, BTN_C, BTN_NORTH, BTN_WEST, BTN_TL, BTN_TR, BTN_SELECT, BTN_START, BTN_MODE];
+    let axes = [(ABS_X, input_absinfo::new(-32768, 32767)), (ABS_Y, input_absinfo::new(-32768, 32767)), (ABS_Z, input_absinfo::new(0, 255)),
+        (ABS_RX, input_absinfo::new(0, 255)), (0x28, input_absinfo::new(0, 1023)), (ABS_HAT0X, input_absinfo::new(-1, 1)), (ABS_HAT0Y, input_absinfo::new(-1, 1))];
+    let mut pad = KLinuxController::new("Pad", input_id::default(), &buttons, &axes).unwrap();
+    assert_eq!(pad.get_name(), "Pad", "Controller name error!");
+
+    // V2 | Gamepad buttons are translated to KControllerButton, auto-repeat is ignored.
+    let events = translate(&mut pad, &[(EV_KEY, BTN_SOUTH, 1), (EV_KEY, BTN_SOUTH, 2), (EV_SYN, SYN_REPORT, 0), (EV_KEY, BTN_SOUTH, 0), 
+        (EV_KEY, BTN_WEST, 1), (EV_KEY, BTN_MODE, 1)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::ButtonDown(3, KControllerButton::A as u8)), format!("{:?}", KEventController::ButtonUp(3, KControllerButton::A as u8)),
+        format!("{:?}", KEventController::ButtonDown(3, KControllerButton::Y as u8)), format!("{:?}", KEventController::ButtonDown(3, KControllerButton::Guide as u8))], "Buttons error!");
+
+    // V3 | Axes are normalized to -32768..32767 and triggers to 0..32767. Unchanged values aren't sent.
+    let events = translate(&mut pad, &[(EV_ABS, ABS_X, -32768), (EV_ABS, ABS_X, -32768), (EV_ABS, ABS_Z, 255), (EV_ABS, ABS_Z, 0), (EV_ABS, ABS_RX, 0), (EV_ABS, ABS_RX, 255)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::Axis(3, KControllerAxis::LeftX as u8, -32768)), format!("{:?}", KEventController::Axis(3, KControllerAxis::TriggerLeft as u8, 32767)),
+        format!("{:?}", KEventController::Axis(3, KControllerAxis::TriggerLeft as u8, 0)), format!("{:?}", KEventController::Axis(3, KControllerAxis::RightX as u8, -32768)), format!("{:?}", KEventController::Axis(3, KControllerAxis::RightX as u8, 32767))], "Axes error!");
+
+    // V4 | First hat is translated to directional pad buttons.
+    let events = translate(&mut pad, &[(EV_ABS, ABS_HAT0X, -1), (EV_ABS, ABS_HAT0X, 1), (EV_ABS, ABS_HAT0Y, -1), (EV_ABS, ABS_HAT0X, 0), (EV_ABS, ABS_HAT0Y, 0)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::ButtonDown(3, KControllerButton::DPadLeft as u8)), format!("{:?}", KEventController::ButtonUp(3, KControllerButton::DPadLeft as u8)),
+        format!("{:?}", KEventController::ButtonDown(3, KControllerButton::DPadRight as u8)), format!("{:?}", KEventController::ButtonDown(3, KControllerButton::DPadUp as u8)),
+        format!("{:?}", KEventController::ButtonUp(3, KControllerButton::DPadRight as u8)), format!("{:?}", KEventController::ButtonUp(3, KControllerButton::DPadUp as u8))], "Hat error!");
+
+    // V5 | Buttons and axes outside the standard layout get raw ids.
+    let events = translate(&mut pad, &[(EV_KEY, BTN_C, 1), (EV_ABS, 0x28, 1023)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::ButtonDown(3, KCONTROLLER_RAW_BUTTON_FIRST + 2)), 
+        format!("{:?}", KEventController::Axis(3, KCONTROLLER_RAW_AXIS_FIRST + 4, 32767))], "Raw ids error!");
+
+    // V6 | Digital triggers are sent as trigger axes when there is no analog trigger.
+    let mut pad = KLinuxController::new("Digital", input_id::default(), &[BTN_SOUTH, BTN_TL2, BTN_TR2], &[(ABS_Z, input_absinfo::new(0, 255))]).unwrap();
+    let events = translate(&mut pad, &[(EV_KEY, BTN_TR2, 1), (EV_KEY, BTN_TR2, 0), (EV_KEY, BTN_TL2, 1)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::Axis(3, KControllerAxis::TriggerRight as u8, 32767)), 
+        format!("{:?}", KEventController::Axis(3, KControllerAxis::TriggerRight as u8, 0)), format!("{:?}", KEventController::ButtonDown(3, KCONTROLLER_RAW_BUTTON_FIRST + 1))], "Digital triggers error!");
+}
+
+#[test]
+/// Translate recorded evdev streams with a KControllerMapping.
+/// 
+/// # Verification(s)
+/// V1 | KLinuxControllerList applies the mapping of the controller GUID on connection and when mappings are added.
+/// V2 | Mapped buttons are swapped and unmapped buttons keep raw ids.
+/// V3 | Half axes write their half of the standard axis and inverted axes are reversed.
+/// V4 | Axes mapped to buttons are pressed past half of their range.
+/// V5 | Hats mapped to buttons and KLinuxController::set_mapping(None) restores the Linux gamepad specification.
+fn klinux_controller_mapping() {
+    let id = input_id { bustype: 0x03, vendor: 0x55aa, product: 0x66bb, version: 0x0100 };
+    let buttons = [BTN_SOUTH, BTN_EAST, BTN_C];
+    let axes = [(ABS_X, input_absinfo::new(-32768, 32767)), (ABS_Y, input_absinfo::new(0, 255)), (ABS_Z, input_absinfo::new(0, 255)),
+        (ABS_HAT0X, input_absinfo::new(-1, 1)), (ABS_HAT0Y, input_absinfo::new(-1, 1))];
+
+    // V1 | KLinuxControllerList applies the mapping of the controller GUID on connection and when mappings are added.
+    let mut list = KLinuxControllerList::new();
+    let controller = list.add_controller(KLinuxController::new("Custom Pad", id, &buttons, &axes).unwrap()).unwrap();
+    assert_eq!(list.get_controller(controller).unwrap().get_guid().to_string(), "03000000aa550000bb66000000010000", "GUID error!");
+    assert_eq!(assert_ok!(list.add_mappings("03000000aa550000bb66000000000000,Custom Pad,a:b1,b:b0,-leftx:-a0,+rightx:+a0,lefty:a1~,x:a2,dpleft:h0.2,dpright:h0.8,")), 1);
+    let mut pad = list.remove_controller(controller).unwrap();
+    assert_eq!(pad.get_mapping().get_name(), "Custom Pad", "Mapping should be applied!");
+
+    // V2 | Mapped buttons are swapped and unmapped buttons keep raw ids.
+    let events = translate(&mut pad, &[(EV_KEY, BTN_SOUTH, 1), (EV_KEY, BTN_EAST, 1), (EV_KEY, BTN_C, 1)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::ButtonDown(3, KControllerButton::B as u8)), format!("{:?}", KEventController::ButtonDown(3, KControllerButton::A as u8)),
+        format!("{:?}", KEventController::ButtonDown(3, KCONTROLLER_RAW_BUTTON_FIRST + 2))], "Mapped buttons error!");
+
+    // V3 | Half axes write their half of the standard axis and inverted axes are reversed.
+    let events = translate(&mut pad, &[(EV_ABS, ABS_X, -32768), (EV_ABS, ABS_X, 0), (EV_ABS, ABS_X, 32767), (EV_ABS, ABS_Y, 255)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::Axis(3, KControllerAxis::LeftX as u8, -32768)), format!("{:?}", KEventController::Axis(3, KControllerAxis::LeftX as u8, 0)),
+        format!("{:?}", KEventController::Axis(3, KControllerAxis::RightX as u8, 32767)), format!("{:?}", KEventController::Axis(3, KControllerAxis::LeftY as u8, -32768))], "Mapped axes error!");
+
+    // V4 | Axes mapped to buttons are pressed past half of their range.
+    let events = translate(&mut pad, &[(EV_ABS, ABS_Z, 100), (EV_ABS, ABS_Z, 200), (EV_ABS, ABS_Z, 0)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::ButtonDown(3, KControllerButton::X as u8)), format!("{:?}", KEventController::ButtonUp(3, KControllerButton::X as u8))], "Axis to button error!");
+
+    // V5 | Hats mapped to buttons and KLinuxController::set_mapping(None) restores the Linux gamepad specification.
+    let events = translate(&mut pad, &[(EV_ABS, ABS_HAT0X, 1), (EV_ABS, ABS_HAT0X, 0), (EV_ABS, ABS_HAT0Y, -1)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::ButtonDown(3, KControllerButton::DPadLeft as u8)), format!("{:?}", KEventController::ButtonUp(3, KControllerButton::DPadLeft as u8))], "Mapped hat error!");
+    pad.set_mapping(None);
+    let events = translate(&mut pad, &[(EV_KEY, BTN_SOUTH, 0), (EV_ABS, ABS_HAT0Y, 0), (EV_ABS, ABS_HAT0Y, -1)]);
+    assert_eq!(events, vec![format!("{:?}", KEventController::ButtonUp(3, KControllerButton::A as u8)), format!("{:?}", KEventController::ButtonDown(3, KControllerButton::DPadUp as u8))], "Default mapping error!");
+}
+
+#[test]
+/// Connect and disconnect controllers.
+/// 
+/// # Verification(s)
+/// V1 | KLinuxControllerList::new() queues a Connected event per device connected.
+/// V2 | KLinuxControllerList::add_controller() gives the lowest free id and queues Connected.
+/// V3 | KLinuxControllerList::remove_controller() queues Disconnected and frees the id.
+fn klinux_controller_list_hotplug() {
+    // V1 | KLinuxControllerList::new() queues a Connected event per device connected.
+    let mut list = KLinuxControllerList::new();
+    assert_eq!(list.get_event_count(), list.get_controllers().len(), "Connected events count error!");
+    while list.pop_event().is_some() {}
+
+    // V2 | KLinuxControllerList::add_controller() gives the lowest free id and queues Connected.
+    let first = list.get_controllers().len() as u8;
+    assert_eq!(list.add_controller(pad("First")), Some(first), "First id error!");
+    assert_eq!(list.add_controller(pad("Second")), Some(first + 1), "Second id error!");
+    assert_eq!(list.get_controller(first + 1).unwrap().get_name(), "Second", "Controller name error!");
+    assert!(matches!(list.pop_event(), Some(KEvent::Controller(KEventController::Connected(id))) if id == first), "Connected event error!");
+    assert_eq!(list.get_event_count(), 1, "Event count error!");
+    while list.pop_event().is_some() {}
+
+    // V3 | KLinuxControllerList::remove_controller() queues Disconnected and frees the id.
+    assert!(list.remove_controller(first).is_some(), "Remove error!");
+    assert!(list.remove_controller(first).is_none(), "Removed twice!");
+    assert!(matches!(list.pop_event(), Some(KEvent::Controller(KEventController::Disconnected(id))) if id == first), "Disconnected event error!");
+    assert!(list.pop_event().is_none(), "Only one Disconnected event expected!");
+    assert_eq!(list.add_controller(pad("Third")), Some(first), "Freed id should be reused!");
+}
+
+#[test]
+/// Controller rumble, player LED and battery.
+/// 
+/// # Verification(s)
+/// V1 | Rumble on a controller without force feedback returns Err(OlympusError::KController(KControllerError::NotSupported)).
+/// V2 | KLinuxController::set_player_led() and KLinuxController::get_battery() return NotSupported without sysfs LEDs and power supply.
+/// V3 | KLinuxController::set_player_led() lights only the LED of the player.
+/// V4 | KLinuxController::set_player_led() writes Xbox 360 patterns.
+/// V5 | KLinuxController::get_battery() reads capacity and status, or capacity level.
+fn klinux_controller_outputs() {
+    let sysfs = std::env::temp_dir().join("klinux_controller_outputs");
+    let _ = std::fs::remove_dir_all(&sysfs);
+    let mut controller = pad("Outputs");
+
+    // V1 | Rumble on a controller without force feedback returns Err(OlympusError::KController(KControllerError::NotSupported)).
+    assert_err!(controller.rumble(1.0, 0.5, Duration::from_millis(100)), OlympusError::KController(KControllerError::NotSupported));
+    assert_err!(controller.stop_rumble(), OlympusError::KController(KControllerError::NotSupported));
+
+    // V2 | KLinuxController::set_player_led() and KLinuxController::get_battery() return NotSupported without sysfs LEDs and power supply.
+    controller.set_sysfs_path(&sysfs);
+    assert_err!(controller.set_player_led(1), OlympusError::KController(KControllerError::NotSupported));
+    assert_err!(controller.get_battery(), OlympusError::KController(KControllerError::NotSupported));
+
+    // V3 | KLinuxController::set_player_led() lights only the LED of the player.
+    for led in ["input9:white:player-1", "input9:white:player-2", "input9:rgb:indicator"] {
+        assert_ok!(std::fs::create_dir_all(sysfs.join("leds").join(led)));
+    }
+    assert_ok!(controller.set_player_led(2));
+    assert_eq!(read_brightness(&sysfs, "input9:white:player-1"), Some(String::from("0")), "Player 1 LED should be off!");
+    assert_eq!(read_brightness(&sysfs, "input9:white:player-2"), Some(String::from("1")), "Player 2 LED should be on!");
+    assert_eq!(read_brightness(&sysfs, "input9:rgb:indicator"), None, "Other LEDs shouldn't be written!");
+
+    // V4 | KLinuxController::set_player_led() writes Xbox 360 patterns.
+    let _ = std::fs::remove_dir_all(sysfs.join("leds"));
+    assert_ok!(std::fs::create_dir_all(sysfs.join("leds").join("xpad0")));
+    assert_ok!(controller.set_player_led(3));
+    assert_eq!(read_brightness(&sysfs, "xpad0"), Some(String::from("8")), "Xbox player 3 pattern error!");
+    assert_ok!(controller.set_player_led(0));
+    assert_eq!(read_brightness(&sysfs, "xpad0"), Some(String::from("0")), "Xbox off pattern error!");
+
+    // V5 | KLinuxController::get_battery() reads capacity and status, or capacity level.
+    let supply = sysfs.join("power_supply").join("ps-controller-battery");
+    assert_ok!(std::fs::create_dir_all(&supply));
+    assert_ok!(std::fs::write(supply.join("capacity"), "42\n"));
+    assert_ok!(std::fs::write(supply.join("status"), "Charging\n"));
+    assert_eq!(assert_ok!(controller.get_battery()), KControllerBattery { level: Some(42), state: KControllerBatteryState::Charging }, "Battery error!");
+    assert_ok!(std::fs::remove_file(supply.join("capacity")));
+    assert_ok!(std::fs::write(supply.join("capacity_level"), "Low\n"));
+    assert_ok!(std::fs::write(supply.join("status"), "Discharging\n"));
+    assert_eq!(assert_ok!(controller.get_battery()), KControllerBattery { level: Some(20), state: KControllerBatteryState::Discharging }, "Battery level error!");
+
+    let _ = std::fs::remove_dir_all(&sysfs);
+}
+
+/************
+* FUNCTIONS * 
+************/
+/// Read the brightness written to a LED of sysfs directory.
+fn read_brightness(sysfs : &std::path::Path, led : &str) -> Option<String> {
+    std::fs::read_to_string(sysfs.join("leds").join(led).join("brightness")).ok()
+}
+
+/// Translate a recorded evdev stream of (type, code, value) as controller 3 and returns events Debug strings.
+fn translate(controller : &mut KLinuxController, stream : &[(u16, u16, i32)]) -> Vec<String> {
+    let mut events = VecDeque::new();
+    for (_type, code, value) in stream {
+        controller.handle_input_event(3, &input_event::new(*_type, *code, *value), &mut events);
+    }
+    events.iter().map(|event| match event {
+        KEvent::Controller(event) => format!("{:?}", event),
+        _ => panic!("Only controller events expected!"),
+    }).collect()
+}
+
+/// Create a simple gamepad.
+fn pad(name : &str) -> KLinuxController {
+    KLinuxController::new(name, input_id::default(), &[BTN_SOUTH, BTN_EAST], &[(ABS_X, input_absinfo::new(-32768, 32767))]).unwrap()
+}
@@ -1,6 +1,6 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, time::{Duration, Instant}};
 
-use olympus::{kleio::display::event::{ KEvent, KEventReceiver, KEventController, KEventKeyboard, KEventMouse, KEventWindow, KEventDispatcher}, error::{OlympusError, KEventDispatcherError }};
+use olympus::{kleio::{display::event::{ KEvent, KEventReceiver, KEventController, KEventKeyboard, KKeyInput, KScancode, KKey, KKeyModifiers, KEventMouse, KMouseButton, KEventWindow, KEventDispatcher, KEventEntry, KEventCategories, KEventReceiverMode, KEVENT_NO_WINDOW, KEVENT_DEFAULT_LAYER, KEventBridgeReceiver, KEVENT_UNHANDLED_CAPACITY, KEVENT_UNHANDLED_JOURNAL_TARGET}, journal::{KJournal, KJournalSeveritySet, KJournalEntrySeverity, KJOURNAL_BUFFER_MIN}}, error::{OlympusError, KEventDispatcherError }};
 
 use crate::{assert_err, assert_ok};
 
@@ -65,34 +65,40 @@ fn kevent_dispatcher_add_event_receiver() {
 
     // V1 | KEventDispatcher::add_event_receiver() correctly add receiver to KEventDispatcher.
     let rc1 = Rc::new(RefCell::new(KEventReceiverControl::new(true, true, true, true)));
-    assert_ok!(ked.add_event_receiver(rc1.clone()), 0);
+    let handle = assert_ok!(ked.add_event_receiver(rc1.clone()));
+    assert_eq!(ked.get_receiver_count(), 1, "1 receiver expected!");
 
     // V2 | Adding the same receiver via KEventDispatcher::add_event_receiver() should result in OlympusError::KEventDispatcherReceiverAlreadyExists.
     assert_err!(ked.add_event_receiver(rc1.clone()), OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverAlreadyExists));
+    drop(handle);
 }
 
 #[test]
 /// Remove an event receiver from KEventDispatcher
 /// 
 /// # Verification(s)
-/// V1 | KEventDispatcher::remove_event_receiver() should return OlympusError::KEventDispatcherReceiverNotFound since receiver was not added.
+/// V1 | KEventDispatcher::remove_event_receiver() should return OlympusError::KEventDispatcherReceiverNotFound for handle of another dispatcher.
 /// V2 | KEventDispatcher::add_event_receiver() correctly add receiver to KEventDispatcher.
-/// V3 | KEventDispatcher::remove_event_receiver() should return Ok(0).
+/// V3 | KEventDispatcher::remove_event_receiver() removes receiver of handle immediately.
 fn kevent_dispatcher_remove_event_receiver() {
 
     let mut ked = KEventDispatcher::new(true);
-
+    let mut other = KEventDispatcher::new(true);
 
     let rc1 = Rc::new(RefCell::new(KEventReceiverControl::new(true, true, true, true)));
 
-    // V1 | KEventDispatcher::remove_event_receiver() should return OlympusError::KEventDispatcherReceiverNotFound since receiver was not added.
-    assert_err!(ked.remove_event_receiver(rc1.clone()), OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverNotFound));
+    // V1 | KEventDispatcher::remove_event_receiver() should return OlympusError::KEventDispatcherReceiverNotFound for handle of another dispatcher.
+    let other_handle = assert_ok!(other.add_event_receiver(rc1.clone()));
+    assert_err!(ked.remove_event_receiver(other_handle), OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverNotFound));
 
     // V2 | KEventDispatcher::add_event_receiver() correctly add receiver to KEventDispatcher.
-    assert_ok!(ked.add_event_receiver(rc1.clone()), 0);
+    let handle = assert_ok!(ked.add_event_receiver(rc1.clone()));
+    assert_eq!(ked.get_receiver_count(), 1, "1 receiver expected!");
 
-    // V3 | KEventDispatcher::remove_event_receiver() should return Ok(0).
-    assert_ok!(ked.remove_event_receiver(rc1.clone()), 0);
+    // V3 | KEventDispatcher::remove_event_receiver() removes receiver of handle immediately.
+    assert_ok!(ked.remove_event_receiver(handle));
+    assert_eq!(ked.get_receiver_count(), 0, "No receiver expected!");
+    let _handle = assert_ok!(ked.add_event_receiver(rc1.clone()));
 }
 
 
@@ -105,7 +111,7 @@ fn kevent_dispatcher_remove_event_receiver() {
 /// V2 | Dispatch all control events via KEventDispatcher::dispatch
 /// V3 | Compare different receiver notification count with control.
 /// V4 | KEventDispatcher shouldn't have any unhandled events.
-/// V5 | KEventDispatcher::remove_event_receiver() for each receiver should return Ok(index).
+/// V5 | KEventDispatcher::remove_event_receiver() for each receiver handle should return Ok.
 /// V6 | Dispatch all control events via KEventDispatcher::dispatch without any receivers.
 /// V7 | KEventDispatcher should have EVENT_COUNT unhandled events.
 /// V8 | KEventDispatcher::clear_unhandled_event() should remove KEvent without error.
@@ -125,12 +131,12 @@ fn kevent_dispatcher_dispatch_events() {
     let rc6 = Rc::new(RefCell::new(KEventReceiverControl::new(false, true, true, true)));
     rc6.borrow_mut().set_enabled(false);    // Disable rc6.
 
-    assert_ok!(ked.add_event_receiver(rc1.clone()), 0);
-    assert_ok!(ked.add_event_receiver(rc2.clone()), 1);
-    assert_ok!(ked.add_event_receiver(rc3.clone()), 2);
-    assert_ok!(ked.add_event_receiver(rc4.clone()), 3);
-    assert_ok!(ked.add_event_receiver(rc5.clone()), 4);
-    assert_ok!(ked.add_event_receiver(rc6.clone()), 5);
+    let h1 = assert_ok!(ked.add_event_receiver(rc1.clone()));
+    let h2 = assert_ok!(ked.add_event_receiver(rc2.clone()));
+    let h3 = assert_ok!(ked.add_event_receiver(rc3.clone()));
+    let h4 = assert_ok!(ked.add_event_receiver(rc4.clone()));
+    let h5 = assert_ok!(ked.add_event_receiver(rc5.clone()));
+    let h6 = assert_ok!(ked.add_event_receiver(rc6.clone()));
 
     // V2 | Dispatch all control events via KEventDispatcher::dispatch
     loop {
@@ -156,13 +162,14 @@ fn kevent_dispatcher_dispatch_events() {
     // V4 | KEventDispatcher shouldn't have any unhandled events.
     assert!(ked.get_unhandled_events().len() == 0, "KEventDispatcher::dispatch() error. Should be empty!");
 
-    // V5 | KEventDispatcher::remove_event_receiver() for each receiver should return Ok(index).
-    assert_ok!(ked.remove_event_receiver(rc6), 5);
-    assert_ok!(ked.remove_event_receiver(rc5), 4);
-    assert_ok!(ked.remove_event_receiver(rc4), 3);
-    assert_ok!(ked.remove_event_receiver(rc3), 2);
-    assert_ok!(ked.remove_event_receiver(rc2), 1);
-    assert_ok!(ked.remove_event_receiver(rc1), 0);
+    // V5 | KEventDispatcher::remove_event_receiver() for each receiver handle should return Ok.
+    assert_ok!(ked.remove_event_receiver(h6));
+    assert_ok!(ked.remove_event_receiver(h5));
+    assert_ok!(ked.remove_event_receiver(h4));
+    assert_ok!(ked.remove_event_receiver(h3));
+    assert_ok!(ked.remove_event_receiver(h2));
+    assert_ok!(ked.remove_event_receiver(h1));
+    assert_eq!(ked.get_receiver_count(), 0, "No receiver expected!");
 
     // V6 | Dispatch all control events via KEventDispatcher::dispatch without any receivers.
     let mut events = KEventDispatcherControl::new();
@@ -189,6 +196,284 @@ fn kevent_dispatcher_dispatch_events() {
 
 }
 
+#[test]
+/// Dispatch events with timestamp, sequence number and window id.
+/// 
+/// # Verification(s)
+/// V1 | KEventDispatcher::dispatch() gives a KEventEntry timestamped now without window.
+/// V2 | KEventDispatcher::dispatch_from() gives a KEventEntry with timestamp and window id.
+/// V3 | Sequence number increments at each dispatch, even if event is handled.
+/// V4 | KEventEntry::get_elapsed() gives time elapsed between entries and zero if other entry is more recent.
+fn kevent_dispatcher_dispatch_entry() {
+    let mut ked = KEventDispatcher::new(false);
+    let recorder = Rc::new(RefCell::new(KEventReceiverRecorder::new()));
+    let _handle = assert_ok!(ked.add_event_receiver(recorder.clone()));
+    assert_eq!(ked.get_sequence(), 0, "Sequence should begin at 0!");
+
+    // V1 | KEventDispatcher::dispatch() gives a KEventEntry timestamped now without window.
+    let before = Instant::now();
+    ked.dispatch(&KEvent::Mouse(KEventMouse::Moved((10,10))));
+    {
+        let entries = &recorder.borrow().entries;
+        assert!(entries[0].get_timestamp() >= before && entries[0].get_timestamp() <= Instant::now(), "Timestamp should be now!");
+        assert_eq!(entries[0].get_window_id(), KEVENT_NO_WINDOW, "Entry shouldn't have a window!");
+        assert!(matches!(entries[0].get_event(), KEvent::Mouse(KEventMouse::Moved((10,10)))), "Wrong event {:?}!", entries[0].get_event());
+    }
+
+    // V2 | KEventDispatcher::dispatch_from() gives a KEventEntry with timestamp and window id.
+    let timestamp = before + Duration::from_millis(200);
+    ked.dispatch_from(&KEvent::Window(KEventWindow::Focus()), timestamp, 7);
+    recorder.borrow_mut().handle = true;
+    assert!(ked.dispatch_from(&KEvent::Window(KEventWindow::Blur()), timestamp, 8), "Event should be handled!");
+    {
+        let entries = &recorder.borrow().entries;
+        assert_eq!(entries[1].get_timestamp(), timestamp, "Wrong timestamp!");
+        assert_eq!(entries[1].get_window_id(), 7, "Wrong window id!");
+        assert_eq!(entries[2].get_window_id(), 8, "Wrong window id!");
+
+        // V3 | Sequence number increments at each dispatch, even if event is handled.
+        for (index, entry) in entries.iter().enumerate() {
+            assert_eq!(entry.get_sequence(), index as u64, "Wrong sequence number!");
+        }
+        assert_eq!(ked.get_sequence(), 3, "Next sequence should be 3!");
+
+        // V4 | KEventEntry::get_elapsed() gives time elapsed between entries and zero if other entry is more recent.
+        assert_eq!(entries[1].get_elapsed(&KEventEntry::new(KEvent::None, before, 0, 0)), Duration::from_millis(200), "Wrong elapsed time!");
+        assert_eq!(entries[0].get_elapsed(&entries[1]), Duration::ZERO, "Elapsed time should be zero!");
+    }
+}
+
+#[test]
+/// Dispatch events through layers with priorities, categories and observers.
+/// 
+/// # Verification(s)
+/// V1 | KEventDispatcher::add_layer() adds layers sorted by priority and fails for existing name.
+/// V2 | KEventDispatcher::add_event_receiver_to() fails for missing layer.
+/// V3 | Higher priority layer handles event before lower priority layers.
+/// V4 | Receiver only receives events of categories subscribed.
+/// V5 | Observer receives handled events and can't handle them.
+/// V6 | KEventDispatcher::set_layer_enabled() and KEventDispatcher::set_layer_priority() change dispatch order.
+fn kevent_dispatcher_dispatch_layers() {
+    let mut ked = KEventDispatcher::new(false);
+
+    // V1 | KEventDispatcher::add_layer() adds layers sorted by priority and fails for existing name.
+    assert_ok!(ked.add_layer("game", -10));
+    assert_ok!(ked.add_layer("UI", 10));
+    assert_err!(ked.add_layer("UI", 0), OlympusError::KEventDispatcher(KEventDispatcherError::LayerAlreadyExists));
+    assert_eq!(ked.get_layers(), vec!["UI", KEVENT_DEFAULT_LAYER, "game"], "Wrong layer order!");
+    assert_eq!(ked.get_layer_priority("game"), Some(-10), "Wrong priority!");
+    assert_eq!(ked.get_layer_priority("missing"), None, "Layer shouldn't exist!");
+
+    // V2 | KEventDispatcher::add_event_receiver_to() fails for missing layer.
+    let game = Rc::new(RefCell::new(KEventReceiverRecorder::new()));
+    assert_err!(ked.add_event_receiver_to(game.clone(), "missing", KEventCategories::ALL, KEventReceiverMode::Consumer), 
+        OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound));
+
+    // Game receiver is added first to verify that priority prevails over order of addition.
+    let _game_handle = assert_ok!(ked.add_event_receiver_to(game.clone(), "game", KEventCategories::ALL, KEventReceiverMode::Consumer));
+    let ui = Rc::new(RefCell::new(KEventReceiverRecorder::new()));
+    ui.borrow_mut().handle = true;
+    let _ui_handle = assert_ok!(ked.add_event_receiver_to(ui.clone(), "UI", KEventCategories::MOUSE | KEventCategories::KEYBOARD, KEventReceiverMode::Consumer));
+    let observer = Rc::new(RefCell::new(KEventReceiverRecorder::new()));
+    observer.borrow_mut().handle = true;
+    let _observer_handle = assert_ok!(ked.add_event_receiver_to(observer.clone(), "game", KEventCategories::MOUSE, KEventReceiverMode::Observer));
+    assert_err!(ked.add_event_receiver_to(observer.clone(), "UI", KEventCategories::ALL, KEventReceiverMode::Observer), 
+        OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverAlreadyExists));
+
+    // V3 | Higher priority layer handles event before lower priority layers.
+    assert!(ked.dispatch(&KEvent::Mouse(KEventMouse::Moved((10,10)))), "Mouse event should be handled!");
+    assert_eq!(ui.borrow().entries.len(), 1, "UI should receive mouse event!");
+    assert_eq!(game.borrow().entries.len(), 0, "Game shouldn't receive handled mouse event!");
+
+    // V4 | Receiver only receives events of categories subscribed.
+    assert!(!ked.dispatch(&KEvent::Window(KEventWindow::Focus())), "Window event shouldn't be handled!");
+    assert_eq!(ui.borrow().entries.len(), 1, "UI shouldn't receive window event!");
+    assert_eq!(game.borrow().entries.len(), 1, "Game should receive window event!");
+
+    // V5 | Observer receives handled events and can't handle them.
+    assert_eq!(observer.borrow().entries.len(), 1, "Observer should receive handled mouse event only!");
+    ui.borrow_mut().handle = false;
+    assert!(!ked.dispatch(&KEvent::Mouse(KEventMouse::Wheel(0, 1))), "Observer shouldn't handle event!");
+    assert_eq!(observer.borrow().entries.len(), 2, "Observer should receive mouse event!");
+    assert_eq!(game.borrow().entries.len(), 2, "Game should receive unhandled mouse event!");
+    ui.borrow_mut().handle = true;
+
+    // V6 | KEventDispatcher::set_layer_enabled() and KEventDispatcher::set_layer_priority() change dispatch order.
+    assert_ok!(ked.set_layer_enabled("UI", false));
+    assert!(!ked.is_layer_enabled("UI"), "UI layer should be disabled!");
+    assert!(!ked.dispatch(&KEvent::Mouse(KEventMouse::Moved((20,20)))), "Disabled UI shouldn't handle event!");
+    assert_eq!(ui.borrow().entries.len(), 2, "Disabled UI shouldn't receive event!");
+    assert_ok!(ked.set_layer_enabled("UI", true));
+
+    game.borrow_mut().handle = true;
+    assert_ok!(ked.set_layer_priority("game", 20));
+    assert_eq!(ked.get_layers(), vec!["game", "UI", KEVENT_DEFAULT_LAYER], "Wrong layer order!");
+    assert!(ked.dispatch(&KEvent::Mouse(KEventMouse::Moved((30,30)))), "Game should handle event!");
+    assert_eq!(ui.borrow().entries.len(), 2, "UI shouldn't receive event handled by game!");
+    assert_err!(ked.set_layer_priority("missing", 0), OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound));
+    assert_err!(ked.set_layer_enabled("missing", true), OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound));
+}
+
+#[test]
+/// Add closure and channel receivers removed by their handle.
+/// 
+/// # Verification(s)
+/// V1 | KEventDispatcher::add_closure_receiver() calls closure for events of categories given.
+/// V2 | KEventDispatcher::add_channel_receiver() sends copies of events, handled or not.
+/// V3 | Dropping a KEventReceiverHandle removes the receiver before next dispatch.
+/// V4 | KEventDispatcher::add_event_receiver_to() refuses a receiver already added.
+/// V5 | Handle dropped after its dispatcher doesn't panic.
+fn kevent_dispatcher_receiver_handle() {
+    let mut ked = KEventDispatcher::new(false);
+
+    // V1 | KEventDispatcher::add_closure_receiver() calls closure for events of categories given.
+    let count = Rc::new(RefCell::new(0));
+    let closure_count = count.clone();
+    let closure = assert_ok!(ked.add_closure_receiver(KEVENT_DEFAULT_LAYER, KEventCategories::KEYBOARD, move |_| { *closure_count.borrow_mut() += 1; true }));
+    assert_err!(ked.add_closure_receiver("missing", KEventCategories::ALL, |_| true), OlympusError::KEventDispatcher(KEventDispatcherError::LayerNotFound));
+
+    let key = KKeyInput::new(KScancode::Q, KKey::Character('a'), KKeyModifiers::NONE, false);
+    assert!(ked.dispatch(&KEvent::Keyboard(KEventKeyboard::KeyDown(key))), "Closure should handle keyboard event!");
+    assert!(!ked.dispatch(&KEvent::Mouse(KEventMouse::Moved((10,10)))), "Closure shouldn't receive mouse event!");
+    assert_eq!(*count.borrow(), 1, "Closure should be called once!");
+
+    // V2 | KEventDispatcher::add_channel_receiver() sends copies of events, handled or not.
+    let (channel, events) = assert_ok!(ked.add_channel_receiver(KEVENT_DEFAULT_LAYER, KEventCategories::ALL));
+    assert_eq!(ked.get_receiver_count(), 2, "2 receivers expected!");
+    assert!(ked.dispatch(&KEvent::Keyboard(KEventKeyboard::KeyUp(key))), "Closure should handle keyboard event!");
+    ked.dispatch(&KEvent::Window(KEventWindow::Focus()));
+    let received : Vec<KEventEntry> = events.try_iter().collect();
+    assert_eq!(received.len(), 2, "Channel should receive 2 events!");
+    assert!(matches!(received[0].get_event(), KEvent::Keyboard(KEventKeyboard::KeyUp(_))), "Wrong event {:?}!", received[0].get_event());
+    assert_eq!(received[1].get_sequence(), 3, "Wrong sequence!");
+
+    // V3 | Dropping a KEventReceiverHandle removes the receiver before next dispatch.
+    drop(closure);
+    assert_eq!(ked.get_receiver_count(), 1, "1 receiver expected!");
+    assert!(!ked.dispatch(&KEvent::Keyboard(KEventKeyboard::KeyDown(key))), "Closure shouldn't handle event!");
+    assert_eq!(*count.borrow(), 2, "Closure shouldn't be called anymore!");
+    assert_eq!(events.try_iter().count(), 1, "Channel should still receive event!");
+    drop(channel);
+    ked.dispatch(&KEvent::Window(KEventWindow::Blur()));
+    assert!(events.try_recv().is_err(), "Channel shouldn't receive event!");
+    assert_eq!(ked.get_receiver_count(), 0, "No receiver expected!");
+
+    // V4 | KEventDispatcher::add_event_receiver_to() refuses a receiver already added.
+    let recorder = Rc::new(RefCell::new(KEventReceiverRecorder::new()));
+    let handle = assert_ok!(ked.add_event_receiver_to(recorder.clone(), KEVENT_DEFAULT_LAYER, KEventCategories::ALL, KEventReceiverMode::Consumer));
+    assert_err!(ked.add_event_receiver(recorder.clone()), OlympusError::KEventDispatcher(KEventDispatcherError::ReceiverAlreadyExists));
+
+    // V5 | Handle dropped after its dispatcher doesn't panic.
+    drop(ked);
+    drop(handle);
+}
+
+#[test]
+/// Forward events from a dispatcher to another thread with KEventBridgeReceiver.
+/// 
+/// # Verification(s)
+/// V1 | KEventBridgeSender forwards events without handling them by default.
+/// V2 | KEventBridgeSender::set_handle_events() masks events for older receivers.
+/// V3 | KEventBridgeReceiver::dispatch_events() dispatches events in order on another thread with stack ordered consumption.
+/// V4 | Forwarded events keep timestamp and window id.
+fn kevent_dispatcher_bridge() {
+    let mut ked = KEventDispatcher::new(true);
+    let mut bridge = KEventBridgeReceiver::new();
+
+    // V1 | KEventBridgeSender forwards events without handling them by default.
+    let sender = Rc::new(RefCell::new(bridge.create_sender()));
+    let _sender_handle = assert_ok!(ked.add_event_receiver(sender.clone()));
+    let timestamp = Instant::now();
+    assert!(!ked.dispatch_from(&KEvent::Mouse(KEventMouse::Moved((10,10))), timestamp, 3), "Bridge shouldn't handle event!");
+    assert_eq!(ked.get_unhandled_events().len(), 1, "Event should be unhandled!");
+
+    // V2 | KEventBridgeSender::set_handle_events() masks events for older receivers.
+    sender.borrow_mut().set_handle_events(true);
+    assert!(sender.borrow().is_handling_events(), "Bridge should handle events!");
+    let key = KKeyInput::new(KScancode::Q, KKey::Character('a'), KKeyModifiers::NONE, false);
+    assert!(ked.dispatch_from(&KEvent::Keyboard(KEventKeyboard::KeyDown(key)), timestamp + Duration::from_millis(5), 3), "Bridge should handle event!");
+    assert!(ked.dispatch(&KEvent::Window(KEventWindow::Focus())), "Bridge should handle event!");
+
+    // V3 | KEventBridgeReceiver::dispatch_events() dispatches events in order on another thread with stack ordered consumption.
+    let simulation = std::thread::spawn(move || {
+        let mut dispatcher = KEventDispatcher::new(false);
+        let older = Rc::new(RefCell::new(KEventReceiverRecorder::new()));
+        let newer = Rc::new(RefCell::new(KEventReceiverControl::new(true, false, false, false)));
+        let _older_handle = assert_ok!(dispatcher.add_event_receiver(older.clone()));
+        let _newer_handle = assert_ok!(dispatcher.add_event_receiver(newer.clone()));
+
+        assert_eq!(bridge.dispatch_events(&mut dispatcher), 3, "3 events should be dispatched!");
+        assert!(bridge.poll_event().is_none(), "Queue should be empty!");
+        assert_eq!(newer.borrow().get_notification_count(), 3, "Newer receiver should receive all events!");
+        let entries = older.borrow().entries.clone();
+        entries
+    });
+    let entries = simulation.join().expect("Simulation thread panicked!");
+    assert_eq!(entries.len(), 2, "Window event should be handled by newer receiver!");
+    assert!(matches!(entries[0].get_event(), KEvent::Mouse(KEventMouse::Moved((10,10)))), "Wrong event {:?}!", entries[0].get_event());
+    assert!(matches!(entries[1].get_event(), KEvent::Keyboard(KEventKeyboard::KeyDown(_))), "Wrong event {:?}!", entries[1].get_event());
+
+    // V4 | Forwarded events keep timestamp and window id.
+    assert_eq!(entries[0].get_timestamp(), timestamp, "Wrong timestamp!");
+    assert_eq!(entries[1].get_elapsed(&entries[0]), Duration::from_millis(5), "Wrong spacing!");
+    assert!(entries.iter().all(|e| e.get_window_id() == 3), "Wrong window id!");
+}
+
+#[test]
+/// Keep unhandled events in a bounded ring buffer with counts per category.
+/// 
+/// # Verification(s)
+/// V1 | KEventDispatcher keeps at most KEVENT_UNHANDLED_CAPACITY unhandled events, dropping oldest.
+/// V2 | KEventDispatcher::set_unhandled_capacity() shrinks ring buffer.
+/// V3 | KEventDispatcher::get_unhandled_count() counts unhandled events per category, even if not logged.
+/// V4 | KEventDispatcher::forward_unhandled_events() writes unhandled events not forwarded yet to KJournal at debug severity.
+/// V5 | KEventDispatcher::reset_unhandled_count() resets counts.
+fn kevent_dispatcher_unhandled_events() {
+    let mut ked = KEventDispatcher::new(true);
+
+    // V1 | KEventDispatcher keeps at most KEVENT_UNHANDLED_CAPACITY unhandled events, dropping oldest.
+    assert_eq!(ked.get_unhandled_capacity(), KEVENT_UNHANDLED_CAPACITY, "Wrong default capacity!");
+    for i in 0..KEVENT_UNHANDLED_CAPACITY + 10 {
+        ked.dispatch(&KEvent::Mouse(KEventMouse::Moved((i as i32, 0))));
+    }
+    assert_eq!(ked.get_unhandled_events().len(), KEVENT_UNHANDLED_CAPACITY, "Ring buffer should be full!");
+    assert!(matches!(ked.get_unhandled_events()[0], KEvent::Mouse(KEventMouse::Moved((10, 0)))), "Oldest events should be dropped!");
+
+    // V2 | KEventDispatcher::set_unhandled_capacity() shrinks ring buffer.
+    ked.set_unhandled_capacity(2);
+    assert_eq!(ked.get_unhandled_events().len(), 2, "Ring buffer should be shrunk!");
+    assert!(matches!(ked.get_unhandled_events()[1], KEvent::Mouse(KEventMouse::Moved((265, 0)))), "Newest events should be kept!");
+
+    // V3 | KEventDispatcher::get_unhandled_count() counts unhandled events per category, even if not logged.
+    ked.dispatch(&KEvent::Window(KEventWindow::Focus()));
+    ked.dispatch(&KEvent::Controller(KEventController::Connected(0)));
+    assert_eq!(ked.get_unhandled_count(KEventCategories::MOUSE), KEVENT_UNHANDLED_CAPACITY as u64 + 10, "Wrong mouse count!");
+    assert_eq!(ked.get_unhandled_count(KEventCategories::WINDOW | KEventCategories::CONTROLLER), 2, "Wrong window and controller count!");
+    assert_eq!(ked.get_unhandled_count(KEventCategories::KEYBOARD), 0, "Wrong keyboard count!");
+
+    let mut unlogged = KEventDispatcher::new(false);
+    unlogged.dispatch(&KEvent::Window(KEventWindow::Blur()));
+    assert_eq!(unlogged.get_unhandled_events().len(), 0, "Unhandled event shouldn't be logged!");
+    assert_eq!(unlogged.get_unhandled_count(KEventCategories::ALL), 1, "Unhandled event should be counted!");
+
+    // V4 | KEventDispatcher::forward_unhandled_events() writes unhandled events not forwarded yet to KJournal at debug severity.
+    let mut journal = assert_ok!(KJournal::new("events", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN));
+    assert_eq!(ked.forward_unhandled_events(&mut journal), 2, "2 events should be forwarded!");
+    assert_eq!(ked.forward_unhandled_events(&mut journal), 0, "Events shouldn't be forwarded twice!");
+    ked.dispatch(&KEvent::Window(KEventWindow::Close()));
+    assert_eq!(ked.forward_unhandled_events(&mut journal), 1, "1 event should be forwarded!");
+    assert_eq!(journal.unread(), 3, "3 journal entries expected!");
+    let entry = journal.read().expect("Journal entry expected!");
+    assert_eq!(entry.get_severity(), KJournalEntrySeverity::Debug, "Wrong severity!");
+    assert_eq!(entry.get_target(), KEVENT_UNHANDLED_JOURNAL_TARGET, "Wrong target!");
+    assert!(entry.get_description().contains("Window(Close)"), "Wrong description {}!", entry.get_description());
+
+    // V5 | KEventDispatcher::reset_unhandled_count() resets counts.
+    ked.reset_unhandled_count();
+    assert_eq!(ked.get_unhandled_count(KEventCategories::ALL), 0, "Counts should be reset!");
+    assert_eq!(ked.get_unhandled_events().len(), 2, "Ring buffer should be kept!");
+}
+
 
 
 
@@ -215,13 +500,14 @@ impl KEventDispatcherControl {
         events.push(KEvent::Controller(KEventController::Disconnected(0)));
 
         // Keyboard events
-        events.push(KEvent::Keyboard(KEventKeyboard::KeyDown(123)));
-        events.push(KEvent::Keyboard(KEventKeyboard::KeyUp(123)));
+        let key = KKeyInput::new(KScancode::Q, KKey::Character('a'), KKeyModifiers::NONE, false);
+        events.push(KEvent::Keyboard(KEventKeyboard::KeyDown(key)));
+        events.push(KEvent::Keyboard(KEventKeyboard::KeyUp(key)));
 
         // Mouse events
         events.push(KEvent::Mouse(KEventMouse::Moved((10,10))));
-        events.push(KEvent::Mouse(KEventMouse::ButtonDown(1,(10,10))));
-        events.push(KEvent::Mouse(KEventMouse::ButtonUp(1,(10,10))));
+        events.push(KEvent::Mouse(KEventMouse::ButtonDown(KMouseButton::Left,(10,10))));
+        events.push(KEvent::Mouse(KEventMouse::ButtonUp(KMouseButton::Left,(10,10))));
         events.push(KEvent::Mouse(KEventMouse::Wheel(-255,255)));
 
         // Window events
@@ -250,6 +536,32 @@ impl KEventDispatcherControl {
         }
     }
 }
+/// ##### Receiver that keeps entries received.
+struct KEventReceiverRecorder {
+    /// Entries received.
+    entries : Vec<KEventEntry>,
+
+    /// Value returned when handling event.
+    handle : bool,
+}
+
+impl KEventReceiverRecorder {
+    pub fn new() -> KEventReceiverRecorder {
+        KEventReceiverRecorder { entries: Vec::new(), handle: false }
+    }
+}
+
+impl KEventReceiver for KEventReceiverRecorder {
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        self.entries.push(*entry);
+        self.handle
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
 /// ##### Control receiver that count how many time it was notified.
 struct KEventReceiverControl {
 
@@ -292,18 +604,20 @@ impl KEventReceiverControl {
 }
 
 impl KEventReceiver for KEventReceiverControl {
-    fn handle_event(&mut self, event : &KEvent) -> bool {
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
         
         // Increment notifications
         self.notification_count += 1;
         
-        match event {
+        match entry.get_event() {
             KEvent::Unknown => panic!("Error : Unknown event received!"),
 
             KEvent::Window(_) => self.handle_window,
             KEvent::Keyboard(_) => self.handle_keyboard,
             KEvent::Mouse(_) => self.handle_mouse,
             KEvent::Controller(_) => self.handle_controller,
+            KEvent::Touch(_) => false,
+            KEvent::User(_) => false,
             KEvent::None => panic!("Error : Unknown event received!"),            
         }
     }
@@ -0,0 +1,273 @@
+use std::{rc::Rc, cell::RefCell};
+
+use olympus::kleio::display::{KWindow, KDisplayContext, KWindowFullscreenMode, linux::{server::KLinuxDisplayServerProvider, headless::KLINUX_HEADLESS_SCREEN_RESOLUTION},
+    event::{KEvent, KEventDispatcher, KEventEntry, KEventReceiver, KEventWindow, KEventKeyboard, KEventMouse, KEventController, KEventUser, KEventCategories, KEventReceiverMode, KEventReceiverHandle, KInputState,
+    KKey, KKeyInput, KKeyModifiers, KScancode, KMouseButton}};
+
+use crate::assert_ok;
+
+/********
+* TESTS *
+********/
+#[test]
+/// Create a headless KWindow without display.
+///
+/// # Verification(s)
+/// V1 | KWindow::new() with KLinuxDisplayServerProvider::Headless creates window centered in virtual screen.
+/// V2 | Virtual screen is primary with KLINUX_HEADLESS_SCREEN_RESOLUTION.
+/// V3 | New window sends Shown and Focus events with its id.
+fn headless_kwindow_new() {
+    // V1 | KWindow::new() with KLinuxDisplayServerProvider::Headless creates window centered in virtual screen.
+    let (mut window, mut dispatcher, _handle, receiver) = create_headless(640, 480);
+    assert_eq!(window.get_display_server_provider(), KLinuxDisplayServerProvider::Headless, "Wrong provider!");
+    assert_eq!(window.get_size(), (640, 480), "Wrong size!");
+    assert_eq!(window.get_position(), (640, 300), "Window should be centered!");
+
+    // V2 | Virtual screen is primary with KLINUX_HEADLESS_SCREEN_RESOLUTION.
+    let screen = window.get_screen_list().get_primary_screen().expect("Primary screen expected!");
+    assert_eq!(screen.get_current_resolution(), KLINUX_HEADLESS_SCREEN_RESOLUTION, "Wrong screen resolution!");
+    assert_eq!(window.get_screen_list().get_screen_list().len(), 1, "Only 1 screen expected!");
+
+    // V3 | New window sends Shown and Focus events with its id.
+    assert_eq!(window.get_event_count(), 2, "2 events expected!");
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(receiver.borrow().get_events(), vec!["Window(Shown)", "Window(Focus)"], "Wrong events!");
+    assert!(receiver.borrow().entries.iter().all(|e| e.get_window_id() == window.get_id()), "Wrong window id!");
+    assert_eq!(window.get_event_count(), 0, "No event expected!");
+}
+
+#[test]
+/// Synthesize window events from KWindow operations.
+///
+/// # Verification(s)
+/// V1 | KWindow::set_size() and KWindow::set_position() send Resized and Moved.
+/// V2 | KWindow::set_fullscreen() covers the screen and sends Fullscreen and MovedResized.
+/// V3 | KWindow::restore() restores position and size and sends Restored and MovedResized.
+/// V4 | Injected Minimized, Maximized and Restored update window state.
+fn headless_kwindow_window_events() {
+    let (mut window, mut dispatcher, _handle, receiver) = create_headless(640, 480);
+    window.dispatch_events(&mut dispatcher, true);
+    receiver.borrow_mut().entries.clear();
+
+    // V1 | KWindow::set_size() and KWindow::set_position() send Resized and Moved.
+    assert_ok!(window.set_size((800, 600)));
+    window.set_position((10, 20));
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(receiver.borrow_mut().take_events(), vec!["Window(Resized((800, 600)))", "Window(Moved((10, 20)))"], "Wrong events!");
+    assert_eq!((window.get_position(), window.get_size()), ((10, 20), (800, 600)), "Wrong position and size!");
+
+    // V2 | KWindow::set_fullscreen() covers the screen and sends Fullscreen and MovedResized.
+    window.set_fullscreen(KWindowFullscreenMode::PrimaryScreen);
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(receiver.borrow_mut().take_events(), vec!["Window(Fullscreen)", "Window(MovedResized((0, 0), (1920, 1080)))"], "Wrong events!");
+    assert!(window.is_fullscreen(), "Window should be fullscreen!");
+    assert_eq!((window.get_position(), window.get_size()), ((0, 0), KLINUX_HEADLESS_SCREEN_RESOLUTION), "Window should cover screen!");
+
+    // V3 | KWindow::restore() restores position and size and sends Restored and MovedResized.
+    window.restore();
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(receiver.borrow_mut().take_events(), vec!["Window(Restored)", "Window(MovedResized((10, 20), (800, 600)))"], "Wrong events!");
+    assert!(!window.is_fullscreen(), "Window shouldn't be fullscreen!");
+    assert_eq!((window.get_position(), window.get_size()), ((10, 20), (800, 600)), "Wrong restored position and size!");
+
+    // V4 | Injected Minimized, Maximized and Restored update window state.
+    assert_ok!(window.inject_event(KEvent::Window(KEventWindow::Minimized())));
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(window.is_minimized(), "Window should be minimized!");
+
+    assert_ok!(window.inject_event(KEvent::Window(KEventWindow::Maximized())));
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(window.is_maximized(), "Window should be maximized!");
+
+    window.restore();
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(!window.is_minimized() && !window.is_maximized(), "Window should be restored!");
+    assert_eq!(receiver.borrow_mut().take_events(), vec!["Window(Minimized)", "Window(Maximized)", "Window(Restored)", "Window(MovedResized((10, 20), (800, 600)))"],
+        "Wrong events!");
+}
+
+#[test]
+/// Inject input events in a headless KWindow.
+///
+/// # Verification(s)
+/// V1 | Injected keyboard, mouse and controller events are dispatched in order.
+/// V2 | KWindow tracks cursor position of injected mouse events.
+/// V3 | Injected Blur releases keys down in KInputState.
+fn headless_kwindow_inject_input() {
+    let (mut window, mut dispatcher, _handle, receiver) = create_headless(640, 480);
+    let state = Rc::new(RefCell::new(KInputState::new()));
+    let _state_handle = assert_ok!(dispatcher.add_event_receiver(state.clone()));
+    window.dispatch_events(&mut dispatcher, true);
+    receiver.borrow_mut().entries.clear();
+
+    // V1 | Injected keyboard, mouse and controller events are dispatched in order.
+    let key = KKeyInput::new(KScancode::W, KKey::Character('w'), KKeyModifiers::NONE, false);
+    assert_ok!(window.inject_event(KEvent::Keyboard(KEventKeyboard::KeyDown(key))));
+    assert_ok!(window.inject_event(KEvent::Mouse(KEventMouse::ButtonDown(KMouseButton::Left, (100, 50)))));
+    assert_ok!(window.inject_event(KEvent::Controller(KEventController::ButtonDown(0, 1))));
+    window.dispatch_events(&mut dispatcher, true);
+
+    assert_eq!(receiver.borrow().entries.len(), 3, "3 events expected!");
+    assert!(state.borrow().is_scancode_down(KScancode::W), "W should be down!");
+    assert!(state.borrow().is_mouse_button_down(KMouseButton::Left), "Left button should be down!");
+    assert!(state.borrow().is_controller_button_down(0, 1), "Controller button should be down!");
+
+    // V2 | KWindow tracks cursor position of injected mouse events.
+    assert_ok!(window.inject_event(KEvent::Mouse(KEventMouse::Moved((200, 150)))));
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(window.get_cursor_position(), (200, 150), "Wrong cursor position!");
+
+    // V3 | Injected Blur releases keys down in KInputState.
+    assert_ok!(window.inject_event(KEvent::Window(KEventWindow::Blur())));
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(!state.borrow().is_scancode_down(KScancode::W), "W should be released!");
+}
+
+#[test]
+/// Post user events delivered at next KWindow::dispatch_events().
+///
+/// # Verification(s)
+/// V1 | KWindow::post_user_event() queues event without dispatching it.
+/// V2 | Posted events are dispatched after display server events, in order, with window id.
+/// V3 | Receiver subscribed to KEventCategories::USER only receives user events.
+/// V4 | KEventUserPoster clones post events to the window without holding it, dispatched in order with window events posted.
+fn headless_kwindow_post_user_event() {
+    let (mut window, mut dispatcher, _handle, receiver) = create_headless(640, 480);
+    let users = Rc::new(RefCell::new(KEventReceiverHeadless { entries: Vec::new() }));
+    let _users_handle = assert_ok!(dispatcher.add_event_receiver_to(users.clone(), "default", KEventCategories::USER, KEventReceiverMode::Observer));
+
+    // V1 | KWindow::post_user_event() queues event without dispatching it.
+    window.post_user_event(KEventUser::from_u64(1, 10));
+    window.post_user_event(KEventUser::from_f64(2, 0.5));
+    assert_eq!(window.get_posted_event_count(), 2, "2 posted events expected!");
+    assert_eq!(receiver.borrow().entries.len(), 0, "Posted events shouldn't be dispatched yet!");
+
+    // V2 | Posted events are dispatched after display server events, in order, with window id.
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(window.get_posted_event_count(), 0, "Posted events should be dispatched!");
+    let events = receiver.borrow().get_events();
+    assert_eq!(events.len(), 4, "4 events expected!");
+    assert_eq!(events[..2], ["Window(Shown)", "Window(Focus)"], "Display server events should be dispatched first!");
+    assert!(receiver.borrow().entries.iter().all(|e| e.get_window_id() == window.get_id()), "Wrong window id!");
+
+    // V3 | Receiver subscribed to KEventCategories::USER only receives user events.
+    let entries = users.borrow().entries.clone();
+    assert_eq!(entries.len(), 2, "2 user events expected!");
+    match (entries[0].get_event(), entries[1].get_event()) {
+        (KEvent::User(first), KEvent::User(second)) => {
+            assert_eq!((first.get_id(), first.get_u64()), (1, 10), "Wrong first user event!");
+            assert_eq!((second.get_id(), second.get_f64()), (2, 0.5), "Wrong second user event!");
+        },
+        _ => panic!("User events expected!"),
+    }
+
+    // V4 | KEventUserPoster clones post events to the window without holding it, dispatched in order with window events posted.
+    let poster = window.get_user_event_poster();
+    let system = poster.clone();
+    system.post(KEventUser::from_u64(3, 30));
+    window.post_user_event(KEventUser::from_u64(4, 40));
+    poster.post(KEventUser::from_u64(5, 50));
+    assert_eq!(window.get_posted_event_count(), 3, "3 posted events expected!");
+    assert_eq!(system.get_posted_count(), 3, "Poster should share window queue!");
+    users.borrow_mut().entries.clear();
+    window.dispatch_events(&mut dispatcher, true);
+    let ids : Vec<u32> = users.borrow().entries.iter().filter_map(|e| match e.get_event() { KEvent::User(user) => Some(user.get_id()), _ => None }).collect();
+    assert_eq!(ids, vec![3, 4, 5], "Wrong posted order!");
+    assert!(users.borrow().entries.iter().all(|e| e.get_window_id() == window.get_id()), "Wrong window id!");
+    assert_eq!(poster.get_posted_count(), 0, "Posted events should be dispatched!");
+}
+
+#[test]
+/// Create several headless KWindow sharing a KDisplayContext.
+///
+/// # Verification(s)
+/// V1 | KWindow::from_context() creates windows with the provider of context and different ids.
+/// V2 | Each window dispatches its own Shown and Focus with its id.
+/// V3 | Event injected in a window is only dispatched by that window.
+/// V4 | KWindow::get_display_context() shares connection after the first context is dropped.
+fn headless_kwindow_from_context() {
+    // V1 | KWindow::from_context() creates windows with the provider of context and different ids.
+    let context = assert_ok!(KDisplayContext::new(KLinuxDisplayServerProvider::Headless));
+    assert_eq!(context.get_display_server_provider(), KLinuxDisplayServerProvider::Headless, "Wrong context provider!");
+    let mut viewport = assert_ok!(KWindow::from_context(&context, 640, 480));
+    let mut palette = assert_ok!(KWindow::from_context(&context, 200, 400));
+    assert_eq!(palette.get_display_server_provider(), KLinuxDisplayServerProvider::Headless, "Wrong window provider!");
+    assert_eq!(palette.get_size(), (200, 400), "Wrong size!");
+    assert_ne!(viewport.get_id(), palette.get_id(), "Windows should have different ids!");
+    assert!(KWindow::from_context(&context, 0, 480).is_err(), "Size error expected!");
+
+    let mut dispatcher = KEventDispatcher::new(false);
+    let receiver = Rc::new(RefCell::new(KEventReceiverHeadless { entries: Vec::new() }));
+    let _receiver_handle = assert_ok!(dispatcher.add_event_receiver(receiver.clone()));
+
+    // V2 | Each window dispatches its own Shown and Focus with its id.
+    for window in [&mut viewport, &mut palette] {
+        window.dispatch_events(&mut dispatcher, true);
+        assert_eq!(receiver.borrow().get_events(), vec!["Window(Shown)", "Window(Focus)"], "Wrong events!");
+        assert!(receiver.borrow().entries.iter().all(|e| e.get_window_id() == window.get_id()), "Wrong window id!");
+        receiver.borrow_mut().entries.clear();
+    }
+
+    // V3 | Event injected in a window is only dispatched by that window.
+    assert_ok!(palette.inject_event(KEvent::Window(KEventWindow::Close())));
+    assert_eq!(viewport.get_event_count(), 0, "Viewport shouldn't have events!");
+    viewport.dispatch_events(&mut dispatcher, true);
+    assert_eq!(receiver.borrow().entries.len(), 0, "Viewport shouldn't dispatch events!");
+    palette.dispatch_events(&mut dispatcher, true);
+    assert_eq!(receiver.borrow().get_events(), vec!["Window(Close)"], "Wrong events!");
+    assert_eq!(receiver.borrow().entries[0].get_window_id(), palette.get_id(), "Wrong window id!");
+
+    // V4 | KWindow::get_display_context() shares connection after the first context is dropped.
+    drop(context);
+    let console = assert_ok!(KWindow::from_context(&viewport.get_display_context(), 320, 240));
+    assert_eq!(console.get_display_context().get_display_server_connection(), viewport.get_display_context().get_display_server_connection(),
+        "Windows should share connection!");
+    assert_eq!(console.get_event_count(), 2, "2 events expected!");
+}
+
+/**********
+* STRUCTS *
+**********/
+/// Receiver that keeps entries received.
+struct KEventReceiverHeadless {
+    /// Entries received.
+    entries : Vec<KEventEntry>,
+}
+
+impl KEventReceiverHeadless {
+    /// Returns events received in their Debug form.
+    fn get_events(&self) -> Vec<String> {
+        self.entries.iter().map(|e| format!("{:?}", e.get_event())).collect()
+    }
+
+    /// Returns events received in their Debug form and clear them.
+    fn take_events(&mut self) -> Vec<String> {
+        let events = self.get_events();
+        self.entries.clear();
+        events
+    }
+}
+
+impl KEventReceiver for KEventReceiverHeadless {
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        self.entries.push(*entry);
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+/************
+* FUNCTIONS *
+************/
+/// Create a headless KWindow with a dispatcher and a receiver.
+fn create_headless(width : u32, height : u32) -> (KWindow, KEventDispatcher, KEventReceiverHandle, Rc<RefCell<KEventReceiverHeadless>>) {
+    let window = assert_ok!(KWindow::new(width, height, KLinuxDisplayServerProvider::Headless));
+    let mut dispatcher = KEventDispatcher::new(false);
+    let receiver = Rc::new(RefCell::new(KEventReceiverHeadless { entries: Vec::new() }));
+    let handle = assert_ok!(dispatcher.add_event_receiver(receiver.clone()));
+
+    (window, dispatcher, handle, receiver)
+}
@@ -0,0 +1,76 @@
+use olympus::kleio::display::{event::{KScancode, KKey, KKeyModifiers, KTextInput, KTEXT_INPUT_MAX}, linux::keyboard::{get_evdev_scancode, get_keysym_key, get_keysym_char}};
+
+/********
+* TESTS *
+********/
+#[test]
+/// Convert Linux evdev key codes and X keysyms.
+/// 
+/// # Verification(s)
+/// V1 | get_evdev_scancode() gives physical keys named after US QWERTY position.
+/// V2 | get_evdev_scancode() gives KScancode::Unknown with key code for unknown key codes.
+/// V3 | get_keysym_key() gives named keys and lower case characters.
+/// V4 | get_keysym_char() converts Latin-1, Unicode and keypad keysyms only.
+fn kkeyboard_linux_conversion() {
+    // V1 | get_evdev_scancode() gives physical keys named after US QWERTY position.
+    assert_eq!(get_evdev_scancode(1), KScancode::Escape, "Escape scancode error!");
+    assert_eq!(get_evdev_scancode(16), KScancode::Q, "Q scancode error!");
+    assert_eq!(get_evdev_scancode(30), KScancode::A, "A scancode error!");
+    assert_eq!(get_evdev_scancode(57), KScancode::Space, "Space scancode error!");
+    assert_eq!(get_evdev_scancode(59), KScancode::Function(1), "F1 scancode error!");
+    assert_eq!(get_evdev_scancode(88), KScancode::Function(12), "F12 scancode error!");
+    assert_eq!(get_evdev_scancode(194), KScancode::Function(24), "F24 scancode error!");
+
+    // V2 | get_evdev_scancode() gives KScancode::Unknown with key code for unknown key codes.
+    assert_eq!(get_evdev_scancode(500), KScancode::Unknown(500), "Unknown scancode error!");
+
+    // V3 | get_keysym_key() gives named keys and lower case characters.
+    assert_eq!(get_keysym_key(0xff1b), KKey::Escape, "Escape key error!");
+    assert_eq!(get_keysym_key(0xff8d), KKey::Enter, "Keypad enter key error!");
+    assert_eq!(get_keysym_key(0xffbe), KKey::Function(1), "F1 key error!");
+    assert_eq!(get_keysym_key(0x61), KKey::Character('a'), "Character key error!");
+    assert_eq!(get_keysym_key(0xe9), KKey::Character('é'), "Latin-1 key error!");
+    assert_eq!(get_keysym_key(0xfe50), KKey::Unknown(0xfe50), "Unknown key error!");
+
+    // V4 | get_keysym_char() converts Latin-1, Unicode and keypad keysyms only.
+    assert_eq!(get_keysym_char(0x41), Some('A'), "Latin-1 char error!");
+    assert_eq!(get_keysym_char(0x0100_20ac), Some('€'), "Unicode char error!");
+    assert_eq!(get_keysym_char(0xffb7), Some('7'), "Keypad char error!");
+    assert_eq!(get_keysym_char(0xff0d), None, "Return shouldn't write a char!");
+}
+
+#[test]
+/// Create KKeyModifiers and KTextInput.
+/// 
+/// # Verification(s)
+/// V1 | KKeyModifiers combined with | contains each modifier.
+/// V2 | KKeyModifiers::insert() and KKeyModifiers::remove() change the set.
+/// V3 | KTextInput::new() keeps UTF-8 text.
+/// V4 | KTextInput::new() truncates text longer than KTEXT_INPUT_MAX on a character boundary.
+fn kkeyboard_modifiers_text() {
+    // V1 | KKeyModifiers combined with | contains each modifier.
+    let mut modifiers = KKeyModifiers::SHIFT | KKeyModifiers::ALT;
+    assert!(modifiers.contains(KKeyModifiers::SHIFT) && modifiers.contains(KKeyModifiers::ALT), "Modifiers should contain SHIFT and ALT!");
+    assert!(!modifiers.contains(KKeyModifiers::SHIFT | KKeyModifiers::CONTROL), "Modifiers shouldn't contain CONTROL!");
+
+    // V2 | KKeyModifiers::insert() and KKeyModifiers::remove() change the set.
+    modifiers.insert(KKeyModifiers::CAPS_LOCK);
+    modifiers.remove(KKeyModifiers::SHIFT | KKeyModifiers::ALT);
+    assert_eq!(modifiers, KKeyModifiers::CAPS_LOCK, "Modifiers insert/remove error!");
+    modifiers.remove(KKeyModifiers::CAPS_LOCK);
+    assert!(modifiers.is_empty(), "Modifiers should be empty!");
+
+    // V3 | KTextInput::new() keeps UTF-8 text.
+    let text = KTextInput::new("ça");
+    assert_eq!(text.as_str(), "ça", "Text input error!");
+    assert_eq!(text.len(), 3, "Text input length error!");
+    assert!(KTextInput::new("").is_empty(), "Text input should be empty!");
+
+    // V4 | KTextInput::new() truncates text longer than KTEXT_INPUT_MAX on a character boundary.
+    let long = "é".repeat(KTEXT_INPUT_MAX);
+    let text = KTextInput::new(&long);
+    assert_eq!(text.len(), KTEXT_INPUT_MAX, "Text input truncation error!");
+    assert_eq!(text.as_str(), "é".repeat(KTEXT_INPUT_MAX / 2), "Text input truncation boundary error!");
+    let text = KTextInput::new(&format!("a{}", long));
+    assert_eq!(text.len(), KTEXT_INPUT_MAX - 1, "Text input should be truncated before a partial character!");
+}
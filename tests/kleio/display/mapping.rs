@@ -0,0 +1,77 @@
+use olympus::{error::{OlympusError, KControllerMappingError}, kleio::display::event::{KControllerAxis, KControllerAxisRange, KControllerButton, KControllerGuid,
+    KControllerMapping, KControllerMappingDatabase, KControllerMappingSource, KControllerMappingTarget}};
+
+use crate::{assert_err, assert_ok};
+
+/********
+* TESTS *
+********/
+#[test]
+/// Parse and write SDL controller mappings.
+/// 
+/// # Verification(s)
+/// V1 | KControllerGuid::from_device() follows SDL layout and KControllerGuid::parse() reads its Display.
+/// V2 | KControllerMapping::parse() reads buttons, half axes, inverted axes and hats. Unknown fields are ignored.
+/// V3 | KControllerMapping Display writes a line parsed to the same mapping.
+/// V4 | KControllerMapping::parse() returns None for invalid GUID or source.
+fn kcontroller_mapping_parse() {
+    // V1 | KControllerGuid::from_device() follows SDL layout and KControllerGuid::parse() reads its Display.
+    let guid = KControllerGuid::from_device(0x03, 0x045e, 0x028e, 0x0114, "Xbox 360 Controller");
+    assert_eq!(guid.to_string(), "030000005e0400008e02000014010000", "GUID layout error!");
+    assert_eq!(KControllerGuid::parse(&guid.to_string()), Some(guid), "GUID parse error!");
+    let guid = KControllerGuid::from_device(0x05, 0, 0, 0, "Pad");
+    assert_eq!(guid.to_string(), "05000000506164000000000000000000", "GUID name layout error!");
+
+    // V2 | KControllerMapping::parse() reads buttons, half axes, inverted axes and hats. Unknown fields are ignored.
+    let mapping = KControllerMapping::parse("03000000aa550000bb66000000000000,Custom Pad,a:b1,-leftx:-a0,+leftx:+a0,lefty:a1~,dpup:h0.1,righttrigger:a5,platform:Linux,")
+        .expect("Mapping parse error!");
+    assert_eq!(mapping.get_name(), "Custom Pad", "Mapping name error!");
+    assert_eq!(mapping.get_entries(), &[
+        (KControllerMappingSource::Button(1), KControllerMappingTarget::Button(KControllerButton::A)),
+        (KControllerMappingSource::Axis(0, KControllerAxisRange::Negative, false), KControllerMappingTarget::Axis(KControllerAxis::LeftX, KControllerAxisRange::Negative)),
+        (KControllerMappingSource::Axis(0, KControllerAxisRange::Positive, false), KControllerMappingTarget::Axis(KControllerAxis::LeftX, KControllerAxisRange::Positive)),
+        (KControllerMappingSource::Axis(1, KControllerAxisRange::Full, true), KControllerMappingTarget::Axis(KControllerAxis::LeftY, KControllerAxisRange::Full)),
+        (KControllerMappingSource::Hat(0, 1), KControllerMappingTarget::Button(KControllerButton::DPadUp)),
+        (KControllerMappingSource::Axis(5, KControllerAxisRange::Full, false), KControllerMappingTarget::Axis(KControllerAxis::TriggerRight, KControllerAxisRange::Full))], "Mapping entries error!");
+
+    // V3 | KControllerMapping Display writes a line parsed to the same mapping.
+    assert_eq!(KControllerMapping::parse(&mapping.to_string()), Some(mapping), "Mapping round trip error!");
+
+    // V4 | KControllerMapping::parse() returns None for invalid GUID or source.
+    assert!(KControllerMapping::parse("03000000aa55,Short GUID,a:b0,").is_none(), "Short GUID should be invalid!");
+    assert!(KControllerMapping::parse("03000000aa550000bb66000000000000,Bad Source,a:x0,").is_none(), "Source x0 should be invalid!");
+}
+
+#[test]
+/// Find controller mappings in KControllerMappingDatabase.
+/// 
+/// # Verification(s)
+/// V1 | Built-in mappings are found, whatever the device version.
+/// V2 | KControllerMappingDatabase::parse() skips comments and other platforms.
+/// V3 | KControllerMappingDatabase::parse() returns InvalidMapping with the line number and adds nothing.
+/// V4 | User mappings override built-in mappings.
+/// V5 | KControllerMappingDatabase::load() returns FileError for missing files.
+fn kcontroller_mapping_database() {
+    let mut database = KControllerMappingDatabase::new();
+
+    // V1 | Built-in mappings are found, whatever the device version.
+    let xbox = KControllerGuid::from_device(0x03, 0x045e, 0x028e, 0x0114, "Xbox 360 Controller");
+    assert_eq!(database.get_mapping(xbox).map(|m| m.get_name()), Some("Xbox 360 Controller"), "Built-in mapping error!");
+    assert!(database.get_mapping(KControllerGuid::from_device(0x03, 0x1234, 0x5678, 0, "Unknown")).is_none(), "Unknown controller mapping!");
+
+    // V2 | KControllerMappingDatabase::parse() skips comments and other platforms.
+    let count = assert_ok!(database.parse("# Comment\n\n03000000aa550000bb66000000000000,Custom Pad,a:b1,platform:Linux,\n03000000aa550000bb66000000000000,Windows Pad,a:b0,platform:Windows,"));
+    assert_eq!(count, 1, "Mapping count error!");
+    assert_eq!(database.get_mapping(KControllerGuid::parse("03000000aa550000bb66000000000000").unwrap()).map(|m| m.get_name()), Some("Custom Pad"), "Parsed mapping error!");
+
+    // V3 | KControllerMappingDatabase::parse() returns InvalidMapping with the line number and adds nothing.
+    assert_err!(database.parse("03000000aa550000cc77000000000000,Valid,a:b0,\n# Comment\nnot a mapping"), OlympusError::KControllerMapping(KControllerMappingError::InvalidMapping(3)));
+    assert!(database.get_mapping(KControllerGuid::parse("03000000aa550000cc77000000000000").unwrap()).is_none(), "No mapping should be added on error!");
+
+    // V4 | User mappings override built-in mappings.
+    assert_ok!(database.parse("030000005e0400008e02000000000000,My Xbox,a:b1,b:b0,"));
+    assert_eq!(database.get_mapping(xbox).map(|m| m.get_name()), Some("My Xbox"), "User mapping should override built-in!");
+
+    // V5 | KControllerMappingDatabase::load() returns FileError for missing files.
+    assert_err!(database.load(std::env::temp_dir().join("kcontroller_mapping_missing.txt")), OlympusError::KControllerMapping(KControllerMappingError::FileError));
+}
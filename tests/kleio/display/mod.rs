@@ -1,6 +1,6 @@
 use std::process::exit;
 
-use olympus::kleio::display::event::KEventReceiver;
+use olympus::kleio::display::event::{KEventReceiver, KScancode};
 
 // Contains tests for screen module
 #[cfg(test)]
@@ -10,10 +10,46 @@ pub mod screen;
 #[cfg(test)]
 pub mod dispatcher;
 
+// Contains tests for keyboard events
+#[cfg(test)]
+pub mod keyboard;
+
+// Contains tests for KInputState
+#[cfg(test)]
+pub mod state;
+
+// Contains tests for KInputActionMap
+#[cfg(test)]
+pub mod action;
+
+// Contains tests for touch gestures
+#[cfg(test)]
+pub mod touch;
+
+// Contains tests for events recording and replay
+#[cfg(test)]
+pub mod record;
+
+// Contains tests for controller mappings
+#[cfg(test)]
+pub mod mapping;
+
+// Contains tests for Linux evdev controllers
+#[cfg(all(not(target_family = "wasm"), target_os = "linux"))]
+pub mod controller;
+
+// Contains tests for headless KWindow Linux Display Server
+#[cfg(all(not(target_family = "wasm"), target_os = "linux"))]
+pub mod headless;
+
 // Contains tests for X11 KWindow Linux Display Server
 #[cfg(all(not(target_family = "wasm"), target_os = "linux"))]
 pub mod x11;
 
+// Contains tests for Wayland KWindow Linux Display Server
+#[cfg(all(not(target_family = "wasm"), target_os = "linux"))]
+pub mod wayland;
+
 
 
 /*********
@@ -35,11 +71,11 @@ macro_rules! kwindow_x11_prepare {
         let mut $dispatcher = KEventDispatcher::new(true);
 
         // Create and add receiver to dispatcher
-        let $receiver = Rc::new(RefCell::new(KEventReceiverControl::new(65, 9)));
-        match $dispatcher.add_event_receiver($receiver.clone()){
-            Ok(_) => {},
+        let $receiver = Rc::new(RefCell::new(KEventReceiverControl::new(olympus::kleio::display::event::KScancode::Space, olympus::kleio::display::event::KScancode::Escape)));
+        let _receiver_handle = match $dispatcher.add_event_receiver($receiver.clone()){
+            Ok(handle) => handle,
             Err(_) => panic!("Receiver error!"),
-        }
+        };
 
         // Test body
         $test_body
@@ -106,10 +142,10 @@ enum KEventReceiverControlState {
 /// Pressing Esc will exit the program.
 struct KEventReceiverControl {
     /// Key to press for next step
-    step_key : u32,
+    step_key : KScancode,
 
     /// Key to press to exit
-    exit_key : u32,
+    exit_key : KScancode,
 
 
     /// State
@@ -117,7 +153,7 @@ struct KEventReceiverControl {
 }
 
 impl KEventReceiverControl {
-    pub fn new(step_key : u32, exit_key : u32) -> KEventReceiverControl {
+    pub fn new(step_key : KScancode, exit_key : KScancode) -> KEventReceiverControl {
         KEventReceiverControl { step_key, exit_key, state : KEventReceiverControlState::Running }
     }
 
@@ -131,24 +167,24 @@ impl KEventReceiverControl {
 }
 
 impl KEventReceiver for KEventReceiverControl {
-    fn handle_event(&mut self, event : &olympus::kleio::display::event::KEvent) -> bool {
-        match event {
+    fn handle_event(&mut self, entry : &olympus::kleio::display::event::KEventEntry) -> bool {
+        match entry.get_event() {
             olympus::kleio::display::event::KEvent::Keyboard(event) => match event {
-            olympus::kleio::display::event::KEventKeyboard::KeyDown(keycode) => 
+            olympus::kleio::display::event::KEventKeyboard::KeyDown(input) => 
                 {
                     self.state = KEventReceiverControlState::Running;
 
-                    println!("KeyCode={}", keycode);
-                    if *keycode == self.step_key {
+                    println!("Key={:?}", input);
+                    if input.scancode == self.step_key {
                         self.state = KEventReceiverControlState::NextStep;
                     }
 
-                    if *keycode == self.exit_key {
+                    if input.scancode == self.exit_key {
                         self.state = KEventReceiverControlState::Exit;
                     }
                     false
                 },
-                olympus::kleio::display::event::KEventKeyboard::KeyUp(_) => { false },
+                _ => { false },
             },
             _ => false,
         }
@@ -0,0 +1,164 @@
+use std::{rc::Rc, cell::RefCell, time::{Duration, Instant}};
+
+use olympus::{kleio::display::event::{KEvent, KEventDispatcher, KEventEntry, KEventReceiver, KEventRecorder, KEventReplay, KEventKeyboard,
+    KEventMouse, KEventController, KEventWindow, KEventTouch, KEventUser, KKey, KKeyInput, KKeyModifiers, KScancode, KMouseButton, KTextInput, KTouch, KPen,
+    KEVENT_RECORD_MAGIC}, error::{OlympusError, KEventRecordError}};
+
+use crate::{assert_err, assert_ok};
+
+/********
+* TESTS *
+********/
+#[test]
+/// Record dispatched events into a file and replay them through a dispatcher.
+///
+/// # Verification(s)
+/// V1 | KEventRecorder records every dispatched event, handled or not.
+/// V2 | KEventReplay reads events with their time since first event and window id.
+/// V3 | KEventReplay::dispatch_until() only dispatches events due at elapsed time.
+/// V4 | Replayed events keep recorded spacing and window id.
+/// V5 | KEventReplay::set_speed() accelerates replay.
+/// V6 | KEventReplay::rewind() restarts replay.
+fn kevent_record_replay() {
+    let path = std::env::temp_dir().join("kevent_record_replay.kevt");
+    let events = get_events();
+
+    // V1 | KEventRecorder records every dispatched event, handled or not.
+    let mut dispatcher = KEventDispatcher::new(false);
+    let handler = Rc::new(RefCell::new(KEventReceiverRecord { entries: Vec::new(), handle: true }));
+    let _handler_handle = assert_ok!(dispatcher.add_event_receiver(handler.clone()));
+    assert!(dispatcher.set_recorder(Some(assert_ok!(KEventRecorder::new(&path)))).is_none(), "No recorder expected!");
+    assert!(dispatcher.is_recording(), "Dispatcher should record!");
+
+    let start = Instant::now();
+    for (index, event) in events.iter().enumerate() {
+        dispatcher.dispatch_from(event, start + Duration::from_millis(10 * index as u64), index as u64 % 2 + 1);
+    }
+    let recorder = dispatcher.set_recorder(None).expect("Recorder expected!");
+    assert!(!dispatcher.is_recording(), "Dispatcher shouldn't record!");
+    assert_ok!(recorder.finish(), events.len());
+
+    // V2 | KEventReplay reads events with their time since first event and window id.
+    let mut replay = assert_ok!(KEventReplay::new(&path));
+    assert_eq!(replay.get_event_count(), events.len(), "Wrong event count!");
+    assert_eq!(replay.get_duration(), Duration::from_millis(10 * (events.len() as u64 - 1)), "Wrong duration!");
+    for (index, (time, window_id, event)) in replay.get_events().iter().enumerate() {
+        assert_eq!(*time, Duration::from_millis(10 * index as u64), "Wrong time!");
+        assert_eq!(*window_id, index as u64 % 2 + 1, "Wrong window id!");
+        assert_eq!(format!("{:?}", event), format!("{:?}", events[index]), "Wrong event!");
+    }
+
+    // V3 | KEventReplay::dispatch_until() only dispatches events due at elapsed time.
+    let mut dispatcher = KEventDispatcher::new(false);
+    let receiver = Rc::new(RefCell::new(KEventReceiverRecord { entries: Vec::new(), handle: false }));
+    let _receiver_handle = assert_ok!(dispatcher.add_event_receiver(receiver.clone()));
+
+    assert_eq!(replay.dispatch_until(&mut dispatcher, Duration::from_millis(25)), 3, "3 events should be dispatched!");
+    assert_eq!(replay.dispatch_until(&mut dispatcher, Duration::from_millis(25)), 0, "No event should be dispatched!");
+    assert_eq!(replay.dispatch_until(&mut dispatcher, Duration::MAX), events.len() - 3, "Remaining events should be dispatched!");
+    assert!(replay.is_finished(), "Replay should be finished!");
+
+    // V4 | Replayed events keep recorded spacing and window id.
+    {
+        let entries = &receiver.borrow().entries;
+        assert_eq!(entries.len(), events.len(), "All events should be received!");
+        for (index, entry) in entries.iter().enumerate() {
+            assert_eq!(entry.get_elapsed(&entries[0]), Duration::from_millis(10 * index as u64), "Wrong spacing!");
+            assert_eq!(entry.get_window_id(), index as u64 % 2 + 1, "Wrong window id!");
+            assert_eq!(entry.get_sequence(), index as u64, "Wrong sequence!");
+        }
+    }
+
+    // V5 | KEventReplay::set_speed() accelerates replay.
+    // V6 | KEventReplay::rewind() restarts replay.
+    replay.rewind();
+    replay.set_speed(2.0);
+    assert_eq!(replay.get_duration(), Duration::from_millis(5 * (events.len() as u64 - 1)), "Wrong accelerated duration!");
+    assert_eq!(replay.dispatch_until(&mut dispatcher, Duration::from_millis(25)), 6, "6 events should be dispatched!");
+
+    std::fs::remove_file(path).ok();
+}
+
+#[test]
+/// Read invalid event records.
+///
+/// # Verification(s)
+/// V1 | Missing file gives FileError.
+/// V2 | Record without magic gives InvalidHeader.
+/// V3 | Record with a newer version gives UnsupportedVersion.
+/// V4 | Truncated event gives CorruptedEvent with its index.
+fn kevent_record_invalid() {
+    // V1 | Missing file gives FileError.
+    assert_err!(KEventReplay::new(std::env::temp_dir().join("kevent_record_missing.kevt")), OlympusError::KEventRecord(KEventRecordError::FileError));
+
+    // V2 | Record without magic gives InvalidHeader.
+    assert_err!(KEventReplay::from_reader(&b"KJNL\x01\x00"[..]), OlympusError::KEventRecord(KEventRecordError::InvalidHeader));
+
+    // V3 | Record with a newer version gives UnsupportedVersion.
+    let mut record = KEVENT_RECORD_MAGIC.to_vec();
+    record.extend_from_slice(&u16::MAX.to_le_bytes());
+    assert_err!(KEventReplay::from_reader(record.as_slice()), OlympusError::KEventRecord(KEventRecordError::UnsupportedVersion));
+
+    // V4 | Truncated event gives CorruptedEvent with its index.
+    let path = std::env::temp_dir().join("kevent_record_invalid.kevt");
+    let mut recorder = assert_ok!(KEventRecorder::new(&path));
+    recorder.record(&KEvent::Mouse(KEventMouse::Moved((1, 2))).into());
+    recorder.record(&KEvent::Mouse(KEventMouse::Moved((3, 4))).into());
+    assert_ok!(recorder.finish(), 2);
+
+    let mut record = std::fs::read(&path).unwrap();
+    record.pop();
+    assert_err!(KEventReplay::from_reader(record.as_slice()), OlympusError::KEventRecord(KEventRecordError::CorruptedEvent(1)));
+
+    std::fs::remove_file(path).ok();
+}
+
+/**********
+* STRUCTS *
+**********/
+/// Receiver that keeps entries received.
+struct KEventReceiverRecord {
+    /// Entries received.
+    entries : Vec<KEventEntry>,
+
+    /// Value returned when handling event.
+    handle : bool,
+}
+
+impl KEventReceiver for KEventReceiverRecord {
+    fn handle_event(&mut self, entry : &KEventEntry) -> bool {
+        self.entries.push(*entry);
+        self.handle
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+/************
+* FUNCTIONS *
+************/
+/// Returns events of every category.
+fn get_events() -> Vec<KEvent> {
+    let key = KKeyInput::new(KScancode::Function(5), KKey::Character('\''), KKeyModifiers::CONTROL | KKeyModifiers::NUM_LOCK, true);
+    let pen = KPen::new((0.25, 0.5), 0.75, (-10.0, 20.5), true);
+
+    vec![
+        KEvent::Window(KEventWindow::Exposed((-1, 2), (300, 400))),
+        KEvent::Window(KEventWindow::MovedResized((10, 20), (640, 480))),
+        KEvent::Window(KEventWindow::Focus()),
+        KEvent::Keyboard(KEventKeyboard::KeyDown(key)),
+        KEvent::Keyboard(KEventKeyboard::KeyUp(KKeyInput::new(KScancode::Unknown(999), KKey::Unknown(65), KKeyModifiers::NONE, false))),
+        KEvent::Keyboard(KEventKeyboard::TextInput(KTextInput::new("é€"))),
+        KEvent::Mouse(KEventMouse::ButtonDown(KMouseButton::Other(9), (5, -5))),
+        KEvent::Mouse(KEventMouse::Wheel(-1, 3)),
+        KEvent::Mouse(KEventMouse::RawMotion(0.125, -3.5)),
+        KEvent::Controller(KEventController::Axis(1, 2, -32768)),
+        KEvent::Controller(KEventController::ButtonUp(3, 200)),
+        KEvent::Touch(KEventTouch::Move(KTouch::new(u64::MAX, (0.5, 1.5)))),
+        KEvent::Touch(KEventTouch::PenUp(pen)),
+        KEvent::User(KEventUser::new(u32::MAX, [7; 16])),
+        KEvent::Unknown,
+    ]
+}
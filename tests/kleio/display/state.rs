@@ -0,0 +1,127 @@
+use std::{rc::Rc, cell::RefCell};
+
+use olympus::kleio::display::event::{KEvent, KEventDispatcher, KEventKeyboard, KEventMouse, KEventController, KEventWindow, KInputState, 
+    KKey, KKeyInput, KKeyModifiers, KScancode, KMouseButton, KTextInput};
+
+/********
+* TESTS *
+********/
+#[test]
+/// Track keyboard and mouse states with KInputState fed by KEventDispatcher.
+/// 
+/// # Verification(s)
+/// V1 | Key down is down and pressed for physical and logical keys. Auto-repeat doesn't press again.
+/// V2 | KInputState::reset_frame() clears pressed edges, text and wheel but keeps keys down.
+/// V3 | Key up is released and not down.
+/// V4 | Key pressed and released in same frame is pressed and released but not down.
+/// V5 | Mouse buttons, cursor position, wheel and raw motion are tracked.
+/// V6 | KEventWindow::Blur releases keys and buttons down.
+/// V7 | KInputState doesn't handle events so older receivers get them.
+fn kinput_state_keyboard_mouse() {
+    let state = Rc::new(RefCell::new(KInputState::new()));
+    let mut dispatcher = KEventDispatcher::new(true);
+    let _state_handle = dispatcher.add_event_receiver(state.clone()).expect("add_event_receiver error!");
+
+    // V1 | Key down is down and pressed for physical and logical keys. Auto-repeat doesn't press again.
+    dispatcher.dispatch(&key_down(KScancode::Q, 'a', false));
+    dispatcher.dispatch(&KEvent::Keyboard(KEventKeyboard::TextInput(KTextInput::new("A"))));
+    assert!(state.borrow().is_scancode_down(KScancode::Q) && state.borrow().is_scancode_pressed(KScancode::Q), "Scancode should be down and pressed!");
+    assert!(state.borrow().is_key_down(KKey::Character('a')) && state.borrow().is_key_pressed(KKey::Character('a')), "Key should be down and pressed!");
+    assert_eq!(state.borrow().get_modifiers(), KKeyModifiers::SHIFT, "Modifiers error!");
+    assert_eq!(state.borrow().get_text(), "A", "Text error!");
+
+    // V2 | KInputState::reset_frame() clears pressed edges, text and wheel but keeps keys down.
+    state.borrow_mut().reset_frame();
+    dispatcher.dispatch(&key_down(KScancode::Q, 'a', true));
+    assert!(state.borrow().is_scancode_down(KScancode::Q), "Scancode should still be down!");
+    assert!(!state.borrow().is_scancode_pressed(KScancode::Q), "Repeated scancode shouldn't be pressed!");
+    assert_eq!(state.borrow().get_text(), "", "Text should be cleared!");
+
+    // V3 | Key up is released and not down.
+    dispatcher.dispatch(&key_up(KScancode::Q, 'a'));
+    assert!(!state.borrow().is_key_down(KKey::Character('a')) && state.borrow().is_key_released(KKey::Character('a')), "Key should be released!");
+
+    // V4 | Key pressed and released in same frame is pressed and released but not down.
+    state.borrow_mut().reset_frame();
+    dispatcher.dispatch(&key_down(KScancode::Space, ' ', false));
+    dispatcher.dispatch(&key_up(KScancode::Space, ' '));
+    assert!(state.borrow().is_scancode_pressed(KScancode::Space) && state.borrow().is_scancode_released(KScancode::Space), "Scancode should be pressed and released!");
+    assert!(!state.borrow().is_scancode_down(KScancode::Space), "Scancode shouldn't be down!");
+
+    // V5 | Mouse buttons, cursor position, wheel and raw motion are tracked.
+    dispatcher.dispatch(&KEvent::Mouse(KEventMouse::Moved((10, 20))));
+    assert_eq!(state.borrow().get_cursor_position(), (10, 20), "Cursor position error!");
+    dispatcher.dispatch(&KEvent::Mouse(KEventMouse::ButtonDown(KMouseButton::Left, (15, 25))));
+    dispatcher.dispatch(&KEvent::Mouse(KEventMouse::Wheel(0, 1)));
+    dispatcher.dispatch(&KEvent::Mouse(KEventMouse::Wheel(1, 1)));
+    assert!(state.borrow().is_mouse_button_down(KMouseButton::Left) && state.borrow().is_mouse_button_pressed(KMouseButton::Left), "Mouse button should be down!");
+    assert_eq!(state.borrow().get_cursor_position(), (15, 25), "Cursor position from button error!");
+    dispatcher.dispatch(&KEvent::Mouse(KEventMouse::RawMotion(1.5, -2.0)));
+    dispatcher.dispatch(&KEvent::Mouse(KEventMouse::RawMotion(0.25, 4.0)));
+    assert_eq!(state.borrow().get_wheel(), (1, 2), "Wheel error!");
+    assert_eq!(state.borrow().get_mouse_motion(), (1.75, 2.0), "Raw motion error!");
+    state.borrow_mut().reset_frame();
+    assert_eq!(state.borrow().get_wheel(), (0, 0), "Wheel should be cleared!");
+    assert_eq!(state.borrow().get_mouse_motion(), (0.0, 0.0), "Raw motion should be cleared!");
+
+    // V6 | KEventWindow::Blur releases keys and buttons down.
+    dispatcher.dispatch(&key_down(KScancode::W, 'w', false));
+    dispatcher.dispatch(&KEvent::Window(KEventWindow::Blur()));
+    assert!(!state.borrow().is_scancode_down(KScancode::W) && state.borrow().is_scancode_released(KScancode::W), "Scancode should be released on blur!");
+    assert!(!state.borrow().is_mouse_button_down(KMouseButton::Left) && state.borrow().is_mouse_button_released(KMouseButton::Left), "Mouse button should be released on blur!");
+
+    // V7 | KInputState doesn't handle events so older receivers get them.
+    assert_eq!(dispatcher.get_unhandled_events().len(), 14, "All events should be unhandled!");
+}
+
+#[test]
+/// Track controllers states with KInputState.
+/// 
+/// # Verification(s)
+/// V1 | Connected controllers are listed.
+/// V2 | Controller buttons are down, pressed and released.
+/// V3 | Controller axes keep last value and give 0 when unknown.
+/// V4 | Disconnected controllers are removed.
+fn kinput_state_controller() {
+    let state = Rc::new(RefCell::new(KInputState::new()));
+    let mut dispatcher = KEventDispatcher::new(false);
+    let _state_handle = dispatcher.add_event_receiver(state.clone()).expect("add_event_receiver error!");
+
+    // V1 | Connected controllers are listed.
+    dispatcher.dispatch(&KEvent::Controller(KEventController::Connected(1)));
+    dispatcher.dispatch(&KEvent::Controller(KEventController::Connected(0)));
+    assert_eq!(state.borrow().get_controllers(), vec![0, 1], "Controllers error!");
+
+    // V2 | Controller buttons are down, pressed and released.
+    dispatcher.dispatch(&KEvent::Controller(KEventController::ButtonDown(0, 3)));
+    assert!(state.borrow().is_controller_button_down(0, 3) && state.borrow().is_controller_button_pressed(0, 3), "Controller button should be down!");
+    assert!(!state.borrow().is_controller_button_down(1, 3), "Other controller button shouldn't be down!");
+    state.borrow_mut().reset_frame();
+    dispatcher.dispatch(&KEvent::Controller(KEventController::ButtonUp(0, 3)));
+    assert!(!state.borrow().is_controller_button_pressed(0, 3) && state.borrow().is_controller_button_released(0, 3), "Controller button should be released!");
+
+    // V3 | Controller axes keep last value and give 0 when unknown.
+    dispatcher.dispatch(&KEvent::Controller(KEventController::Axis(1, 2, -100)));
+    dispatcher.dispatch(&KEvent::Controller(KEventController::Axis(1, 2, 250)));
+    assert_eq!(state.borrow().get_controller_axis(1, 2), 250, "Controller axis error!");
+    assert_eq!(state.borrow().get_controller_axis(1, 0), 0, "Unknown axis should be 0!");
+    assert_eq!(state.borrow().get_controller_axis(5, 2), 0, "Unknown controller axis should be 0!");
+
+    // V4 | Disconnected controllers are removed.
+    dispatcher.dispatch(&KEvent::Controller(KEventController::Disconnected(1)));
+    assert!(!state.borrow().is_controller_connected(1), "Controller should be disconnected!");
+    assert_eq!(state.borrow().get_controller_axis(1, 2), 0, "Disconnected controller axis should be 0!");
+}
+
+/************
+* FUNCTIONS * 
+************/
+/// Create a key down event with shift modifier.
+fn key_down(scancode : KScancode, character : char, repeat : bool) -> KEvent {
+    KEvent::Keyboard(KEventKeyboard::KeyDown(KKeyInput::new(scancode, KKey::Character(character), KKeyModifiers::SHIFT, repeat)))
+}
+
+/// Create a key up event without modifier.
+fn key_up(scancode : KScancode, character : char) -> KEvent {
+    KEvent::Keyboard(KEventKeyboard::KeyUp(KKeyInput::new(scancode, KKey::Character(character), KKeyModifiers::NONE, false)))
+}
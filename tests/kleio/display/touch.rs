@@ -0,0 +1,86 @@
+use olympus::kleio::display::event::{KEvent, KEventReceiver, KEventTouch, KTouch, KPen, KTouchGesture, KTouchGestureRecognizer, KTouchSwipeDirection};
+
+/********
+* TESTS *
+********/
+#[test]
+/// Recognize swipes from KEventTouch.
+/// 
+/// # Verification(s)
+/// V1 | A finger moved far enough then lifted is a swipe in the direction of the motion.
+/// V2 | Fingers lifted one after the other are a swipe with the count of fingers.
+/// V3 | A short motion isn't a swipe.
+/// V4 | A cancelled touch isn't a swipe.
+/// V5 | Pen events are ignored and KTouchGestureRecognizer doesn't handle events.
+fn ktouch_gesture_swipe() {
+    let mut recognizer = KTouchGestureRecognizer::new();
+
+    // V1 | A finger moved far enough then lifted is a swipe in the direction of the motion.
+    touch(&mut recognizer, &[KEventTouch::Begin(KTouch::new(1, (0.5, 0.8))), KEventTouch::Move(KTouch::new(1, (0.5, 0.6))), 
+        KEventTouch::End(KTouch::new(1, (0.52, 0.3)))]);
+    assert_eq!(recognizer.pop_gesture(), Some(KTouchGesture::Swipe(KTouchSwipeDirection::Up, 1)), "Swipe up error!");
+    assert_eq!(recognizer.pop_gesture(), None, "Only one gesture expected!");
+
+    // V2 | Fingers lifted one after the other are a swipe with the count of fingers.
+    touch(&mut recognizer, &[KEventTouch::Begin(KTouch::new(1, (0.6, 0.4))), KEventTouch::Begin(KTouch::new(2, (0.6, 0.6))), 
+        KEventTouch::Begin(KTouch::new(3, (0.6, 0.8))), KEventTouch::End(KTouch::new(3, (0.3, 0.8))), KEventTouch::End(KTouch::new(1, (0.3, 0.4))),
+        KEventTouch::End(KTouch::new(2, (0.3, 0.6)))]);
+    assert_eq!(recognizer.get_touch_count(), 0, "All fingers should be lifted!");
+    assert_eq!(recognizer.pop_gesture(), Some(KTouchGesture::Swipe(KTouchSwipeDirection::Left, 3)), "3 fingers swipe left error!");
+
+    // V3 | A short motion isn't a swipe.
+    touch(&mut recognizer, &[KEventTouch::Begin(KTouch::new(4, (0.5, 0.5))), KEventTouch::End(KTouch::new(4, (0.55, 0.52)))]);
+    assert_eq!(recognizer.pop_gesture(), None, "Tap shouldn't be a swipe!");
+
+    // V4 | A cancelled touch isn't a swipe.
+    touch(&mut recognizer, &[KEventTouch::Begin(KTouch::new(5, (0.1, 0.1))), KEventTouch::Cancel(KTouch::new(5, (0.1, 0.9)))]);
+    assert_eq!(recognizer.pop_gesture(), None, "Cancelled touch shouldn't be a swipe!");
+
+    // V5 | Pen events are ignored and KTouchGestureRecognizer doesn't handle events.
+    let pen = KPen::new((0.1, 0.1), 0.5, (10.0, -20.0), false);
+    assert!(!recognizer.handle_event(&KEvent::Touch(KEventTouch::PenDown(pen)).into()), "Pen event shouldn't be handled!");
+    assert!(!recognizer.handle_event(&KEvent::Touch(KEventTouch::PenUp(KPen { position: (0.9, 0.1), ..pen })).into()), "Pen event shouldn't be handled!");
+    assert_eq!(recognizer.pop_gesture(), None, "Pen shouldn't make gestures!");
+}
+
+#[test]
+/// Recognize pinches from KEventTouch.
+/// 
+/// # Verification(s)
+/// V1 | 2 fingers moving apart give a pinch with scale greater than 1.0 and their center.
+/// V2 | Lifting a finger ends the pinch with the final scale.
+/// V3 | A pinch isn't a swipe.
+/// V4 | 2 fingers barely moving don't pinch.
+fn ktouch_gesture_pinch() {
+    let mut recognizer = KTouchGestureRecognizer::new();
+
+    // V1 | 2 fingers moving apart give a pinch with scale greater than 1.0 and their center.
+    touch(&mut recognizer, &[KEventTouch::Begin(KTouch::new(1, (0.25, 0.5))), KEventTouch::Begin(KTouch::new(2, (0.5, 0.5))), 
+        KEventTouch::Move(KTouch::new(2, (0.75, 0.5)))]);
+    assert_eq!(recognizer.pop_gesture(), Some(KTouchGesture::Pinch(2.0, (0.5, 0.5))), "Pinch error!");
+    touch(&mut recognizer, &[KEventTouch::Move(KTouch::new(1, (0.0, 0.5)))]);
+    assert_eq!(recognizer.pop_gesture(), Some(KTouchGesture::Pinch(3.0, (0.375, 0.5))), "Pinch update error!");
+
+    // V2 | Lifting a finger ends the pinch with the final scale.
+    touch(&mut recognizer, &[KEventTouch::End(KTouch::new(2, (0.75, 0.5)))]);
+    assert_eq!(recognizer.pop_gesture(), Some(KTouchGesture::PinchEnd(3.0)), "Pinch end error!");
+
+    // V3 | A pinch isn't a swipe.
+    touch(&mut recognizer, &[KEventTouch::End(KTouch::new(1, (0.0, 0.5)))]);
+    assert_eq!(recognizer.pop_gesture(), None, "Pinch shouldn't be a swipe!");
+
+    // V4 | 2 fingers barely moving don't pinch.
+    touch(&mut recognizer, &[KEventTouch::Begin(KTouch::new(1, (0.4, 0.5))), KEventTouch::Begin(KTouch::new(2, (0.6, 0.5))), 
+        KEventTouch::Move(KTouch::new(2, (0.61, 0.5))), KEventTouch::End(KTouch::new(1, (0.4, 0.5))), KEventTouch::End(KTouch::new(2, (0.61, 0.5)))]);
+    assert_eq!(recognizer.pop_gesture(), None, "No gesture expected!");
+}
+
+/************
+* FUNCTIONS * 
+************/
+/// Send touch events to recognizer.
+fn touch(recognizer : &mut KTouchGestureRecognizer, events : &[KEventTouch]) {
+    for event in events {
+        recognizer.handle_event(&KEvent::Touch(*event).into());
+    }
+}
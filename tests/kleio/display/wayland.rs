@@ -0,0 +1,215 @@
+use std::{sync::mpsc::Receiver, time::{Duration, Instant}};
+
+use olympus::kleio::display::{KWindow, KDisplayContext, KWindowFullscreenMode, KCursorMode, linux::server::KLinuxDisplayServerProvider, screen::KScreenList,
+    event::{KEvent, KEventDispatcher, KEventEntry, KEventCategories, KEventReceiverHandle, KEventWindow, KEVENT_DEFAULT_LAYER}};
+
+use crate::assert_ok;
+
+/*********
+* CONSTS *
+*********/
+/// Window dimension
+pub const KWINDOW_WIDTH:u32 = 320;
+pub const KWINDOW_HEIGHT:u32 = 240;
+
+/// Time given to compositor to answer a request.
+pub const KWAYLAND_TIMEOUT : Duration = Duration::from_secs(5);
+
+/********
+* TESTS *
+********/
+#[test]
+#[ignore = "Wayland compositor needed (ie. weston --backend=headless-backend.so)"]
+/// Get Wayland screens from compositor outputs.
+///
+/// # Verification(s)
+/// V1 | KScreenList::new() with Wayland provider creates KScreenList without error.
+/// V2 | First output is the primary screen with a resolution.
+/// V3 | Desktop size covers the primary screen.
+fn kwindow_wayland_screen_list() {
+    // V1 | KScreenList::new() with Wayland provider creates KScreenList without error.
+    let screen_list = assert_ok!(KScreenList::new(KLinuxDisplayServerProvider::Wayland));
+    assert!(!screen_list.get_screen_list().is_empty(), "At least 1 screen expected!");
+
+    // V2 | First output is the primary screen with a resolution.
+    let primary = screen_list.get_primary_screen().expect("Primary screen expected!");
+    assert_eq!(primary.get_identifier(), screen_list.get_screen_list()[0].get_identifier(), "First output should be primary!");
+    assert!(primary.get_current_resolution().0 > 0 && primary.get_current_resolution().1 > 0, "Resolution expected!");
+
+    // V3 | Desktop size covers the primary screen.
+    assert!(screen_list.get_desktop_width() >= primary.get_current_resolution().0, "Wrong desktop width!");
+    assert!(screen_list.get_desktop_height() >= primary.get_current_resolution().1, "Wrong desktop height!");
+}
+
+#[test]
+#[ignore = "Wayland compositor needed (ie. weston --backend=headless-backend.so)"]
+/// Create a Wayland KWindow with xdg-shell.
+///
+/// # Verification(s)
+/// V1 | KWindow::new() with Wayland provider creates window without error.
+/// V2 | KWindow::get_display_server_connection() and KWindow::get_display_server_window() return valid pointers.
+/// V3 | Window is shown and exposed after first configure.
+/// V4 | KWindow::set_title() and KWindow::set_position() don't generate error.
+fn kwindow_wayland_new() {
+    // V1 | KWindow::new() with Wayland provider creates window without error.
+    let (mut window, mut dispatcher, _handle, events) = create_wayland(KEventCategories::WINDOW);
+    assert_eq!(window.get_display_server_provider(), KLinuxDisplayServerProvider::Wayland, "Wrong provider given!");
+
+    // V2 | KWindow::get_display_server_connection() and KWindow::get_display_server_window() return valid pointers.
+    assert_ne!(window.get_display_server_connection(), std::ptr::null(), "KWindow Wayland connection pointer error!");
+    assert_ne!(window.get_display_server_window(), std::ptr::null(), "KWindow Wayland window pointer error!");
+
+    // V3 | Window is shown and exposed after first configure.
+    assert!(dispatch_until(&mut window, &mut dispatcher, &events, |e| matches!(e, KEvent::Window(KEventWindow::Shown()))), "Shown expected!");
+    assert!(dispatch_until(&mut window, &mut dispatcher, &events, |e| matches!(e, KEvent::Window(KEventWindow::Exposed(_, _)))), "Exposed expected!");
+
+    // V4 | KWindow::set_title() and KWindow::set_position() don't generate error.
+    window.set_title("*Test window title çéàè*&?%!");
+    window.set_position((100, 100));
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(window.get_title(), "*Test window title çéàè*&?%!", "Wrong title!");
+}
+
+#[test]
+#[ignore = "Wayland compositor needed (ie. weston --backend=headless-backend.so)"]
+/// Resize, fullscreen and restore a Wayland KWindow.
+///
+/// # Verification(s)
+/// V1 | KWindow::set_size() resizes window and sends Resized.
+/// V2 | KWindow::set_fullscreen() sends Fullscreen and KWindow::is_fullscreen() is true.
+/// V3 | KWindow::restore() sends Restored and gives back the size before fullscreen.
+fn kwindow_wayland_resize_fullscreen_restore() {
+    let (mut window, mut dispatcher, _handle, events) = create_wayland(KEventCategories::WINDOW);
+    assert!(dispatch_until(&mut window, &mut dispatcher, &events, |e| matches!(e, KEvent::Window(KEventWindow::Shown()))), "Shown expected!");
+
+    // V1 | KWindow::set_size() resizes window and sends Resized.
+    assert_ok!(window.set_size((400, 300)));
+    assert!(dispatch_until(&mut window, &mut dispatcher, &events, |e| matches!(e, KEvent::Window(KEventWindow::Resized((400, 300))))), "Resized expected!");
+    assert_eq!(window.get_size(), (400, 300), "Wrong size!");
+
+    // V2 | KWindow::set_fullscreen() sends Fullscreen and KWindow::is_fullscreen() is true.
+    window.set_fullscreen(KWindowFullscreenMode::PrimaryScreen);
+    assert!(dispatch_until(&mut window, &mut dispatcher, &events, |e| matches!(e, KEvent::Window(KEventWindow::Fullscreen()))), "Fullscreen expected!");
+    assert!(window.is_fullscreen(), "Window should be fullscreen!");
+
+    // V3 | KWindow::restore() sends Restored and gives back the size before fullscreen.
+    window.restore();
+    assert!(dispatch_until(&mut window, &mut dispatcher, &events, |e| matches!(e, KEvent::Window(KEventWindow::Restored()))), "Restored expected!");
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(!window.is_fullscreen(), "Window shouldn't be fullscreen!");
+    assert_eq!(window.get_size(), (400, 300), "Size before fullscreen expected!");
+}
+
+#[test]
+#[ignore = "Wayland compositor needed (ie. weston --backend=headless-backend.so)"]
+/// Hide, confine and lock cursor of a Wayland KWindow.
+///
+/// # Verification(s)
+/// V1 | KWindow::hide_cursor() and KWindow::show_cursor() change cursor visibility without error.
+/// V2 | KWindow::confine_cursor() and KWindow::release_cursor() change cursor confinement without error.
+/// V3 | KWindow::set_cursor_mode() to Acceleration locks cursor in center of window.
+/// V4 | KWindow::set_cursor_mode() to Pointer unlocks cursor while staying confined.
+fn kwindow_wayland_cursor() {
+    let (mut window, mut dispatcher, _handle, events) = create_wayland(KEventCategories::WINDOW | KEventCategories::MOUSE);
+    assert!(dispatch_until(&mut window, &mut dispatcher, &events, |e| matches!(e, KEvent::Window(KEventWindow::Shown()))), "Shown expected!");
+
+    // V1 | KWindow::hide_cursor() and KWindow::show_cursor() change cursor visibility without error.
+    window.hide_cursor();
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(!window.is_cursor_visible(), "Cursor shouldn't be visible!");
+    window.show_cursor();
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(window.is_cursor_visible(), "Cursor should be visible!");
+
+    // V2 | KWindow::confine_cursor() and KWindow::release_cursor() change cursor confinement without error.
+    window.confine_cursor();
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(window.is_cursor_confined(), "Cursor should be confined!");
+    window.release_cursor();
+    window.dispatch_events(&mut dispatcher, true);
+    assert!(!window.is_cursor_confined(), "Cursor shouldn't be confined!");
+
+    // V3 | KWindow::set_cursor_mode() to Acceleration locks cursor in center of window.
+    window.confine_cursor();
+    window.set_cursor_mode(KCursorMode::Acceleration);
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(window.get_cursor_mode(), KCursorMode::Acceleration, "Wrong cursor mode!");
+    assert_eq!(window.get_cursor_position(), (window.get_size().0 as i32 / 2, window.get_size().1 as i32 / 2), "Cursor should be in center!");
+
+    // V4 | KWindow::set_cursor_mode() to Pointer unlocks cursor while staying confined.
+    window.set_cursor_mode(KCursorMode::Pointer);
+    window.dispatch_events(&mut dispatcher, true);
+    assert_eq!(window.get_cursor_mode(), KCursorMode::Pointer, "Wrong cursor mode!");
+    assert!(window.is_cursor_confined(), "Cursor should stay confined!");
+}
+
+#[test]
+#[ignore = "Wayland compositor needed (ie. weston --backend=headless-backend.so)"]
+/// Create several Wayland KWindow sharing one display context.
+///
+/// # Verification(s)
+/// V1 | KWindow::from_context() creates windows sharing the same connection with different surfaces and ids.
+/// V2 | Each window dispatches its own Shown with its window id.
+/// V3 | KWindow::set_size() on one window sends Resized only to that window.
+fn kwindow_wayland_from_context() {
+    // V1 | KWindow::from_context() creates windows sharing the same connection with different surfaces and ids.
+    let context = assert_ok!(KDisplayContext::new(KLinuxDisplayServerProvider::Wayland));
+    let mut viewport = assert_ok!(KWindow::from_context(&context, KWINDOW_WIDTH, KWINDOW_HEIGHT));
+    let mut palette = assert_ok!(KWindow::from_context(&context, KWINDOW_WIDTH / 2, KWINDOW_HEIGHT));
+    assert_eq!(viewport.get_display_server_connection(), context.get_display_server_connection(), "Viewport should use context connection!");
+    assert_eq!(palette.get_display_server_connection(), context.get_display_server_connection(), "Palette should use context connection!");
+    assert_ne!(viewport.get_display_server_window(), palette.get_display_server_window(), "Windows should have different surfaces!");
+    assert_ne!(viewport.get_id(), palette.get_id(), "Windows should have different ids!");
+
+    let mut dispatcher = KEventDispatcher::new(false);
+    let (_handle, events) = assert_ok!(dispatcher.add_channel_receiver(KEVENT_DEFAULT_LAYER, KEventCategories::WINDOW));
+
+    // V2 | Each window dispatches its own Shown with its window id.
+    let id = viewport.get_id();
+    assert!(dispatch_entry_until(&mut viewport, &mut dispatcher, &events, |e| e.get_window_id() == id && matches!(e.get_event(), KEvent::Window(KEventWindow::Shown()))), "Viewport Shown expected!");
+    let id = palette.get_id();
+    assert!(dispatch_entry_until(&mut palette, &mut dispatcher, &events, |e| e.get_window_id() == id && matches!(e.get_event(), KEvent::Window(KEventWindow::Shown()))), "Palette Shown expected!");
+    viewport.dispatch_events(&mut dispatcher, true);
+    palette.dispatch_events(&mut dispatcher, true);
+    events.try_iter().for_each(drop);
+
+    // V3 | KWindow::set_size() on one window sends Resized only to that window.
+    assert_ok!(palette.set_size((200, 300)));
+    let id = palette.get_id();
+    assert!(dispatch_entry_until(&mut palette, &mut dispatcher, &events, |e| e.get_window_id() == id && matches!(e.get_event(), KEvent::Window(KEventWindow::Resized((200, 300))))), "Palette Resized expected!");
+    viewport.dispatch_events(&mut dispatcher, true);
+    assert!(!events.try_iter().any(|e| matches!(e.get_event(), KEvent::Window(KEventWindow::Resized(_)))), "Viewport shouldn't be resized!");
+    assert_eq!(viewport.get_size(), (KWINDOW_WIDTH, KWINDOW_HEIGHT), "Wrong viewport size!");
+    assert_eq!(palette.get_size(), (200, 300), "Wrong palette size!");
+}
+
+/************
+* FUNCTIONS *
+************/
+/// Create a Wayland KWindow with a dispatcher and a channel receiving events of categories.
+fn create_wayland(categories : KEventCategories) -> (KWindow, KEventDispatcher, KEventReceiverHandle, Receiver<KEventEntry>) {
+    let window = assert_ok!(KWindow::new(KWINDOW_WIDTH, KWINDOW_HEIGHT, KLinuxDisplayServerProvider::Wayland));
+    let mut dispatcher = KEventDispatcher::new(false);
+    let (handle, events) = assert_ok!(dispatcher.add_channel_receiver(KEVENT_DEFAULT_LAYER, categories));
+
+    (window, dispatcher, handle, events)
+}
+
+/// Dispatch events until an event matches predicate. Returns False if compositor didn't send it before timeout.
+fn dispatch_until(window : &mut KWindow, dispatcher : &mut KEventDispatcher, events : &Receiver<KEventEntry>, predicate : impl Fn(&KEvent) -> bool) -> bool {
+    dispatch_entry_until(window, dispatcher, events, |entry| predicate(entry.get_event()))
+}
+
+/// Dispatch events until an entry matches predicate. Returns False if compositor didn't send it before timeout.
+fn dispatch_entry_until(window : &mut KWindow, dispatcher : &mut KEventDispatcher, events : &Receiver<KEventEntry>, predicate : impl Fn(&KEventEntry) -> bool) -> bool {
+    let timeout = Instant::now() + KWAYLAND_TIMEOUT;
+
+    while Instant::now() < timeout {
+        window.dispatch_events(dispatcher, true);
+        if events.try_iter().any(|entry| predicate(&entry)) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
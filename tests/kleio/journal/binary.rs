@@ -0,0 +1,255 @@
+use std::{path::PathBuf, fs::File, io::Cursor, time::{Duration, UNIX_EPOCH}, process::Command};
+use olympus::kleio::journal::{ KJournal, KJournalEntrySeverity, KJournalSeveritySet, KJournalListenerBinary, KJournalBinaryReader, KJournalBinaryError, KJournalRecord, KJournalFilter, KJournalThrottle, KJOURNAL_BUFFER_MIN, binary::{merge_records, merge_updates, KJOURNAL_BINARY_MAGIC, KJOURNAL_BINARY_VERSION, KJOURNAL_BINARY_RECORD_MAX} };
+
+use crate::{assert_err, assert_ok};
+
+/********
+* TESTS *
+********/
+#[test]
+/// Write entries with KJournalListenerBinary and read them back with KJournalBinaryReader.
+/// 
+/// # Verification(s)
+/// V1 | KJournalListenerBinary::create() create binary journal without error.
+/// V2 | Entries written in KJournal are written in binary journal according to listener severity.
+/// V3 | KJournalBinaryReader::new() read header without error.
+/// V4 | Records read have the same severity, target, description and fields as entries.
+fn kjournal_binary_write_read() {
+    let path = temp_path("kjournal_binary_write_read.kjnl");
+
+    {
+        // V1 | KJournalListenerBinary::create() create binary journal without error.
+        let l = assert_ok!(KJournalListenerBinary::create(&path, KJournalSeveritySet::ALL_NO_DEBUG));
+        let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+        assert!(j.add_listener(&l).is_ok(), "add_listener error!");
+
+        // V2 | Entries written in KJournal are written in binary journal according to listener severity.
+        j.write(KJournalEntrySeverity::Debug, "Debug entry");
+        j.write(KJournalEntrySeverity::Warning, "Warning entry");
+        j.write_fields("kleio::display", KJournalEntrySeverity::Information, "Window \"created\"", &[("width", "320"), ("height", "240")]);
+        assert!(l.take_error().is_none(), "Binary journal write error!");
+    }
+
+    // V3 | KJournalBinaryReader::new() read header without error.
+    let reader = assert_ok!(KJournalBinaryReader::new(assert_ok!(File::open(&path))));
+    assert_eq!(reader.get_version(), KJOURNAL_BINARY_VERSION, "Binary journal version error!");
+
+    // V4 | Records read have the same severity, target, description and fields as entries.
+    let records : Vec<KJournalRecord> = reader.map(|r| assert_ok!(r)).collect();
+    assert_eq!(records.len(), 2, "Debug entry shouldn't be written!");
+    assert_eq!(records[0].severity, KJournalEntrySeverity::Warning, "Record severity error!");
+    assert_eq!(records[0].description, "Warning entry", "Record description error!");
+    assert_eq!(records[1].target, "kleio::display", "Record target error!");
+    assert_eq!(records[1].fields, vec![("width".to_string(), "320".to_string()), ("height".to_string(), "240".to_string())], "Record fields error!");
+    assert_eq!(records[1].repeat_count, 1, "Record repeat count error!");
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+/// Read invalid binary journals.
+/// 
+/// # Verification(s)
+/// V1 | KJournalBinaryReader::new() with wrong magic returns Err(KJournalBinaryError::InvalidHeader).
+/// V2 | KJournalBinaryReader::new() with newer version returns Err(KJournalBinaryError::UnsupportedVersion).
+/// V3 | Truncated record returns Err(KJournalBinaryError::CorruptedRecord) after valid records.
+/// V4 | Extra bytes at the end of a record payload are ignored.
+fn kjournal_binary_read_invalid() {
+    // V1 | KJournalBinaryReader::new() with wrong magic returns Err(KJournalBinaryError::InvalidHeader).
+    assert_err!(KJournalBinaryReader::new(Cursor::new(b"NOPE\x01\x00".to_vec())), KJournalBinaryError::InvalidHeader);
+    assert_err!(KJournalBinaryReader::new(Cursor::new(b"KJ".to_vec())), KJournalBinaryError::InvalidHeader);
+
+    // V2 | KJournalBinaryReader::new() with newer version returns Err(KJournalBinaryError::UnsupportedVersion).
+    let mut data = KJOURNAL_BINARY_MAGIC.to_vec();
+    data.extend_from_slice(&(KJOURNAL_BINARY_VERSION + 1).to_le_bytes());
+    assert_err!(KJournalBinaryReader::new(Cursor::new(data)), KJournalBinaryError::UnsupportedVersion);
+
+    // V3 | Truncated record returns Err(KJournalBinaryError::CorruptedRecord) after valid records.
+    let record = create_record(KJournalEntrySeverity::Error, "kleio", "Error", 10);
+    let mut data = header();
+    data.extend(assert_ok!(record.encode()));
+    data.extend(&assert_ok!(record.encode())[0..10]);
+    let mut reader = assert_ok!(KJournalBinaryReader::new(Cursor::new(data)));
+    assert_eq!(assert_ok!(reader.next().unwrap()), record, "First record error!");
+    assert!(matches!(reader.next(), Some(Err(KJournalBinaryError::CorruptedRecord))), "Truncated record should be corrupted!");
+    assert!(reader.next().is_none(), "Reader should stop after error!");
+
+    // V4 | Extra bytes at the end of a record payload are ignored.
+    let mut encoded = assert_ok!(record.encode());
+    encoded.extend_from_slice(&[1, 2, 3]);
+    let length = (encoded.len() - 4) as u32;
+    encoded[0..4].copy_from_slice(&length.to_le_bytes());
+    let mut data = header();
+    data.extend(encoded);
+    let mut reader = assert_ok!(KJournalBinaryReader::new(Cursor::new(data)));
+    assert_eq!(assert_ok!(reader.next().unwrap()), record, "Record with extra bytes error!");
+    assert!(reader.next().is_none(), "Reader should be at end!");
+}
+
+#[test]
+/// Write entries too large for a binary journal.
+/// 
+/// # Verification(s)
+/// V1 | KJournalRecord::encode() of a payload bigger than KJOURNAL_BINARY_RECORD_MAX returns Err(KJournalBinaryError::RecordTooLarge).
+/// V2 | KJournalListenerBinary skips oversized entry and reports KJournalBinaryError::RecordTooLarge.
+/// V3 | Records written before and after oversized entry are read back.
+fn kjournal_binary_record_too_large() {
+    let path = temp_path("kjournal_binary_record_too_large.kjnl");
+    let description = "X".repeat(KJOURNAL_BINARY_RECORD_MAX as usize);
+
+    // V1 | KJournalRecord::encode() of a payload bigger than KJOURNAL_BINARY_RECORD_MAX returns Err(KJournalBinaryError::RecordTooLarge).
+    assert_err!(create_record(KJournalEntrySeverity::Error, "kleio", &description, 1).encode(), KJournalBinaryError::RecordTooLarge);
+
+    {
+        // V2 | KJournalListenerBinary skips oversized entry and reports KJournalBinaryError::RecordTooLarge.
+        let l = assert_ok!(KJournalListenerBinary::create(&path, KJournalSeveritySet::ALL_WITH_DEBUG));
+        let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+        assert!(j.add_listener(&l).is_ok(), "add_listener error!");
+
+        j.write(KJournalEntrySeverity::Warning, "Before");
+        j.write(KJournalEntrySeverity::Warning, &description);
+        assert_eq!(l.take_error(), Some(KJournalBinaryError::RecordTooLarge), "Oversized entry should be reported!");
+        j.write(KJournalEntrySeverity::Warning, "After");
+        assert!(l.take_error().is_none(), "Binary journal write error!");
+    }
+
+    // V3 | Records written before and after oversized entry are read back.
+    let records : Vec<KJournalRecord> = assert_ok!(KJournalBinaryReader::new(assert_ok!(File::open(&path)))).map(|r| assert_ok!(r)).collect();
+    let descriptions : Vec<&str> = records.iter().map(|r| r.description.as_str()).collect();
+    assert_eq!(descriptions, vec!["Before", "After"], "Records read error!");
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+/// Merge, filter and convert KJournalRecord.
+/// 
+/// # Verification(s)
+/// V1 | merge_records() order records of multiple journals by date and time.
+/// V2 | KJournalRecord::is_enabled() filter records with KJournalFilter.
+/// V3 | KJournalRecord::to_text() gives severity symbol, target, description and fields.
+/// V4 | KJournalRecord::to_json() escape strings.
+fn kjournal_binary_merge_filter_convert() {
+    // V1 | merge_records() order records of multiple journals by date and time.
+    let j1 = vec![create_record(KJournalEntrySeverity::Error, "a", "1", 1), create_record(KJournalEntrySeverity::Debug, "a", "3", 3)];
+    let j2 = vec![create_record(KJournalEntrySeverity::Warning, "b", "2", 2), create_record(KJournalEntrySeverity::Information, "b", "4", 4)];
+    let merged = merge_records(vec![j1, j2]);
+    let order : Vec<&str> = merged.iter().map(|r| r.description.as_str()).collect();
+    assert_eq!(order, vec!["1", "2", "3", "4"], "Merged order error!");
+
+    // V2 | KJournalRecord::is_enabled() filter records with KJournalFilter.
+    let filter = assert_ok!(KJournalFilter::parse("warning, b=debug"));
+    let filtered : Vec<&str> = merged.iter().filter(|r| r.is_enabled(&filter)).map(|r| r.description.as_str()).collect();
+    assert_eq!(filtered, vec!["1", "2", "4"], "Filtered records error!");
+
+    // V3 | KJournalRecord::to_text() gives severity symbol, target, description and fields.
+    let mut record = create_record(KJournalEntrySeverity::Warning, "kleio", "Text \"entry\"\n", 5);
+    record.fields.push(("key".to_string(), "value".to_string()));
+    assert_eq!(record.to_text(), "[W 5.000000] kleio : Text \"entry\"\n {key=value}", "Text conversion error!");
+
+    // V4 | KJournalRecord::to_json() escape strings.
+    assert_eq!(record.to_json(), "{\"severity\":\"WARNING\",\"first_time\":5.000000,\"time\":5.000000,\"repeat\":1,\"target\":\"kleio\",\"description\":\"Text \\\"entry\\\"\\n\",\"fields\":{\"key\":\"value\"}}", "JSON conversion error!");
+}
+
+#[test]
+/// Merge updates of collapsed duplicates written by KJournalListenerBinary.
+/// 
+/// # Verification(s)
+/// V1 | Collapsed duplicates are written as first occurrence followed by an update with total repeat count.
+/// V2 | merge_updates() keep each collapsed entry once with its total repeat count.
+/// V3 | merge_records() merge updates of each journal.
+/// V4 | merge_updates() keep updates without earlier record.
+fn kjournal_binary_merge_updates() {
+    let path = temp_path("kjournal_binary_merge_updates.kjnl");
+
+    {
+        let l = assert_ok!(KJournalListenerBinary::create(&path, KJournalSeveritySet::ALL_WITH_DEBUG));
+        let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+        j.set_throttle(KJournalThrottle::new(true, None));
+        assert!(j.add_listener(&l).is_ok(), "add_listener error!");
+
+        for _ in 0..100 {
+            j.write(KJournalEntrySeverity::Warning, "Flood");
+        }
+        j.write(KJournalEntrySeverity::Warning, "Other");
+    }
+
+    // V1 | Collapsed duplicates are written as first occurrence followed by an update with total repeat count.
+    let records : Vec<KJournalRecord> = assert_ok!(KJournalBinaryReader::new(assert_ok!(File::open(&path)))).map(|r| assert_ok!(r)).collect();
+    let counts : Vec<(u32, bool)> = records.iter().map(|r| (r.repeat_count, r.is_update())).collect();
+    assert_eq!(counts, vec![(1, false), (100, true), (1, false)], "Records written error!");
+    assert!(records[1].is_update_of(&records[0]), "Second record should update the first!");
+
+    // V2 | merge_updates() keep each collapsed entry once with its total repeat count.
+    let merged = merge_updates(records.clone());
+    let counts : Vec<(&str, u32)> = merged.iter().map(|r| (r.description.as_str(), r.repeat_count)).collect();
+    assert_eq!(counts, vec![("Flood", 100), ("Other", 1)], "Merged updates error!");
+
+    // V3 | merge_records() merge updates of each journal.
+    assert_eq!(merge_records(vec![records.clone()]).iter().map(|r| r.repeat_count).sum::<u32>(), 101, "Total repeat count error!");
+
+    // V4 | merge_updates() keep updates without earlier record.
+    assert_eq!(merge_updates(records[1..].to_vec()).len(), 2, "Update without earlier record should be kept!");
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+/// Read binary journals with the olympus_journal command line tool.
+/// 
+/// # Verification(s)
+/// V1 | olympus_journal without journal exits with error.
+/// V2 | olympus_journal merge and filter multiple journals as JSON lines.
+fn kjournal_binary_cli() {
+    let exe = env!("CARGO_BIN_EXE_olympus_journal");
+
+    // V1 | olympus_journal without journal exits with error.
+    let output = assert_ok!(Command::new(exe).output());
+    assert!(!output.status.success(), "olympus_journal without journal should fail!");
+
+    // V2 | olympus_journal merge and filter multiple journals as JSON lines.
+    let p1 = temp_path("kjournal_binary_cli_1.kjnl");
+    let p2 = temp_path("kjournal_binary_cli_2.kjnl");
+    write_journal(&p1, &[create_record(KJournalEntrySeverity::Error, "a", "1", 1), create_record(KJournalEntrySeverity::Debug, "a", "3", 3)]);
+    write_journal(&p2, &[create_record(KJournalEntrySeverity::Warning, "b", "2", 2)]);
+
+    let output = assert_ok!(Command::new(exe).args(["--json", "--filter", "warning"]).arg(&p1).arg(&p2).output());
+    assert!(output.status.success(), "olympus_journal should succeed!");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines : Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "olympus_journal should print 2 records!");
+    assert!(lines[0].contains("\"description\":\"1\"") && lines[1].contains("\"description\":\"2\""), "olympus_journal order error!");
+
+    let _ = std::fs::remove_file(&p1);
+    let _ = std::fs::remove_file(&p2);
+}
+
+/************
+* FUNCTIONS * 
+************/
+/// Returns a path in temporary folder.
+fn temp_path(name : &str) -> PathBuf {
+    std::env::temp_dir().join(name)
+}
+
+/// Returns a binary journal header.
+fn header() -> Vec<u8> {
+    let mut data = KJOURNAL_BINARY_MAGIC.to_vec();
+    data.extend_from_slice(&KJOURNAL_BINARY_VERSION.to_le_bytes());
+    data
+}
+
+/// Create a record at given seconds since UNIX epoch.
+fn create_record(severity : KJournalEntrySeverity, target : &str, description : &str, secs : u64) -> KJournalRecord {
+    let time = UNIX_EPOCH + Duration::from_secs(secs);
+    KJournalRecord { severity, first_date_time: time, date_time: time, repeat_count: 1, target: target.to_string(), description: description.to_string(), fields: Vec::new(), span: None }
+}
+
+/// Write records into a binary journal file.
+fn write_journal(path : &PathBuf, records : &[KJournalRecord]) {
+    let mut data = header();
+    for record in records {
+        data.extend(assert_ok!(record.encode()));
+    }
+    assert_ok!(std::fs::write(path, data));
+}
@@ -110,29 +110,29 @@ fn kjournal_buffer_write() {
     let mut kb = KJournalBuffer::new(buffer_size).unwrap();
 
     // V1 | KJournalBuffer::write() write an entry into buffer without error.
-    kb.write(KJournalEntrySeverity::DEBUG, "Debug entry");
+    kb.write(KJournalEntrySeverity::Debug, "Debug entry");
 
     // V2 | Entry created is verified.
-    verify_journal_entry(kb.latest(), KJournalEntrySeverity::DEBUG, &"Debug entry".to_owned());
+    verify_journal_entry(kb.latest(), KJournalEntrySeverity::Debug, &"Debug entry".to_owned());
 
     // V3 | Write multiple different entries without error.
     // V4 | Write more entries that buffer MAX without error.
     for _ in 0..(KJOURNAL_BUFFER_MAX + 5) {
-        kb.write(KJournalEntrySeverity::DEBUG, "Debug entry");
-        kb.write(KJournalEntrySeverity::OTHER, "Other entry");
-        kb.write(KJournalEntrySeverity::INFORMATION, "Information entry");
-        kb.write(KJournalEntrySeverity::WARNING, "Warning entry");
-        kb.write(KJournalEntrySeverity::ERROR, "Error entry");
-        kb.write(KJournalEntrySeverity::FATAL, "Fatal entry");
+        kb.write(KJournalEntrySeverity::Debug, "Debug entry");
+        kb.write(KJournalEntrySeverity::Other, "Other entry");
+        kb.write(KJournalEntrySeverity::Information, "Information entry");
+        kb.write(KJournalEntrySeverity::Warning, "Warning entry");
+        kb.write(KJournalEntrySeverity::Error, "Error entry");
+        kb.write(KJournalEntrySeverity::Fatal, "Fatal entry");
     }
 
     // V5 | Retrieve and verify entries in correct order. (latest to oldest)
-    verify_journal_entry(kb.latest(), KJournalEntrySeverity::FATAL, "Fatal entry");
-    verify_journal_entry(kb.latest(), KJournalEntrySeverity::ERROR, "Error entry");
-    verify_journal_entry(kb.latest(), KJournalEntrySeverity::WARNING, "Warning entry");
-    verify_journal_entry(kb.latest(), KJournalEntrySeverity::INFORMATION, "Information entry");
-    verify_journal_entry(kb.latest(), KJournalEntrySeverity::OTHER, "Other entry");
-    verify_journal_entry(kb.latest(), KJournalEntrySeverity::DEBUG, "Debug entry");
+    verify_journal_entry(kb.latest(), KJournalEntrySeverity::Fatal, "Fatal entry");
+    verify_journal_entry(kb.latest(), KJournalEntrySeverity::Error, "Error entry");
+    verify_journal_entry(kb.latest(), KJournalEntrySeverity::Warning, "Warning entry");
+    verify_journal_entry(kb.latest(), KJournalEntrySeverity::Information, "Information entry");
+    verify_journal_entry(kb.latest(), KJournalEntrySeverity::Other, "Other entry");
+    verify_journal_entry(kb.latest(), KJournalEntrySeverity::Debug, "Debug entry");
 
 }
 
@@ -158,7 +158,7 @@ fn kjournal_buffer_unread() {
     assert!(kb.unread() == 0, "Unread ({}) count incorrect! Should be {}!", kb.unread(), 0);
 
     // Write debug entry
-    kb.write(KJournalEntrySeverity::DEBUG, "Debug entry");
+    kb.write(KJournalEntrySeverity::Debug, "Debug entry");
 
     // V2 | KJournalBuffer::unread() size must be 1 after writing an entry.
     assert!(kb.unread() == 1, "Unread ({}) count incorrect! Should be {}!", kb.unread(), 1);
@@ -171,12 +171,12 @@ fn kjournal_buffer_unread() {
 
     // Write a bunch of entries.
     for _ in 0..(KJOURNAL_BUFFER_MAX + 5) {
-        kb.write(KJournalEntrySeverity::DEBUG, "Debug entry");
-        kb.write(KJournalEntrySeverity::OTHER, "Other entry");
-        kb.write(KJournalEntrySeverity::INFORMATION, "Information entry");
-        kb.write(KJournalEntrySeverity::WARNING, "Warning entry");
-        kb.write(KJournalEntrySeverity::ERROR, "Error entry");
-        kb.write(KJournalEntrySeverity::FATAL, "Fatal entry");
+        kb.write(KJournalEntrySeverity::Debug, "Debug entry");
+        kb.write(KJournalEntrySeverity::Other, "Other entry");
+        kb.write(KJournalEntrySeverity::Information, "Information entry");
+        kb.write(KJournalEntrySeverity::Warning, "Warning entry");
+        kb.write(KJournalEntrySeverity::Error, "Error entry");
+        kb.write(KJournalEntrySeverity::Fatal, "Fatal entry");
 
     }
 
@@ -234,20 +234,20 @@ fn kjournal_buffer_stress() {
         assert!(kb.unread() == 0, "Unread ({}) count incorrect! Should be {}!", kb.unread(), 0);
 
         // V3 | Write an entry into buffer and verify size is 1.
-        kb.write(KJournalEntrySeverity::DEBUG, "Debug entry");
+        kb.write(KJournalEntrySeverity::Debug, "Debug entry");
         assert!(kb.unread() == 1, "Unread ({}) count incorrect! Should be {}!", kb.unread(), 1);
 
         // V4 | Verify that entry values are correct.
-        verify_journal_entry(kb.latest(), KJournalEntrySeverity::DEBUG, &"Debug entry".to_owned());
+        verify_journal_entry(kb.latest(), KJournalEntrySeverity::Debug, &"Debug entry".to_owned());
 
         // V5 | Fill buffer with entries.
         for _ in 0..buffer_size + 5{
-            kb.write(KJournalEntrySeverity::DEBUG, "Debug entry");
-            kb.write(KJournalEntrySeverity::OTHER, "Other entry");
-            kb.write(KJournalEntrySeverity::INFORMATION, "Information entry");
-            kb.write(KJournalEntrySeverity::WARNING, "Warning entry");
-            kb.write(KJournalEntrySeverity::ERROR, "Error entry");
-            kb.write(KJournalEntrySeverity::FATAL, "Fatal entry");
+            kb.write(KJournalEntrySeverity::Debug, "Debug entry");
+            kb.write(KJournalEntrySeverity::Other, "Other entry");
+            kb.write(KJournalEntrySeverity::Information, "Information entry");
+            kb.write(KJournalEntrySeverity::Warning, "Warning entry");
+            kb.write(KJournalEntrySeverity::Error, "Error entry");
+            kb.write(KJournalEntrySeverity::Fatal, "Fatal entry");
 
         }
 
@@ -255,12 +255,12 @@ fn kjournal_buffer_stress() {
         assert!(kb.unread() == buffer_size, "Unread ({}) count incorrect! Should be {}!", kb.unread(), buffer_size);
 
         // V7 | Verify 6 latest entries.
-        verify_journal_entry(kb.latest(), KJournalEntrySeverity::FATAL, &"Fatal entry".to_owned());
-        verify_journal_entry(kb.latest(), KJournalEntrySeverity::ERROR, &"Error entry".to_owned());
-        verify_journal_entry(kb.latest(), KJournalEntrySeverity::WARNING, &"Warning entry".to_owned());
-        verify_journal_entry(kb.latest(), KJournalEntrySeverity::INFORMATION, &"Information entry".to_owned());
-        verify_journal_entry(kb.latest(), KJournalEntrySeverity::OTHER, &"Other entry".to_owned());
-        verify_journal_entry(kb.latest(), KJournalEntrySeverity::DEBUG, &"Debug entry".to_owned());
+        verify_journal_entry(kb.latest(), KJournalEntrySeverity::Fatal, &"Fatal entry".to_owned());
+        verify_journal_entry(kb.latest(), KJournalEntrySeverity::Error, &"Error entry".to_owned());
+        verify_journal_entry(kb.latest(), KJournalEntrySeverity::Warning, &"Warning entry".to_owned());
+        verify_journal_entry(kb.latest(), KJournalEntrySeverity::Information, &"Information entry".to_owned());
+        verify_journal_entry(kb.latest(), KJournalEntrySeverity::Other, &"Other entry".to_owned());
+        verify_journal_entry(kb.latest(), KJournalEntrySeverity::Debug, &"Debug entry".to_owned());
 
         // V8 | Verify size() is equal to buffer_size - 6.
         assert!(kb.unread() == buffer_size - 6, "Unread ({}) count incorrect! Should be {}!", kb.unread(), buffer_size);
@@ -286,7 +286,7 @@ fn kjournal_buffer_stress() {
 /// 
 /// # Panic
 /// Will panic if any entry parameters are wrong.
-fn verify_journal_entry(entry: Option<&KJournalEntry>, severity:u8, desc : &str){
+fn verify_journal_entry(entry: Option<&KJournalEntry>, severity : KJournalEntrySeverity, desc : &str){
 
     match entry {
         Some(entry) => {
@@ -10,7 +10,7 @@ use olympus::kleio::journal::{KJournalEntry, KJournalEntrySeverity};
 /// V3 | Verify that entry date is valid.
 fn kjournal_entry_new() {
     // Initial variables for comparison.
-    let severity = KJournalEntrySeverity::INFORMATION;
+    let severity = KJournalEntrySeverity::Information;
     let description = "Entry kjournal_entry_new";
 
     // V1 | KJournalEntry::new() create an entry without error.
@@ -38,7 +38,7 @@ fn kjournal_entry_new() {
 /// V3 | Verify that entry date is valid.
 fn kjournal_entry_update() {
     // Initial variables for comparison.
-    let severity = KJournalEntrySeverity::INFORMATION;
+    let severity = KJournalEntrySeverity::Information;
     let description = "Entry kjournal_entry_new";
 
     // Create new entry.
@@ -51,7 +51,7 @@ fn kjournal_entry_update() {
     sleep(Duration::new(1, 0));
 
     // Update value and entry
-    let severity = KJournalEntrySeverity::ERROR;
+    let severity = KJournalEntrySeverity::Error;
     let description = "Entry updated";
 
     // V1 | KJournalEntry::update() update values without error.
@@ -69,3 +69,30 @@ fn kjournal_entry_update() {
     }
 
 }
+
+#[test]
+/// Repeat a KJournalEntry.
+/// 
+/// # Verification(s)
+/// V1 | New entry repeat count is 1 and first date and time equals date and time.
+/// V2 | KJournalEntry::repeat() increment repeat count and keep first date and time.
+/// V3 | KJournalEntry::update() reset repeat count.
+fn kjournal_entry_repeat() {
+    let mut j = KJournalEntry::new(KJournalEntrySeverity::Warning, "Repeated entry");
+
+    // V1 | New entry repeat count is 1 and first date and time equals date and time.
+    assert_eq!(j.get_repeat_count(), 1, "Error! New entry repeat count should be 1!");
+    assert_eq!(j.get_first_date_time(), j.get_date_time(), "Error! First date and time should be date and time!");
+    let first = j.get_first_date_time();
+
+    // V2 | KJournalEntry::repeat() increment repeat count and keep first date and time.
+    sleep(Duration::from_millis(10));
+    j.repeat();
+    assert_eq!(j.get_repeat_count(), 2, "Error! Repeat count should be 2!");
+    assert_eq!(j.get_first_date_time(), first, "Error! First date and time shouldn't change!");
+    assert!(j.get_date_time() > first, "Error! Last date and time should be updated!");
+
+    // V3 | KJournalEntry::update() reset repeat count.
+    j.update(KJournalEntrySeverity::Error, "Updated entry");
+    assert_eq!(j.get_repeat_count(), 1, "Error! Updated entry repeat count should be 1!");
+}
@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+use olympus::kleio::journal::{ KJournalFilter, KJournalFilterError, KJournalEntrySeverity, KJournalSeveritySet };
+
+use crate::{assert_err, assert_ok};
+
+/*********
+* CONSTS *
+*********/
+/// Severity flags of WARNING and above.
+const WARNING_AND_ABOVE : KJournalSeveritySet = KJournalSeveritySet::at_least(KJournalEntrySeverity::Warning);
+
+/********
+* TESTS *
+********/
+#[test]
+/// Create a new KJournalFilter and verify default severity.
+/// 
+/// # Verification(s)
+/// V1 | KJournalFilter::new() create a filter without error.
+/// V2 | KJournalFilter::get_severity() returns severity given.
+/// V3 | KJournalFilter::get_target_severity() returns default severity for any target.
+fn kjournal_filter_new() {
+    // V1 | KJournalFilter::new() create a filter without error.
+    let f = KJournalFilter::new(KJournalSeveritySet::ALL_NO_DEBUG);
+
+    // V2 | KJournalFilter::get_severity() returns severity given.
+    assert_eq!(f.get_severity(), KJournalSeveritySet::ALL_NO_DEBUG, "Default severity error!");
+
+    // V3 | KJournalFilter::get_target_severity() returns default severity for any target.
+    assert_eq!(f.get_target_severity(""), KJournalSeveritySet::ALL_NO_DEBUG, "Empty target severity error!");
+    assert_eq!(f.get_target_severity("kleio::display"), KJournalSeveritySet::ALL_NO_DEBUG, "Target severity error!");
+}
+
+#[test]
+/// Set and remove target overrides and verify hierarchical matching.
+/// 
+/// # Verification(s)
+/// V1 | Override of parent target applies to children.
+/// V2 | Most specific override wins.
+/// V3 | Target sharing a prefix without separator is not matched.
+/// V4 | KJournalFilter::remove_target_severity() returns true for existing override and false otherwise.
+fn kjournal_filter_target_severity() {
+    let mut f = KJournalFilter::new(KJournalEntrySeverity::Error);
+    f.set_target_severity("kleio::display", KJournalEntrySeverity::Warning);
+    f.set_target_severity("kleio::display::x11", KJournalEntrySeverity::Debug);
+
+    // V1 | Override of parent target applies to children.
+    assert_eq!(f.get_target_severity("kleio::display::wayland"), KJournalSeveritySet::from(KJournalEntrySeverity::Warning), "Parent override error!");
+
+    // V2 | Most specific override wins.
+    assert_eq!(f.get_target_severity("kleio::display::x11::event"), KJournalSeveritySet::from(KJournalEntrySeverity::Debug), "Specific override error!");
+    assert!(f.is_enabled("kleio::display::x11", KJournalEntrySeverity::Debug), "Debug should be enabled for x11!");
+    assert!(!f.is_enabled("kleio::display", KJournalEntrySeverity::Debug), "Debug should be disabled for display!");
+
+    // V3 | Target sharing a prefix without separator is not matched.
+    assert_eq!(f.get_target_severity("kleio::displays"), KJournalSeveritySet::from(KJournalEntrySeverity::Error), "Prefix without separator shouldn't match!");
+
+    // V4 | KJournalFilter::remove_target_severity() returns true for existing override and false otherwise.
+    assert!(f.remove_target_severity("kleio::display::x11"), "Override should be removed!");
+    assert!(!f.remove_target_severity("kleio::display::x11"), "Override shouldn't exists!");
+    assert_eq!(f.get_target_severity("kleio::display::x11"), KJournalSeveritySet::from(KJournalEntrySeverity::Warning), "Removed override still applied!");
+}
+
+#[test]
+/// Parse KJournalFilter directives.
+/// 
+/// # Verification(s)
+/// V1 | Empty directives gives ALL_WITH_DEBUG default severity.
+/// V2 | Severity names and aliases are parsed as severity and above.
+/// V3 | Raw number severity is parsed as flags.
+/// V4 | Invalid severity returns Err(KJournalFilterError::InvalidSeverity).
+/// V5 | Invalid target returns Err(KJournalFilterError::InvalidTarget).
+fn kjournal_filter_parse() {
+    // V1 | Empty directives gives ALL_WITH_DEBUG default severity.
+    let f = assert_ok!(KJournalFilter::parse(""));
+    assert_eq!(f.get_severity(), KJournalSeveritySet::ALL_WITH_DEBUG, "Empty directives severity error!");
+
+    // V2 | Severity names and aliases are parsed as severity and above.
+    let f = assert_ok!(KJournalFilter::parse(" WARN , kleio::asset = fatal, kleio::display=off"));
+    assert_eq!(f.get_severity(), WARNING_AND_ABOVE, "Default severity error!");
+    assert_eq!(f.get_target_severity("kleio::asset::broker"), KJournalSeveritySet::from(KJournalEntrySeverity::Fatal), "kleio::asset severity error!");
+    assert_eq!(f.get_target_severity("kleio::display"), KJournalSeveritySet::NONE, "kleio::display severity error!");
+
+    // V3 | Raw number severity is parsed as flags.
+    let f = assert_ok!(KJournalFilter::parse("kleio=5"));
+    assert_eq!(f.get_target_severity("kleio"), KJournalEntrySeverity::Debug | KJournalEntrySeverity::Information, "Raw severity error!");
+
+    // V4 | Invalid severity returns Err(KJournalFilterError::InvalidSeverity).
+    assert_err!(KJournalFilter::parse("kleio=loud"), KJournalFilterError::InvalidSeverity);
+    assert_err!(KJournalFilter::parse("kleio=256"), KJournalFilterError::InvalidSeverity);
+
+    // V5 | Invalid target returns Err(KJournalFilterError::InvalidTarget).
+    assert_err!(KJournalFilter::parse("=debug"), KJournalFilterError::InvalidTarget);
+    assert_err!(KJournalFilter::parse("kleio::::x11=debug"), KJournalFilterError::InvalidTarget);
+}
+
+#[test]
+/// Parse KJournalFilter from environment variable and file.
+/// 
+/// # Verification(s)
+/// V1 | KJournalFilter::from_env() with unset variable returns Err(KJournalFilterError::EnvironmentVariableNotFound).
+/// V2 | KJournalFilter::from_env() parse variable value.
+/// V3 | KJournalFilter::from_file() with missing file returns Err(KJournalFilterError::FileReadError).
+/// V4 | KJournalFilter::from_file() parse one directive per line and ignore comments.
+fn kjournal_filter_from_env_file() {
+    // V1 | KJournalFilter::from_env() with unset variable returns Err(KJournalFilterError::EnvironmentVariableNotFound).
+    assert_err!(KJournalFilter::from_env("OLYMPUS_JOURNAL_TEST_UNSET"), KJournalFilterError::EnvironmentVariableNotFound);
+
+    // V2 | KJournalFilter::from_env() parse variable value.
+    std::env::set_var("OLYMPUS_JOURNAL_TEST_SET", "error,kleio::display::x11=debug");
+    let f = assert_ok!(KJournalFilter::from_env("OLYMPUS_JOURNAL_TEST_SET"));
+    assert_eq!(f.get_severity(), KJournalEntrySeverity::Error | KJournalEntrySeverity::Fatal, "Environment default severity error!");
+    assert_eq!(f.get_target_severity("kleio::display::x11"), KJournalSeveritySet::ALL_WITH_DEBUG, "Environment target severity error!");
+
+    // V3 | KJournalFilter::from_file() with missing file returns Err(KJournalFilterError::FileReadError).
+    assert_err!(KJournalFilter::from_file(&PathBuf::from("kjournal_filter_missing.conf")), KJournalFilterError::FileReadError);
+
+    // V4 | KJournalFilter::from_file() parse one directive per line and ignore comments.
+    let path = std::env::temp_dir().join("kjournal_filter_from_file.conf");
+    assert_ok!(std::fs::write(&path, "# Journal filter\ninformation\nkleio::asset=warning\n"));
+    let f = assert_ok!(KJournalFilter::from_file(&path));
+    assert_eq!(f.get_target_severity("kleio::asset"), WARNING_AND_ABOVE, "File target severity error!");
+    assert_eq!(f.get_severity(), WARNING_AND_ABOVE | KJournalEntrySeverity::Information, "File default severity error!");
+    let _ = std::fs::remove_file(&path);
+}
@@ -1,5 +1,6 @@
-use std::{cell::RefCell, rc::Rc};
-use olympus::kleio::journal::{ KJournal, KJournalEntrySeverity, KJOURNAL_BUFFER_MIN, KJOURNAL_BUFFER_MAX, KJournalEntry, KJournalListener, listener::KJournalListenerListError };
+use std::{cell::RefCell, rc::Rc, thread::sleep, time::Duration};
+use crate::{assert_err, assert_ok};
+use olympus::kleio::journal::{ KJournal, KJournalEntrySeverity, KJournalSeveritySet, KJOURNAL_BUFFER_MIN, KJOURNAL_BUFFER_MAX, KJournalEntry, KJournalListener, KJournalThrottle, listener::KJournalListenerListError };
 
 #[test]
 /// Create a new instance of KJournal.
@@ -9,7 +10,7 @@ use olympus::kleio::journal::{ KJournal, KJournalEntrySeverity, KJOURNAL_BUFFER_
 /// V2 | Verify KJournal name with control.
 fn kjournal_new() {
     // V1 | New KJournal created without error.
-    match KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN) {
+    match KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN) {
         Ok(j) => {
             // V2 | Verify KJournal name with control.
             assert!(j.get_name().eq(&"J1".to_string()), "KJournal name error!");
@@ -27,7 +28,7 @@ fn kjournal_new() {
 fn kjournal_new_limit() {
 
     // V1 | New KJournal created with buffer size < KJOURNAL_BUFFER_MIN must return Err(KJournalError::BufferSizeTooSmall).
-    match KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN - 1) {
+    match KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN - 1) {
         Ok(_) => panic!("KJournal must return Err(KJournalError::BufferSizeTooSmall)!"),
         Err(err) => match err {
             olympus::kleio::journal::journal::KJournalError::BufferSizeTooSmall => {},
@@ -36,7 +37,7 @@ fn kjournal_new_limit() {
     }
     
     // V2 | New KJournal created with buffer size > KJOURNAL_BUFFER_MAX must return Err(KJournalError::BufferSizeTooBig).
-    match KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MAX + 1) {
+    match KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MAX + 1) {
         Ok(_) => panic!("KJournal must return Err(KJournalError::BufferSizeTooBig)!"),
         Err(err) => match err {
             olympus::kleio::journal::journal::KJournalError::BufferSizeTooBig => {},
@@ -59,7 +60,7 @@ fn kjournal_new_limit() {
 /// V5 | Entries count should be 0.
 fn kjournal_write_read() {
     
-    let mut j = KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
 
     // V1 | Unread count should be 0 at first.
     assert!(j.unread() == 0, "Unread count should be 0 at first!");
@@ -71,16 +72,16 @@ fn kjournal_write_read() {
     assert!(j.unread() == 10, "Unread count should be 10!");
 
     // V4 | 10 entries read without error.
-    verify_journal_entry(j.read(), KJournalEntrySeverity::FATAL, &"Fatal entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::ERROR, &"Error entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::WARNING, &"Warning entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::INFORMATION, &"Information entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::FATAL, &"Fatal entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::ERROR, &"Error entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::WARNING, &"Warning entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::INFORMATION, &"Information entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::OTHER, &"Other entry".to_owned());
-    verify_journal_entry(j.read(), KJournalEntrySeverity::DEBUG, &"Debug entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Fatal, &"Fatal entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Error, &"Error entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Warning, &"Warning entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Information, &"Information entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Fatal, &"Fatal entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Error, &"Error entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Warning, &"Warning entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Information, &"Information entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Other, &"Other entry".to_owned());
+    verify_journal_entry(j.read(), KJournalEntrySeverity::Debug, &"Debug entry".to_owned());
 
     // V5 | Entries count should be 0.
     assert!(j.unread() == 0, "Unread count should be 0!");
@@ -100,7 +101,7 @@ fn kjournal_write_read() {
 /// V5 | Entries count should be 0.
 fn kjournal_clear() {
 
-    let mut j = KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
 
 
     // V1 | Unread count should be 0 at first.
@@ -135,10 +136,10 @@ fn kjournal_clear() {
 /// V9 | Remove all listeners successfully.
 fn kjournal_listeners() {
     
-    let mut j = KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
 
     // V1 | Try to remove listener without it being added. Must return Err(ListenerNotFound).
-    let nl0 = NotifiedListener::new(0);
+    let nl0 = NotifiedListener::new(KJournalSeveritySet::NONE);
     match j.remove_listener(&nl0){
         Ok(_) => panic!("Cannot remove listener not added!"),
         Err(err) => match err {
@@ -160,14 +161,14 @@ fn kjournal_listeners() {
     }
 
     // V4 | Add multiple listener with different severity to listen to.
-    let nl1= NotifiedListener::new(KJournalEntrySeverity::DEBUG);
-    let nl2= NotifiedListener::new( KJournalEntrySeverity::OTHER);
-    let nl3 = NotifiedListener::new( KJournalEntrySeverity::INFORMATION);
-    let nl4= NotifiedListener::new( KJournalEntrySeverity::WARNING);
-    let nl5= NotifiedListener::new( KJournalEntrySeverity::ERROR);
-    let nl6 = NotifiedListener::new( KJournalEntrySeverity::FATAL);
-    let nl7 = NotifiedListener::new( KJournalEntrySeverity::ALL_NO_DEBUG);
-    let nl8 = NotifiedListener::new( KJournalEntrySeverity::ALL_WITH_DEBUG);
+    let nl1= NotifiedListener::new(KJournalEntrySeverity::Debug);
+    let nl2= NotifiedListener::new( KJournalEntrySeverity::Other);
+    let nl3 = NotifiedListener::new( KJournalEntrySeverity::Information);
+    let nl4= NotifiedListener::new( KJournalEntrySeverity::Warning);
+    let nl5= NotifiedListener::new( KJournalEntrySeverity::Error);
+    let nl6 = NotifiedListener::new( KJournalEntrySeverity::Fatal);
+    let nl7 = NotifiedListener::new( KJournalSeveritySet::ALL_NO_DEBUG);
+    let nl8 = NotifiedListener::new( KJournalSeveritySet::ALL_WITH_DEBUG);
     handle_listener_error(j.add_listener(&nl1));
     handle_listener_error(j.add_listener(&nl2));
     handle_listener_error(j.add_listener(&nl3));
@@ -191,10 +192,10 @@ fn kjournal_listeners() {
     assert!(nl8.get_notification_count() == 10, "NotifiedListener8::get_notification_count() should be 10 instead of {}!", nl8.get_notification_count());
 
     // V6 | Modify KJournal severity without error.
-    j.set_severity(KJournalEntrySeverity::WARNING);
+    j.set_severity(KJournalEntrySeverity::Warning);
 
     // V7 | Verify new severity set with get_severity().
-    assert!(j.get_severity() == KJournalEntrySeverity::WARNING, "Wrong severity set!");
+    assert!(j.get_severity() == KJournalSeveritySet::from(KJournalEntrySeverity::Warning), "Wrong severity set!");
 
     // V8 | Write multiple entries and verify if listener listened with new severity.
     write_10_journal_entries(&mut j);
@@ -229,7 +230,7 @@ fn kjournal_listeners() {
 /// V1 | set_max_entries() buffer size < KJOURNAL_BUFFER_MIN must return Err(KJournalError::BufferSizeTooSmall).
 /// V2 | set_max_entries() with buffer size > KJOURNAL_BUFFER_MAX must return Err(KJournalError::BufferSizeTooBig).
 fn kjournal_set_max_entries_limit() {
-    let mut j = KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
 
     // V1 | set_max_entries() buffer size < KJOURNAL_BUFFER_MIN must return Err(KJournalError::BufferSizeTooSmall).
     match j.set_max_entries(KJOURNAL_BUFFER_MIN - 1) {
@@ -259,7 +260,7 @@ fn kjournal_set_max_entries_limit() {
 /// V2 | set_max_entries() create a new buffer without error.
 /// V3 | get_max_entries() gives the new KJournal buffer size.
 fn kjournal_set_max_entries() {
-    let mut j = KJournal::new("J1", KJournalEntrySeverity::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
 
     // V1 | get_max_entries() gives the correct KJournal buffer creation size.
     assert!(j.get_max_entries() == KJOURNAL_BUFFER_MIN, "KJournal buffer creation size error!");
@@ -275,6 +276,139 @@ fn kjournal_set_max_entries() {
 }
 
 
+#[test]
+/// Write entries for targets and reload KJournal filter at runtime.
+/// 
+/// # Verification(s)
+/// V1 | KJournal::write_target() with default filter writes entry with target.
+/// V2 | KJournal::reload_filter() with valid directives modify target severity.
+/// V3 | KJournal::write_target() ignore entry filtered by target severity.
+/// V4 | KJournal::reload_filter() with invalid directives returns Err and keep previous filter.
+fn kjournal_write_target_reload_filter() {
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_NO_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+
+    // V1 | KJournal::write_target() with default filter writes entry with target.
+    j.write_target("kleio::display::x11", KJournalEntrySeverity::Debug, "Debug entry");
+    assert!(j.unread() == 0, "Debug entry should be ignored!");
+    j.write_target("kleio::display::x11", KJournalEntrySeverity::Error, "Error entry");
+    match j.read() {
+        Some(entry) => assert_eq!(entry.get_target(), "kleio::display::x11", "Entry target error!"),
+        None => panic!("Error entry should be written!"),
+    }
+
+    // V2 | KJournal::reload_filter() with valid directives modify target severity.
+    assert_ok!(j.reload_filter("error, kleio::display::x11=debug"));
+    assert_eq!(j.get_filter().get_target_severity("kleio::display::x11"), KJournalSeveritySet::ALL_WITH_DEBUG, "Reloaded filter error!");
+
+    // V3 | KJournal::write_target() ignore entry filtered by target severity.
+    j.write_target("kleio::display::x11", KJournalEntrySeverity::Debug, "Debug entry");
+    j.write_target("kleio::asset", KJournalEntrySeverity::Warning, "Warning entry");
+    j.write(KJournalEntrySeverity::Information, "Information entry");
+    assert!(j.unread() == 1, "Only x11 debug entry should be written!");
+
+    // V4 | KJournal::reload_filter() with invalid directives returns Err and keep previous filter.
+    assert_err!(j.reload_filter("kleio=loud"), olympus::kleio::journal::KJournalFilterError::InvalidSeverity);
+    assert_eq!(j.get_filter().get_target_severity("kleio::display::x11"), KJournalSeveritySet::ALL_WITH_DEBUG, "Previous filter should be kept!");
+}
+
+#[test]
+/// Collapse consecutive duplicates entries in KJournal.
+/// 
+/// # Verification(s)
+/// V1 | Without KJournalThrottle, duplicates are written as separate entries.
+/// V2 | With deduplication, consecutive duplicates are collapsed into 1 entry.
+/// V3 | Listener is notified once when duplicates are collapsed and once more when the run ends.
+/// V4 | Collapsed entry has repeat count and first date and time <= last date and time.
+/// V5 | After a read, duplicate of an older unread entry is written as a new entry.
+fn kjournal_throttle_deduplicate() {
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+    let nl = NotifiedListener::new(KJournalSeveritySet::ALL_WITH_DEBUG);
+    handle_listener_error(j.add_listener(&nl));
+
+    // V1 | Without KJournalThrottle, duplicates are written as separate entries.
+    j.write(KJournalEntrySeverity::Warning, "Duplicate");
+    j.write(KJournalEntrySeverity::Warning, "Duplicate");
+    assert!(j.unread() == 2, "Duplicates shouldn't be collapsed by default!");
+    j.clear();
+
+    // V2 | With deduplication, consecutive duplicates are collapsed into 1 entry.
+    j.set_throttle(KJournalThrottle::new(true, None));
+    for _ in 0..5 {
+        j.write(KJournalEntrySeverity::Warning, "Duplicate");
+    }
+    j.write_target("kleio", KJournalEntrySeverity::Warning, "Duplicate");
+    assert!(j.unread() == 2, "Duplicates should be collapsed!");
+
+    // V3 | Listener is notified once when duplicates are collapsed and once more when the run ends.
+    assert!(nl.get_notification_count() == 5, "NotifiedListener::get_notification_count() should be 5 instead of {}!", nl.get_notification_count());
+
+    // V4 | Collapsed entry has repeat count and first date and time <= last date and time.
+    j.read();
+    match j.read() {
+        Some(entry) => {
+            assert_eq!(entry.get_repeat_count(), 5, "Repeat count error!");
+            assert!(entry.get_first_date_time() <= entry.get_date_time(), "First date and time should be before last!");
+        },
+        None => panic!("Collapsed entry not found!"),
+    }
+
+    // V5 | After a read, duplicate of an older unread entry is written as a new entry.
+    j.write(KJournalEntrySeverity::Warning, "Older");
+    j.write(KJournalEntrySeverity::Warning, "Latest");
+    j.read();
+    j.write(KJournalEntrySeverity::Warning, "Older");
+    assert!(j.unread() == 2, "Duplicate of a read entry shouldn't be collapsed!");
+    match j.read() {
+        Some(entry) => assert_eq!(entry.get_repeat_count(), 1, "New entry repeat count error!"),
+        None => panic!("New entry not found!"),
+    }
+}
+
+#[test]
+/// Rate limit entries with the same message in KJournal.
+/// 
+/// # Verification(s)
+/// V1 | Entries over the rate limit of a message are suppressed and counted.
+/// V2 | Different messages have their own rate limit.
+/// V3 | Rate limit is reset after period.
+/// V4 | With deduplication, duplicates are collapsed before rate limit and counted in repeat count.
+fn kjournal_throttle_rate_limit() {
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+    j.set_throttle(KJournalThrottle::new(false, Some((3, Duration::from_millis(100)))));
+    assert_eq!(j.get_throttle(), KJournalThrottle::new(false, Some((3, Duration::from_millis(100)))), "Throttle error!");
+
+    // V1 | Entries over the rate limit of a message are suppressed and counted.
+    for _ in 0..10 {
+        j.write(KJournalEntrySeverity::Warning, "Flood");
+    }
+    assert!(j.unread() == 3, "Only 3 entries should be written!");
+    assert_eq!(j.get_suppressed_count(), 7, "Suppressed count error!");
+
+    // V2 | Different messages have their own rate limit.
+    j.write(KJournalEntrySeverity::Warning, "Other");
+    j.write_target("kleio", KJournalEntrySeverity::Warning, "Flood");
+    assert!(j.unread() == 5, "Different messages shouldn't be suppressed!");
+
+    // V3 | Rate limit is reset after period.
+    sleep(Duration::from_millis(150));
+    j.write(KJournalEntrySeverity::Warning, "Flood");
+    assert!(j.unread() == 6, "Rate limit should be reset after period!");
+    assert_eq!(j.get_suppressed_count(), 7, "Suppressed count error!");
+
+    // V4 | With deduplication, duplicates are collapsed before rate limit and counted in repeat count.
+    j.clear();
+    j.set_throttle(KJournalThrottle::new(true, Some((3, Duration::from_millis(100)))));
+    for _ in 0..100 {
+        j.write(KJournalEntrySeverity::Warning, "Flood");
+    }
+    assert!(j.unread() == 1, "Duplicates should be collapsed!");
+    assert_eq!(j.get_suppressed_count(), 7, "Collapsed duplicates shouldn't be suppressed!");
+    match j.read() {
+        Some(entry) => assert_eq!(entry.get_repeat_count(), 100, "Repeat count should include every occurrence!"),
+        None => panic!("Collapsed entry not found!"),
+    }
+}
+
 
 /*************
 * STRUCTURES * 
@@ -283,7 +417,7 @@ fn kjournal_set_max_entries() {
 /// ##### Custom listeners that count how many time it was notified.
 struct NotifiedListener {
     // Severity to listen to
-    severity : u8,
+    severity : KJournalSeveritySet,
 
     // Was listener notified.
     notification_count: Rc<RefCell<usize>>,
@@ -293,8 +427,8 @@ impl NotifiedListener {
     /// Create a new instance of NotifiedListener with severity listened.
     /// 
     /// Returns new NotifiedListener created.
-    pub fn new(severity:u8) -> NotifiedListener {
-        NotifiedListener { severity, notification_count : Rc::new(RefCell::new(0)) }
+    pub fn new(severity : impl Into<KJournalSeveritySet>) -> NotifiedListener {
+        NotifiedListener { severity: severity.into(), notification_count : Rc::new(RefCell::new(0)) }
     }
 
     /// Get the count of notifications.
@@ -314,11 +448,11 @@ impl<'a> KJournalListener for NotifiedListener{
         b.replace(b.take() + 1);
     }
 
-    fn set_severity(&mut self, severity:u8){
+    fn set_severity(&mut self, severity : KJournalSeveritySet){
         self.severity = severity;
     }
 
-    fn get_severity(&self) -> u8{
+    fn get_severity(&self) -> KJournalSeveritySet{
         self.severity
     }
 }
@@ -331,7 +465,7 @@ impl<'a> KJournalListener for NotifiedListener{
 /// 
 /// # Panic
 /// Will panic if any entry parameters are wrong.
-fn verify_journal_entry(entry: Option<&KJournalEntry>, severity:u8, desc : &String){
+fn verify_journal_entry(entry: Option<&KJournalEntry>, severity : KJournalEntrySeverity, desc : &String){
 
     match entry {
         Some(entry) => {
@@ -364,15 +498,15 @@ fn handle_listener_error(res : Result<usize, KJournalListenerListError>){
 
 /// Write 10 journals entries
 fn write_10_journal_entries(j : &mut KJournal){
-    j.write(KJournalEntrySeverity::DEBUG, "Debug entry");
-    j.write(KJournalEntrySeverity::OTHER, "Other entry");
-    j.write(KJournalEntrySeverity::INFORMATION, "Information entry");
-    j.write(KJournalEntrySeverity::WARNING, "Warning entry");
-    j.write(KJournalEntrySeverity::ERROR, "Error entry");
-    j.write(KJournalEntrySeverity::FATAL, "Fatal entry");
-    j.write(KJournalEntrySeverity::INFORMATION, "Information entry");
-    j.write(KJournalEntrySeverity::WARNING, "Warning entry");
-    j.write(KJournalEntrySeverity::ERROR, "Error entry");
-    j.write(KJournalEntrySeverity::FATAL, "Fatal entry");
+    j.write(KJournalEntrySeverity::Debug, "Debug entry");
+    j.write(KJournalEntrySeverity::Other, "Other entry");
+    j.write(KJournalEntrySeverity::Information, "Information entry");
+    j.write(KJournalEntrySeverity::Warning, "Warning entry");
+    j.write(KJournalEntrySeverity::Error, "Error entry");
+    j.write(KJournalEntrySeverity::Fatal, "Fatal entry");
+    j.write(KJournalEntrySeverity::Information, "Information entry");
+    j.write(KJournalEntrySeverity::Warning, "Warning entry");
+    j.write(KJournalEntrySeverity::Error, "Error entry");
+    j.write(KJournalEntrySeverity::Fatal, "Fatal entry");
 
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use std::{rc::Rc, cell::RefCell};
-use olympus::kleio::journal::{listener::{KJournalListenerList, KJournalListenerListError}, KJournalListenerPrint, KJournalEntrySeverity, KJournalListener, KJournalEntry};
+use olympus::kleio::journal::{listener::{KJournalListenerList, KJournalListenerListError}, KJournalListenerPrint, KJournalEntrySeverity, KJournalSeveritySet, KJournalListener, KJournalEntry};
 
 #[test]
 /// Create a new intance of KJournalListenerList.
@@ -27,7 +27,7 @@ fn kjournal_listener_list_add_listener() {
     assert!(list.count() == 0, "KJournalListenerList::count() should be 0!");
 
     // V2 | KJournalListenerPrint::new() create a new instance without error
-    let listener = KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
+    let listener = KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
 
     // V3 | KJournalListenerList::add_listener() add listener created.
     handle_listener_error(list.add_listener(&listener));
@@ -44,7 +44,7 @@ fn kjournal_listener_list_add_listener() {
 /// V1 | KJournalListenerList::add_listener() should panic!
 fn kjournal_listener_list_add_listener_twice() {
     let mut list = KJournalListenerList::new();
-    let listener = KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
+    let listener = KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
     handle_listener_error(list.add_listener(&listener));
 
 
@@ -61,7 +61,7 @@ fn kjournal_listener_list_add_listener_twice() {
 /// V2 | KJournalListenerList::count() should be 0 after removal.
 fn kjournal_listener_list_remove_listener() {
     let mut list = KJournalListenerList::new();
-    let listener = KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
+    let listener = KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
     handle_listener_error(list.add_listener(&listener));
 
     // V1 | KJournalListener::remove_listener() should remove listener without error.
@@ -79,7 +79,7 @@ fn kjournal_listener_list_remove_listener() {
 /// V1 | KJournalListener::remove_listener() should panic! trying to remove unknown listener.
 fn kjournal_listener_list_remove_listener_not_added() {
     let mut list = KJournalListenerList::new();
-    let listener = KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
+    let listener = KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
 
     // V1 | KJournalListener::remove_listener() should panic! trying to remove unknown listener.
     handle_listener_error(list.remove_listener(&listener));
@@ -101,9 +101,9 @@ fn kjournal_listener_list_clear() {
     assert!(list.count() == 0, "KJournalListenerList::count() should be 0!");
 
     // V2 | Add 3 different listeners to list.
-    let l1 = KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
-    let l2= KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
-    let l3= KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
+    let l1 = KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
+    let l2= KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
+    let l3= KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
     handle_listener_error(list.add_listener(&l1));
     handle_listener_error(list.add_listener(&l2));
     handle_listener_error(list.add_listener(&l3));
@@ -129,7 +129,7 @@ fn kjournal_listener_list_clear() {
 /// V4 | Add ALL listeners to list.
 /// V5 | KJournalListenerList::count() should be 15.
 /// v6 | Send a notification for each severity.
-/// V7 | Send differents notifications combinations of 2, 3, 4, 5 and 6 severities.
+/// V7 | Send a notification for each severity of KJournalSeveritySet::ALL_WITH_DEBUG.
 /// V8 | Verify notification count of each NotifiedListener.
 fn kjournal_listener_list_notify() {
 
@@ -137,29 +137,29 @@ fn kjournal_listener_list_notify() {
     let mut list = KJournalListenerList::new();
 
     // V1 | Create a notified listener for each severity.
-    let nl0 = NotifiedListener::new(0);
-    let nl1= NotifiedListener::new(KJournalEntrySeverity::DEBUG);
-    let nl2= NotifiedListener::new( KJournalEntrySeverity::OTHER);
-    let nl3 = NotifiedListener::new( KJournalEntrySeverity::INFORMATION);
-    let nl4= NotifiedListener::new( KJournalEntrySeverity::WARNING);
-    let nl5= NotifiedListener::new( KJournalEntrySeverity::ERROR);
-    let nl6 = NotifiedListener::new( KJournalEntrySeverity::FATAL);
-    let nl7 = NotifiedListener::new( KJournalEntrySeverity::ALL_NO_DEBUG);
-    let nl8 = NotifiedListener::new( KJournalEntrySeverity::ALL_WITH_DEBUG);
+    let nl0 = NotifiedListener::new(KJournalSeveritySet::NONE);
+    let nl1= NotifiedListener::new(KJournalEntrySeverity::Debug);
+    let nl2= NotifiedListener::new( KJournalEntrySeverity::Other);
+    let nl3 = NotifiedListener::new( KJournalEntrySeverity::Information);
+    let nl4= NotifiedListener::new( KJournalEntrySeverity::Warning);
+    let nl5= NotifiedListener::new( KJournalEntrySeverity::Error);
+    let nl6 = NotifiedListener::new( KJournalEntrySeverity::Fatal);
+    let nl7 = NotifiedListener::new( KJournalSeveritySet::ALL_NO_DEBUG);
+    let nl8 = NotifiedListener::new( KJournalSeveritySet::ALL_WITH_DEBUG);
     
     // V2 | Create differents combinations of 2, 3, 4, 5 and 6 severities.
-    let nls0= NotifiedListener::new( KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER);
-    let nls1 = NotifiedListener::new(  KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER |
-        KJournalEntrySeverity::INFORMATION);
-    let nls2= NotifiedListener::new( KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER |
-        KJournalEntrySeverity::INFORMATION | KJournalEntrySeverity::WARNING);
-    let nls3= NotifiedListener::new(  KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER |
-        KJournalEntrySeverity::INFORMATION | KJournalEntrySeverity::WARNING | KJournalEntrySeverity::ERROR);
-    let nls4 = NotifiedListener::new(  KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER |
-        KJournalEntrySeverity::INFORMATION | KJournalEntrySeverity::WARNING | KJournalEntrySeverity::ERROR | KJournalEntrySeverity::FATAL);
+    let nls0= NotifiedListener::new( KJournalEntrySeverity::Debug | KJournalEntrySeverity::Other);
+    let nls1 = NotifiedListener::new(  KJournalEntrySeverity::Debug | KJournalEntrySeverity::Other |
+        KJournalEntrySeverity::Information);
+    let nls2= NotifiedListener::new( KJournalEntrySeverity::Debug | KJournalEntrySeverity::Other |
+        KJournalEntrySeverity::Information | KJournalEntrySeverity::Warning);
+    let nls3= NotifiedListener::new(  KJournalEntrySeverity::Debug | KJournalEntrySeverity::Other |
+        KJournalEntrySeverity::Information | KJournalEntrySeverity::Warning | KJournalEntrySeverity::Error);
+    let nls4 = NotifiedListener::new(  KJournalEntrySeverity::Debug | KJournalEntrySeverity::Other |
+        KJournalEntrySeverity::Information | KJournalEntrySeverity::Warning | KJournalEntrySeverity::Error | KJournalEntrySeverity::Fatal);
 
     // V3 | Create a KJournalListenerPrint.
-    let lp0 = KJournalListenerPrint::new(KJournalEntrySeverity::ALL_WITH_DEBUG);
+    let lp0 = KJournalListenerPrint::new(KJournalSeveritySet::ALL_WITH_DEBUG);
 
     // V4 | Add ALL listeners to list.
     handle_listener_error(list.add_listener(&nl0));
@@ -182,40 +182,34 @@ fn kjournal_listener_list_notify() {
     assert!(list.count() == 15, "KJournalListenerList::count() should be 15!");
 
     // v6 | Send a notification for each severity.
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::DEBUG, "DEBUG"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::OTHER, "OTHER"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::INFORMATION, "INFORMATION"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::WARNING, "WARNING"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::ERROR, "ERROR"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::FATAL, "FATAL"));
-
-    // V7 | Send differents notifications combinations of 2, 3, 4, 5 and 6 severities.
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER, "DO"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER |
-        KJournalEntrySeverity::INFORMATION, "DOI"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER |
-        KJournalEntrySeverity::INFORMATION | KJournalEntrySeverity::WARNING, "DOIW"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER |
-        KJournalEntrySeverity::INFORMATION | KJournalEntrySeverity::WARNING | KJournalEntrySeverity::ERROR, "DOIWE"));
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::DEBUG | KJournalEntrySeverity::OTHER |
-        KJournalEntrySeverity::INFORMATION | KJournalEntrySeverity::WARNING | KJournalEntrySeverity::ERROR | KJournalEntrySeverity::FATAL, "DOIWEF"));
+    list.notify(&KJournalEntry::new(KJournalEntrySeverity::Debug, "DEBUG"));
+    list.notify(&KJournalEntry::new(KJournalEntrySeverity::Other, "OTHER"));
+    list.notify(&KJournalEntry::new(KJournalEntrySeverity::Information, "INFORMATION"));
+    list.notify(&KJournalEntry::new(KJournalEntrySeverity::Warning, "WARNING"));
+    list.notify(&KJournalEntry::new(KJournalEntrySeverity::Error, "ERROR"));
+    list.notify(&KJournalEntry::new(KJournalEntrySeverity::Fatal, "FATAL"));
+
+    // V7 | Send a notification for each severity of KJournalSeveritySet::ALL_WITH_DEBUG.
+    for severity in KJournalSeveritySet::ALL_WITH_DEBUG.iter() {
+        list.notify(&KJournalEntry::new(severity, severity.get_name()));
+    }
 
     // V8 | Verify notification count of each NotifiedListener.
     assert!(nl0.get_notification_count() == 0, "NotifiedListener0::get_notification_count() should be 0 instead of {}!", nl0.get_notification_count());
-    assert!(nl1.get_notification_count() == 6, "NotifiedListener1::get_notification_count() should be 6 instead of {}!", nl1.get_notification_count());
-    assert!(nl2.get_notification_count() == 6, "NotifiedListener2::get_notification_count() should be 6 instead of {}!", nl2.get_notification_count());
-    assert!(nl3.get_notification_count() == 5, "NotifiedListener3::get_notification_count() should be 5 instead of {}!", nl3.get_notification_count());
-    assert!(nl4.get_notification_count() == 4, "NotifiedListener4::get_notification_count() should be 4 instead of {}!", nl4.get_notification_count());
-    assert!(nl5.get_notification_count() == 3, "NotifiedListener5::get_notification_count() should be 3 instead of {}!", nl5.get_notification_count());
+    assert!(nl1.get_notification_count() == 2, "NotifiedListener1::get_notification_count() should be 2 instead of {}!", nl1.get_notification_count());
+    assert!(nl2.get_notification_count() == 2, "NotifiedListener2::get_notification_count() should be 2 instead of {}!", nl2.get_notification_count());
+    assert!(nl3.get_notification_count() == 2, "NotifiedListener3::get_notification_count() should be 2 instead of {}!", nl3.get_notification_count());
+    assert!(nl4.get_notification_count() == 2, "NotifiedListener4::get_notification_count() should be 2 instead of {}!", nl4.get_notification_count());
+    assert!(nl5.get_notification_count() == 2, "NotifiedListener5::get_notification_count() should be 2 instead of {}!", nl5.get_notification_count());
     assert!(nl6.get_notification_count() == 2, "NotifiedListener6::get_notification_count() should be 2 instead of {}!", nl6.get_notification_count());
     assert!(nl7.get_notification_count() == 10, "NotifiedListener7::get_notification_count() should be 10 instead of {}!", nl7.get_notification_count());
-    assert!(nl8.get_notification_count() == 11, "NotifiedListener8::get_notification_count() should be 11 instead of {}!", nl8.get_notification_count());
+    assert!(nl8.get_notification_count() == 12, "NotifiedListener8::get_notification_count() should be 12 instead of {}!", nl8.get_notification_count());
 
-    assert!(nls0.get_notification_count() == 7, "NotifiedListenerS0::get_notification_count() should be 7! instead of {}!", nls0.get_notification_count());
-    assert!(nls1.get_notification_count() == 8, "NotifiedListenerS1::get_notification_count() should be 8! instead of {}!", nls1.get_notification_count());
-    assert!(nls2.get_notification_count() == 9, "NotifiedListenerS2::get_notification_count() should be 9! instead of {}!", nls2.get_notification_count());
+    assert!(nls0.get_notification_count() == 4, "NotifiedListenerS0::get_notification_count() should be 4! instead of {}!", nls0.get_notification_count());
+    assert!(nls1.get_notification_count() == 6, "NotifiedListenerS1::get_notification_count() should be 6! instead of {}!", nls1.get_notification_count());
+    assert!(nls2.get_notification_count() == 8, "NotifiedListenerS2::get_notification_count() should be 8! instead of {}!", nls2.get_notification_count());
     assert!(nls3.get_notification_count() == 10, "NotifiedListenerS3::get_notification_count() should be 10! instead of {}!", nls3.get_notification_count());
-    assert!(nls4.get_notification_count() == 11, "NotifiedListenerS4::get_notification_count() should be 11! instead of {}!", nls4.get_notification_count());
+    assert!(nls4.get_notification_count() == 12, "NotifiedListenerS4::get_notification_count() should be 12! instead of {}!", nls4.get_notification_count());
  
 }
 
@@ -229,7 +223,7 @@ fn kjournal_listener_list_notify_empty() {
     let list = KJournalListenerList::new();
 
     // V1 | KJournalListener::notify() should NOT panic! when empty and notify is called.
-    list.notify(&KJournalEntry::new(KJournalEntrySeverity::DEBUG, "DEBUG"));
+    list.notify(&KJournalEntry::new(KJournalEntrySeverity::Debug, "DEBUG"));
 }
 
 /************
@@ -256,7 +250,7 @@ fn handle_listener_error(res : Result<usize, KJournalListenerListError>){
 /// ##### Custom listeners that count how many time it was notified.
 struct NotifiedListener {
     // Severity to listen to
-    severity : u8,
+    severity : KJournalSeveritySet,
 
     // Was listener notified.
     notification_count: Rc<RefCell<usize>>,
@@ -266,8 +260,8 @@ impl NotifiedListener {
     /// Create a new instance of NotifiedListener with severity listened.
     /// 
     /// Returns new NotifiedListener created.
-    pub fn new(severity:u8) -> NotifiedListener {
-        NotifiedListener { severity, notification_count : Rc::new(RefCell::new(0)) }
+    pub fn new(severity : impl Into<KJournalSeveritySet>) -> NotifiedListener {
+        NotifiedListener { severity: severity.into(), notification_count : Rc::new(RefCell::new(0)) }
     }
 
     /// Get the count of notifications.
@@ -287,11 +281,11 @@ impl<'a> KJournalListener for NotifiedListener{
         b.replace(b.take() + 1);
     }
 
-    fn set_severity(&mut self, severity:u8){
+    fn set_severity(&mut self, severity : KJournalSeveritySet){
         self.severity = severity;
     }
 
-    fn get_severity(&self) -> u8{
+    fn get_severity(&self) -> KJournalSeveritySet{
         self.severity
     }
 }
\ No newline at end of file
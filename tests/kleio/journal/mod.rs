@@ -12,4 +12,20 @@ pub mod listener;
 
 // Contains tests for KJournal
 #[cfg(test)]
-pub mod journal;
\ No newline at end of file
+pub mod journal;
+
+// Contains tests for KJournalFilter
+#[cfg(test)]
+pub mod filter;
+
+// Contains tests for KJournalListenerBinary and binary journal reader
+#[cfg(test)]
+pub mod binary;
+
+// Contains tests for KJournalEntrySeverity and KJournalSeveritySet
+#[cfg(test)]
+pub mod severity;
+
+// Contains tests for KJournal spans
+#[cfg(test)]
+pub mod span;
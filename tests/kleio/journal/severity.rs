@@ -0,0 +1,80 @@
+use olympus::kleio::journal::{KJournalEntrySeverity, KJournalSeveritySet, KJournalSeverityError};
+
+use crate::{assert_err, assert_ok};
+
+#[test]
+/// Parse, display and compare KJournalEntrySeverity.
+/// 
+/// # Verification(s)
+/// V1 | KJournalEntrySeverity are ordered from Debug to Fatal.
+/// V2 | KJournalEntrySeverity parse names and aliases, case insensitive.
+/// V3 | Invalid name returns Err(KJournalSeverityError::InvalidSeverity).
+/// V4 | KJournalEntrySeverity display upper case name and symbol.
+fn kjournal_severity_parse_display_order() {
+    // V1 | KJournalEntrySeverity are ordered from Debug to Fatal.
+    for pair in KJournalEntrySeverity::ALL.windows(2) {
+        assert!(pair[0] < pair[1], "{} should be lower than {}!", pair[0], pair[1]);
+    }
+    assert!(KJournalEntrySeverity::Error >= KJournalEntrySeverity::Warning, "Error should be Warning and above!");
+
+    // V2 | KJournalEntrySeverity parse names and aliases, case insensitive.
+    assert_eq!(assert_ok!("Debug".parse::<KJournalEntrySeverity>()), KJournalEntrySeverity::Debug, "Debug parse error!");
+    assert_eq!(assert_ok!(" info ".parse::<KJournalEntrySeverity>()), KJournalEntrySeverity::Information, "Info alias parse error!");
+    assert_eq!(assert_ok!("WARN".parse::<KJournalEntrySeverity>()), KJournalEntrySeverity::Warning, "Warn alias parse error!");
+
+    // V3 | Invalid name returns Err(KJournalSeverityError::InvalidSeverity).
+    assert_err!("loud".parse::<KJournalEntrySeverity>(), KJournalSeverityError::InvalidSeverity);
+
+    // V4 | KJournalEntrySeverity display upper case name and symbol.
+    for severity in KJournalEntrySeverity::ALL {
+        assert_eq!(assert_ok!(severity.to_string().parse::<KJournalEntrySeverity>()), severity, "Display and parse should round trip!");
+    }
+    assert_eq!(KJournalEntrySeverity::Information.to_string(), "INFORMATION", "Display error!");
+    assert_eq!(KJournalEntrySeverity::Fatal.get_symbol(), 'F', "Symbol error!");
+}
+
+#[test]
+/// Create, combine, parse and display KJournalSeveritySet.
+/// 
+/// # Verification(s)
+/// V1 | KJournalSeveritySet::at_least() contains severity and above only.
+/// V2 | Severities and sets are combined with |, & and !.
+/// V3 | KJournalSeveritySet::from_flags() refuses unknown flags.
+/// V4 | KJournalSeveritySet::insert() and remove() modify the set.
+/// V5 | KJournalSeveritySet parse and display names separated by |.
+fn kjournal_severity_set() {
+    // V1 | KJournalSeveritySet::at_least() contains severity and above only.
+    let set = KJournalSeveritySet::at_least(KJournalEntrySeverity::Warning);
+    for severity in KJournalEntrySeverity::ALL {
+        assert_eq!(set.contains(severity), severity >= KJournalEntrySeverity::Warning, "at_least() error for {}!", severity);
+    }
+    assert_eq!(KJournalSeveritySet::at_least(KJournalEntrySeverity::Debug), KJournalSeveritySet::ALL_WITH_DEBUG, "at_least(Debug) error!");
+    assert_eq!(KJournalSeveritySet::at_least(KJournalEntrySeverity::Other), KJournalSeveritySet::ALL_NO_DEBUG, "at_least(Other) error!");
+
+    // V2 | Severities and sets are combined with |, & and !.
+    assert_eq!(set, KJournalEntrySeverity::Warning | KJournalEntrySeverity::Error | KJournalEntrySeverity::Fatal, "| error!");
+    assert_eq!(set & KJournalSeveritySet::from(KJournalEntrySeverity::Error), KJournalSeveritySet::from(KJournalEntrySeverity::Error), "& error!");
+    assert_eq!(!set, KJournalEntrySeverity::Debug | KJournalEntrySeverity::Other | KJournalEntrySeverity::Information, "! error!");
+    assert_eq!(!KJournalSeveritySet::ALL_WITH_DEBUG, KJournalSeveritySet::NONE, "! of all error!");
+
+    // V3 | KJournalSeveritySet::from_flags() refuses unknown flags.
+    assert_eq!(KJournalSeveritySet::from_flags(5), Some(KJournalEntrySeverity::Debug | KJournalEntrySeverity::Information), "from_flags() error!");
+    assert_eq!(KJournalSeveritySet::from_flags(64), None, "from_flags() should refuse unknown flags!");
+
+    // V4 | KJournalSeveritySet::insert() and remove() modify the set.
+    let mut set = KJournalSeveritySet::NONE;
+    assert!(set.is_empty(), "Set should be empty!");
+    set.insert(KJournalEntrySeverity::Debug);
+    set.insert(KJournalEntrySeverity::Fatal);
+    set.remove(KJournalEntrySeverity::Debug);
+    assert_eq!(set.iter().collect::<Vec<KJournalEntrySeverity>>(), vec![KJournalEntrySeverity::Fatal], "insert() and remove() error!");
+
+    // V5 | KJournalSeveritySet parse and display names separated by |.
+    assert_eq!((KJournalEntrySeverity::Debug | KJournalEntrySeverity::Error).to_string(), "DEBUG|ERROR", "Display error!");
+    assert_eq!(KJournalSeveritySet::NONE.to_string(), "NONE", "Display of empty set error!");
+    assert_eq!(assert_ok!("error | debug".parse::<KJournalSeveritySet>()), KJournalEntrySeverity::Debug | KJournalEntrySeverity::Error, "Parse error!");
+    assert_eq!(assert_ok!("all".parse::<KJournalSeveritySet>()), KJournalSeveritySet::ALL_WITH_DEBUG, "Parse all error!");
+    assert_eq!(assert_ok!("none".parse::<KJournalSeveritySet>()), KJournalSeveritySet::NONE, "Parse none error!");
+    assert_err!("debug|loud".parse::<KJournalSeveritySet>(), KJournalSeverityError::InvalidSeverity);
+    assert_err!("128".parse::<KJournalSeveritySet>(), KJournalSeverityError::InvalidSeverity);
+}
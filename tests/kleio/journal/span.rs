@@ -0,0 +1,130 @@
+use std::{thread::sleep, time::{Duration, SystemTime}};
+use olympus::kleio::journal::{KJournal, KJournalEntrySeverity, KJournalSeveritySet, KJournalEntrySpan, KJournalRecord, KJOURNAL_BUFFER_MIN};
+
+use crate::assert_ok;
+
+#[test]
+/// Enter and exit nested spans with KJournalSpanGuard.
+/// 
+/// # Verification(s)
+/// V1 | Entries written outside of spans have no span.
+/// V2 | Entries written inside spans have span ID, parent ID and depth.
+/// V3 | Dropping KJournalSpanGuard exits the span and writes an entry with its duration.
+/// V4 | Span depth goes back to 0 once all guards are dropped.
+fn kjournal_span_guard() {
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_WITH_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+
+    // V1 | Entries written outside of spans have no span.
+    j.write(KJournalEntrySeverity::Information, "Outside");
+    assert!(j.read().unwrap().get_span().is_none(), "Entry outside of span shouldn't have span!");
+
+    let (outer_id, inner_id);
+    {
+        let mut outer = j.span("kleio::display", "outer");
+        outer_id = outer.get_id();
+        outer.write(KJournalEntrySeverity::Information, "In outer");
+        {
+            let mut inner = outer.span("kleio::display::x11", "inner");
+            inner_id = inner.get_id();
+            assert_eq!(inner.get_current_span(), Some(inner_id), "Current span error!");
+            assert_eq!(inner.get_span_depth(), 2, "Span depth error!");
+            inner.write(KJournalEntrySeverity::Information, "In inner");
+            sleep(Duration::from_millis(5));
+        }
+        outer.write(KJournalEntrySeverity::Information, "Back in outer");
+    }
+
+    // V4 | Span depth goes back to 0 once all guards are dropped.
+    assert_eq!(j.get_span_depth(), 0, "Span depth should be 0!");
+    assert_eq!(j.get_current_span(), None, "Current span should be None!");
+    assert_ne!(outer_id, inner_id, "Span IDs should be unique!");
+
+    // V3 | Dropping KJournalSpanGuard exits the span and writes an entry with its duration.
+    let e = j.read().unwrap();
+    assert_eq!(e.get_description(), "outer", "Outer exit description error!");
+    assert_eq!(e.get_target(), "kleio::display", "Outer exit target error!");
+    assert_eq!(e.get_severity(), KJournalEntrySeverity::Debug, "Span exit severity error!");
+    let outer_span = e.get_span().unwrap();
+    assert_eq!((outer_span.get_id(), outer_span.get_parent_id(), outer_span.get_depth()), (outer_id, None, 1), "Outer exit span error!");
+    assert!(outer_span.get_duration().unwrap() >= Duration::from_millis(5), "Outer duration error!");
+
+    // V2 | Entries written inside spans have span ID, parent ID and depth.
+    let e = j.read().unwrap();
+    assert_eq!(e.get_description(), "Back in outer", "Entry order error!");
+    assert_eq!(e.get_span(), Some(KJournalEntrySpan::new(outer_id, None, 1, None)), "Back in outer span error!");
+
+    let e = j.read().unwrap();
+    assert_eq!(e.get_description(), "inner", "Inner exit description error!");
+    let inner_span = e.get_span().unwrap();
+    assert!(inner_span.is_exit(), "Inner exit should be an exit entry!");
+    assert_eq!((inner_span.get_id(), inner_span.get_parent_id(), inner_span.get_depth()), (inner_id, Some(outer_id), 2), "Inner exit span error!");
+    assert!(inner_span.get_duration().unwrap() <= outer_span.get_duration().unwrap(), "Inner span should be shorter than outer!");
+
+    let e = j.read().unwrap();
+    assert_eq!(e.get_span(), Some(KJournalEntrySpan::new(inner_id, Some(outer_id), 2, None)), "In inner span error!");
+
+    let e = j.read().unwrap();
+    assert_eq!(e.get_span(), Some(KJournalEntrySpan::new(outer_id, None, 1, None)), "In outer span error!");
+}
+
+#[test]
+/// Enter and exit spans manually.
+/// 
+/// # Verification(s)
+/// V1 | KJournal::exit_span() without span returns None.
+/// V2 | KJournal::exit_span_id() exits the span and the spans nested in it.
+/// V3 | KJournal::exit_span_id() of a span already exited returns None.
+/// V4 | Span exit entries are filtered with KJournal::set_span_severity().
+/// V5 | KJournalSpanGuard::exit() returns the duration of the span.
+fn kjournal_span_manual() {
+    let mut j = KJournal::new("J1", KJournalSeveritySet::ALL_NO_DEBUG, KJOURNAL_BUFFER_MIN).unwrap();
+
+    // V1 | KJournal::exit_span() without span returns None.
+    assert!(j.exit_span().is_none(), "exit_span() without span should be None!");
+
+    // V2 | KJournal::exit_span_id() exits the span and the spans nested in it.
+    j.set_span_severity(KJournalEntrySeverity::Information);
+    assert_eq!(j.get_span_severity(), KJournalEntrySeverity::Information, "Span severity error!");
+    let a = j.enter_span("", "a");
+    j.enter_span("", "b");
+    j.enter_span("", "c");
+    assert!(j.exit_span_id(a).is_some(), "exit_span_id() should return duration!");
+    assert_eq!(j.get_span_depth(), 0, "All spans should be exited!");
+    assert_eq!(j.unread(), 3, "Each exited span should write an entry!");
+    let exits : Vec<String> = (0..3).map(|_| j.read().unwrap().get_description().clone()).collect();
+    assert_eq!(exits, vec!["a", "b", "c"], "Spans should exit from innermost!");
+
+    // V3 | KJournal::exit_span_id() of a span already exited returns None.
+    assert!(j.exit_span_id(a).is_none(), "Span already exited should return None!");
+
+    // V4 | Span exit entries are filtered with KJournal::set_span_severity().
+    j.set_span_severity(KJournalEntrySeverity::Debug);
+    j.enter_span("", "filtered");
+    assert!(j.exit_span().is_some(), "Filtered span should still return duration!");
+    assert_eq!(j.unread(), 0, "Debug span exit entry should be filtered!");
+
+    // V5 | KJournalSpanGuard::exit() returns the duration of the span.
+    let guard = j.span("", "guard");
+    assert!(guard.exit().is_some(), "KJournalSpanGuard::exit() should return duration!");
+    assert_eq!(j.get_span_depth(), 0, "Guard span should be exited!");
+}
+
+#[test]
+/// Encode and decode KJournalRecord with span.
+/// 
+/// # Verification(s)
+/// V1 | KJournalRecord with exit span is the same after encode and decode.
+/// V2 | KJournalRecord::to_text() indents description according to span depth.
+fn kjournal_span_record() {
+    // V1 | KJournalRecord with exit span is the same after encode and decode.
+    let record = KJournalRecord { severity: KJournalEntrySeverity::Debug, first_date_time: SystemTime::UNIX_EPOCH, date_time: SystemTime::UNIX_EPOCH,
+        repeat_count: 1, target: String::new(), description: "render".to_string(), fields: Vec::new(),
+        span: Some(KJournalEntrySpan::new(7, Some(3), 2, Some(Duration::from_micros(1500)))) };
+    let encoded = assert_ok!(record.encode());
+    assert_eq!(assert_ok!(KJournalRecord::decode(&encoded[4..])), record, "Record with span round trip error!");
+
+    // V2 | KJournalRecord::to_text() indents description according to span depth.
+    assert_eq!(record.to_text(), "[D 0.000000]   render [1.5ms]", "Span exit text error!");
+    let inner = KJournalRecord { span: Some(KJournalEntrySpan::new(7, Some(3), 2, None)), description: "drawing".to_string(), ..record };
+    assert_eq!(inner.to_text(), "[D 0.000000]     drawing", "Span entry text error!");
+}